@@ -0,0 +1,169 @@
+// src/abilities.rs
+use std::collections::HashMap;
+
+use crate::format::format_ability_name;
+use crate::state::Ability;
+
+// Base cooldown and mana cost per level for an ability, before talents and items
+#[derive(Clone, Copy, Debug)]
+pub struct AbilityInfo {
+    // Internal name as sent by GSI, e.g. "axe_culling_blade"
+    pub class: &'static str,
+    // Hero class the ability belongs to
+    pub hero: &'static str,
+    pub ultimate: bool,
+    pub cooldowns: &'static [i32],
+    pub mana_costs: &'static [i32],
+}
+
+impl AbilityInfo {
+    // Values for a 1-based ability level, clamped to the levels the ability has
+    fn at_level(values: &[i32], level: i32) -> i32 {
+        let index = (level.max(1) as usize - 1).min(values.len() - 1);
+        values[index]
+    }
+
+    pub fn cooldown_at(&self, level: i32) -> i32 {
+        Self::at_level(self.cooldowns, level)
+    }
+
+    pub fn mana_at(&self, level: i32) -> i32 {
+        Self::at_level(self.mana_costs, level)
+    }
+
+    // Earliest game time the ability is back up after a cast, ignoring cooldown reduction
+    pub fn ready_at(&self, level: i32, cast_time: i32) -> i32 {
+        cast_time + self.cooldown_at(level)
+    }
+}
+
+// Ultimates and the spells that decide fights. Values are approximate and follow the current patch.
+pub const ABILITIES: &[AbilityInfo] = &[
+    AbilityInfo { class: "abaddon_borrowed_time", hero: "abaddon", ultimate: true, cooldowns: &[100, 80, 60], mana_costs: &[0, 0, 0] },
+    AbilityInfo { class: "ancient_apparition_ice_blast", hero: "ancient_apparition", ultimate: true, cooldowns: &[40, 40, 40], mana_costs: &[100, 125, 150] },
+    AbilityInfo { class: "antimage_blink", hero: "antimage", ultimate: false, cooldowns: &[12, 10, 8, 6], mana_costs: &[60, 60, 60, 60] },
+    AbilityInfo { class: "axe_berserkers_call", hero: "axe", ultimate: false, cooldowns: &[17, 15, 13, 11], mana_costs: &[80, 90, 100, 110] },
+    AbilityInfo { class: "axe_culling_blade", hero: "axe", ultimate: true, cooldowns: &[75, 65, 55], mana_costs: &[60, 120, 180] },
+    AbilityInfo { class: "bane_nightmare", hero: "bane", ultimate: false, cooldowns: &[24, 21, 18, 15], mana_costs: &[120, 130, 140, 150] },
+    AbilityInfo { class: "bane_fiends_grip", hero: "bane", ultimate: true, cooldowns: &[120, 110, 100], mana_costs: &[200, 300, 400] },
+    AbilityInfo { class: "batrider_flaming_lasso", hero: "batrider", ultimate: true, cooldowns: &[90, 75, 60], mana_costs: &[150, 175, 200] },
+    AbilityInfo { class: "crystal_maiden_frostbite", hero: "crystal_maiden", ultimate: false, cooldowns: &[9, 8, 7, 6], mana_costs: &[140, 145, 150, 155] },
+    AbilityInfo { class: "crystal_maiden_freezing_field", hero: "crystal_maiden", ultimate: true, cooldowns: &[90, 80, 70], mana_costs: &[200, 400, 600] },
+    AbilityInfo { class: "dark_seer_wall_of_replica", hero: "dark_seer", ultimate: true, cooldowns: &[100, 100, 100], mana_costs: &[125, 250, 375] },
+    AbilityInfo { class: "dazzle_shallow_grave", hero: "dazzle", ultimate: false, cooldowns: &[42, 34, 26, 18], mana_costs: &[150, 140, 130, 120] },
+    AbilityInfo { class: "death_prophet_exorcism", hero: "death_prophet", ultimate: true, cooldowns: &[145, 145, 145], mana_costs: &[200, 300, 400] },
+    AbilityInfo { class: "doom_bringer_doom", hero: "doom_bringer", ultimate: true, cooldowns: &[145, 145, 145], mana_costs: &[150, 200, 250] },
+    AbilityInfo { class: "earthshaker_fissure", hero: "earthshaker", ultimate: false, cooldowns: &[18, 17, 16, 15], mana_costs: &[110, 130, 150, 170] },
+    AbilityInfo { class: "earthshaker_echo_slam", hero: "earthshaker", ultimate: true, cooldowns: &[110, 100, 90], mana_costs: &[145, 205, 265] },
+    AbilityInfo { class: "enigma_black_hole", hero: "enigma", ultimate: true, cooldowns: &[200, 180, 160], mana_costs: &[300, 400, 500] },
+    AbilityInfo { class: "faceless_void_time_walk", hero: "faceless_void", ultimate: false, cooldowns: &[24, 18, 12, 6], mana_costs: &[40, 40, 40, 40] },
+    AbilityInfo { class: "faceless_void_chronosphere", hero: "faceless_void", ultimate: true, cooldowns: &[160, 150, 140], mana_costs: &[150, 225, 300] },
+    AbilityInfo { class: "furion_wrath_of_nature", hero: "furion", ultimate: true, cooldowns: &[90, 85, 80], mana_costs: &[175, 225, 275] },
+    AbilityInfo { class: "juggernaut_omni_slash", hero: "juggernaut", ultimate: true, cooldowns: &[140, 120, 100], mana_costs: &[200, 275, 350] },
+    AbilityInfo { class: "legion_commander_duel", hero: "legion_commander", ultimate: true, cooldowns: &[50, 50, 50], mana_costs: &[75, 75, 75] },
+    AbilityInfo { class: "lina_light_strike_array", hero: "lina", ultimate: false, cooldowns: &[7, 7, 7, 7], mana_costs: &[100, 110, 120, 130] },
+    AbilityInfo { class: "lina_laguna_blade", hero: "lina", ultimate: true, cooldowns: &[70, 60, 50], mana_costs: &[150, 250, 350] },
+    AbilityInfo { class: "lion_impale", hero: "lion", ultimate: false, cooldowns: &[12, 12, 12, 12], mana_costs: &[80, 100, 120, 140] },
+    AbilityInfo { class: "lion_voodoo", hero: "lion", ultimate: false, cooldowns: &[24, 20, 16, 12], mana_costs: &[125, 150, 175, 200] },
+    AbilityInfo { class: "lion_finger_of_death", hero: "lion", ultimate: true, cooldowns: &[100, 85, 70], mana_costs: &[200, 420, 650] },
+    AbilityInfo { class: "magnataur_reverse_polarity", hero: "magnataur", ultimate: true, cooldowns: &[130, 120, 110], mana_costs: &[200, 250, 300] },
+    AbilityInfo { class: "mars_arena_of_blood", hero: "mars", ultimate: true, cooldowns: &[90, 80, 70], mana_costs: &[150, 200, 250] },
+    AbilityInfo { class: "medusa_stone_gaze", hero: "medusa", ultimate: true, cooldowns: &[90, 90, 90], mana_costs: &[200, 200, 200] },
+    AbilityInfo { class: "mirana_arrow", hero: "mirana", ultimate: false, cooldowns: &[17, 16, 15, 14], mana_costs: &[100, 100, 100, 100] },
+    AbilityInfo { class: "necrolyte_reapers_scythe", hero: "necrolyte", ultimate: true, cooldowns: &[100, 85, 70], mana_costs: &[200, 350, 500] },
+    AbilityInfo { class: "nevermore_requiem", hero: "nevermore", ultimate: true, cooldowns: &[120, 110, 100], mana_costs: &[150, 175, 200] },
+    AbilityInfo { class: "oracle_false_promise", hero: "oracle", ultimate: true, cooldowns: &[110, 85, 60], mana_costs: &[100, 150, 200] },
+    AbilityInfo { class: "puck_dream_coil", hero: "puck", ultimate: true, cooldowns: &[70, 65, 60], mana_costs: &[100, 150, 200] },
+    AbilityInfo { class: "pudge_meat_hook", hero: "pudge", ultimate: false, cooldowns: &[18, 16, 14, 12], mana_costs: &[125, 130, 135, 140] },
+    AbilityInfo { class: "queenofpain_blink", hero: "queenofpain", ultimate: false, cooldowns: &[12, 10, 8, 6], mana_costs: &[60, 60, 60, 60] },
+    AbilityInfo { class: "queenofpain_sonic_wave", hero: "queenofpain", ultimate: true, cooldowns: &[135, 125, 115], mana_costs: &[250, 400, 500] },
+    AbilityInfo { class: "shadow_shaman_voodoo", hero: "shadow_shaman", ultimate: false, cooldowns: &[13, 12, 11, 10], mana_costs: &[110, 140, 170, 200] },
+    AbilityInfo { class: "shadow_shaman_shackles", hero: "shadow_shaman", ultimate: false, cooldowns: &[16, 14, 12, 10], mana_costs: &[140, 150, 160, 170] },
+    AbilityInfo { class: "shadow_shaman_mass_serpent_ward", hero: "shadow_shaman", ultimate: true, cooldowns: &[120, 110, 100], mana_costs: &[200, 350, 600] },
+    AbilityInfo { class: "silencer_global_silence", hero: "silencer", ultimate: true, cooldowns: &[130, 115, 100], mana_costs: &[250, 375, 500] },
+    AbilityInfo { class: "skeleton_king_reincarnation", hero: "skeleton_king", ultimate: true, cooldowns: &[180, 140, 100], mana_costs: &[180, 180, 180] },
+    AbilityInfo { class: "slardar_slithereen_crush", hero: "slardar", ultimate: false, cooldowns: &[8, 8, 8, 8], mana_costs: &[80, 95, 105, 115] },
+    AbilityInfo { class: "spectre_haunt", hero: "spectre", ultimate: true, cooldowns: &[120, 110, 100], mana_costs: &[150, 200, 250] },
+    AbilityInfo { class: "sven_storm_bolt", hero: "sven", ultimate: false, cooldowns: &[17, 16, 15, 14], mana_costs: &[110, 120, 130, 140] },
+    AbilityInfo { class: "sven_gods_strength", hero: "sven", ultimate: true, cooldowns: &[110, 105, 100], mana_costs: &[100, 150, 200] },
+    AbilityInfo { class: "tidehunter_ravage", hero: "tidehunter", ultimate: true, cooldowns: &[150, 150, 150], mana_costs: &[150, 225, 325] },
+    AbilityInfo { class: "vengefulspirit_magic_missile", hero: "vengefulspirit", ultimate: false, cooldowns: &[12, 11, 10, 9], mana_costs: &[100, 110, 120, 130] },
+    AbilityInfo { class: "warlock_rain_of_chaos", hero: "warlock", ultimate: true, cooldowns: &[170, 160, 150], mana_costs: &[250, 375, 500] },
+    AbilityInfo { class: "windrunner_shackleshot", hero: "windrunner", ultimate: false, cooldowns: &[14, 13, 12, 11], mana_costs: &[70, 80, 90, 100] },
+    AbilityInfo { class: "winter_wyvern_winters_curse", hero: "winter_wyvern", ultimate: true, cooldowns: &[100, 90, 80], mana_costs: &[250, 250, 250] },
+    AbilityInfo { class: "witch_doctor_paralyzing_cask", hero: "witch_doctor", ultimate: false, cooldowns: &[20, 18, 16, 14], mana_costs: &[80, 90, 100, 110] },
+    AbilityInfo { class: "witch_doctor_death_ward", hero: "witch_doctor", ultimate: true, cooldowns: &[80, 80, 80], mana_costs: &[200, 325, 450] },
+    AbilityInfo { class: "zuus_thundergods_wrath", hero: "zuus", ultimate: true, cooldowns: &[130, 125, 120], mana_costs: &[250, 350, 450] },];
+
+pub fn by_class(class: &str) -> Option<&'static AbilityInfo> {
+    ABILITIES.iter().find(|a| a.class == class)
+}
+
+// Known abilities of a hero, given as "npc_dota_hero_axe" or "axe"
+pub fn for_hero(hero: &str) -> Vec<&'static AbilityInfo> {
+    let class = hero.strip_prefix("npc_dota_hero_").unwrap_or(hero);
+    ABILITIES.iter().filter(|a| a.hero == class).collect()
+}
+
+// The hero's ultimate, if it is in the table
+pub fn ultimate_of(hero: &str) -> Option<&'static AbilityInfo> {
+    for_hero(hero).into_iter().find(|a| a.ultimate)
+}
+
+// Where one of the player's abilities stands right now
+#[derive(Clone, Debug)]
+pub struct AbilityStatus {
+    pub name: String,
+    pub level: i32,
+    pub ultimate: bool,
+    pub passive: bool,
+    // Seconds left on cooldown
+    pub cooldown: i32,
+    pub can_cast: bool,
+    // None when the mana cost or the hero's mana is unknown
+    pub enough_mana: Option<bool>,
+}
+
+// Entries of the abilities block ordered by their "abilityN" key
+pub fn in_slot_order(abilities: &HashMap<String, Ability>) -> Vec<&Ability> {
+    let mut slots: Vec<(usize, &Ability)> = abilities.iter()
+        .filter_map(|(key, ability)| key.strip_prefix("ability")
+            .and_then(|i| i.parse().ok())
+            .map(|i| (i, ability)))
+        .collect();
+    slots.sort_by_key(|(i, _)| *i);
+    slots.into_iter().map(|(_, ability)| ability).collect()
+}
+
+// Talents and hidden placeholders that GSI lists among the abilities
+pub fn is_placeholder(class: &str) -> bool {
+    class.starts_with("special_bonus") || class.starts_with("generic_hidden")
+}
+
+// The player's abilities in slot order, skipping talents and hidden placeholders
+pub fn statuses(abilities: &HashMap<String, Ability>, mana: Option<i32>) -> Vec<AbilityStatus> {
+    in_slot_order(abilities).into_iter()
+        .filter_map(|ability| {
+            let class = ability.name.as_deref()?;
+            if is_placeholder(class) {
+                return None;
+            }
+
+            let level = ability.level.unwrap_or(0);
+            let enough_mana = match (by_class(class), mana) {
+                (Some(info), Some(mana)) if level > 0 => Some(mana >= info.mana_at(level)),
+                _ => None,
+            };
+
+            Some(AbilityStatus {
+                name: format_ability_name(class),
+                level,
+                ultimate: ability.ultimate.unwrap_or(false),
+                passive: ability.passive.unwrap_or(false),
+                cooldown: ability.cooldown.unwrap_or(0),
+                can_cast: ability.can_cast.unwrap_or(false),
+                enough_mana,
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,389 @@
+// src/alerts.rs
+use std::collections::{HashMap, VecDeque};
+
+use colored::{ColoredString, Colorize};
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize)]
+#[serde(try_from = "String")]
+pub enum Severity {
+    Info,
+    Warning,
+    Critical,
+}
+
+impl Severity {
+    pub fn slug(&self) -> &'static str {
+        match self {
+            Severity::Info => "info",
+            Severity::Warning => "warning",
+            Severity::Critical => "critical",
+        }
+    }
+}
+
+impl TryFrom<String> for Severity {
+    type Error = String;
+
+    fn try_from(slug: String) -> Result<Self, Self::Error> {
+        [Severity::Info, Severity::Warning, Severity::Critical].into_iter()
+            .find(|s| s.slug() == slug)
+            .ok_or_else(|| format!("unknown severity '{}', expected info, warning or critical", slug))
+    }
+}
+
+// What kind of coaching signal an alert carries
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Deserialize)]
+#[serde(try_from = "String")]
+pub enum AlertCategory {
+    Rune,
+    Stack,
+    MissingEnemies,
+    Smoke,
+    Objective,
+    Teleport,
+    LastHits,
+    Counter,
+    Skill,
+    PowerSpike,
+    Buyback,
+    Rotation,
+    Roshan,
+    Courier,
+    // Ward and smoke timings for supports
+    Vision,
+    // Kill streaks, the player's own and enemies worth shutting down
+    Streak,
+    // Calmer play after a run of deaths or lost farm
+    Pacing,
+    // Unreliable gold carried into danger
+    Gold,
+    // Raised by the user's own [[rules]]
+    Rule,
+    // Raised by a loaded plugin
+    Plugin,
+}
+
+impl AlertCategory {
+    pub const ALL: [AlertCategory; 20] = [
+        AlertCategory::Rune,
+        AlertCategory::Stack,
+        AlertCategory::MissingEnemies,
+        AlertCategory::Smoke,
+        AlertCategory::Objective,
+        AlertCategory::Teleport,
+        AlertCategory::LastHits,
+        AlertCategory::Counter,
+        AlertCategory::Skill,
+        AlertCategory::PowerSpike,
+        AlertCategory::Buyback,
+        AlertCategory::Rotation,
+        AlertCategory::Roshan,
+        AlertCategory::Courier,
+        AlertCategory::Vision,
+        AlertCategory::Streak,
+        AlertCategory::Pacing,
+        AlertCategory::Gold,
+        AlertCategory::Rule,
+        AlertCategory::Plugin,
+    ];
+
+    // Short identifier used on the command line and in the config file
+    pub fn slug(&self) -> &'static str {
+        match self {
+            AlertCategory::Rune => "rune",
+            AlertCategory::Stack => "stack",
+            AlertCategory::MissingEnemies => "missing",
+            AlertCategory::Smoke => "smoke",
+            AlertCategory::Objective => "objective",
+            AlertCategory::Teleport => "tp",
+            AlertCategory::LastHits => "cs",
+            AlertCategory::Counter => "counter",
+            AlertCategory::Skill => "skill",
+            AlertCategory::PowerSpike => "spike",
+            AlertCategory::Buyback => "buyback",
+            AlertCategory::Rotation => "rotate",
+            AlertCategory::Roshan => "roshan",
+            AlertCategory::Courier => "courier",
+            AlertCategory::Vision => "vision",
+            AlertCategory::Streak => "streak",
+            AlertCategory::Pacing => "pace",
+            AlertCategory::Gold => "gold",
+            AlertCategory::Rule => "rule",
+            AlertCategory::Plugin => "plugin",
+        }
+    }
+
+    pub fn from_slug(slug: &str) -> Option<Self> {
+        Self::ALL.iter().copied().find(|c| c.slug() == slug)
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            AlertCategory::Rune => "RUNE",
+            AlertCategory::Stack => "STACK",
+            AlertCategory::MissingEnemies => "SS - ENEMIES MISSING",
+            AlertCategory::Smoke => "POSSIBLE SMOKE",
+            AlertCategory::Objective => "OBJECTIVE",
+            AlertCategory::Teleport => "NO TP SCROLL",
+            AlertCategory::LastHits => "LAST HITS",
+            AlertCategory::Counter => "COUNTER ITEMS",
+            AlertCategory::Skill => "LEVEL UP",
+            AlertCategory::PowerSpike => "POWER SPIKE",
+            AlertCategory::Buyback => "BUYBACK",
+            AlertCategory::Rotation => "ROTATE",
+            AlertCategory::Roshan => "ROSHAN",
+            AlertCategory::Courier => "COURIER",
+            AlertCategory::Vision => "VISION",
+            AlertCategory::Streak => "KILL STREAK",
+            AlertCategory::Pacing => "PACING",
+            AlertCategory::Gold => "UNSPENT GOLD",
+            AlertCategory::Rule => "RULE",
+            AlertCategory::Plugin => "PLUGIN",
+        }
+    }
+
+    // Minimum seconds between two alerts with the same category and key
+    pub fn cooldown(&self) -> i32 {
+        match self {
+            AlertCategory::Rune => 10,
+            AlertCategory::Stack => 50,
+            AlertCategory::MissingEnemies => 30,
+            AlertCategory::Smoke => 20,
+            AlertCategory::Objective => 60,
+            AlertCategory::Teleport => 90,
+            AlertCategory::LastHits => 60,
+            AlertCategory::Counter => 600,
+            AlertCategory::Skill => 30,
+            AlertCategory::PowerSpike => 10,
+            AlertCategory::Buyback => 20,
+            AlertCategory::Rotation => 120,
+            AlertCategory::Roshan => 30,
+            AlertCategory::Courier => 45,
+            AlertCategory::Vision => 60,
+            AlertCategory::Streak => 30,
+            AlertCategory::Pacing => 300,
+            AlertCategory::Gold => 60,
+            // Rules and plugins decide for themselves when to fire again
+            AlertCategory::Rule | AlertCategory::Plugin => 0,
+        }
+    }
+
+    // How long an alert of this category stays on the active list
+    pub fn default_ttl(&self) -> i32 {
+        match self {
+            AlertCategory::Rune => 25,
+            AlertCategory::Stack => 10,
+            AlertCategory::MissingEnemies => 20,
+            AlertCategory::Smoke => 20,
+            AlertCategory::Objective => 30,
+            AlertCategory::Teleport => 30,
+            AlertCategory::LastHits => 30,
+            AlertCategory::Counter => 60,
+            AlertCategory::Skill => 20,
+            AlertCategory::PowerSpike => 45,
+            AlertCategory::Buyback => 30,
+            AlertCategory::Rotation => 20,
+            AlertCategory::Roshan => 30,
+            AlertCategory::Courier => 15,
+            AlertCategory::Vision => 30,
+            AlertCategory::Streak => 30,
+            AlertCategory::Pacing => 45,
+            AlertCategory::Gold => 20,
+            AlertCategory::Rule | AlertCategory::Plugin => 20,
+        }
+    }
+}
+
+impl TryFrom<String> for AlertCategory {
+    type Error = String;
+
+    fn try_from(slug: String) -> Result<Self, Self::Error> {
+        Self::from_slug(&slug).ok_or_else(|| format!("unknown alert category '{}'", slug))
+    }
+}
+
+// Colored label for an alert, emphasised by severity
+pub fn alert_label(category: AlertCategory, severity: Severity) -> ColoredString {
+    match severity {
+        Severity::Critical => category.name().white().on_red().bold(),
+        Severity::Warning => category.name().yellow().bold(),
+        Severity::Info => category.name().cyan(),
+    }
+}
+
+// A single coaching signal
+#[derive(Clone, Debug)]
+pub struct Alert {
+    pub severity: Severity,
+    pub category: AlertCategory,
+    pub message: String,
+    // Seconds of game time the alert stays active
+    pub ttl: i32,
+    // Distinguishes independent alerts within a category (e.g. one per building)
+    pub key: String,
+    // Short phrase to read out instead of the full message
+    pub speech: Option<String>,
+    pub raised_at: i32,
+}
+
+impl Alert {
+    pub fn new(severity: Severity, category: AlertCategory, message: String) -> Self {
+        Alert {
+            severity,
+            category,
+            message,
+            ttl: category.default_ttl(),
+            key: String::new(),
+            speech: None,
+            raised_at: 0,
+        }
+    }
+
+    pub fn with_key(mut self, key: impl Into<String>) -> Self {
+        self.key = key.into();
+        self
+    }
+
+    pub fn with_speech(mut self, speech: impl Into<String>) -> Self {
+        self.speech = Some(speech.into());
+        self
+    }
+
+    pub fn expires_at(&self) -> i32 {
+        self.raised_at + self.ttl
+    }
+
+    // What to say out loud for this alert
+    pub fn spoken_text(&self) -> String {
+        self.speech.clone().unwrap_or_else(|| strip_colors(&self.message))
+    }
+
+    // As sent to webhooks and MQTT
+    pub fn to_json(&self) -> Value {
+        json!({
+            "category": self.category.slug(),
+            "severity": self.severity.slug(),
+            "title": self.category.name(),
+            "message": strip_colors(&self.message),
+            "speech": self.speech,
+            "key": self.key,
+            "game_time": self.raised_at,
+        })
+    }
+}
+
+// Remove ANSI color codes so messages can be spoken or sent elsewhere
+pub fn strip_colors(text: &str) -> String {
+    let mut plain = String::with_capacity(text.len());
+    let mut chars = text.chars();
+
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            // Skip the escape sequence up to and including its final letter
+            for c in chars.by_ref() {
+                if c.is_ascii_alphabetic() {
+                    break;
+                }
+            }
+        } else {
+            plain.push(c);
+        }
+    }
+
+    plain
+}
+
+// Something that wants to hear about every alert that gets through
+pub trait AlertSink: Send {
+    fn notify(&self, alert: &Alert);
+}
+
+// How many raised alerts are remembered after they expire
+pub const ALERT_HISTORY: usize = 100;
+
+// Central store for alerts: applies cooldowns, dedupes and expires them
+pub struct AlertManager {
+    active: Vec<Alert>,
+    history: VecDeque<Alert>,
+    last_raised: HashMap<(AlertCategory, String), (i32, Severity)>,
+    enabled: Vec<AlertCategory>,
+    sinks: Vec<Box<dyn AlertSink>>,
+}
+
+impl AlertManager {
+    pub fn new(enabled: Vec<AlertCategory>) -> Self {
+        AlertManager {
+            active: Vec::new(),
+            history: VecDeque::with_capacity(ALERT_HISTORY),
+            last_raised: HashMap::new(),
+            enabled,
+            sinks: Vec::new(),
+        }
+    }
+
+    // Forward every accepted alert to an additional output
+    pub fn add_sink(&mut self, sink: Box<dyn AlertSink>) {
+        self.sinks.push(sink);
+    }
+
+    // Try to raise an alert. Returns the stored alert, or None if it was suppressed.
+    pub fn raise(&mut self, game_time: i32, mut alert: Alert) -> Option<&Alert> {
+        if !self.enabled.contains(&alert.category) {
+            return None;
+        }
+
+        alert.raised_at = game_time;
+        let slot = (alert.category, alert.key.clone());
+
+        // An escalation in severity always gets through; otherwise respect the cooldown
+        if let Some(&(last_time, last_severity)) = self.last_raised.get(&slot) {
+            let cooling_down = game_time - last_time < alert.category.cooldown();
+            if cooling_down && alert.severity <= last_severity {
+                return None;
+            }
+        }
+
+        // Never show the same message twice at once
+        if self.active.iter().any(|a| a.category == alert.category && a.message == alert.message) {
+            return None;
+        }
+
+        self.last_raised.insert(slot, (game_time, alert.severity));
+        self.active.retain(|a| !(a.category == alert.category && a.key == alert.key));
+        for sink in &self.sinks {
+            sink.notify(&alert);
+        }
+        if self.history.len() == ALERT_HISTORY {
+            self.history.pop_front();
+        }
+        self.history.push_back(alert.clone());
+        self.active.push(alert);
+        self.active.last()
+    }
+
+    // Forget every alert and cooldown, e.g. when a new match starts. Sinks stay attached.
+    pub fn reset(&mut self) {
+        self.active.clear();
+        self.history.clear();
+        self.last_raised.clear();
+    }
+
+    // Drop alerts whose time to live has passed
+    pub fn expire(&mut self, game_time: i32) {
+        self.active.retain(|a| a.expires_at() > game_time);
+    }
+
+    // Every alert raised at or after `game_time`, whether or not it is still active
+    pub fn raised_since(&self, game_time: i32) -> impl Iterator<Item = &Alert> {
+        self.history.iter().filter(move |a| a.raised_at >= game_time)
+    }
+
+    // Active alerts, most severe first and newest first within a severity
+    pub fn active_sorted(&self) -> Vec<&Alert> {
+        let mut alerts: Vec<&Alert> = self.active.iter().collect();
+        alerts.sort_by(|a, b| b.severity.cmp(&a.severity).then(b.raised_at.cmp(&a.raised_at)));
+        alerts
+    }
+}
@@ -0,0 +1,131 @@
+// src/analysis/awareness.rs
+use std::collections::HashMap;
+
+use crate::format::format_game_time;
+use crate::missing;
+use crate::state::{GameState, Team};
+use crate::tracking::EnemyHeroState;
+
+// Longer gaps between payloads, e.g. pauses, count as this much
+const MAX_STEP: i32 = 5;
+
+// Stretches shorter than this aren't worth listing
+const MIN_STRETCH: i32 = 10;
+
+// How many of the worst stretches the report lists
+const WORST: usize = 3;
+
+// Time spent past the river without enough of the enemy team in sight
+#[derive(Clone, Copy, Debug)]
+pub struct BlindStretch {
+    pub start: i32,
+    pub end: i32,
+    // Fewest enemies in sight during it
+    pub fewest_visible: usize,
+}
+
+impl BlindStretch {
+    pub fn seconds(&self) -> i32 {
+        self.end - self.start
+    }
+
+    pub fn describe(&self) -> String {
+        format!("{} to {} ({}s, as few as {} enem{} in sight)",
+            format_game_time(Some(self.start)), format_game_time(Some(self.end)), self.seconds(),
+            self.fewest_visible, if self.fewest_visible == 1 { "y" } else { "ies" })
+    }
+}
+
+// How well the player knew where the enemy was while farming on their side of the map
+#[derive(Clone, Debug)]
+pub struct AwarenessReport {
+    // Percent of the time past the river with enough enemies on the minimap
+    pub score: i32,
+    pub exposed: i32,
+    pub needed: usize,
+    pub worst: Vec<BlindStretch>,
+}
+
+// Adds up the time the player spends alive past the river, and how much of it enough
+// enemies were on the minimap to farm there safely
+pub struct AwarenessTracker {
+    exposed: i32,
+    covered: i32,
+    stretch: Option<BlindStretch>,
+    stretches: Vec<BlindStretch>,
+    last_time: Option<i32>,
+    needed: usize,
+}
+
+impl AwarenessTracker {
+    pub fn new() -> Self {
+        AwarenessTracker {
+            exposed: 0,
+            covered: 0,
+            stretch: None,
+            stretches: Vec::new(),
+            last_time: None,
+            needed: 0,
+        }
+    }
+
+    fn close_stretch(&mut self) {
+        if let Some(stretch) = self.stretch.take().filter(|s| s.seconds() >= MIN_STRETCH) {
+            self.stretches.push(stretch);
+        }
+    }
+
+    // `needed` is how many enemies should be in sight to farm past the river
+    pub fn update(&mut self, state: &GameState, enemies: &HashMap<String, EnemyHeroState>, needed: usize) {
+        let Some(game_time) = state.game_time() else { return };
+        self.needed = needed;
+        let step = self.last_time.map(|t| (game_time - t).clamp(0, MAX_STEP)).unwrap_or(0);
+        self.last_time = Some(game_time);
+
+        let team = state.player_team().and_then(|t| Team::from_name(&t));
+        let alive = state.hero().and_then(|h| h.alive).unwrap_or(false);
+        let exposed = match (team, state.hero_position()) {
+            (Some(team), Some(position)) if alive => missing::is_past_river(position, team == Team::Radiant),
+            _ => false,
+        };
+        if !exposed {
+            self.close_stretch();
+            return;
+        }
+
+        let visible = enemies.values().filter(|e| e.last_seen_time >= game_time).count();
+        self.exposed += step;
+        if visible >= needed {
+            self.covered += step;
+            self.close_stretch();
+            return;
+        }
+        let stretch = self.stretch.get_or_insert(BlindStretch { start: game_time, end: game_time, fewest_visible: visible });
+        stretch.end = game_time;
+        stretch.fewest_visible = stretch.fewest_visible.min(visible);
+    }
+
+    pub fn report(&self) -> Option<AwarenessReport> {
+        if self.exposed <= 0 {
+            return None;
+        }
+        let mut worst: Vec<BlindStretch> = self.stretches.iter().chain(self.stretch.iter())
+            .filter(|s| s.seconds() >= MIN_STRETCH)
+            .copied()
+            .collect();
+        worst.sort_by_key(|s| (-s.seconds(), s.start));
+        worst.truncate(WORST);
+        Some(AwarenessReport {
+            score: self.covered * 100 / self.exposed,
+            exposed: self.exposed,
+            needed: self.needed,
+            worst,
+        })
+    }
+}
+
+impl Default for AwarenessTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,141 @@
+// src/analysis/death_recap.rs
+use std::collections::{HashMap, VecDeque};
+
+use crate::alerts::{AlertCategory, AlertManager, Severity};
+use crate::format::format_game_time;
+use crate::state::GameState;
+use crate::tracking::{calculate_distance, describe_map_location, EnemyHeroState};
+
+// Seconds of health and mana history kept for the recap
+pub const VITALS_WINDOW: i32 = 15;
+
+// Enemies seen this recently near the death spot count as involved
+pub const ENEMY_WINDOW: i32 = 10;
+
+// Warnings raised this long before the death are worth mentioning
+pub const ALERT_WINDOW: i32 = 60;
+
+// Losing this much health within BURST_SECONDS counts as burst
+const BURST_PERCENT: i32 = 60;
+const BURST_SECONDS: i32 = 3;
+
+#[derive(Clone, Copy, Debug)]
+struct Vitals {
+    time: i32,
+    health_percent: i32,
+    mana_percent: Option<i32>,
+}
+
+#[derive(Clone, Debug)]
+pub struct DeathRecap {
+    pub time: i32,
+    pub location: Option<String>,
+    pub nearby_enemies: Vec<String>,
+    // Likely reasons, most important first
+    pub factors: Vec<String>,
+}
+
+impl DeathRecap {
+    pub fn describe(&self) -> String {
+        let place = self.location.as_deref().map(|l| format!(" in the {}", l)).unwrap_or_default();
+        if self.factors.is_empty() {
+            format!("Died at {}{}", format_game_time(Some(self.time)), place)
+        } else {
+            format!("Died at {}{}: {}", format_game_time(Some(self.time)), place, self.factors.join("; "))
+        }
+    }
+}
+
+// Keeps a little history of the player's vitals and explains each death
+pub struct DeathRecorder {
+    vitals: VecDeque<Vitals>,
+}
+
+impl DeathRecorder {
+    pub fn new() -> Self {
+        DeathRecorder {
+            vitals: VecDeque::new(),
+        }
+    }
+
+    fn percent(current: Option<i32>, max: Option<i32>) -> Option<i32> {
+        match (current, max) {
+            (Some(current), Some(max)) if max > 0 => Some(current * 100 / max),
+            _ => None,
+        }
+    }
+
+    // Returns a recap when the player's hero just died
+    pub fn update(&mut self, state: &GameState, died: bool, enemies: &HashMap<String, EnemyHeroState>, alerts: &AlertManager, nearby_distance: f32) -> Option<DeathRecap> {
+        let time = state.game_time()?;
+        let hero = state.hero()?;
+        if hero.alive.unwrap_or(true) {
+            if let Some(health_percent) = Self::percent(hero.health, hero.max_health) {
+                self.vitals.push_back(Vitals { time, health_percent, mana_percent: Self::percent(hero.mana, hero.max_mana) });
+            }
+            while self.vitals.front().map(|v| v.time < time - VITALS_WINDOW).unwrap_or(false) {
+                self.vitals.pop_front();
+            }
+        }
+        if !died {
+            return None;
+        }
+
+        let position = state.hero_position();
+        let nearby_enemies: Vec<String> = enemies.values()
+            .filter(|e| time - e.last_seen_time <= ENEMY_WINDOW)
+            .filter(|e| position.map(|p| calculate_distance(p, e.last_seen_position) <= nearby_distance).unwrap_or(true))
+            .map(|e| e.name.clone())
+            .collect();
+
+        let mut factors = Vec::new();
+        match nearby_enemies.len() {
+            0 => factors.push("no enemy was visible nearby - you walked into the fog".to_string()),
+            1 => factors.push(format!("{} was close by", nearby_enemies[0])),
+            n => factors.push(format!("caught by {} enemies ({})", n, nearby_enemies.join(", "))),
+        }
+
+        // How fast the health went
+        let peak = self.vitals.iter().max_by_key(|v| (v.health_percent, v.time));
+        if let (Some(peak), Some(last)) = (peak, self.vitals.back()) {
+            let seconds = time - peak.time;
+            if peak.health_percent >= BURST_PERCENT && seconds <= BURST_SECONDS {
+                factors.push(format!("burst from {}% health in {}s - keep a defensive item or escape ready", peak.health_percent, seconds.max(1)));
+            } else if peak.health_percent - last.health_percent > 0 {
+                factors.push(format!("health dropped from {}% over {}s - there was time to back off", peak.health_percent, seconds));
+            }
+            if last.mana_percent.map(|m| m < 15).unwrap_or(false) {
+                factors.push("out of mana, so spells weren't an option".to_string());
+            }
+        }
+
+        if state.inventory().map(|i| !i.has_teleport()).unwrap_or(false) {
+            factors.push("no TP scroll to escape with".to_string());
+        }
+
+        // Warnings that came shortly before, the latest of each kind
+        let mut warned = Vec::new();
+        for alert in alerts.raised_since(time - ALERT_WINDOW).collect::<Vec<_>>().into_iter().rev()
+            .filter(|a| a.severity >= Severity::Warning)
+            .filter(|a| matches!(a.category, AlertCategory::MissingEnemies | AlertCategory::Smoke | AlertCategory::PowerSpike)) {
+            if !warned.contains(&alert.category) {
+                warned.push(alert.category);
+                factors.push(format!("{} warning {}s earlier", alert.category.name(), time - alert.raised_at));
+            }
+        }
+
+        self.vitals.clear();
+        Some(DeathRecap {
+            time,
+            location: position.map(describe_map_location),
+            nearby_enemies,
+            factors,
+        })
+    }
+}
+
+impl Default for DeathRecorder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,106 @@
+// src/analysis/gank_risk.rs
+use std::collections::HashMap;
+
+use crate::briefing::Lane;
+use crate::config::Thresholds;
+use crate::lane_assignment::{Assignment, LaneTracker};
+use crate::missing::{self, RIVER_MARGIN};
+use crate::state::{GameState, Team};
+use crate::timers::DayNight;
+use crate::tracking::{calculate_distance, predict_position, EnemyHeroState};
+
+// Points each factor adds to the score, which is capped at 100
+const PER_MISSING_ENEMY: i32 = 12;
+// Mids and roamers are the heroes that usually come for you
+const ROTATING_ROLE: i32 = 6;
+const NIGHT: i32 = 10;
+const PER_CLOSING_ENEMY: i32 = 10;
+const PER_NEARBY_ENEMY: i32 = 8;
+// Every this many units past the river adds a point, up to MAX_DEPTH
+const DEPTH_UNIT: i32 = 150;
+const MAX_DEPTH: i32 = 30;
+
+// Sightings this recent count as enemies being around
+const RECENT_SIGHTING: i32 = 10;
+
+#[derive(Clone, Debug)]
+pub struct GankRisk {
+    // 0 (safe) to 100 (expect to be jumped)
+    pub score: i32,
+    // What pushed the score up, biggest factor first
+    pub reasons: Vec<String>,
+}
+
+impl GankRisk {
+    pub fn level(&self) -> &'static str {
+        match self.score {
+            0..=24 => "low",
+            25..=49 => "moderate",
+            50..=74 => "high",
+            _ => "very high",
+        }
+    }
+}
+
+// Score how likely the player is to be ganked where they stand right now
+pub fn assess(
+    state: &GameState,
+    enemies: &HashMap<String, EnemyHeroState>,
+    lanes: &LaneTracker,
+    thresholds: &Thresholds,
+) -> Option<GankRisk> {
+    let game_time = state.game_time()?;
+    let position = state.hero_position()?;
+    let team = state.player_team().and_then(|t| Team::from_name(&t))?;
+    if state.hero().and_then(|h| h.alive) == Some(false) {
+        return None;
+    }
+
+    let mut factors: Vec<(i32, String)> = Vec::new();
+
+    let missing = missing::missing_enemies(enemies, game_time, thresholds.missing_after_seconds);
+    if !missing.is_empty() {
+        let rotating = missing.iter()
+            .filter(|m| matches!(lanes.assignment(&m.name), Some(Assignment::Roaming | Assignment::Lane(Lane::Mid))))
+            .count() as i32;
+        let names: Vec<String> = missing.iter()
+            .map(|m| match lanes.assignment(&m.name) {
+                Some(assignment) => format!("{} ({})", m.name, assignment.role()),
+                None => m.name.clone(),
+            })
+            .collect();
+        factors.push((missing.len() as i32 * PER_MISSING_ENEMY + rotating * ROTATING_ROLE,
+            format!("{} missing: {}", missing.len(), names.join(", "))));
+    }
+
+    // x + y grows towards Dire, so flip it for Dire players
+    let depth = if team == Team::Radiant { position.0 + position.1 } else { -(position.0 + position.1) };
+    if depth > RIVER_MARGIN {
+        factors.push((((depth - RIVER_MARGIN) / DEPTH_UNIT).min(MAX_DEPTH),
+            format!("{} units past the river", depth - RIVER_MARGIN)));
+    }
+
+    if let Some(map) = state.map.as_ref() {
+        let cycle = DayNight::from_clock(map.clock_time.unwrap_or(0), map.daytime, map.nightstalker_night.unwrap_or(false));
+        if !cycle.is_day {
+            factors.push((NIGHT, "night - shorter vision".to_string()));
+        }
+    }
+
+    for enemy in enemies.values() {
+        let unseen = game_time - enemy.last_seen_time;
+        if let Some(prediction) = predict_position(enemy, game_time)
+            .filter(|p| calculate_distance(position, p.position) < calculate_distance(position, enemy.last_seen_position))
+            .filter(|p| calculate_distance(position, p.position) <= thresholds.medium_distance) {
+            factors.push((PER_CLOSING_ENEMY, format!("{} was heading your way {}s ago", enemy.name, prediction.seconds_unseen)));
+        } else if unseen <= RECENT_SIGHTING && calculate_distance(position, enemy.last_seen_position) <= thresholds.medium_distance {
+            factors.push((PER_NEARBY_ENEMY, format!("{} seen close by {}s ago", enemy.name, unseen)));
+        }
+    }
+
+    factors.sort_by_key(|(points, _)| -points);
+    Some(GankRisk {
+        score: factors.iter().map(|(points, _)| points).sum::<i32>().min(100),
+        reasons: factors.into_iter().map(|(_, reason)| reason).collect(),
+    })
+}
@@ -0,0 +1,102 @@
+// src/analysis/item_timings.rs
+use std::collections::{HashMap, HashSet};
+
+use crate::format::{format_game_time, format_item_name};
+use crate::inventory::Inventory;
+use crate::items;
+
+// Completed items at least this expensive count as key items even without a target
+pub const KEY_ITEM_COST: i32 = 1400;
+
+#[derive(Clone, Debug)]
+pub struct ItemTiming {
+    // Item class without the item_ prefix
+    pub item: String,
+    // Clock time the item first showed up
+    pub time: i32,
+    // Benchmark clock minute for the player's role, if there is one
+    pub target_minute: Option<i32>,
+}
+
+impl ItemTiming {
+    // Seconds ahead of (negative) or behind (positive) the benchmark
+    pub fn delta(&self) -> Option<i32> {
+        self.target_minute.map(|minute| self.time - minute * 60)
+    }
+
+    // e.g. "Black King Bar at 22:30 - 1:30 ahead of the 24:00 target"
+    pub fn describe(&self) -> String {
+        let name = format_item_name(&self.item);
+        match (self.delta(), self.target_minute) {
+            (Some(delta), Some(minute)) => format!("{} at {} - {} {} the {}:00 target",
+                name,
+                format_game_time(Some(self.time)),
+                format_game_time(Some(delta.abs())),
+                if delta <= 0 { "ahead of" } else { "behind" },
+                minute),
+            _ => format!("{} at {}", name, format_game_time(Some(self.time))),
+        }
+    }
+}
+
+// Records when key items first appear in the inventory or stash
+pub struct ItemTimingTracker {
+    // None until the first inventory has been seen
+    seen: Option<HashSet<String>>,
+    timings: Vec<ItemTiming>,
+}
+
+fn is_key_item(class: &str, targets: &HashMap<String, i32>) -> bool {
+    targets.contains_key(class) || items::by_class(class)
+        .map(|info| !info.consumable && info.cost >= KEY_ITEM_COST)
+        .unwrap_or(false)
+}
+
+impl ItemTimingTracker {
+    pub fn new() -> Self {
+        ItemTimingTracker {
+            seen: None,
+            timings: Vec::new(),
+        }
+    }
+
+    // Timings of items that appeared since the last update. Items owned when the
+    // coach first sees the inventory are skipped, since their timing is unknown.
+    pub fn update(&mut self, inventory: &Inventory, clock_time: i32, targets: &HashMap<String, i32>) -> Vec<ItemTiming> {
+        let current: HashSet<String> = inventory.all_items()
+            .filter_map(|item| item.name.as_deref())
+            .map(|name| name.trim_start_matches("item_").to_string())
+            .filter(|class| is_key_item(class, targets))
+            .collect();
+
+        let Some(seen) = &mut self.seen else {
+            self.seen = Some(current);
+            return Vec::new();
+        };
+
+        let mut new = Vec::new();
+        for class in current {
+            if seen.insert(class.clone()) {
+                new.push(ItemTiming {
+                    target_minute: targets.get(&class).copied(),
+                    item: class,
+                    time: clock_time,
+                });
+            }
+        }
+        new.sort_by(|a, b| a.item.cmp(&b.item));
+        self.timings.extend(new.iter().cloned());
+        new
+    }
+
+    // Every recorded timing in the order the items were finished
+    pub fn timings(&self) -> &[ItemTiming] {
+        &self.timings
+    }
+}
+
+impl Default for ItemTimingTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,135 @@
+// src/analysis/laning.rs
+use crate::analysis::performance::{HeroPerformanceTracker, Metric, Trend};
+use crate::benchmarks::{BenchmarkConfig, CsCheck};
+use crate::briefing::Lane;
+use crate::buildings::{self, BuildingKind, BuildingLane};
+use crate::state::{GameState, Team};
+
+// The laning stage is judged at 10:00
+pub const REPORT_AT: i32 = 10 * 60;
+
+// A coach started later than this after 10:00 skips the report, since the laning stage is long over
+const REPORT_WINDOW: i32 = 60;
+
+// Tier 1 towers in the player's lane, as health percent. None once destroyed.
+#[derive(Clone, Copy, Debug)]
+pub struct LaneTowers {
+    pub own: Option<i32>,
+    pub enemy: Option<i32>,
+}
+
+#[derive(Clone, Debug)]
+pub struct LaningReport {
+    pub team: Team,
+    pub lane: Lane,
+    pub cs: CsCheck,
+    pub deaths: i32,
+    // None when the payload has no buildings
+    pub towers: Option<LaneTowers>,
+    pub gpm: i32,
+    pub xpm: i32,
+    pub gpm_trend: Option<Trend>,
+    pub xpm_trend: Option<Trend>,
+}
+
+// The building lane a team's lane is on
+fn building_lane(lane: Lane, team: Team) -> BuildingLane {
+    match (lane, team) {
+        (Lane::Mid, _) => BuildingLane::Mid,
+        (Lane::Safe, Team::Radiant) | (Lane::Off, Team::Dire) => BuildingLane::Bottom,
+        (Lane::Safe, Team::Dire) | (Lane::Off, Team::Radiant) => BuildingLane::Top,
+    }
+}
+
+fn tier_one(state: &GameState, team: Team, lane: BuildingLane) -> Option<i32> {
+    buildings::team_buildings(state, team).iter()
+        .find(|b| b.kind == BuildingKind::Tower(1) && b.lane == lane)
+        .map(|b| b.health_percent())
+}
+
+impl LaningReport {
+    // "won", "even" or "lost", from farm, deaths and the lane towers
+    pub fn verdict(&self) -> &'static str {
+        let mut points = 0;
+        points += if self.cs.last_hits >= self.cs.target_last_hits { 1 } else if self.cs.on_track() { 0 } else { -1 };
+        points += match self.deaths {
+            0 => 1,
+            1 | 2 => 0,
+            _ => -1,
+        };
+        if let Some(towers) = self.towers {
+            points += towers.enemy.is_none() as i32 - towers.own.is_none() as i32;
+        }
+        match points {
+            1.. => "won",
+            0 => "even",
+            _ => "lost",
+        }
+    }
+
+    fn describe_towers(&self) -> Option<String> {
+        let towers = self.towers?;
+        let status = |health: Option<i32>| health.map(|h| format!("{}%", h)).unwrap_or_else(|| "destroyed".to_string());
+        Some(format!("Tier 1 {}: yours {}, theirs {}",
+            building_lane(self.lane, self.team).name(), status(towers.own), status(towers.enemy)))
+    }
+
+    // One line per part of the report
+    pub fn lines(&self) -> Vec<String> {
+        let trend = |trend: Option<Trend>| trend.map(|t| format!(" {}", t.arrow())).unwrap_or_default();
+        let mut lines = vec![
+            format!("CS: {} last hits / {} denies (target {} / {})",
+                self.cs.last_hits, self.cs.denies, self.cs.target_last_hits, self.cs.target_denies),
+            format!("Deaths: {}", self.deaths),
+        ];
+        lines.extend(self.describe_towers());
+        lines.push(format!("GPM {}{}, XPM {}{}", self.gpm, trend(self.gpm_trend), self.xpm, trend(self.xpm_trend)));
+        lines
+    }
+
+    // e.g. "Safe lane won - 84/80 CS, 1 death, GPM 480, XPM 560"
+    pub fn summary(&self) -> String {
+        let name = self.lane.name();
+        format!("{}{} {} - {}/{} CS, {} death{}, GPM {}, XPM {}",
+            name[..1].to_uppercase(), &name[1..],
+            self.verdict(),
+            self.cs.last_hits, self.cs.target_last_hits,
+            self.deaths, if self.deaths == 1 { "" } else { "s" },
+            self.gpm, self.xpm)
+    }
+}
+
+// The laning report for the player, once the clock passes 10:00
+pub fn build(
+    state: &GameState,
+    lane: Lane,
+    position: u8,
+    benchmarks: &BenchmarkConfig,
+    performance: &HeroPerformanceTracker,
+) -> Option<LaningReport> {
+    let clock_time = state.clock_time()?;
+    if !(REPORT_AT..REPORT_AT + REPORT_WINDOW).contains(&clock_time) {
+        return None;
+    }
+    let team = state.player_team().and_then(|t| Team::from_name(&t))?;
+    let player = state.player()?;
+    let cs = benchmarks.compare(REPORT_AT / 60, position, player.last_hits.unwrap_or(0), player.denies.unwrap_or(0))?;
+
+    let enemy_team = if team == Team::Radiant { Team::Dire } else { Team::Radiant };
+    let towers = state.buildings.as_ref().map(|_| LaneTowers {
+        own: tier_one(state, team, building_lane(lane, team)),
+        enemy: tier_one(state, enemy_team, building_lane(lane, team)),
+    });
+
+    Some(LaningReport {
+        team,
+        lane,
+        cs,
+        deaths: player.deaths.unwrap_or(0),
+        towers,
+        gpm: player.gpm.unwrap_or(0),
+        xpm: player.xpm.unwrap_or(0),
+        gpm_trend: performance.trend(Metric::Gpm),
+        xpm_trend: performance.trend(Metric::Xpm),
+    })
+}
@@ -0,0 +1,10 @@
+// src/analysis/mod.rs
+// Trackers that read meaning out of the differences between consecutive game states
+
+pub mod awareness;
+pub mod death_recap;
+pub mod gank_risk;
+pub mod item_timings;
+pub mod laning;
+pub mod performance;
+pub mod teamfight;
@@ -0,0 +1,221 @@
+// src/analysis/performance.rs
+use std::collections::VecDeque;
+
+use serde::Serialize;
+
+use crate::state::GameState;
+
+// Seconds between samples at the start of a game
+pub const SAMPLE_INTERVAL: i32 = 30;
+
+// Once this many samples are kept, every other one is dropped and the interval doubles.
+// Long games keep their whole history at a coarser resolution.
+pub const MAX_SAMPLES: usize = 240;
+
+// How far back trends look
+pub const TREND_WINDOW: i32 = 5 * 60;
+
+#[derive(Clone, Copy, Debug, Serialize)]
+pub struct Sample {
+    pub time: i32,
+    pub gpm: i32,
+    pub xpm: i32,
+    pub last_hits: i32,
+    pub deaths: i32,
+    pub net_worth: i32,
+}
+
+impl Sample {
+    // The player's numbers right now
+    pub fn from_state(state: &GameState) -> Option<Self> {
+        let player = state.player()?;
+        Some(Sample {
+            time: state.game_time()?,
+            gpm: player.gpm.unwrap_or(0),
+            xpm: player.xpm.unwrap_or(0),
+            last_hits: player.last_hits.unwrap_or(0),
+            deaths: player.deaths.unwrap_or(0),
+            net_worth: player.net_worth.unwrap_or(0),
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Metric {
+    Gpm,
+    Xpm,
+    NetWorth,
+    LastHits,
+    Deaths,
+}
+
+impl Metric {
+    pub const ALL: [Metric; 5] = [Metric::Gpm, Metric::Xpm, Metric::NetWorth, Metric::LastHits, Metric::Deaths];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Metric::Gpm => "GPM",
+            Metric::Xpm => "XPM",
+            Metric::NetWorth => "NW",
+            Metric::LastHits => "CS",
+            Metric::Deaths => "Deaths",
+        }
+    }
+
+    pub fn value(&self, sample: &Sample) -> i32 {
+        match self {
+            Metric::Gpm => sample.gpm,
+            Metric::Xpm => sample.xpm,
+            Metric::NetWorth => sample.net_worth,
+            Metric::LastHits => sample.last_hits,
+            Metric::Deaths => sample.deaths,
+        }
+    }
+
+    // Running totals are compared by rate, per-minute stats by value
+    fn cumulative(&self) -> bool {
+        matches!(self, Metric::NetWorth | Metric::LastHits | Metric::Deaths)
+    }
+
+    pub fn higher_is_better(&self) -> bool {
+        *self != Metric::Deaths
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Trend {
+    Rising,
+    Steady,
+    Falling,
+}
+
+impl Trend {
+    pub fn arrow(&self) -> &'static str {
+        match self {
+            Trend::Rising => "↑",
+            Trend::Steady => "→",
+            Trend::Falling => "↓",
+        }
+    }
+}
+
+// Summary shown once the game is over
+#[derive(Clone, Debug)]
+pub struct PerformanceReport {
+    pub duration: i32,
+    pub gpm: i32,
+    pub xpm: i32,
+    pub last_hits: i32,
+    pub cs_per_minute: f32,
+    pub peak_gpm: (i32, i32),
+    // Game times (to the sample) at which the player died
+    pub deaths: Vec<i32>,
+}
+
+// Time series of the player's economy and deaths
+pub struct HeroPerformanceTracker {
+    samples: VecDeque<Sample>,
+    interval: i32,
+}
+
+impl HeroPerformanceTracker {
+    pub fn new() -> Self {
+        HeroPerformanceTracker {
+            samples: VecDeque::with_capacity(MAX_SAMPLES),
+            interval: SAMPLE_INTERVAL,
+        }
+    }
+
+    pub fn update(&mut self, state: &GameState) {
+        let Some(sample) = Sample::from_state(state) else { return };
+        if sample.time < 0 || self.latest().map(|s| sample.time < s.time + self.interval).unwrap_or(false) {
+            return;
+        }
+
+        if self.samples.len() == MAX_SAMPLES {
+            let mut keep = false;
+            self.samples.retain(|_| { keep = !keep; keep });
+            self.interval *= 2;
+        }
+
+        self.samples.push_back(sample);
+    }
+
+    pub fn samples(&self) -> impl Iterator<Item = &Sample> {
+        self.samples.iter()
+    }
+
+    // Every recorded value of one metric, oldest first
+    pub fn series(&self, metric: Metric) -> Vec<i32> {
+        self.samples.iter().map(|s| metric.value(s)).collect()
+    }
+
+    pub fn latest(&self) -> Option<&Sample> {
+        self.samples.back()
+    }
+
+    // The newest sample at least `seconds` older than the latest one
+    fn sample_before(&self, seconds: i32) -> Option<&Sample> {
+        let latest = self.latest()?;
+        self.samples.iter().rev().find(|s| s.time <= latest.time - seconds)
+    }
+
+    fn per_minute(from: &Sample, to: &Sample, metric: Metric) -> Option<f32> {
+        let minutes = (to.time - from.time) as f32 / 60.0;
+        (minutes > 0.0).then(|| (metric.value(to) - metric.value(from)) as f32 / minutes)
+    }
+
+    // Per-minute stats compare now with TREND_WINDOW ago; running totals compare
+    // their recent rate with the rate over the whole game.
+    pub fn trend(&self, metric: Metric) -> Option<Trend> {
+        let latest = self.latest()?;
+        let before = self.sample_before(TREND_WINDOW)?;
+
+        let (recent, baseline, tolerance) = if metric.cumulative() {
+            let first = self.samples.front()?;
+            let overall = Self::per_minute(first, latest, metric)?;
+            (Self::per_minute(before, latest, metric)?, overall, (overall * 0.1).max(0.1))
+        } else {
+            (metric.value(latest) as f32, metric.value(before) as f32, 20.0)
+        };
+
+        Some(if recent > baseline + tolerance {
+            Trend::Rising
+        } else if recent < baseline - tolerance {
+            Trend::Falling
+        } else {
+            Trend::Steady
+        })
+    }
+
+    pub fn report(&self) -> Option<PerformanceReport> {
+        let latest = self.latest()?;
+        let peak = self.samples.iter().max_by_key(|s| s.gpm)?;
+        let minutes = latest.time as f32 / 60.0;
+
+        let mut deaths = Vec::new();
+        let mut previous = 0;
+        for sample in &self.samples {
+            for _ in previous..sample.deaths {
+                deaths.push(sample.time);
+            }
+            previous = previous.max(sample.deaths);
+        }
+
+        Some(PerformanceReport {
+            duration: latest.time,
+            gpm: latest.gpm,
+            xpm: latest.xpm,
+            last_hits: latest.last_hits,
+            cs_per_minute: if minutes > 0.0 { latest.last_hits as f32 / minutes } else { 0.0 },
+            peak_gpm: (peak.gpm, peak.time),
+            deaths,
+        })
+    }
+}
+
+impl Default for HeroPerformanceTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
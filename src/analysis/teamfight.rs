@@ -0,0 +1,191 @@
+// src/analysis/teamfight.rs
+use std::collections::VecDeque;
+
+use crate::format::format_game_time;
+use crate::state::{GameState, Team};
+
+// Kills this close together make a fight
+pub const FIGHT_WINDOW: i32 = 30;
+pub const FIGHT_MIN_KILLS: usize = 3;
+
+// A fight is over once nobody has died for this long
+pub const FIGHT_COOLDOWN: i32 = 15;
+
+// Kills in the last minute that hint a fight is developing
+const SKIRMISH_WINDOW: i32 = 60;
+const SKIRMISH_MIN_KILLS: usize = 2;
+
+#[derive(Clone, Copy, Debug)]
+struct Kill {
+    time: i32,
+    // The team that lost the hero, when known
+    victim: Option<Team>,
+}
+
+#[derive(Clone, Debug)]
+pub struct TeamFight {
+    pub start: i32,
+    pub last_kill: i32,
+    pub radiant_deaths: i32,
+    pub dire_deaths: i32,
+    // Deaths whose team couldn't be told from the game state
+    pub unknown_deaths: i32,
+}
+
+impl TeamFight {
+    fn count(&mut self, victim: Option<Team>) {
+        match victim {
+            Some(Team::Radiant) => self.radiant_deaths += 1,
+            Some(Team::Dire) => self.dire_deaths += 1,
+            None => self.unknown_deaths += 1,
+        }
+    }
+
+    pub fn deaths(&self, team: Team) -> i32 {
+        match team {
+            Team::Radiant => self.radiant_deaths,
+            Team::Dire => self.dire_deaths,
+        }
+    }
+
+    pub fn total_deaths(&self) -> i32 {
+        self.radiant_deaths + self.dire_deaths + self.unknown_deaths
+    }
+
+    pub fn duration(&self) -> i32 {
+        self.last_kill - self.start
+    }
+
+    // e.g. "won 3 for 1", seen from `team`
+    pub fn outcome(&self, team: Option<Team>) -> String {
+        let Some(team) = team.filter(|_| self.unknown_deaths == 0) else {
+            return format!("{} heroes died", self.total_deaths());
+        };
+        let enemy = if team == Team::Radiant { Team::Dire } else { Team::Radiant };
+        let (killed, lost) = (self.deaths(enemy), self.deaths(team));
+        let verdict = if killed > lost { "won" } else if killed < lost { "lost" } else { "traded" };
+        format!("{} {} for {}", verdict, killed, lost)
+    }
+
+    pub fn describe(&self, team: Option<Team>) -> String {
+        format!("Fight from {} lasted {}s: {}",
+            format_game_time(Some(self.start)),
+            self.duration(),
+            self.outcome(team))
+    }
+}
+
+pub enum FightTransition {
+    Started(TeamFight),
+    Ended(TeamFight),
+}
+
+// Notices clusters of kills. Uses the team scores when the client sends them,
+// otherwise the player's own deaths and kill list.
+pub struct TeamFightAnalyzer {
+    kills: VecDeque<Kill>,
+    fight: Option<TeamFight>,
+}
+
+fn scores(state: &GameState) -> Option<(i32, i32)> {
+    let map = state.map.as_ref()?;
+    Some((map.radiant_score?, map.dire_score?))
+}
+
+fn total_player_kills(state: &GameState) -> Option<i32> {
+    state.player()?.kill_list.as_ref().map(|list| list.values().sum())
+}
+
+impl TeamFightAnalyzer {
+    pub fn new() -> Self {
+        TeamFightAnalyzer {
+            kills: VecDeque::new(),
+            fight: None,
+        }
+    }
+
+    // The fight going on right now, if any
+    pub fn current(&self) -> Option<&TeamFight> {
+        self.fight.as_ref()
+    }
+
+    // Kills since `game_time - window`
+    fn recent_kills(&self, game_time: i32, window: i32) -> impl Iterator<Item = &Kill> {
+        self.kills.iter().filter(move |k| k.time >= game_time - window)
+    }
+
+    // Scattered kills that may turn into a fight
+    pub fn skirmishing(&self, game_time: i32) -> bool {
+        self.fight.is_none() && self.recent_kills(game_time, SKIRMISH_WINDOW).count() >= SKIRMISH_MIN_KILLS
+    }
+
+    fn deaths_since(state: &GameState, last: &GameState, team: Option<Team>) -> Vec<Kill> {
+        let time = state.game_time().unwrap_or(0);
+        let enemy = team.map(|t| if t == Team::Radiant { Team::Dire } else { Team::Radiant });
+
+        if let (Some((radiant, dire)), Some((last_radiant, last_dire))) = (scores(state), scores(last)) {
+            // Radiant's score counts Dire heroes killed and the other way round
+            let mut kills = vec![Kill { time, victim: Some(Team::Dire) }; (radiant - last_radiant).max(0) as usize];
+            kills.extend(vec![Kill { time, victim: Some(Team::Radiant) }; (dire - last_dire).max(0) as usize]);
+            return kills;
+        }
+
+        let mut kills = Vec::new();
+        let was_alive = last.hero().and_then(|h| h.alive).unwrap_or(true);
+        let is_alive = state.hero().and_then(|h| h.alive).unwrap_or(true);
+        if was_alive && !is_alive {
+            kills.push(Kill { time, victim: team });
+        }
+        if let (Some(now), Some(before)) = (total_player_kills(state), total_player_kills(last)) {
+            kills.extend(vec![Kill { time, victim: enemy }; (now - before).max(0) as usize]);
+        }
+        kills
+    }
+
+    pub fn update(&mut self, state: &GameState, last_state: Option<&GameState>) -> Option<FightTransition> {
+        let game_time = state.game_time()?;
+        let team = state.player_team().and_then(|t| Team::from_name(&t));
+
+        let new_kills = last_state.map(|last| Self::deaths_since(state, last, team)).unwrap_or_default();
+        for kill in &new_kills {
+            self.kills.push_back(*kill);
+            if let Some(fight) = &mut self.fight {
+                fight.last_kill = kill.time;
+                fight.count(kill.victim);
+            }
+        }
+
+        // Only the fight window matters for detection
+        while self.kills.front().map(|k| k.time < game_time - SKIRMISH_WINDOW).unwrap_or(false) {
+            self.kills.pop_front();
+        }
+
+        match &self.fight {
+            Some(fight) if game_time - fight.last_kill >= FIGHT_COOLDOWN => {
+                self.fight.take().map(FightTransition::Ended)
+            },
+            None if !new_kills.is_empty() && self.recent_kills(game_time, FIGHT_WINDOW).count() >= FIGHT_MIN_KILLS => {
+                let mut fight = TeamFight {
+                    start: game_time,
+                    last_kill: game_time,
+                    radiant_deaths: 0,
+                    dire_deaths: 0,
+                    unknown_deaths: 0,
+                };
+                for kill in self.recent_kills(game_time, FIGHT_WINDOW) {
+                    fight.start = fight.start.min(kill.time);
+                    fight.count(kill.victim);
+                }
+                self.fight = Some(fight.clone());
+                Some(FightTransition::Started(fight))
+            },
+            _ => None,
+        }
+    }
+}
+
+impl Default for TeamFightAnalyzer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,149 @@
+// src/audio.rs
+use std::io::{self, Write};
+use std::process::{Command, ExitStatus, Stdio};
+use std::sync::mpsc::{self, Sender};
+use std::thread;
+
+use serde::Deserialize;
+
+use crate::alerts::{Alert, AlertCategory, AlertSink, Severity};
+
+// Which alerts should be heard, and how
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct AudioSettings {
+    // Read alerts out loud through the operating system's speech synthesizer
+    pub speech: bool,
+    // Play short tones (needs the `sound` feature)
+    pub sound: bool,
+    pub categories: Vec<AlertCategory>,
+}
+
+impl Default for AudioSettings {
+    fn default() -> Self {
+        AudioSettings {
+            speech: false,
+            sound: false,
+            categories: vec![AlertCategory::MissingEnemies, AlertCategory::Smoke, AlertCategory::Rune],
+        }
+    }
+}
+
+impl AudioSettings {
+    pub fn enabled(&self) -> bool {
+        self.speech || self.sound
+    }
+}
+
+// Something to play, queued for the audio thread
+struct Cue {
+    severity: Severity,
+    speech: Option<String>,
+}
+
+// Plays alerts on a background thread so the GSI handler never waits on audio
+pub struct AudioOutput {
+    categories: Vec<AlertCategory>,
+    speech: bool,
+    queue: Sender<Cue>,
+}
+
+impl AudioOutput {
+    pub fn new(settings: &AudioSettings) -> Self {
+        let (queue, cues) = mpsc::channel::<Cue>();
+        let sound = settings.sound;
+
+        if sound && !cfg!(feature = "sound") {
+            eprintln!("Sound cues requested but the coach was built without the `sound` feature");
+        }
+
+        thread::spawn(move || {
+            for cue in cues {
+                if sound {
+                    play_tone(cue.severity);
+                }
+                if let Some(text) = cue.speech {
+                    speak(&text);
+                }
+            }
+        });
+
+        AudioOutput {
+            categories: settings.categories.clone(),
+            speech: settings.speech,
+            queue,
+        }
+    }
+}
+
+impl AlertSink for AudioOutput {
+    fn notify(&self, alert: &Alert) {
+        if !self.categories.contains(&alert.category) {
+            return;
+        }
+
+        let speech = if self.speech { Some(alert.spoken_text()).filter(|text| !text.trim().is_empty()) } else { None };
+        let _ = self.queue.send(Cue { severity: alert.severity, speech });
+    }
+}
+
+// Run a synthesizer that reads the phrase from stdin
+fn speak_stdin(mut command: Command, text: &str) -> io::Result<ExitStatus> {
+    let mut child = command.stdin(Stdio::piped()).spawn()?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(text.as_bytes())?;
+    }
+    child.wait()
+}
+
+// Speak a phrase with whatever synthesizer the platform provides, blocking until done.
+// Alert text can come from the game state or plugins, so it never becomes part of a script
+// and never sits where it could be read as an option.
+fn speak(text: &str) {
+    let result = if cfg!(target_os = "macos") {
+        let mut say = Command::new("say");
+        say.args(["-f", "-"]);
+        speak_stdin(say, text)
+    } else if cfg!(target_os = "windows") {
+        let script = "Add-Type -AssemblyName System.Speech; (New-Object System.Speech.Synthesis.SpeechSynthesizer).Speak($env:COACH_SPEECH)";
+        Command::new("powershell").args(["-NoProfile", "-Command", script]).env("COACH_SPEECH", text).status()
+    } else {
+        let mut espeak = Command::new("espeak");
+        espeak.arg("--stdin");
+        speak_stdin(espeak, text)
+            .or_else(|_| Command::new("spd-say").args(["--wait", "--", text]).status())
+    };
+
+    if let Err(e) = result {
+        eprintln!("Could not speak alert: {}", e);
+    }
+}
+
+// Short beeps: one for warnings, two higher ones for critical alerts
+#[cfg(feature = "sound")]
+fn play_tone(severity: Severity) {
+    use rodio::source::{SineWave, Source};
+    use std::time::Duration;
+
+    let stream = match rodio::OutputStreamBuilder::open_default_stream() {
+        Ok(stream) => stream,
+        Err(e) => {
+            eprintln!("Could not open audio output: {}", e);
+            return;
+        }
+    };
+
+    let (frequency, beeps) = match severity {
+        Severity::Critical => (1320.0, 2),
+        Severity::Warning => (880.0, 1),
+        Severity::Info => (660.0, 1),
+    };
+
+    for _ in 0..beeps {
+        stream.mixer().add(SineWave::new(frequency).amplify(0.2).take_duration(Duration::from_millis(150)));
+        thread::sleep(Duration::from_millis(250));
+    }
+}
+
+#[cfg(not(feature = "sound"))]
+fn play_tone(_severity: Severity) {}
@@ -0,0 +1,99 @@
+// src/barracks.rs
+use std::collections::HashMap;
+
+use crate::buildings::{self, BuildingKind, BuildingLane};
+use crate::state::{GameState, Team};
+
+const LANES: [BuildingLane; 3] = [BuildingLane::Top, BuildingLane::Mid, BuildingLane::Bottom];
+const KINDS: [BuildingKind; 2] = [BuildingKind::MeleeBarracks, BuildingKind::RangedBarracks];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum CreepTier {
+    Normal,
+    // The enemy lost a barracks in the lane
+    Super,
+    // The enemy lost all six
+    Mega,
+}
+
+impl CreepTier {
+    pub fn name(&self) -> &'static str {
+        match self {
+            CreepTier::Normal => "normal creeps",
+            CreepTier::Super => "super creeps",
+            CreepTier::Mega => "mega creeps",
+        }
+    }
+}
+
+// Barracks of `team` that are gone, by lane. None when the payload doesn't list the
+// team's buildings at all.
+pub fn destroyed(state: &GameState, team: Team) -> Option<Vec<(BuildingLane, BuildingKind)>> {
+    let standing = buildings::team_buildings(state, team);
+    if standing.is_empty() {
+        return None;
+    }
+    Some(LANES.iter()
+        .flat_map(|&lane| KINDS.iter().map(move |&kind| (lane, kind)))
+        .filter(|(lane, kind)| !standing.iter().any(|b| b.lane == *lane && b.kind == *kind))
+        .collect())
+}
+
+// The creeps `team` sends down each lane
+pub fn creeps(state: &GameState, team: Team) -> Option<Vec<(BuildingLane, CreepTier)>> {
+    let enemy = if team == Team::Radiant { Team::Dire } else { Team::Radiant };
+    let lost = destroyed(state, enemy)?;
+    let mega = lost.len() == LANES.len() * KINDS.len();
+    Some(LANES.iter().map(|&lane| (lane, match (mega, lost.iter().any(|(l, _)| *l == lane)) {
+        (true, _) => CreepTier::Mega,
+        (false, true) => CreepTier::Super,
+        (false, false) => CreepTier::Normal,
+    })).collect())
+}
+
+// A team's creeps getting stronger in some lanes
+#[derive(Clone, Debug)]
+pub struct CreepUpgrade {
+    pub team: Team,
+    pub tier: CreepTier,
+    pub lanes: Vec<BuildingLane>,
+}
+
+// Remembers each team's creeps, to call out upgrades as barracks fall
+#[derive(Default)]
+pub struct CreepTracker {
+    known: HashMap<Team, Vec<(BuildingLane, CreepTier)>>,
+}
+
+impl CreepTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn upgrades(&mut self, state: &GameState) -> Vec<CreepUpgrade> {
+        let mut upgrades = Vec::new();
+        for team in Team::BOTH {
+            let Some(now) = creeps(state, team) else { continue };
+            let before = self.known.insert(team, now.clone());
+            for tier in [CreepTier::Super, CreepTier::Mega] {
+                let was = |lane: BuildingLane| before.as_ref()
+                    .and_then(|b| b.iter().find(|(l, _)| *l == lane))
+                    .map(|(_, t)| *t)
+                    .unwrap_or(CreepTier::Normal);
+                let lanes: Vec<BuildingLane> = now.iter()
+                    .filter(|(lane, t)| *t == tier && was(*lane) < tier)
+                    .map(|(lane, _)| *lane)
+                    .collect();
+                if !lanes.is_empty() {
+                    upgrades.push(CreepUpgrade { team, tier, lanes });
+                }
+            }
+        }
+        upgrades
+    }
+
+    // Whether `team` has mega creeps as of the last update
+    pub fn has_megas(&self, team: Team) -> bool {
+        self.known.get(&team).map(|lanes| lanes.iter().all(|(_, t)| *t == CreepTier::Mega)).unwrap_or(false)
+    }
+}
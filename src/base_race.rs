@@ -0,0 +1,123 @@
+// src/base_race.rs
+use std::collections::{HashMap, VecDeque};
+
+use crate::buildings::{self, BuildingKind};
+use crate::state::{GameState, Team};
+
+// Damage is averaged over this many seconds
+const WINDOW: i32 = 20;
+
+// Too little damage to go on before this long
+const MIN_OBSERVED: i32 = 5;
+
+// A race won by less than this could go either way
+const MARGIN: i32 = 5;
+
+// Advice is repeated this often while the verdict holds
+const REPEAT_EVERY: i32 = 20;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    Winnable,
+    TooClose,
+    Lost,
+}
+
+// Both ancients going down at once, in seconds left for each at the current damage
+#[derive(Clone, Copy, Debug)]
+pub struct Race {
+    // Until the player's team kills the enemy ancient
+    pub ours: i32,
+    // Until the enemy kills the player's ancient
+    pub theirs: i32,
+}
+
+impl Race {
+    pub fn outcome(&self) -> Outcome {
+        if self.ours + MARGIN < self.theirs {
+            Outcome::Winnable
+        } else if self.theirs + MARGIN < self.ours {
+            Outcome::Lost
+        } else {
+            Outcome::TooClose
+        }
+    }
+
+    pub fn describe(&self) -> String {
+        let advice = match self.outcome() {
+            Outcome::Winnable => "you win the race, keep hitting",
+            Outcome::TooClose => "too close to call, send someone back if you can",
+            Outcome::Lost => "you lose the race, go back and defend",
+        };
+        format!("Base race: their ancient falls in ~{}s, yours in ~{}s - {}", self.ours, self.theirs, advice)
+    }
+}
+
+// The ancient's health once both tier 4 towers are gone, None while either stands
+fn exposed_ancient(state: &GameState, team: Team) -> Option<i32> {
+    let standing = buildings::team_buildings(state, team);
+    if standing.iter().any(|b| b.kind == BuildingKind::Tower(4)) {
+        return None;
+    }
+    standing.iter().find(|b| b.kind == BuildingKind::Ancient).map(|b| b.health)
+}
+
+// Follows both ancients' health while they're exposed, and works out who kills theirs first
+// from the damage each has taken lately
+#[derive(Default)]
+pub struct BaseRaceTracker {
+    health: HashMap<Team, VecDeque<(i32, i32)>>,
+    last_advice: Option<(Outcome, i32)>,
+}
+
+impl BaseRaceTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Seconds until `team`'s ancient falls at the damage it took over the last WINDOW
+    fn time_to_kill(&self, team: Team) -> Option<i32> {
+        let samples = self.health.get(&team)?;
+        let (&(first_time, first_health), &(last_time, last_health)) = (samples.front()?, samples.back()?);
+        let elapsed = last_time - first_time;
+        let damage = first_health - last_health;
+        if elapsed < MIN_OBSERVED || damage <= 0 {
+            return None;
+        }
+        Some((last_health as f32 / (damage as f32 / elapsed as f32)).ceil() as i32)
+    }
+
+    // The race when both ancients are exposed and taking damage, and advice is due
+    pub fn update(&mut self, state: &GameState, team: Team, game_time: i32) -> Option<Race> {
+        for side in Team::BOTH {
+            match exposed_ancient(state, side) {
+                Some(health) => {
+                    let samples = self.health.entry(side).or_default();
+                    samples.push_back((game_time, health));
+                    while samples.front().map(|(t, _)| game_time - t > WINDOW).unwrap_or(false) {
+                        samples.pop_front();
+                    }
+                },
+                None => {
+                    self.health.remove(&side);
+                },
+            }
+        }
+
+        let enemy = if team == Team::Radiant { Team::Dire } else { Team::Radiant };
+        let Some(race) = self.time_to_kill(enemy).zip(self.time_to_kill(team)).map(|(ours, theirs)| Race { ours, theirs }) else {
+            self.last_advice = None;
+            return None;
+        };
+        let outcome = race.outcome();
+        let due = match self.last_advice {
+            Some((last, at)) => last != outcome || game_time - at >= REPEAT_EVERY,
+            None => true,
+        };
+        if !due {
+            return None;
+        }
+        self.last_advice = Some((outcome, game_time));
+        Some(race)
+    }
+}
@@ -0,0 +1,153 @@
+// src/baselines.rs
+use crate::analysis::performance::{Metric, Sample};
+use crate::format::format_hero_name;
+use crate::history::MatchHistory;
+
+// Past matches needed before an average means anything
+pub const MIN_MATCHES: usize = 3;
+
+// What is compared, in display order
+const METRICS: [Metric; 4] = [Metric::LastHits, Metric::Gpm, Metric::Xpm, Metric::NetWorth];
+
+// The player's current numbers against their own average at the same point of past matches
+#[derive(Clone, Debug)]
+pub struct Baseline {
+    pub metric: Metric,
+    // Clock minute compared at
+    pub minute: i32,
+    // Display name of the hero the average is for, None when it covers every hero
+    pub hero: Option<String>,
+    pub matches: usize,
+    pub average: i32,
+    pub current: i32,
+}
+
+impl Baseline {
+    pub fn ahead(&self) -> bool {
+        if self.metric.higher_is_better() {
+            self.current >= self.average
+        } else {
+            self.current <= self.average
+        }
+    }
+
+    // e.g. "Your average CS@10 on Juggernaut is 42, currently 35"
+    pub fn describe(&self) -> String {
+        format!("Your average {}@{} {} is {}, currently {}",
+            self.metric.name(),
+            self.minute,
+            self.hero.as_ref().map(|h| format!("on {}", h)).unwrap_or_else(|| "across your games".to_string()),
+            self.average,
+            self.current)
+    }
+}
+
+// Compare `current` with the same moment of the player's past matches. Uses the
+// matches on `hero` when there are enough of them, and every match otherwise.
+pub fn compare(
+    history: &MatchHistory,
+    steam_id: &str,
+    hero: Option<&str>,
+    match_id: Option<&str>,
+    clock_time: i32,
+    current: &Sample,
+) -> Vec<Baseline> {
+    let past = |hero: Option<&str>| history.samples_at(steam_id, hero, current.time, match_id).unwrap_or_else(|e| {
+        eprintln!("Could not read match history: {}", e);
+        Vec::new()
+    });
+
+    let on_hero = hero.map(|hero| past(Some(hero))).unwrap_or_default();
+    let (samples, hero) = if on_hero.len() >= MIN_MATCHES {
+        (on_hero, hero.map(format_hero_name))
+    } else {
+        (past(None), None)
+    };
+    if samples.len() < MIN_MATCHES {
+        return Vec::new();
+    }
+
+    METRICS.iter()
+        .map(|&metric| Baseline {
+            metric,
+            minute: clock_time / 60,
+            hero: hero.clone(),
+            matches: samples.len(),
+            average: samples.iter().map(|s| metric.value(s)).sum::<i32>() / samples.len() as i32,
+            current: metric.value(current),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::history::MatchSummary;
+    use crate::series::MatchSeries;
+    use crate::vod::ClockMapping;
+
+    const PLAYER: &str = "76561198000000001";
+
+    fn sample(time: i32, last_hits: i32) -> Sample {
+        Sample { time, gpm: 500, xpm: 550, last_hits, deaths: 1, net_worth: 5000 }
+    }
+
+    fn save(history: &mut MatchHistory, match_id: &str, hero: &str, last_hits_at_ten: i32) {
+        let summary = MatchSummary {
+            match_id: match_id.to_string(),
+            steam_id: PLAYER.to_string(),
+            hero: Some(hero.to_string()),
+            team: None,
+            won: None,
+            duration: 1500,
+            kills: 0,
+            deaths: 0,
+            assists: 0,
+            last_hits: 0,
+            denies: 0,
+            gpm: 0,
+            xpm: 0,
+            net_worth: 0,
+            played_at: 0,
+        };
+        let samples = [sample(300, last_hits_at_ten / 2), sample(600, last_hits_at_ten), sample(900, last_hits_at_ten * 2)];
+        history.save(&summary, &samples, &ClockMapping::new(), &MatchSeries::new()).unwrap();
+    }
+
+    fn last_hits(baselines: &[Baseline]) -> Option<&Baseline> {
+        baselines.iter().find(|b| b.metric == Metric::LastHits)
+    }
+
+    #[test]
+    fn compares_against_the_hero_once_there_are_enough_matches() {
+        let mut history = MatchHistory::in_memory().unwrap();
+        for (match_id, last_hits) in [("1", 40), ("2", 50), ("3", 60)] {
+            save(&mut history, match_id, "juggernaut", last_hits);
+        }
+        save(&mut history, "4", "lion", 10);
+
+        let baselines = compare(&history, PLAYER, Some("juggernaut"), None, 600, &sample(600, 35));
+        let baseline = last_hits(&baselines).unwrap();
+        assert_eq!((baseline.average, baseline.current, baseline.matches, baseline.minute), (50, 35, 3, 10));
+        assert_eq!(baseline.hero.as_deref(), Some("Juggernaut"));
+        assert!(!baseline.ahead());
+    }
+
+    #[test]
+    fn falls_back_to_every_hero_and_needs_enough_matches() {
+        let mut history = MatchHistory::in_memory().unwrap();
+        save(&mut history, "1", "juggernaut", 40);
+        save(&mut history, "2", "lion", 10);
+        assert!(compare(&history, PLAYER, Some("juggernaut"), None, 600, &sample(600, 35)).is_empty());
+
+        save(&mut history, "3", "lion", 25);
+        let baselines = compare(&history, PLAYER, Some("juggernaut"), None, 600, &sample(600, 35));
+        let baseline = last_hits(&baselines).unwrap();
+        assert_eq!((baseline.average, baseline.matches), (25, 3));
+        assert_eq!(baseline.hero, None);
+        assert!(baseline.ahead());
+
+        // The match being played doesn't count towards its own baseline
+        assert!(compare(&history, PLAYER, None, Some("3"), 600, &sample(600, 35)).is_empty());
+    }
+}
@@ -0,0 +1,186 @@
+// src/benchmarks.rs
+use std::collections::HashMap;
+
+use serde::Deserialize;
+
+// Targets by role, one value per checkpoint
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct RoleTargets {
+    pub safe: Vec<i32>,
+    pub mid: Vec<i32>,
+    pub off: Vec<i32>,
+    pub support: Vec<i32>,
+}
+
+impl RoleTargets {
+    // Targets for a usual position (1-5)
+    pub fn for_position(&self, position: u8) -> &[i32] {
+        match position {
+            1 => &self.safe,
+            2 => &self.mid,
+            3 => &self.off,
+            _ => &self.support,
+        }
+    }
+}
+
+impl Default for RoleTargets {
+    fn default() -> Self {
+        // Last hits a decent player of each role has by 5, 10, 15 and 20 minutes
+        RoleTargets {
+            safe: vec![35, 80, 130, 180],
+            mid: vec![40, 85, 135, 185],
+            off: vec![25, 55, 90, 130],
+            support: vec![5, 12, 20, 30],
+        }
+    }
+}
+
+// Target clock minute for key items by role, keyed by item class without the item_ prefix
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct ItemTimingTargets {
+    pub safe: HashMap<String, i32>,
+    pub mid: HashMap<String, i32>,
+    pub off: HashMap<String, i32>,
+    pub support: HashMap<String, i32>,
+}
+
+impl ItemTimingTargets {
+    pub fn for_position(&self, position: u8) -> &HashMap<String, i32> {
+        match position {
+            1 => &self.safe,
+            2 => &self.mid,
+            3 => &self.off,
+            _ => &self.support,
+        }
+    }
+}
+
+impl Default for ItemTimingTargets {
+    fn default() -> Self {
+        let targets = |items: &[(&str, i32)]| items.iter().map(|(item, minute)| (item.to_string(), *minute)).collect();
+        ItemTimingTargets {
+            safe: targets(&[("power_treads", 11), ("manta", 19), ("black_king_bar", 24), ("greater_crit", 30)]),
+            mid: targets(&[("power_treads", 9), ("kaya", 13), ("blink", 15), ("black_king_bar", 22)]),
+            off: targets(&[("phase_boots", 10), ("blink", 14), ("blade_mail", 16), ("black_king_bar", 24)]),
+            support: targets(&[("tranquil_boots", 9), ("arcane_boots", 10), ("glimmer_cape", 16), ("force_staff", 18)]),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct BenchmarkConfig {
+    // Clock minutes at which the player's farm is compared with the targets
+    pub checkpoints: Vec<i32>,
+    pub last_hits: RoleTargets,
+    pub denies: RoleTargets,
+    pub item_timings: ItemTimingTargets,
+}
+
+impl Default for BenchmarkConfig {
+    fn default() -> Self {
+        BenchmarkConfig {
+            checkpoints: vec![5, 10, 15, 20],
+            last_hits: RoleTargets::default(),
+            denies: RoleTargets {
+                safe: vec![8, 14, 18, 20],
+                mid: vec![10, 16, 20, 22],
+                off: vec![4, 8, 10, 12],
+                support: vec![2, 4, 5, 6],
+            },
+            item_timings: ItemTimingTargets::default(),
+        }
+    }
+}
+
+impl BenchmarkConfig {
+    // Farm compared with the targets for one checkpoint minute, if there is one
+    pub fn compare(&self, minute: i32, position: u8, last_hits: i32, denies: i32) -> Option<CsCheck> {
+        let index = self.checkpoints.iter().position(|m| *m == minute)?;
+        Some(CsCheck {
+            minute,
+            position,
+            last_hits,
+            denies,
+            target_last_hits: self.last_hits.for_position(position).get(index).copied()?,
+            target_denies: self.denies.for_position(position).get(index).copied().unwrap_or(0),
+        })
+    }
+}
+
+pub fn role_name(position: u8) -> &'static str {
+    match position {
+        1 => "safe lane core",
+        2 => "mid",
+        3 => "offlaner",
+        _ => "support",
+    }
+}
+
+// The player's farm compared with the benchmark at one checkpoint
+#[derive(Clone, Debug)]
+pub struct CsCheck {
+    pub minute: i32,
+    pub position: u8,
+    pub last_hits: i32,
+    pub denies: i32,
+    pub target_last_hits: i32,
+    pub target_denies: i32,
+}
+
+impl CsCheck {
+    // Within 10% of the target still counts
+    pub fn on_track(&self) -> bool {
+        self.last_hits * 10 >= self.target_last_hits * 9
+    }
+
+    pub fn describe(&self) -> String {
+        let numbers = format!("{} last hits / {} denies at {}:00 (target for a {}: {} / {})",
+            self.last_hits, self.denies, self.minute, role_name(self.position),
+            self.target_last_hits, self.target_denies);
+
+        if self.last_hits >= self.target_last_hits {
+            format!("{} - ahead of the curve, keep it up", numbers)
+        } else if self.on_track() {
+            format!("{} - close to target, don't miss the next waves", numbers)
+        } else if self.denies < self.target_denies / 2 {
+            format!("{} - {} short. Farm between fights and contest the creep equilibrium with denies", numbers, self.target_last_hits - self.last_hits)
+        } else {
+            format!("{} - {} short. Farm between fights and push out waves before rotating", numbers, self.target_last_hits - self.last_hits)
+        }
+    }
+}
+
+// Reports each checkpoint once as the clock passes it
+pub struct CsBenchmark {
+    checked: usize,
+}
+
+impl CsBenchmark {
+    pub fn new() -> Self {
+        CsBenchmark { checked: 0 }
+    }
+
+    pub fn check(&mut self, config: &BenchmarkConfig, clock_time: i32, position: u8, last_hits: i32, denies: i32) -> Option<CsCheck> {
+        let mut due = None;
+        while let Some(&minute) = config.checkpoints.get(self.checked) {
+            if clock_time < minute * 60 {
+                break;
+            }
+            due = Some(self.checked);
+            self.checked += 1;
+        }
+
+        // Only the latest passed checkpoint is worth reporting
+        config.compare(config.checkpoints[due?], position, last_hits, denies)
+    }
+}
+
+impl Default for CsBenchmark {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,166 @@
+// src/briefing.rs
+use crate::format::format_hero_name;
+use crate::heroes;
+use crate::state::{Draft, Team};
+
+// Lanes from one team's point of view
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Lane {
+    Safe,
+    Mid,
+    Off,
+}
+
+impl Lane {
+    pub const ALL: [Lane; 3] = [Lane::Safe, Lane::Mid, Lane::Off];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Lane::Safe => "safe lane",
+            Lane::Mid => "mid",
+            Lane::Off => "off lane",
+        }
+    }
+
+    // Where the lane is on the map for a given team
+    pub fn map_side(&self, team: Team) -> &'static str {
+        match (self, team) {
+            (Lane::Mid, _) => "middle",
+            (Lane::Safe, Team::Radiant) | (Lane::Off, Team::Dire) => "bottom",
+            (Lane::Safe, Team::Dire) | (Lane::Off, Team::Radiant) => "top",
+        }
+    }
+
+    // The enemy lane that shares the map lane with this one
+    pub fn opposite(&self) -> Lane {
+        match self {
+            Lane::Safe => Lane::Off,
+            Lane::Mid => Lane::Mid,
+            Lane::Off => Lane::Safe,
+        }
+    }
+
+    // Lane a position (1-5) usually plays in
+    pub fn of_position(position: u8) -> Lane {
+        match position {
+            2 => Lane::Mid,
+            3 | 4 => Lane::Off,
+            _ => Lane::Safe,
+        }
+    }
+}
+
+// (ranged, usual position) for a hero class such as "shadow_shaman"
+fn profile(hero_class: &str) -> Option<(bool, u8)> {
+    heroes::by_class(hero_class).map(|hero| (hero.ranged, hero.position))
+}
+
+// Both sides of one map lane
+#[derive(Clone, Debug)]
+pub struct LaneReport {
+    pub lane: Lane,
+    pub allies: Vec<String>,
+    pub enemies: Vec<String>,
+}
+
+// What the player should know before the horn
+#[derive(Clone, Debug)]
+pub struct LaneBriefing {
+    pub team: Team,
+    pub hero: Option<String>,
+    pub lane: Option<Lane>,
+    pub lanes: Vec<LaneReport>,
+    pub advice: Vec<String>,
+    pub starting_items: Vec<&'static str>,
+}
+
+// Picked heroes of a team with the lane they are expected in
+fn expected_laners(draft: Option<&Draft>, team: Team) -> Vec<(String, Option<(bool, u8)>)> {
+    draft.and_then(|d| d.team(team))
+        .map(|side| side.picks().into_iter()
+            .map(|pick| {
+                let profile = profile(&pick.hero_class);
+                (pick.hero_class, profile)
+            })
+            .collect())
+        .unwrap_or_default()
+}
+
+fn starting_items(position: u8, ranged: bool, heavy_harass: bool) -> Vec<&'static str> {
+    let mut items = match (position, ranged) {
+        (1, false) => vec!["Tango", "Quelling Blade", "Iron Branch x2", "Slippers of Agility"],
+        (1, true) => vec!["Tango", "Iron Branch x2", "Slippers of Agility", "Circlet"],
+        (2, _) => vec!["Tango", "Faerie Fire", "Iron Branch x2", "Circlet"],
+        (3, false) => vec!["Tango", "Quelling Blade", "Gauntlets of Strength", "Iron Branch"],
+        (3, true) => vec!["Tango", "Iron Branch x2", "Circlet", "Faerie Fire"],
+        _ => vec!["Tango", "Observer Ward", "Sentry Ward", "Blood Grenade", "Iron Branch"],
+    };
+
+    if heavy_harass {
+        items.push("Healing Salve");
+    }
+    items
+}
+
+// Build the briefing for a player on `team`, optionally knowing their hero ("npc_dota_hero_...")
+pub fn build(team: Team, hero_name: Option<&str>, draft: Option<&Draft>) -> LaneBriefing {
+    let enemy_team = if team == Team::Radiant { Team::Dire } else { Team::Radiant };
+    let allies = expected_laners(draft, team);
+    let enemies = expected_laners(draft, enemy_team);
+
+    let lanes = Lane::ALL.iter()
+        .map(|&lane| LaneReport {
+            lane,
+            allies: allies.iter()
+                .filter(|(_, p)| p.map(|(_, pos)| Lane::of_position(pos)) == Some(lane))
+                .map(|(class, _)| format_hero_name(class))
+                .collect(),
+            enemies: enemies.iter()
+                .filter(|(_, p)| p.map(|(_, pos)| Lane::of_position(pos)) == Some(lane.opposite()))
+                .map(|(class, _)| format_hero_name(class))
+                .collect(),
+        })
+        .collect();
+
+    let hero_class = hero_name.map(|name| name.replace("npc_dota_hero_", ""));
+    let own = hero_class.as_deref().and_then(profile);
+    let lane = own.map(|(_, position)| Lane::of_position(position));
+
+    let mut advice = Vec::new();
+    let mut heavy_harass = false;
+
+    if let (Some((ranged, _)), Some(lane)) = (own, lane) {
+        let opponents: Vec<(bool, String)> = enemies.iter()
+            .filter_map(|(class, p)| p
+                .filter(|(_, pos)| Lane::of_position(*pos) == lane.opposite())
+                .map(|(enemy_ranged, _)| (enemy_ranged, format_hero_name(class))))
+            .collect();
+
+        let ranged_opponents: Vec<&str> = opponents.iter().filter(|(r, _)| *r).map(|(_, n)| n.as_str()).collect();
+        heavy_harass = !ranged && !ranged_opponents.is_empty();
+
+        if opponents.is_empty() {
+            advice.push("Enemy laners unknown - scout the lane with your first creep wave".to_string());
+        } else if heavy_harass {
+            advice.push(format!("{} out-range you: avoid trades, last hit from behind your creeps and save regen", ranged_opponents.join(", ")));
+        } else if ranged && opponents.iter().all(|(r, _)| !r) {
+            advice.push("You out-range your lane opponents: harass them whenever they walk up for a last hit".to_string());
+        } else {
+            advice.push("Even range matchup: trade hits when they commit to a last hit, back off when their creeps aggro you".to_string());
+        }
+    }
+
+    let starting_items = match own {
+        Some((ranged, position)) => starting_items(position, ranged, heavy_harass),
+        None => Vec::new(),
+    };
+
+    LaneBriefing {
+        team,
+        hero: hero_class.as_deref().map(format_hero_name),
+        lane,
+        lanes,
+        advice,
+        starting_items,
+    }
+}
@@ -0,0 +1,127 @@
+// src/buildings.rs
+use crate::state::{Building, GameState, Team};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum BuildingLane {
+    Top,
+    Mid,
+    Bottom,
+    // Tier 4 towers and the ancient
+    Base,
+}
+
+impl BuildingLane {
+    pub const ALL: [BuildingLane; 4] = [BuildingLane::Top, BuildingLane::Mid, BuildingLane::Bottom, BuildingLane::Base];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            BuildingLane::Top => "top",
+            BuildingLane::Mid => "mid",
+            BuildingLane::Bottom => "bot",
+            BuildingLane::Base => "base",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum BuildingKind {
+    Tower(u8),
+    MeleeBarracks,
+    RangedBarracks,
+    Ancient,
+    Other,
+}
+
+impl BuildingKind {
+    pub fn name(&self) -> String {
+        match self {
+            BuildingKind::Tower(tier) => format!("T{}", tier),
+            BuildingKind::MeleeBarracks => "melee rax".to_string(),
+            BuildingKind::RangedBarracks => "ranged rax".to_string(),
+            BuildingKind::Ancient => "ancient".to_string(),
+            BuildingKind::Other => "building".to_string(),
+        }
+    }
+}
+
+// A standing building. GSI drops buildings from the map once they are destroyed.
+#[derive(Clone, Debug)]
+pub struct BuildingStatus {
+    // GSI key, e.g. "dota_badguys_tower2_mid"
+    pub key: String,
+    pub team: Team,
+    pub kind: BuildingKind,
+    pub lane: BuildingLane,
+    pub health: i32,
+    pub max_health: i32,
+}
+
+impl BuildingStatus {
+    pub fn health_percent(&self) -> i32 {
+        if self.max_health <= 0 {
+            return 0;
+        }
+        (self.health as f32 / self.max_health as f32 * 100.0) as i32
+    }
+
+    // e.g. "Dire T2 mid"
+    pub fn describe(&self) -> String {
+        match self.kind {
+            BuildingKind::Ancient => format!("{} ancient", self.team.name()),
+            BuildingKind::Tower(4) => format!("{} T4 {}", self.team.name(), self.key.rsplit('_').next().unwrap_or_default()),
+            _ => format!("{} {} {}", self.team.name(), self.kind.name(), self.lane.name()),
+        }
+    }
+}
+
+// Work out kind and lane from a GSI building key such as "good_rax_melee_top" or "dota_goodguys_tower3_bot"
+pub fn classify(key: &str) -> (BuildingKind, BuildingLane) {
+    let lane = if key.ends_with("_top") {
+        BuildingLane::Top
+    } else if key.ends_with("_mid") {
+        BuildingLane::Mid
+    } else if key.ends_with("_bot") {
+        BuildingLane::Bottom
+    } else {
+        BuildingLane::Base
+    };
+
+    let kind = if key.ends_with("_fort") {
+        BuildingKind::Ancient
+    } else if key.contains("rax_melee") {
+        BuildingKind::MeleeBarracks
+    } else if key.contains("rax_range") {
+        BuildingKind::RangedBarracks
+    } else if let Some(tier) = key.split("tower").nth(1).and_then(|rest| rest.chars().next()).and_then(|c| c.to_digit(10)) {
+        BuildingKind::Tower(tier as u8)
+    } else {
+        BuildingKind::Other
+    };
+
+    // Both tier 4 towers guard the ancient rather than a lane
+    let lane = if kind == BuildingKind::Tower(4) { BuildingLane::Base } else { lane };
+    (kind, lane)
+}
+
+fn structure(team: Team, key: &str, building: &Building) -> BuildingStatus {
+    let (kind, lane) = classify(key);
+    BuildingStatus {
+        key: key.to_string(),
+        team,
+        kind,
+        lane,
+        health: building.health,
+        max_health: building.max_health,
+    }
+}
+
+// Every standing building of a team, by lane and then from the outermost tower inwards
+pub fn team_buildings(state: &GameState, team: Team) -> Vec<BuildingStatus> {
+    let mut buildings: Vec<BuildingStatus> = state.buildings.as_ref()
+        .and_then(|b| b.get(&team.name().to_lowercase()))
+        .map(|b| b.iter().map(|(key, building)| structure(team, key, building)).collect())
+        .unwrap_or_default();
+
+    buildings.sort_by(|a, b| a.lane.cmp(&b.lane).then(a.kind.cmp(&b.kind)).then(a.key.cmp(&b.key)));
+    buildings
+}
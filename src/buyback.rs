@@ -0,0 +1,89 @@
+// src/buyback.rs
+use crate::alerts::Severity;
+use crate::format::format_game_time;
+use crate::state::Hero;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum BuybackStatus {
+    // Seconds left
+    OnCooldown(i32),
+    // Gold missing
+    Short(i32),
+    // Gold to spare above the cost
+    Available(i32),
+}
+
+impl BuybackStatus {
+    pub fn describe(&self, cost: i32) -> String {
+        match self {
+            BuybackStatus::OnCooldown(seconds) => format!("on cooldown for {}", format_game_time(Some(*seconds))),
+            BuybackStatus::Short(missing) => format!("{} gold, {} short", cost, missing),
+            BuybackStatus::Available(spare) => format!("available ({} gold, {} to spare)", cost, spare),
+        }
+    }
+
+    fn same_kind(&self, other: &BuybackStatus) -> bool {
+        std::mem::discriminant(self) == std::mem::discriminant(other)
+    }
+}
+
+// Where the hero's buyback stands given the gold they hold
+pub fn status(hero: &Hero, gold: i32) -> Option<BuybackStatus> {
+    let cost = hero.buyback_cost?;
+    let cooldown = hero.buyback_cooldown.unwrap_or(0);
+    Some(if cooldown > 0 {
+        BuybackStatus::OnCooldown(cooldown)
+    } else if gold >= cost {
+        BuybackStatus::Available(gold - cost)
+    } else {
+        BuybackStatus::Short(cost - gold)
+    })
+}
+
+// Speaks up when buyback becomes safe, when it is lost, and before a purchase would lose it
+pub struct BuybackCoach {
+    last: Option<BuybackStatus>,
+    warned_item: Option<String>,
+}
+
+impl BuybackCoach {
+    pub fn new() -> Self {
+        BuybackCoach {
+            last: None,
+            warned_item: None,
+        }
+    }
+
+    // `planned` is the next purchase (name, cost) the player is likely to make
+    pub fn check(&mut self, hero: &Hero, gold: i32, planned: Option<(&str, i32)>) -> Option<(Severity, String)> {
+        let cost = hero.buyback_cost?;
+        let current = status(hero, gold)?;
+        let previous = self.last.replace(current);
+        let changed = previous.map(|p| !p.same_kind(&current)).unwrap_or(true);
+
+        match current {
+            BuybackStatus::Available(spare) => {
+                if let Some((item, price)) = planned.filter(|(_, price)| *price > spare) {
+                    if self.warned_item.as_deref() != Some(item) {
+                        self.warned_item = Some(item.to_string());
+                        return Some((Severity::Warning, format!("Buying {} ({} gold) would lose buyback - keep {} gold for it",
+                            item, price, cost)));
+                    }
+                }
+                changed.then(|| (Severity::Info, format!("Buyback is safe: {} gold to spare over the {} cost", spare, cost)))
+            },
+            BuybackStatus::Short(missing) if hero.alive.unwrap_or(true)
+                && matches!(previous, Some(BuybackStatus::Available(_))) => {
+                self.warned_item = None;
+                Some((Severity::Warning, format!("You no longer have buyback gold - {} short of {}", missing, cost)))
+            },
+            _ => None,
+        }
+    }
+}
+
+impl Default for BuybackCoach {
+    fn default() -> Self {
+        Self::new()
+    }
+}
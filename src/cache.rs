@@ -0,0 +1,49 @@
+// src/cache.rs
+use std::fs;
+use std::future::Future;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+
+// API responses kept as files so the same question is asked at most once per `max_age`
+#[derive(Clone, Debug)]
+pub struct DiskCache {
+    // No caching without a directory
+    dir: Option<PathBuf>,
+    max_age: Duration,
+}
+
+impl DiskCache {
+    pub fn new(dir: Option<PathBuf>, max_age: Duration) -> Self {
+        DiskCache { dir, max_age }
+    }
+
+    // The cached body when it's fresh enough, otherwise whatever `fetch` returns. A
+    // stale body beats nothing when the fetch fails.
+    pub async fn get_or_fetch<F>(&self, name: &str, fetch: F) -> Result<String, String>
+    where
+        F: Future<Output = Result<String, String>>,
+    {
+        let file = self.dir.as_ref().map(|dir| dir.join(name));
+        let cached = file.as_ref().and_then(|file| {
+            let age = fs::metadata(file).and_then(|m| m.modified()).ok()
+                .and_then(|modified| SystemTime::now().duration_since(modified).ok())?;
+            Some((fs::read_to_string(file).ok()?, age <= self.max_age))
+        });
+        if let Some((body, true)) = &cached {
+            return Ok(body.clone());
+        }
+
+        match fetch.await {
+            Ok(body) => {
+                if let Some(file) = &file {
+                    let written = file.parent().map(fs::create_dir_all).unwrap_or(Ok(())).and_then(|_| fs::write(file, &body));
+                    if let Err(e) = written {
+                        eprintln!("Could not cache response in {}: {}", file.display(), e);
+                    }
+                }
+                Ok(body)
+            },
+            Err(e) => cached.map(|(body, _)| body).ok_or(e),
+        }
+    }
+}
@@ -0,0 +1,190 @@
+// src/camps.rs
+use std::collections::HashMap;
+
+use crate::tracking::calculate_distance;
+
+// Stacking only makes sense while camps are worth stacking for the team
+pub const STACK_PHASE_START: i32 = 60;
+pub const STACK_PHASE_END: i32 = 25 * 60;
+
+// Neutrals first spawn at 1:00. After that an empty camp respawns on the next minute mark.
+pub const FIRST_SPAWN: i32 = 60;
+
+// Seconds into each minute during which the reminder may fire
+pub const STACK_WINDOW: (i32, i32) = (45, 53);
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CampSize {
+    Small,
+    Medium,
+    Large,
+    Ancient,
+}
+
+impl CampSize {
+    // Second of the minute at which to pull the camp out of its box
+    pub fn pull_second(&self) -> i32 {
+        match self {
+            CampSize::Small | CampSize::Medium => 55,
+            CampSize::Large => 54,
+            CampSize::Ancient => 53,
+        }
+    }
+}
+
+// A neutral camp on the map
+#[derive(Clone, Copy, Debug)]
+pub struct Camp {
+    pub name: &'static str,
+    pub size: CampSize,
+    pub radiant_side: bool,
+    pub position: (i32, i32),
+}
+
+// Radiant-side camps. These are approximate positions; the map is close to
+// point-symmetric, so Dire camps are derived by mirroring through the centre.
+const RADIANT_CAMPS: [(&str, CampSize, (i32, i32)); 9] = [
+    ("safe lane small camp", CampSize::Small, (3200, -4600)),
+    ("safe lane hard camp", CampSize::Large, (500, -4500)),
+    ("safe lane medium camp", CampSize::Medium, (-400, -3300)),
+    ("triangle hard camp", CampSize::Large, (-1800, -4100)),
+    ("mid medium camp", CampSize::Medium, (-1900, -1200)),
+    ("ancient camp", CampSize::Ancient, (-2700, -200)),
+    ("off lane hard camp", CampSize::Large, (-4800, 700)),
+    ("off lane medium camp", CampSize::Medium, (-3800, 1800)),
+    ("off lane small camp", CampSize::Small, (-5300, 2200)),
+];
+
+// All neutral camps on the map, Radiant first
+pub fn all_camps() -> Vec<Camp> {
+    let radiant = RADIANT_CAMPS.iter().map(|&(name, size, position)| Camp {
+        name,
+        size,
+        radiant_side: true,
+        position,
+    });
+    let dire = RADIANT_CAMPS.iter().map(|&(name, size, (x, y))| Camp {
+        name,
+        size,
+        radiant_side: false,
+        position: (-x, -y),
+    });
+
+    radiant.chain(dire).collect()
+}
+
+impl Camp {
+    pub fn describe(&self) -> String {
+        format!("{} {}", if self.radiant_side { "Radiant" } else { "Dire" }, self.name)
+    }
+
+    fn key(&self) -> (bool, &'static str) {
+        (self.radiant_side, self.name)
+    }
+}
+
+// Camps within stacking range of a position, closest first
+pub fn camps_near(position: (i32, i32), range: f32) -> Vec<(Camp, f32)> {
+    let mut nearby: Vec<(Camp, f32)> = all_camps().into_iter()
+        .map(|camp| (camp, calculate_distance(position, camp.position)))
+        .filter(|(_, distance)| *distance <= range)
+        .collect();
+
+    nearby.sort_by(|a, b| a.1.total_cmp(&b.1));
+    nearby
+}
+
+// Fires at most one stack reminder per minute
+pub struct StackReminder {
+    last_minute: i32,
+}
+
+impl StackReminder {
+    pub fn new() -> Self {
+        StackReminder { last_minute: -1 }
+    }
+
+    // Camps within `range` the player could stack this minute, or None if no reminder is due.
+    // Camps `is_up` rules out have nothing in them to pull.
+    pub fn check(
+        &mut self,
+        clock_time: i32,
+        player_position: (i32, i32),
+        range: f32,
+        is_up: impl Fn(&Camp) -> bool,
+    ) -> Option<Vec<Camp>> {
+        if !(STACK_PHASE_START..STACK_PHASE_END).contains(&clock_time) {
+            return None;
+        }
+
+        let minute = clock_time / 60;
+        let second = clock_time % 60;
+        if second < STACK_WINDOW.0 || second > STACK_WINDOW.1 || minute == self.last_minute {
+            return None;
+        }
+
+        // Only the closest couple of camps are realistic in the time left
+        let camps: Vec<Camp> = camps_near(player_position, range).into_iter()
+            .filter(|(camp, _)| is_up(camp))
+            .take(2)
+            .map(|(camp, _)| camp)
+            .collect();
+
+        if camps.is_empty() {
+            return None;
+        }
+
+        self.last_minute = minute;
+        Some(camps)
+    }
+}
+
+impl Default for StackReminder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Which camps should have neutrals in them. GSI doesn't report camps, so this
+// knows only about camps the player marked as cleared.
+pub struct CampTracker {
+    // Clock time each camp was last cleared
+    cleared: HashMap<(bool, &'static str), i32>,
+}
+
+impl CampTracker {
+    pub fn new() -> Self {
+        CampTracker { cleared: HashMap::new() }
+    }
+
+    pub fn mark_cleared(&mut self, camp: &Camp, clock_time: i32) {
+        self.cleared.insert(camp.key(), clock_time);
+    }
+
+    // Seconds until the camp respawns, or None if it should be up
+    pub fn respawns_in(&self, camp: &Camp, clock_time: i32) -> Option<i32> {
+        if clock_time < FIRST_SPAWN {
+            return Some(FIRST_SPAWN - clock_time);
+        }
+        let cleared_at = *self.cleared.get(&camp.key())?;
+        // Cleared in this minute, so it comes back on the next minute mark
+        (clock_time.div_euclid(60) == cleared_at.div_euclid(60)).then(|| 60 - clock_time.rem_euclid(60))
+    }
+
+    pub fn is_up(&self, camp: &Camp, clock_time: i32) -> bool {
+        self.respawns_in(camp, clock_time).is_none()
+    }
+
+    // Camps that should be empty right now, with seconds until they respawn
+    pub fn down(&self, clock_time: i32) -> Vec<(Camp, i32)> {
+        all_camps().into_iter()
+            .filter_map(|camp| self.respawns_in(&camp, clock_time).map(|seconds| (camp, seconds)))
+            .collect()
+    }
+}
+
+impl Default for CampTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
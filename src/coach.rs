@@ -0,0 +1,2041 @@
+// src/coach.rs
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+use std::time::Instant;
+
+use chrono::Local;
+use colored::Colorize;
+use serde_json::Value;
+
+use crate::alerts::{Alert, AlertCategory, AlertManager, Severity};
+use crate::analysis::awareness::AwarenessTracker;
+use crate::analysis::death_recap::DeathRecorder;
+use crate::analysis::gank_risk::{self, GankRisk};
+use crate::analysis::item_timings::ItemTimingTracker;
+use crate::analysis::laning::{self, LaningReport};
+use crate::analysis::performance::{HeroPerformanceTracker, Metric, Sample};
+use crate::analysis::teamfight::{FightTransition, TeamFight, TeamFightAnalyzer};
+use crate::barracks::{CreepTier, CreepTracker};
+use crate::base_race::{BaseRaceTracker, Outcome};
+use crate::baselines::{self, Baseline};
+use crate::benchmarks::{role_name, CsBenchmark};
+use crate::briefing::{self, Lane, LaneBriefing};
+use crate::buildings;
+use crate::buyback::{self, BuybackCoach};
+use crate::camps::{self, Camp, CampTracker, StackReminder};
+use crate::config::Config;
+use crate::consumables::{Consumable, ConsumableTracker};
+use crate::counters::{self, Threat};
+use crate::couriers;
+use crate::diff::{self, Change};
+use crate::draft::{DraftAction, DraftTracker};
+use crate::estimates::{self, EnemyEstimate};
+use crate::events::{EventKind, EventLog, GameEvent, Marker};
+use crate::farming::{self, FarmRoute};
+use crate::format::{format_game_time, format_gold, format_hero_name};
+use crate::game_mode::{GameMode, GameModeDetector, TURBO_ITEM_PACE};
+use crate::gold::GoldWatch;
+use crate::heatmap::Heatmap;
+use crate::high_ground::{self, HighGroundAdvisor, Verdict};
+use crate::heroes::{self, HeroInfo};
+use crate::history::{MatchHistory, MatchSummary};
+use crate::item_build::{ItemAdvisor, Recommendation};
+use crate::lane_assignment::{Assignment, LaneTracker};
+use crate::llm;
+use crate::missing;
+use crate::objectives;
+use crate::opendota::{self, Grade, HeroBenchmarks, PlayerHeroProfile};
+use crate::outposts::{self, Outpost, OutpostTracker};
+use crate::phase::{PhaseTracker, PhaseTransition};
+use crate::plugins::{Panel, Plugin, PluginHost};
+use crate::power_spikes::SpikeTracker;
+use crate::respawns::RespawnTracker;
+use crate::roles::{self, Detection, RoleDetector};
+use crate::roshan::{RoshanAlert, RoshanStatus, RoshanTracker, AEGIS_DURATION, RESPAWN_MAX, RESPAWN_MIN};
+use crate::roshan_contest::{self, ContestKind, ContestWatch, Situation, CONTEST_FROM};
+use crate::rotations::{RotationAdvisor, ROTATIONS_FROM};
+use crate::rules::RuleEngine;
+use crate::series::MatchSeries;
+use crate::skill_build::{SkillAdvisor, SkillPick, TALENT_LEVELS};
+use crate::smoke::{Confidence, SmokeDetector};
+use crate::snapshot;
+use crate::state::{Draft, GamePhase, GameState, GsiEvent, GsiEventKind, Team};
+use crate::steam::{self, SteamProfile};
+use crate::stratz::HeroMeta;
+use crate::streaks::{self, StreakTracker, SPREE};
+use crate::team_cooldowns::{EnemyCooldowns, TeamAbility};
+use crate::teleport::TpReminder;
+use crate::tilt::TiltDetector;
+use crate::timers::{DayNight, RuneKind, RuneTimers};
+use crate::vod::ClockMapping;
+use crate::wards::{WardAlert, WardOwner, WardTracker};
+use crate::waves::SiegeTimers;
+use crate::tracking::{calculate_distance, describe_map_location, describe_position_relative_to_player, estimate_hero_level, has_moved_significantly, predict_position, EnemyHeroState, EnemyStatus, Observed, Prediction, PREDICT_FOR};
+
+// Farming advice stops once the gank risk gets this high
+const FARM_MAX_RISK: i32 = 50;
+
+// Game time may wobble by a second between payloads; going back further than this is a restart
+const RESTART_TOLERANCE: i32 = 5;
+
+// A camp marked as cleared must be at least this close to the player
+const MARK_CAMP_RANGE: f32 = 1200.0;
+
+// How long before nightfall supports are reminded to ward
+const VISION_LEAD: i32 = 30;
+
+// Everything the coach knows about the current match. Feed it game states, get events back.
+pub struct Coach {
+    config: Config,
+    game_state: Option<GameState>,
+    // What changed between the last two processed states
+    changes: Vec<Change>,
+    match_id: Option<String>,
+    last_game_time: i32,
+    // Game time before the pause, and when it started
+    paused: Option<(i32, Instant)>,
+    phase: PhaseTracker,
+    game_mode: GameModeDetector,
+    draft: Option<Draft>,
+    draft_tracker: DraftTracker,
+    briefing: Option<LaneBriefing>,
+    laning_report: Option<LaningReport>,
+    history: Option<MatchHistory>,
+    enemies: HashMap<String, EnemyHeroState>,
+    enemy_team_heroes: Vec<String>,
+    event_log: EventLog,
+    alerts: AlertManager,
+    rune_timers: RuneTimers,
+    roshan: RoshanTracker,
+    // Entries of the GSI events block already handled
+    gsi_events: Vec<GsiEvent>,
+    stack_reminder: StackReminder,
+    camps: CampTracker,
+    smoke_detector: SmokeDetector,
+    tp_reminder: TpReminder,
+    consumables: ConsumableTracker,
+    wards: WardTracker,
+    outposts: OutpostTracker,
+    siege_timers: SiegeTimers,
+    high_ground: HighGroundAdvisor,
+    creeps: CreepTracker,
+    base_race: BaseRaceTracker,
+    streaks: StreakTracker,
+    tilt: TiltDetector,
+    gold: GoldWatch,
+    awareness: AwarenessTracker,
+    roshan_contest: ContestWatch,
+    enemy_cooldowns: EnemyCooldowns,
+    teamfights: TeamFightAnalyzer,
+    performance: HeroPerformanceTracker,
+    cs_benchmark: CsBenchmark,
+    // OpenDota percentile bands for the player's hero, once fetched
+    hero_benchmarks: Option<HeroBenchmarks>,
+    benchmarks_requested: bool,
+    // How the player behind each enemy hero has done on it, keyed by hero name
+    enemy_profiles: HashMap<String, PlayerHeroProfile>,
+    profiles_requested: Vec<u32>,
+    // Stratz win rates by hero id and the player's hero's builds and matchups, once fetched
+    hero_win_rates: HashMap<i32, f32>,
+    win_rates_requested: bool,
+    hero_meta: Option<HeroMeta>,
+    meta_requested: bool,
+    // Steam profiles keyed by the steamid GSI sent
+    steam_profiles: HashMap<String, SteamProfile>,
+    steam_requested: Vec<String>,
+    // The model's latest tip with the game time it came in, and when one was last asked for
+    coach_tip: Option<(i32, String)>,
+    tip_requested_at: Option<i32>,
+    item_timings: ItemTimingTracker,
+    item_advisor: ItemAdvisor,
+    skill_advisor: SkillAdvisor,
+    spikes: SpikeTracker,
+    deaths: DeathRecorder,
+    buyback: BuybackCoach,
+    respawns: RespawnTracker,
+    heatmap: Heatmap,
+    lanes: LaneTracker,
+    rotations: RotationAdvisor,
+    roles: RoleDetector,
+    // Position (1-5) the player set by hand, which wins over the detected one
+    role_override: Option<u8>,
+    // The user's own [[rules]]
+    rules: RuleEngine,
+    plugins: PluginHost,
+    clock_mapping: ClockMapping,
+    series: MatchSeries,
+}
+
+// Raise an alert and report it as an event unless it was suppressed
+fn raise_alert(alerts: &mut AlertManager, events: &mut Vec<GameEvent>, game_time: i32, alert: Alert) {
+    if let Some(alert) = alerts.raise(game_time, alert) {
+        events.push(GameEvent::new(game_time, EventKind::Alert(alert.category, alert.severity), alert.message.clone()));
+    }
+}
+
+impl Coach {
+    pub fn new(config: Config) -> Self {
+        let alerts = AlertManager::new(config.alerts.categories.clone());
+        let event_log = EventLog::new(config.timeline.capacity.max(1));
+        let rules = RuleEngine::new(&config.rules);
+        Coach {
+            config,
+            game_state: None,
+            changes: Vec::new(),
+            match_id: None,
+            last_game_time: -1,
+            paused: None,
+            phase: PhaseTracker::new(),
+            game_mode: GameModeDetector::new(),
+            draft: None,
+            draft_tracker: DraftTracker::new(),
+            briefing: None,
+            laning_report: None,
+            history: None,
+            enemies: HashMap::new(),
+            enemy_team_heroes: Vec::new(),
+            event_log,
+            alerts,
+            rune_timers: RuneTimers::new(),
+            roshan: RoshanTracker::new(),
+            gsi_events: Vec::new(),
+            stack_reminder: StackReminder::new(),
+            camps: CampTracker::new(),
+            smoke_detector: SmokeDetector::new(),
+            tp_reminder: TpReminder::new(),
+            consumables: ConsumableTracker::new(),
+            wards: WardTracker::new(),
+            outposts: OutpostTracker::new(),
+            siege_timers: SiegeTimers::new(),
+            high_ground: HighGroundAdvisor::new(),
+            creeps: CreepTracker::new(),
+            base_race: BaseRaceTracker::new(),
+            streaks: StreakTracker::new(),
+            tilt: TiltDetector::new(),
+            gold: GoldWatch::new(),
+            awareness: AwarenessTracker::new(),
+            roshan_contest: ContestWatch::new(),
+            enemy_cooldowns: EnemyCooldowns::new(),
+            teamfights: TeamFightAnalyzer::new(),
+            performance: HeroPerformanceTracker::new(),
+            cs_benchmark: CsBenchmark::new(),
+            hero_benchmarks: None,
+            benchmarks_requested: false,
+            enemy_profiles: HashMap::new(),
+            profiles_requested: Vec::new(),
+            hero_win_rates: HashMap::new(),
+            win_rates_requested: false,
+            hero_meta: None,
+            meta_requested: false,
+            steam_profiles: HashMap::new(),
+            steam_requested: Vec::new(),
+            coach_tip: None,
+            tip_requested_at: None,
+            item_timings: ItemTimingTracker::new(),
+            item_advisor: ItemAdvisor::new(),
+            skill_advisor: SkillAdvisor::new(),
+            spikes: SpikeTracker::new(),
+            deaths: DeathRecorder::new(),
+            buyback: BuybackCoach::new(),
+            respawns: RespawnTracker::new(),
+            heatmap: Heatmap::new(),
+            lanes: LaneTracker::new(),
+            rotations: RotationAdvisor::new(),
+            roles: RoleDetector::new(),
+            role_override: None,
+            rules,
+            plugins: PluginHost::default(),
+            clock_mapping: ClockMapping::new(),
+            series: MatchSeries::new(),
+        }
+    }
+
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    // Tune a threshold for the rest of the session. Invalid values leave it unchanged.
+    pub fn set_threshold(&mut self, key: &str, value: &str) -> Result<(), String> {
+        let mut thresholds = self.config.thresholds.clone();
+        thresholds.set(key, value)?;
+        self.config.thresholds = thresholds;
+        Ok(())
+    }
+
+    // The most recent game state that was processed
+    pub fn game_state(&self) -> Option<&GameState> {
+        self.game_state.as_ref()
+    }
+
+    // What the last update changed: deaths, level-ups, items, gold, towers and score
+    pub fn changes(&self) -> &[Change] {
+        &self.changes
+    }
+
+    // Game time of the last processed update, or -1 before the first one
+    pub fn game_time(&self) -> i32 {
+        self.last_game_time
+    }
+
+    // Normal until Turbo is recognised
+    pub fn game_mode(&self) -> GameMode {
+        self.game_mode.mode()
+    }
+
+    // Last reported game phase, if the client sent one
+    pub fn phase(&self) -> Option<GamePhase> {
+        self.phase.current()
+    }
+
+    // The latest draft block, kept after hero selection ends
+    pub fn draft(&self) -> Option<&Draft> {
+        self.draft.as_ref()
+    }
+
+    // Lane briefing prepared at strategy time
+    pub fn briefing(&self) -> Option<&LaneBriefing> {
+        self.briefing.as_ref()
+    }
+
+    // Summary of the laning stage, made at 10:00
+    pub fn laning_report(&self) -> Option<&LaningReport> {
+        self.laning_report.as_ref()
+    }
+
+    pub fn enemies(&self) -> &HashMap<String, EnemyHeroState> {
+        &self.enemies
+    }
+
+    // Enemy heroes in the order they were first seen
+    pub fn enemy_team_heroes(&self) -> &[String] {
+        &self.enemy_team_heroes
+    }
+
+    // Match clock against the provider's timestamps, for VOD timestamps
+    pub fn clock_mapping(&self) -> &ClockMapping {
+        &self.clock_mapping
+    }
+
+    // Every event of the session, including changes picked up between payloads
+    pub fn event_log(&self) -> &EventLog {
+        &self.event_log
+    }
+
+    // Keep events that no longer fit in memory in this file
+    pub fn spill_timeline_to(&mut self, path: &Path) -> Result<(), String> {
+        self.event_log.spill_to(path)
+    }
+
+    pub fn alerts(&self) -> &AlertManager {
+        &self.alerts
+    }
+
+    pub fn alerts_mut(&mut self) -> &mut AlertManager {
+        &mut self.alerts
+    }
+
+    pub fn add_plugin(&mut self, plugin: Box<dyn Plugin>) {
+        self.plugins.add(plugin);
+    }
+
+    // What the plugins keep on screen, by plugin name
+    pub fn plugin_panels(&self) -> impl Iterator<Item = (&str, &Panel)> {
+        self.plugins.panels()
+    }
+
+    // Store finished matches here from now on
+    pub fn set_history(&mut self, history: MatchHistory) {
+        self.history = Some(history);
+    }
+
+    // The player's stored matches, when history is kept
+    pub fn history(&self) -> Option<&MatchHistory> {
+        self.history.as_ref()
+    }
+
+    // The player's numbers now against their own average at this point of past matches
+    pub fn baselines(&self) -> Vec<Baseline> {
+        self.game_state.as_ref().map(|state| self.baselines_for(state)).unwrap_or_default()
+    }
+
+    // History only holds normal games, so there is nothing to compare a Turbo game with
+    fn baselines_for(&self, state: &GameState) -> Vec<Baseline> {
+        let Some(history) = self.history.as_ref().filter(|_| self.game_mode() == GameMode::Normal) else { return Vec::new() };
+        let (Some(steam_id), Some(clock_time), Some(current)) =
+            (state.player().and_then(|p| p.steamid.as_deref()), state.clock_time(), Sample::from_state(state)) else {
+            return Vec::new();
+        };
+        let hero = state.hero().and_then(|h| h.name.as_deref()).map(|n| n.replace("npc_dota_hero_", ""));
+        let match_id = state.map.as_ref().and_then(|m| m.matchid.as_deref());
+        baselines::compare(history, steam_id, hero.as_deref(), match_id, clock_time, &current)
+    }
+
+    pub fn enemy_cooldowns(&self) -> &EnemyCooldowns {
+        &self.enemy_cooldowns
+    }
+
+    // The fight going on right now, if any
+    pub fn teamfight(&self) -> Option<&TeamFight> {
+        self.teamfights.current()
+    }
+
+    // Net worth and likely items of each known enemy, from the clock and the team scores
+    pub fn enemy_estimates(&self) -> Vec<EnemyEstimate> {
+        let Some(state) = self.game_state.as_ref() else { return Vec::new() };
+        let (Some(clock_time), Some(team)) = (state.clock_time(), state.player_team().and_then(|t| Team::from_name(&t))) else {
+            return Vec::new();
+        };
+        let (radiant, dire) = state.map.as_ref()
+            .map(|m| (m.radiant_score.unwrap_or(0), m.dire_score.unwrap_or(0)))
+            .unwrap_or((0, 0));
+        // Radiant's score counts Dire heroes killed and the other way round
+        let (kills, deaths) = if team == Team::Radiant { (dire, radiant) } else { (radiant, dire) };
+
+        self.enemy_hero_infos().into_iter()
+            .map(|hero| estimates::estimate(hero, clock_time, kills, deaths))
+            .collect()
+    }
+
+    // Likely positions of enemies who recently dropped off the minimap, most certain first
+    pub fn predictions(&self) -> Vec<Prediction> {
+        let mut predictions: Vec<Prediction> = self.enemies.values()
+            .filter_map(|e| predict_position(e, self.last_game_time))
+            .collect();
+        predictions.sort_by_key(|p| p.seconds_unseen);
+        predictions
+    }
+
+    // The position (1-5) the player is coached as: set by hand, else worked out from the
+    // laning stage, else the hero's usual one
+    pub fn position(&self) -> Option<u8> {
+        let hero = self.game_state.as_ref()?.hero().and_then(|h| h.name.as_deref()).and_then(heroes::by_class);
+        self.role_override.or(self.roles.detected().map(|d| d.position)).or(hero.map(|h| h.position))
+    }
+
+    fn position_of(&self, hero: &HeroInfo) -> u8 {
+        self.role_override.or(self.roles.detected().map(|d| d.position)).unwrap_or(hero.position)
+    }
+
+    pub fn role_override(&self) -> Option<u8> {
+        self.role_override
+    }
+
+    pub fn role_detection(&self) -> Option<&Detection> {
+        self.roles.detected()
+    }
+
+    // Coach the player as this position for the rest of the match, or go back to detecting it
+    pub fn set_role(&mut self, position: Option<u8>) -> GameEvent {
+        self.role_override = position;
+        let description = match (position, self.roles.detected()) {
+            (Some(position), _) => format!("Coaching you as position {} ({}) - set by you", position, role_name(position)),
+            (None, Some(detection)) => detection.describe(),
+            (None, None) => "Working out your role from the laning stage".to_string(),
+        };
+        let event = GameEvent::new(self.last_game_time.max(0), EventKind::Role, description);
+        self.record(std::slice::from_ref(&event));
+        event
+    }
+
+    // How exposed the player is to a gank right now
+    pub fn gank_risk(&self) -> Option<GankRisk> {
+        let state = self.game_state.as_ref()?;
+        gank_risk::assess(state, &self.enemies, &self.lanes, &self.config.thresholds)
+    }
+
+    // Where a core should farm next, when nothing is about to happen
+    pub fn farm_route(&self) -> Option<FarmRoute> {
+        let state = self.game_state.as_ref()?;
+        let core = self.position().map(|position| !roles::is_support(position)).unwrap_or(false);
+        let calm = !self.game_mode().tracking_only() && self.teamfight().is_none() && !self.skirmishing()
+            && self.gank_risk().map(|risk| risk.score < FARM_MAX_RISK).unwrap_or(false);
+        if !core || !calm {
+            return None;
+        }
+
+        let team = state.player_team().and_then(|t| Team::from_name(&t))?;
+        let missing = missing::missing_enemies(&self.enemies, self.last_game_time, self.config.thresholds.missing_after_seconds);
+        let clock_time = state.clock_time()?;
+        farming::plan(state.hero_position()?, team, clock_time, missing.len(), |camp| self.camps.is_up(camp, clock_time))
+    }
+
+    // Roshan's respawn window and the Aegis
+    pub fn roshan(&self) -> &RoshanTracker {
+        &self.roshan
+    }
+
+    // Which jungle camps should be up
+    pub fn camps(&self) -> &CampTracker {
+        &self.camps
+    }
+
+    // Which lane each enemy played, guessed from early sightings
+    pub fn lanes(&self) -> &LaneTracker {
+        &self.lanes
+    }
+
+    // Where enemies have been seen over the match
+    pub fn heatmap(&self) -> &Heatmap {
+        &self.heatmap
+    }
+
+    // Dead heroes of both teams and their respawn timers
+    pub fn respawns(&self) -> &RespawnTracker {
+        &self.respawns
+    }
+
+    // Kills are piling up but not yet a fight
+    pub fn skirmishing(&self) -> bool {
+        self.teamfights.skirmishing(self.last_game_time)
+    }
+
+    // GPM, XPM, last hit and death history of the player
+    pub fn performance(&self) -> &HeroPerformanceTracker {
+        &self.performance
+    }
+
+    // Wards, smokes and dust the player bought, was given and used
+    pub fn consumables(&self) -> &ConsumableTracker {
+        &self.consumables
+    }
+
+    pub fn wards(&self) -> &WardTracker {
+        &self.wards
+    }
+
+    pub fn outposts(&self) -> &OutpostTracker {
+        &self.outposts
+    }
+
+    pub fn streaks(&self) -> &StreakTracker {
+        &self.streaks
+    }
+
+    pub fn gold(&self) -> &GoldWatch {
+        &self.gold
+    }
+
+    pub fn awareness(&self) -> &AwarenessTracker {
+        &self.awareness
+    }
+
+    // When the player finished their key items
+    pub fn item_timings(&self) -> &ItemTimingTracker {
+        &self.item_timings
+    }
+
+    // What the player should buy next
+    pub fn item_recommendation(&self) -> Option<&Recommendation> {
+        self.item_advisor.current()
+    }
+
+    // Enemy heroes known from the draft or seen on the minimap
+    pub fn enemy_hero_infos(&self) -> Vec<&'static HeroInfo> {
+        let own_team = self.game_state.as_ref().and_then(|s| s.player_team()).and_then(|t| Team::from_name(&t));
+        let mut enemies: Vec<&'static HeroInfo> = own_team
+            .and_then(|team| self.draft.as_ref().and_then(|d| d.team(if team == Team::Radiant { Team::Dire } else { Team::Radiant })))
+            .map(|side| side.picks().iter().filter_map(|pick| heroes::by_id(pick.hero_id)).collect())
+            .unwrap_or_default();
+
+        for name in &self.enemy_team_heroes {
+            if let Some(hero) = heroes::HEROES.iter().find(|h| h.name == name) {
+                if !enemies.iter().any(|e| e.id == hero.id) {
+                    enemies.push(hero);
+                }
+            }
+        }
+        enemies
+    }
+
+    // The player saw the enemy use their glyph or scan just now
+    pub fn mark_enemy_used(&mut self, ability: TeamAbility) -> GameEvent {
+        let game_time = self.last_game_time.max(0);
+        self.enemy_cooldowns.mark_used(ability, game_time);
+
+        let event = GameEvent::new(game_time, EventKind::EnemyCooldown,
+            format!("Enemy used their {} - back at {}",
+                ability.name(),
+                format_game_time(Some(game_time + ability.cooldown()))));
+        self.record(std::slice::from_ref(&event));
+        event
+    }
+
+    // The player's hero, once its OpenDota benchmarks should be fetched. Asks only once per match.
+    pub fn wanted_benchmarks(&mut self) -> Option<i32> {
+        if !self.config.opendota.enabled || self.benchmarks_requested {
+            return None;
+        }
+        let hero = self.game_state.as_ref()?.hero()?.name.as_deref().and_then(heroes::by_class)?;
+        self.benchmarks_requested = true;
+        Some(hero.id)
+    }
+
+    pub fn set_hero_benchmarks(&mut self, benchmarks: HeroBenchmarks) {
+        self.hero_benchmarks = Some(benchmarks);
+    }
+
+    // Enemy players whose OpenDota profiles haven't been asked for yet, as hero name, account ID
+    // and hero ID. Account IDs only come with spectator payloads; without a team of our own
+    // both teams count.
+    pub fn wanted_profiles(&mut self) -> Vec<(String, u32, i32)> {
+        if !self.config.opendota.enabled || !self.config.opendota.enemy_profiles {
+            return Vec::new();
+        }
+        let Some(state) = self.game_state.as_ref() else { return Vec::new() };
+        let own_team = state.player_team().and_then(|t| Team::from_name(&t));
+        let wanted: Vec<(String, u32, i32)> = state.spectated_players().into_iter()
+            .filter(|p| Some(p.team) != own_team)
+            .filter_map(|p| {
+                let account_id = p.player?.steamid.as_deref().and_then(opendota::account_id)?;
+                let hero = p.hero?.name.as_deref().and_then(heroes::by_class)?;
+                Some((hero.name.to_string(), account_id, hero.id))
+            })
+            .filter(|(_, account_id, _)| !self.profiles_requested.contains(account_id))
+            .collect();
+        self.profiles_requested.extend(wanted.iter().map(|(_, account_id, _)| *account_id));
+        wanted
+    }
+
+    pub fn set_enemy_profile(&mut self, hero: String, profile: PlayerHeroProfile) {
+        self.enemy_profiles.insert(hero, profile);
+    }
+
+    // Keyed by hero name as shown in the client
+    pub fn enemy_profiles(&self) -> &HashMap<String, PlayerHeroProfile> {
+        &self.enemy_profiles
+    }
+
+    // Whether the week's Stratz win rates should be fetched. Asks only once per match.
+    pub fn wanted_win_rates(&mut self) -> bool {
+        if !self.config.stratz.enabled || self.win_rates_requested {
+            return false;
+        }
+        self.win_rates_requested = true;
+        true
+    }
+
+    pub fn set_hero_win_rates(&mut self, win_rates: HashMap<i32, f32>) {
+        self.hero_win_rates = win_rates;
+    }
+
+    // Win rate (0 to 1) by hero id, empty until Stratz answers
+    pub fn hero_win_rates(&self) -> &HashMap<i32, f32> {
+        &self.hero_win_rates
+    }
+
+    // The player's hero, once its Stratz builds and matchups should be fetched. Asks only once per match.
+    pub fn wanted_meta(&mut self) -> Option<i32> {
+        if !self.config.stratz.enabled || self.meta_requested {
+            return None;
+        }
+        let hero = self.game_state.as_ref()?.hero()?.name.as_deref().and_then(heroes::by_class)?;
+        self.meta_requested = true;
+        Some(hero.id)
+    }
+
+    pub fn set_hero_meta(&mut self, meta: HeroMeta) {
+        self.hero_meta = Some(meta);
+    }
+
+    // Players whose Steam profiles haven't been asked for yet, as GSI steamid and Steam64 ID:
+    // the local player, and everyone in a spectator payload
+    pub fn wanted_steam_profiles(&mut self) -> Vec<(String, u64)> {
+        if !self.config.steam.enabled {
+            return Vec::new();
+        }
+        let Some(state) = self.game_state.as_ref() else { return Vec::new() };
+        let mut wanted: Vec<(String, u64)> = Vec::new();
+        let steamids = state.player().and_then(|p| p.steamid.as_deref()).into_iter()
+            .chain(state.spectated_players().into_iter().filter_map(|p| p.player?.steamid.as_deref()));
+        for steamid in steamids {
+            let Some(id) = steam::steam64(steamid) else { continue };
+            if !self.steam_requested.iter().any(|s| s == steamid) && !wanted.iter().any(|(s, _)| s == steamid) {
+                wanted.push((steamid.to_string(), id));
+            }
+        }
+        self.steam_requested.extend(wanted.iter().map(|(steamid, _)| steamid.clone()));
+        wanted
+    }
+
+    pub fn set_steam_profile(&mut self, steamid: String, profile: SteamProfile) {
+        self.steam_profiles.insert(steamid, profile);
+    }
+
+    // Keyed by the steamid GSI sent
+    pub fn steam_profiles(&self) -> &HashMap<String, SteamProfile> {
+        &self.steam_profiles
+    }
+
+    // The local player's Steam profile, once fetched
+    pub fn player_profile(&self) -> Option<&SteamProfile> {
+        let steamid = self.game_state.as_ref()?.player()?.steamid.as_deref()?;
+        self.steam_profiles.get(steamid)
+    }
+
+    // Whether to ask the model for a tip now: while playing, at most once per `interval_secs`
+    // of game time and never while one is on its way
+    pub fn wanted_tip(&mut self) -> bool {
+        if !self.config.llm.enabled || !self.phase().map(|p| p.is_playing()).unwrap_or(false) {
+            return false;
+        }
+        let interval = self.config.llm.interval_secs.max(llm::MIN_INTERVAL) as i32;
+        if self.tip_requested_at.map(|at| self.last_game_time - at < interval).unwrap_or(false) {
+            return false;
+        }
+        self.tip_requested_at = Some(self.last_game_time);
+        true
+    }
+
+    // Keep the model's tip and log it. None for a tip asked for before the match started over.
+    pub fn set_tip(&mut self, tip: String) -> Option<GameEvent> {
+        self.tip_requested_at?;
+        let event = GameEvent::new(self.last_game_time.max(0), EventKind::CoachTip, tip.clone());
+        self.coach_tip = Some((self.last_game_time, tip));
+        self.record(std::slice::from_ref(&event));
+        Some(event)
+    }
+
+    // Log a question typed in the terminal together with its answer
+    pub fn add_question(&mut self, question: &str, answer: String) -> Vec<GameEvent> {
+        let game_time = self.last_game_time.max(0);
+        let events = vec![
+            GameEvent::new(game_time, EventKind::Question, question.to_string()),
+            GameEvent::new(game_time, EventKind::Answer, answer),
+        ];
+        self.record(&events);
+        events
+    }
+
+    // The latest tip and the game time it came in
+    pub fn coach_tip(&self) -> Option<(i32, &str)> {
+        self.coach_tip.as_ref().map(|(time, tip)| (*time, tip.as_str()))
+    }
+
+    // A pick or ban with the hero's win rate this week and, for enemy picks, how the
+    // player's hero does against it
+    fn describe_draft_action(&self, action: &DraftAction, own_team: Option<Team>) -> String {
+        let mut description = action.describe();
+        if let Some(win_rate) = self.hero_win_rates.get(&action.hero_id) {
+            description.push_str(&format!(" - {:.1}% win rate this week", win_rate * 100.0));
+        }
+        let enemy_pick = action.pick && own_team.map(|team| team != action.team).unwrap_or(false);
+        let matchup = self.hero_meta.as_ref().filter(|_| enemy_pick)
+            .and_then(|meta| Some((heroes::by_id(meta.hero_id)?, meta.matchup(action.hero_id)?)));
+        if let Some((hero, matchup)) = matchup {
+            description.push_str(&format!(", your {} is {} ({:+.1}%)", hero.name,
+                if matchup.advantage >= 0.0 { "favoured" } else { "unfavoured" }, matchup.advantage));
+        }
+        description
+    }
+
+    // The player's live GPM, XPM and last hits against the hero's OpenDota bands
+    pub fn benchmark_grades(&self) -> Vec<Grade> {
+        self.game_state.as_ref().map(|state| self.grade(state)).unwrap_or_default()
+    }
+
+    fn grade(&self, state: &GameState) -> Vec<Grade> {
+        let (Some(benchmarks), Some(player), Some(clock_time)) = (&self.hero_benchmarks, state.player(), state.clock_time()) else { return Vec::new() };
+        let hero_id = state.hero().and_then(|h| h.name.as_deref()).and_then(heroes::by_class).map(|h| h.id);
+        if hero_id != Some(benchmarks.hero_id) || clock_time <= 0 {
+            return Vec::new();
+        }
+        benchmarks.grade(player.gpm.unwrap_or(0), player.xpm.unwrap_or(0), player.last_hits.unwrap_or(0), clock_time)
+    }
+
+    // Flag the current moment in the timeline
+    pub fn add_marker(&mut self, note: Option<String>) -> Marker {
+        let marker = Marker { game_time: self.last_game_time.max(0), note };
+        self.record(&[marker.event()]);
+        marker
+    }
+
+    // Mark the camp the player is standing at as cleared. None when there is no camp close by.
+    pub fn mark_camp_cleared(&mut self) -> Option<(Camp, GameEvent)> {
+        let state = self.game_state.as_ref()?;
+        let clock_time = state.clock_time()?;
+        let (camp, _) = camps::camps_near(state.hero_position()?, MARK_CAMP_RANGE).into_iter().next()?;
+        self.camps.mark_cleared(&camp, clock_time);
+
+        let event = GameEvent::new(self.last_game_time.max(0), EventKind::Camp,
+            format!("{} cleared - respawns at {}",
+                camp.describe(),
+                format_game_time(Some(clock_time + self.camps.respawns_in(&camp, clock_time).unwrap_or(0)))));
+        self.record(std::slice::from_ref(&event));
+        Some((camp, event))
+    }
+
+    // Mark a ward planted by the player where their hero stands, or an enemy observer just
+    // spotted near them. None before the clock runs.
+    pub fn mark_ward(&mut self, kind: Consumable, owner: WardOwner) -> Option<GameEvent> {
+        let state = self.game_state.as_ref()?;
+        let clock_time = state.clock_time()?;
+        let ward = self.wards.place(kind, owner, clock_time, state.hero_position(), true);
+        let event = GameEvent::new(self.last_game_time.max(0), EventKind::Ward, ward.describe());
+        self.record(std::slice::from_ref(&event));
+        Some(event)
+    }
+
+    // Say whether an outpost is the player's team's, or flip it when not given. None before
+    // the clock runs or the player's team is known.
+    pub fn mark_outpost(&mut self, outpost: Outpost, ours: Option<bool>) -> Option<GameEvent> {
+        let state = self.game_state.as_ref()?;
+        let clock_time = state.clock_time()?;
+        let team = state.player_team().and_then(|t| Team::from_name(&t))?;
+        let enemy = if team == Team::Radiant { Team::Dire } else { Team::Radiant };
+        let owner = ours.map(|ours| if ours { team } else { enemy });
+        let ownership = self.outposts.mark(outpost, owner, team, clock_time);
+        let event = GameEvent::new(self.last_game_time.max(0), EventKind::Outpost,
+            format!("The {} is {}", outpost.name(), if ownership.owner == team { "yours" } else { "theirs" }));
+        self.record(std::slice::from_ref(&event));
+        Some(event)
+    }
+
+    // Set the Aegis by hand: picked up just now by the player's team or the enemy, or None
+    // when it's been used or denied
+    pub fn mark_aegis(&mut self, ours: Option<bool>) -> Option<GameEvent> {
+        let state = self.game_state.as_ref()?;
+        let clock_time = state.clock_time()?;
+        let team = state.player_team().and_then(|t| Team::from_name(&t))?;
+        let enemy = if team == Team::Radiant { Team::Dire } else { Team::Radiant };
+        let description = match ours {
+            Some(ours) => {
+                self.roshan.aegis_picked_up(clock_time, Some(if ours { team } else { enemy }), false);
+                self.roshan.aegis(clock_time).and_then(|a| a.advice(Some(team)))?
+            },
+            None => {
+                self.roshan.aegis_used()?;
+                "The Aegis is gone".to_string()
+            },
+        };
+        let event = GameEvent::new(self.last_game_time.max(0), EventKind::Roshan, description);
+        self.record(std::slice::from_ref(&event));
+        Some(event)
+    }
+
+    // Whether a payload carries the configured auth token, or none is configured
+    pub fn authorized(&self, state: &GameState) -> bool {
+        match &self.config.server.auth_token {
+            Some(expected) => state.auth.as_ref().and_then(|a| a.token.as_ref()) == Some(expected),
+            None => true,
+        }
+    }
+
+    // Process one GSI payload. Returns the events it produced, which are also kept in the event log.
+    pub fn update(&mut self, state: GameState) -> Vec<GameEvent> {
+        // Ignore payloads that don't carry the configured auth token
+        if !self.authorized(&state) {
+            return Vec::new();
+        }
+
+        let current_game_time = state.game_time().unwrap_or(0);
+        let mut events = Vec::new();
+
+        if self.is_new_match(&state) {
+            self.reset_match();
+            events.push(GameEvent::new(current_game_time, EventKind::NewMatch,
+                "New match - enemy tracking, timers and alerts start over".to_string()));
+        }
+        // Game time going back within a match is a restart or remake, and nothing tracked still holds
+        if state.game_time().map(|t| t + RESTART_TOLERANCE < self.last_game_time).unwrap_or(false) {
+            let from = self.last_game_time;
+            self.reset_match();
+            events.push(GameEvent::new(current_game_time, EventKind::Restart,
+                format!("Game time went back from {} to {} - tracking starts over",
+                    format_game_time(Some(from)), format_game_time(Some(current_game_time)))));
+        }
+        if let Some(match_id) = state.map.as_ref().and_then(|m| m.matchid.clone()) {
+            self.match_id = Some(match_id);
+        }
+        self.clock_mapping.record(&state);
+
+        // Phase changes can arrive without the game time moving, so look at them first
+        if let Some(transition) = state.phase().and_then(|phase| self.phase.update(phase)) {
+            events.push(match transition {
+                PhaseTransition::GameStarted => GameEvent::new(current_game_time, EventKind::GameStarted,
+                    "Heroes are on the map. Good luck!".to_string()),
+                PhaseTransition::GameEnded => GameEvent::new(current_game_time, EventKind::GameEnded,
+                    format!("Game over after {}", format_game_time(state.clock_time()))),
+                PhaseTransition::Changed(Some(from), to) => GameEvent::new(current_game_time, EventKind::PhaseChanged,
+                    format!("{} -> {}", from.name(), to.name())),
+                PhaseTransition::Changed(None, to) => GameEvent::new(current_game_time, EventKind::PhaseChanged,
+                    format!("Now in {}", to.name())),
+            });
+        }
+
+        // Keep the finished match for comparisons in later games. Turbo would skew the averages.
+        if events.iter().any(|e| e.kind == EventKind::GameEnded) && self.game_mode() == GameMode::Normal {
+            if let (Some(history), Some(summary)) = (self.history.as_mut(), MatchSummary::from_state(&state)) {
+                if let Err(e) = history.save(&summary, self.performance.samples(), &self.clock_mapping, &self.series) {
+                    eprintln!("Could not save match {} to history: {}", summary.match_id, e);
+                }
+            }
+        }
+
+        // Report picks and bans as they come in
+        if let Some(draft) = &state.draft {
+            let own_team = state.player_team().and_then(|t| Team::from_name(&t));
+            for action in self.draft_tracker.update(draft) {
+                events.push(GameEvent::new(current_game_time, EventKind::Draft, self.describe_draft_action(&action, own_team)));
+            }
+            self.draft = Some(draft.clone());
+        }
+
+        match self.game_mode.update(&state) {
+            Some(GameMode::Turbo) => events.push(GameEvent::new(current_game_time, EventKind::GameMode,
+                "Turbo - farm targets are for normal games, item timings are scaled and no laning report or history is kept".to_string())),
+            Some(mode) if mode.tracking_only() => events.push(GameEvent::new(current_game_time, EventKind::GameMode,
+                format!("{} - tracking enemies only, with hero, item and map advice turned off", mode.name()))),
+            _ => {},
+        }
+        let tracking_only = self.game_mode().tracking_only();
+
+        // Brief the player on their lane once the draft is done
+        let strategy_time = state.phase() == Some(GamePhase::StrategyTime) && !tracking_only;
+        let team = state.player_team().and_then(|t| Team::from_name(&t));
+        if let (true, None, Some(team)) = (strategy_time, &self.briefing, team) {
+            let hero = state.hero().and_then(|h| h.name.as_deref());
+            let briefing = briefing::build(team, hero, self.draft.as_ref());
+            let summary = match (&briefing.hero, briefing.lane) {
+                (Some(hero), Some(lane)) => format!("{} is usually played {} ({})", hero, lane.name(), lane.map_side(team)),
+                _ => "Expected laners from the draft".to_string(),
+            };
+            events.push(GameEvent::new(current_game_time, EventKind::Briefing, summary));
+            self.briefing = Some(briefing);
+        }
+
+        // Nothing moves while the game is paused, so hold every tracker where it is
+        if state.map.as_ref().and_then(|m| m.paused).unwrap_or(false) {
+            if self.paused.is_none() {
+                let paused_at = if self.last_game_time >= 0 { self.last_game_time } else { current_game_time };
+                self.paused = Some((paused_at, Instant::now()));
+                events.push(GameEvent::new(current_game_time, EventKind::Pause,
+                    "Game paused - tracking is on hold".to_string()));
+            }
+            self.record(&events);
+            return events;
+        }
+        if let Some((paused_at, since)) = self.paused.take() {
+            // Game time that kept counting through the pause, less the second that would pass anyway
+            let skipped = (current_game_time - paused_at - 1).max(0);
+            for enemy in self.enemies.values_mut() {
+                enemy.shift_times(skipped);
+            }
+            self.respawns.shift_times(skipped);
+            events.push(GameEvent::new(current_game_time, EventKind::Pause,
+                format!("Game resumed after {}", format_game_time(Some(since.elapsed().as_secs() as i32)))));
+        }
+
+        // Roshan, Aegis, bounty rune and courier events. Payloads repeat recent events, so skip the ones already seen.
+        if let (Some(clock_time), false) = (state.clock_time(), tracking_only) {
+            for event in state.events() {
+                if self.gsi_events.contains(event) {
+                    continue;
+                }
+                self.gsi_events.push(event.clone());
+                // Clock time the event happened at
+                let at = clock_time - event.game_time.map(|t| (current_game_time - t).max(0)).unwrap_or(0);
+
+                match &event.kind {
+                    GsiEventKind::RoshanKilled { killed_by_team, killer_player_id } => {
+                        let killer = killed_by_team.as_deref().and_then(Team::from_name).or_else(|| killer_player_id.and_then(Team::of_player));
+                        self.roshan.killed(at, killer);
+                        events.push(GameEvent::new(current_game_time, EventKind::Roshan,
+                            format!("{} killed Roshan - he respawns between {} and {}",
+                                killer.map(|t| t.name()).unwrap_or("Someone"),
+                                format_game_time(Some(at + RESPAWN_MIN)),
+                                format_game_time(Some(at + RESPAWN_MAX)))));
+                    },
+                    GsiEventKind::AegisPickedUp { player_id, snatched } => {
+                        let holder = player_id.and_then(Team::of_player);
+                        let snatched = snatched.unwrap_or(false);
+                        self.roshan.aegis_picked_up(at, holder, snatched);
+                        // Spectators see every hero, so the slot names the holder
+                        let hero = state.spectated_players().iter()
+                            .find(|p| Some(p.slot as i32) == *player_id)
+                            .and_then(|p| p.hero?.name.as_deref())
+                            .map(|n| format_hero_name(&n.replace("npc_dota_hero_", "")));
+                        if let Some(hero) = hero {
+                            self.roshan.set_aegis_holder(hero);
+                        }
+                        events.push(GameEvent::new(current_game_time, EventKind::Roshan,
+                            format!("{} {} the Aegis - it expires at {}",
+                                holder.map(|t| t.name()).unwrap_or("Someone"),
+                                if snatched { "snatched" } else { "picked up" },
+                                format_game_time(Some(at + AEGIS_DURATION)))));
+                        if let Some(advice) = self.roshan.aegis(clock_time).and_then(|a| a.advice(team)) {
+                            raise_alert(&mut self.alerts, &mut events, current_game_time,
+                                Alert::new(Severity::Warning, AlertCategory::Roshan, advice)
+                                    .with_key("aegis_taken")
+                                    .with_speech(if holder == team { "We have the Aegis, force fights" } else { "Enemy Aegis, avoid even fights" }));
+                        }
+                    },
+                    GsiEventKind::AegisDenied { .. } => {
+                        self.roshan.aegis_denied();
+                        events.push(GameEvent::new(current_game_time, EventKind::Roshan, "The Aegis was denied".to_string()));
+                    },
+                    GsiEventKind::BountyRuneActivated { player_id, team: taker, bounty_value, team_gold } => {
+                        // The player knows about their own team's runes; enemy pickups show where enemies just were
+                        let taker = taker.as_deref().and_then(Team::from_name).or_else(|| player_id.and_then(Team::of_player));
+                        if let (Some(taker), Some(team)) = (taker, team) {
+                            if taker != team {
+                                raise_alert(&mut self.alerts, &mut events, current_game_time,
+                                    Alert::new(Severity::Info, AlertCategory::Rune,
+                                        format!("{} took a bounty rune{} - enemies were just at a rune spot",
+                                            taker.name(),
+                                            team_gold.or(*bounty_value).map(|gold| format!(" (+{} gold)", gold)).unwrap_or_default()))
+                                        .with_key("bounty_taken"));
+                            }
+                        }
+                    },
+                    GsiEventKind::CourierKilled { courier_team, owner_player_id, .. } => {
+                        let owner = courier_team.as_deref().and_then(Team::from_name).or_else(|| owner_player_id.and_then(Team::of_player));
+                        let description = match (owner, team) {
+                            (Some(owner), Some(team)) if owner == team => "Your courier was killed - anything on it waits until it respawns".to_string(),
+                            (Some(owner), _) => format!("{} courier killed", owner.name()),
+                            _ => "A courier was killed".to_string(),
+                        };
+                        events.push(GameEvent::new(current_game_time, EventKind::Courier, description));
+                    },
+                    GsiEventKind::Other => {},
+                }
+            }
+        }
+
+        // Check if this is a new game time to avoid processing duplicates
+        if self.last_game_time == current_game_time {
+            self.record(&events);
+            return events;
+        }
+        self.last_game_time = current_game_time;
+        self.changes = self.game_state.as_ref().map(|previous| diff::diff(previous, &state)).unwrap_or_default();
+        for change in &self.changes {
+            self.event_log.push(GameEvent::new(current_game_time, EventKind::Change, change.describe()));
+        }
+
+        let turbo = self.game_mode() == GameMode::Turbo;
+
+        let thresholds = &self.config.thresholds;
+
+        // Determine player's team
+        let player_team = state.player_team().unwrap_or_else(|| "unknown".to_string());
+        let enemy_team_id = if player_team == "radiant" { 3 } else { 2 };
+
+        // Extract currently visible enemies from minimap
+        let mut visible_enemies = Vec::new();
+        if let Some(minimap) = &state.minimap {
+            for obj in minimap.values() {
+                if obj.image == "minimap_enemyicon" && obj.team == enemy_team_id {
+                    if let Some(name) = &obj.name {
+                        let hero_name = format_hero_name(name);
+                        visible_enemies.push((hero_name, (obj.xpos, obj.ypos)));
+                    }
+                }
+            }
+        }
+
+        // Get player position for relative directions
+        let player_position = state.hero_position();
+
+        // Every coaching signal below goes through the alert manager
+        self.alerts.expire(current_game_time);
+
+        // Enemies that just died leave the minimap too, so deaths are counted first
+        let enemy_deaths = self.respawns.update(&state, self.game_state.as_ref());
+
+        // Look for several enemies dropping off the minimap at once
+        if let Some(warning) = self.smoke_detector.update(current_game_time, &visible_enemies, &enemy_deaths, player_position) {
+            let (severity, confidence) = match warning.confidence {
+                Confidence::High => (Severity::Critical, warning.confidence.name().red().bold()),
+                Confidence::Medium => (Severity::Warning, warning.confidence.name().yellow().bold()),
+                Confidence::Low => (Severity::Info, warning.confidence.name().normal()),
+            };
+
+            raise_alert(&mut self.alerts, &mut events, current_game_time,
+                Alert::new(severity, AlertCategory::Smoke,
+                    format!("{} vanished together around {} ({} confidence). {}",
+                        warning.heroes.join(", ").yellow().bold(),
+                        warning.vanished_side.name(),
+                        confidence,
+                        "Possible smoke - back off!".red().bold()))
+                    .with_speech("Possible smoke. Back off."));
+        }
+
+        // First mark all enemies as potentially lost
+        for enemy in self.enemies.values_mut() {
+            if enemy.status != EnemyStatus::Lost && current_game_time - enemy.last_seen_time > thresholds.lost_after_seconds {
+                enemy.status = EnemyStatus::Lost;
+            }
+        }
+
+        // Then update with current sightings
+        for (name, position) in visible_enemies {
+            let mut status = EnemyStatus::Tracking;
+            self.heatmap.record(&name, position);
+            self.series.record_sighting(current_game_time, &name, position);
+            if let (Some(team), Some(clock_time)) = (Team::from_name(&player_team), state.clock_time()) {
+                self.lanes.record(&name, position, if team == Team::Radiant { Team::Dire } else { Team::Radiant }, clock_time);
+            }
+
+            match self.enemies.get(&name) {
+                None => status = EnemyStatus::NewlySpotted,
+                Some(existing) => {
+                    if has_moved_significantly(existing.last_seen_position, position, thresholds) {
+                        status = EnemyStatus::MovedSignificantly;
+                    }
+                }
+            }
+
+            let previous = self.enemies.get(&name);
+            let times_spotted = previous.map(|existing| existing.times_spotted + 1).unwrap_or(1);
+
+            // Vitals are only sent for every hero when spectating
+            let spectated = state.spectated_players().into_iter()
+                .find(|p| p.hero.and_then(|h| h.name.as_deref()).map(format_hero_name).as_deref() == Some(name.as_str()))
+                .and_then(|p| p.hero);
+            let seen = |value: Option<i32>, max: Option<i32>, old: Option<Observed<i32>>| match (value, max) {
+                (Some(value), Some(max)) if max > 0 => Observed::LastKnown { value: value * 100 / max, at: current_game_time },
+                _ => old.unwrap_or(Observed::Unknown),
+            };
+
+            // Update or create entry
+            self.enemies.insert(name.clone(), EnemyHeroState {
+                name: name.clone(),
+                last_seen_position: position,
+                last_seen_time: current_game_time,
+                previous_sighting: match previous {
+                    Some(e) if e.last_seen_position != position => Some((e.last_seen_position, e.last_seen_time)),
+                    Some(e) => e.previous_sighting,
+                    None => None,
+                },
+                level: match spectated.and_then(|h| h.level) {
+                    Some(level) => Observed::LastKnown { value: level, at: current_game_time },
+                    None => previous.map(|e| e.level).unwrap_or(Observed::Unknown),
+                },
+                health: seen(spectated.and_then(|h| h.health), spectated.and_then(|h| h.max_health), previous.map(|e| e.health)),
+                mana: seen(spectated.and_then(|h| h.mana), spectated.and_then(|h| h.max_mana), previous.map(|e| e.mana)),
+                times_spotted,
+                status,
+            });
+
+            // Add to enemy team heroes list if not already there
+            if !self.enemy_team_heroes.contains(&name) {
+                self.enemy_team_heroes.push(name.clone());
+                events.push(GameEvent::new(current_game_time, EventKind::EnemyDiscovered,
+                    format!("{} spotted for the first time. Now tracking {} enemies:",
+                        name.yellow().bold(),
+                        self.enemy_team_heroes.len())));
+            }
+        }
+
+        // Process enemy states to generate text updates
+        if let Some(pos) = player_position {
+            for (name, enemy) in self.enemies.iter() {
+                match enemy.status {
+                    EnemyStatus::NewlySpotted => {
+                        events.push(GameEvent::new(current_game_time, EventKind::EnemySpotted,
+                            format!("{} {} ({}) spotted {} in the {}",
+                            name.yellow().bold(),
+                            if enemy.times_spotted > 1 { "reappeared" } else { "appeared" },
+                            enemy.describe_level(current_game_time),
+                            describe_position_relative_to_player(pos, enemy.last_seen_position, thresholds),
+                            describe_map_location(enemy.last_seen_position))));
+                    },
+                    EnemyStatus::MovedSignificantly => {
+                        let location = describe_position_relative_to_player(pos, enemy.last_seen_position, thresholds);
+                        events.push(GameEvent::new(current_game_time, EventKind::EnemyMovement,
+                            format!("{} is moving, now {} in the {}",
+                            name.yellow(),
+                            location,
+                            describe_map_location(enemy.last_seen_position))));
+                    },
+                    EnemyStatus::Lost => {
+                        events.push(GameEvent::new(current_game_time, EventKind::EnemyMissing,
+                            format!("Lost track of {}{}, last seen {} seconds ago",
+                            name,
+                            self.lanes.assignment(name).map(|a| format!(" (enemy {})", a.role())).unwrap_or_default(),
+                            current_game_time - enemy.last_seen_time)));
+                    },
+                    _ => {}
+                }
+            }
+        }
+
+        // Warn when an enemy who dropped off the minimap is probably closing in
+        if let Some(pos) = player_position {
+            for (enemy, prediction) in self.enemies.values().filter_map(|e| predict_position(e, current_game_time).map(|p| (e, p))) {
+                let closing = calculate_distance(pos, prediction.position) < calculate_distance(pos, enemy.last_seen_position);
+                let reaches = calculate_distance(pos, prediction.position) <= thresholds.nearby_distance + prediction.radius / 2.0;
+                if closing && reaches && (2..=PREDICT_FOR / 2).contains(&prediction.seconds_unseen) {
+                    raise_alert(&mut self.alerts, &mut events, current_game_time,
+                        Alert::new(Severity::Warning, AlertCategory::MissingEnemies,
+                            format!("{} dropped off the minimap {}s ago heading your way - may now be {}",
+                                prediction.hero.yellow().bold(),
+                                prediction.seconds_unseen,
+                                describe_position_relative_to_player(pos, prediction.position, thresholds)))
+                            .with_key(format!("predicted_{}", prediction.hero))
+                            .with_speech(format!("{} may be coming", prediction.hero)));
+                }
+            }
+        }
+
+        // Warn when several enemies are unaccounted for while the player is exposed
+        if let (Some(pos), "radiant" | "dire") = (player_position, player_team.as_str()) {
+            let report = missing::check_missing_enemies(current_game_time, &self.enemies, pos, player_team == "radiant", thresholds);
+
+            if let Some(missing) = report {
+                let details: Vec<String> = missing.iter()
+                    .map(|m| format!("{} ({}{}, {}s ago)",
+                        m.name.yellow().bold(),
+                        self.lanes.assignment(&m.name).map(|a| format!("{}, ", a.role())).unwrap_or_default(),
+                        m.last_seen_side.name(),
+                        m.seconds_unseen))
+                    .collect();
+
+                // Keyed by count so more enemies disappearing escalates past the cooldown
+                raise_alert(&mut self.alerts, &mut events, current_game_time,
+                    Alert::new(Severity::Critical, AlertCategory::MissingEnemies,
+                        format!("{} enemies missing while you are past the river! Last seen: {}. {}",
+                            missing.len(),
+                            details.join(", "),
+                            "Back off or group up.".red().bold()))
+                        .with_key(missing.len().to_string())
+                        .with_speech(format!("{} enemies missing. Back off.", missing.len())));
+            }
+        }
+
+        // The user's own rules work on any game
+        for alert in self.rules.update(&state, current_game_time) {
+            raise_alert(&mut self.alerts, &mut events, current_game_time, alert);
+        }
+        for alert in self.plugins.update(current_game_time, &state, &self.changes) {
+            raise_alert(&mut self.alerts, &mut events, current_game_time, alert);
+        }
+
+        // Custom games and Ability Draft play too differently for the rest of the advice
+        if tracking_only {
+            self.game_state = Some(state);
+            self.record(&events);
+            return events;
+        }
+
+        // Check for low health buildings
+        let enemy_team = if player_team == "radiant" { Team::Dire } else { Team::Radiant };
+        let enemy_buildings = buildings::team_buildings(&state, enemy_team);
+
+        // Losing a tier 1 tower refreshes a team's glyph
+        let lost_tier_one = self.changes.iter().any(|c| matches!(c, Change::TowerFell { team, tier: 1, .. } if *team == enemy_team));
+        let glyph_down = self.enemy_cooldowns.remaining(TeamAbility::Glyph, current_game_time).is_some();
+        if lost_tier_one && glyph_down {
+            self.enemy_cooldowns.reset(TeamAbility::Glyph);
+            events.push(GameEvent::new(current_game_time, EventKind::EnemyCooldown,
+                format!("{} lost a tier 1 tower - their glyph is refreshed", enemy_team.name())));
+        }
+        for ability in self.enemy_cooldowns.take_ready(current_game_time) {
+            events.push(GameEvent::new(current_game_time, EventKind::EnemyCooldown,
+                format!("Enemy {} is back up", ability.name())));
+        }
+        for building in &enemy_buildings {
+            let health_percent = building.health_percent();
+
+            // Only alert for low health buildings
+            if health_percent <= thresholds.low_building_percent {
+                raise_alert(&mut self.alerts, &mut events, current_game_time,
+                    Alert::new(Severity::Info, AlertCategory::Objective,
+                        format!("{} at {}% health",
+                            building.describe().green(),
+                            health_percent))
+                        .with_key(building.key.as_str()));
+            }
+        }
+
+        // Recommend the next building to take
+        if let Some(suggestion) = objectives::suggest(current_game_time, &enemy_buildings, &self.enemies) {
+            let severity = if suggestion.elsewhere_side.is_some() { Severity::Warning } else { Severity::Info };
+            let speech = format!("Push {}", suggestion.target.lane.name());
+            let glyph = match self.enemy_cooldowns.remaining(TeamAbility::Glyph, current_game_time) {
+                Some(seconds) => format!("enemy glyph down for {}", format_game_time(Some(seconds))).green(),
+                None => "enemy glyph may be up".yellow(),
+            };
+            raise_alert(&mut self.alerts, &mut events, current_game_time,
+                Alert::new(severity, AlertCategory::Objective, format!("{} ({})", suggestion.describe(), glyph))
+                    .with_key(format!("push_{}", suggestion.target.key))
+                    .with_speech(speech));
+        }
+
+        // Point the player at a lane that needs defending. One suggestion at a time, so it doesn't nag.
+        let own_team = if enemy_team == Team::Radiant { Team::Dire } else { Team::Radiant };
+        if let (Some(clock_time), Some(pos), true) = (state.clock_time(), player_position, state.buildings.is_some()) {
+            let own_buildings = buildings::team_buildings(&state, own_team);
+            let rotation = self.rotations.suggest(current_game_time, &own_buildings, &self.enemies,
+                &self.lanes, pos, thresholds.missing_after_seconds);
+            if let Some(rotation) = rotation.filter(|_| clock_time >= ROTATIONS_FROM) {
+                raise_alert(&mut self.alerts, &mut events, current_game_time,
+                    Alert::new(Severity::Warning, AlertCategory::Rotation, rotation.describe())
+                        .with_key("rotate")
+                        .with_speech(format!("Rotate {}", rotation.side.name())));
+            }
+        }
+
+        // Call siege waves about to reach a contested tower, either way round
+        if let (Some(team), Some(clock_time)) = (team, state.clock_time()) {
+            let standing: Vec<_> = Team::BOTH.iter().flat_map(|&t| buildings::team_buildings(&state, t)).collect();
+            for wave in self.siege_timers.due(&state, clock_time, &standing) {
+                let ours = wave.attackers == team;
+                raise_alert(&mut self.alerts, &mut events, current_game_time,
+                    Alert::new(if ours { Severity::Info } else { Severity::Warning }, AlertCategory::Objective, wave.describe(team, clock_time))
+                        .with_key(format!("siege_{}", wave.target.key))
+                        .with_speech(if ours { "Siege wave, push now" } else { "Enemy siege wave" }));
+            }
+        }
+
+        // Fallen barracks make the other side's creeps super in that lane, and mega once all six are gone
+        if let Some(team) = team {
+            for upgrade in self.creeps.upgrades(&state) {
+                let lanes = upgrade.lanes.iter().map(|l| l.name()).collect::<Vec<_>>().join(" and ");
+                let (severity, message, speech) = match (upgrade.team == team, upgrade.tier) {
+                    (true, CreepTier::Mega) => (Severity::Info,
+                        "You have mega creeps - play safe, let the waves siege and don't buy back aggressively".to_string(), "You have mega creeps"),
+                    (false, CreepTier::Mega) => (Severity::Critical,
+                        "They have mega creeps - clear waves together, keep buyback gold and only take fights you must".to_string(), "They have mega creeps"),
+                    (true, _) => (Severity::Info,
+                        format!("Your {} creeps are super creeps now - that lane pushes itself, pressure the others", lanes), "Super creeps"),
+                    (false, _) => (Severity::Warning,
+                        format!("Their {} creeps are super creeps now - clear those waves before they reach your base", lanes), "Enemy super creeps"),
+                };
+                raise_alert(&mut self.alerts, &mut events, current_game_time,
+                    Alert::new(severity, AlertCategory::Objective, message)
+                        .with_key(format!("creeps_{}_{}", upgrade.team.name(), lanes))
+                        .with_speech(speech));
+            }
+        }
+
+        // Go for the outposts once a tier 2 tower near one falls, and before night falls
+        if let (Some(team), Some(clock_time)) = (team, state.clock_time()) {
+            self.outposts.update(&state, clock_time);
+            for change in &self.changes {
+                let Change::TowerFell { team: lost_by, tier: 2, key } = change else { continue };
+                let lane = buildings::classify(key).1;
+                for outpost in Outpost::near_lane(lane) {
+                    let Some(advice) = self.outposts.advice(outpost, team) else { continue };
+                    let message = if *lost_by == team {
+                        format!("Your tier 2 {} tower fell - expect them at the {} and take it back once they leave",
+                            lane.name(), outpost.name())
+                    } else {
+                        format!("Their tier 2 {} tower is down - {} the {} while you're there", lane.name(), advice, outpost.name())
+                    };
+                    raise_alert(&mut self.alerts, &mut events, current_game_time,
+                        Alert::new(Severity::Info, AlertCategory::Objective, message)
+                            .with_key(format!("{}_{}", outpost.name(), key))
+                            .with_speech(format!("Take the {}", outpost.name())));
+                }
+            }
+
+            let map = state.map.as_ref();
+            let day_night = DayNight::from_clock(clock_time, map.and_then(|m| m.daytime), map.and_then(|m| m.nightstalker_night).unwrap_or(false));
+            let wanted: Vec<String> = Outpost::BOTH.iter()
+                .filter_map(|&outpost| self.outposts.advice(outpost, team).map(|advice| format!("{} the {}", advice, outpost.name())))
+                .collect();
+            let until = day_night.night_falls_in().filter(|secs| *secs <= VISION_LEAD);
+            if let (true, Some(until)) = (clock_time >= outposts::REMINDERS_FROM && !wanted.is_empty(), until) {
+                let night = clock_time + until;
+                raise_alert(&mut self.alerts, &mut events, current_game_time,
+                    Alert::new(Severity::Info, AlertCategory::Objective,
+                        format!("Night falls at {} - {} before then, outpost vision counts most when everyone sees less",
+                            format_game_time(Some(night)), wanted.join(" and ")))
+                        .with_key(format!("outposts_{}", night))
+                        .with_speech("Take the outposts before night"));
+            }
+        }
+
+        // Keep the courier out of reach while enemies are unaccounted for, and point out enemy couriers worth a detour
+        if let Some(team) = team {
+            let missing = missing::missing_enemies(&self.enemies, current_game_time, thresholds.missing_after_seconds).len();
+            if missing >= thresholds.missing_enemy_count {
+                for courier in couriers::in_danger(&state, self.game_state.as_ref(), team) {
+                    raise_alert(&mut self.alerts, &mut events, current_game_time,
+                        Alert::new(Severity::Warning, AlertCategory::Courier,
+                            format!("Your courier is in the {}{} with {} enemies missing - send it back or around",
+                                describe_map_location(courier.position),
+                                if courier.value > 0 { format!(" carrying {} gold of items", courier.value) } else { String::new() },
+                                missing))
+                            .with_key(courier.key)
+                            .with_speech("Courier in danger"));
+                }
+            }
+
+            let enemy = if team == Team::Radiant { Team::Dire } else { Team::Radiant };
+            if let Some(target) = couriers::snipe_targets(&state, enemy, player_position).into_iter().next() {
+                raise_alert(&mut self.alerts, &mut events, current_game_time,
+                    Alert::new(Severity::Info, AlertCategory::Courier,
+                        format!("Enemy courier {}in the {}{} - worth sniping",
+                            player_position.map(|pos| format!("{} ", describe_position_relative_to_player(pos, target.position, thresholds))).unwrap_or_default(),
+                            describe_map_location(target.position),
+                            target.describe_cargo().map(|cargo| format!(", {}", cargo)).unwrap_or_default()))
+                        .with_key("snipe")
+                        .with_speech("Enemy courier"));
+            }
+        }
+
+        // Announce rune spawns shortly before they happen
+        let clock_time = state.clock_time();
+        if let Some(clock_time) = clock_time {
+            // Bounty and water runes come with a hint for the player's position
+            let position = state.hero().and_then(|h| h.name.as_deref()).and_then(heroes::by_class).map(|hero| self.position_of(hero));
+            for spawn in self.rune_timers.due_alerts(clock_time, thresholds.rune_alert_lead) {
+                let hint = position.and_then(|position| roles::rune_hint(position, &spawn));
+                raise_alert(&mut self.alerts, &mut events, current_game_time,
+                    Alert::new(Severity::Warning, AlertCategory::Rune,
+                        format!("{} spawning in {} seconds (at {}){}",
+                            spawn.kind.name().cyan(),
+                            spawn.seconds_until,
+                            format_game_time(Some(spawn.spawn_time)),
+                            hint.map(|hint| format!(" - {}", hint)).unwrap_or_default()))
+                        .with_key(spawn.kind.name())
+                        .with_speech(format!("{} in {} seconds", spawn.kind.name(), spawn.seconds_until)));
+            }
+        }
+
+        // The Aegis in the player's own inventory: theirs to carry, gone once it's used
+        if let (Some(clock_time), Some(inventory)) = (clock_time, state.inventory()) {
+            let hero = state.hero().and_then(|h| h.name.as_deref()).map(|n| format_hero_name(&n.replace("npc_dota_hero_", "")));
+            let held = self.roshan.aegis(clock_time).map(|a| a.team == team && a.holder.is_some() && a.holder == hero);
+            if inventory.contains("item_aegis") && held != Some(true) {
+                if self.roshan.aegis(clock_time).map(|a| a.team != team).unwrap_or(true) {
+                    self.roshan.aegis_picked_up(clock_time, team, false);
+                }
+                if let Some(hero) = hero {
+                    self.roshan.set_aegis_holder(hero);
+                }
+                if let Some(advice) = self.roshan.aegis(clock_time).and_then(|a| a.advice(team)) {
+                    raise_alert(&mut self.alerts, &mut events, current_game_time,
+                        Alert::new(Severity::Warning, AlertCategory::Roshan, advice)
+                            .with_key("aegis_taken")
+                            .with_speech("You have the Aegis, force fights"));
+                }
+            } else if !inventory.contains("item_aegis") && held == Some(true) {
+                self.roshan.aegis_used();
+                events.push(GameEvent::new(current_game_time, EventKind::Roshan, "Your Aegis is spent - no second life now".to_string()));
+            }
+        }
+
+        // Roshan's respawn window and the Aegis running out
+        if let Some(clock_time) = clock_time {
+            for due in self.roshan.due_alerts(clock_time) {
+                let alert = match due {
+                    RoshanAlert::WindowOpen { respawn_by } => Alert::new(Severity::Info, AlertCategory::Roshan,
+                        format!("Roshan may be up - he is certainly back by {}", format_game_time(Some(respawn_by))))
+                        .with_key("window")
+                        .with_speech("Roshan may be up"),
+                    RoshanAlert::Up => Alert::new(Severity::Warning, AlertCategory::Roshan,
+                        "Roshan is up - take him or ward the pit".to_string())
+                        .with_key("up")
+                        .with_speech("Roshan is up"),
+                    RoshanAlert::AegisExpiring(aegis) => Alert::new(Severity::Info, AlertCategory::Roshan,
+                        format!("{} Aegis expires in {}s{}",
+                            match aegis.team {
+                                Some(holder) if Some(holder) == team => "Your team's".to_string(),
+                                Some(holder) => format!("{}'s", holder.name()),
+                                None => "The".to_string(),
+                            },
+                            aegis.expires_at() - clock_time,
+                            match aegis.team {
+                                Some(holder) if Some(holder) == team => " - use it now or lose it",
+                                Some(_) => " - fight once it's gone",
+                                None => "",
+                            }))
+                        .with_key("aegis")
+                        .with_speech("Aegis expiring"),
+                };
+                raise_alert(&mut self.alerts, &mut events, current_game_time, alert);
+            }
+        }
+
+        // Remind the player to stack nearby camps
+        let hero_alive = state.hero().and_then(|h| h.alive).unwrap_or(true);
+        if let (Some(clock_time), Some(pos), true) = (clock_time, player_position, hero_alive) {
+            if let Some(camps) = self.stack_reminder.check(clock_time, pos, thresholds.stack_range, |camp| self.camps.is_up(camp, clock_time)) {
+                let advice: Vec<String> = camps.iter()
+                    .map(|camp| format!("{} (pull at X:{})", camp.describe().cyan(), camp.size.pull_second()))
+                    .collect();
+
+                raise_alert(&mut self.alerts, &mut events, current_game_time,
+                    Alert::new(Severity::Info, AlertCategory::Stack,
+                        format!("Stack timing! Nearby: {}", advice.join(", "))));
+            }
+        }
+
+        // Nag about an empty teleport slot once the horn has sounded
+        let playing = state.phase().map(|p| p == GamePhase::InProgress).unwrap_or(true);
+        let player = state.player();
+        if let (true, Some(inventory), Some(gold)) = (playing, state.inventory(), player.and_then(|p| p.gold)) {
+            let deaths = player.and_then(|p| p.deaths).unwrap_or(0);
+            if let Some(severity) = self.tp_reminder.check(inventory.has_teleport(), gold, deaths) {
+                let message = match severity {
+                    Severity::Info => "No TP scroll - buy one so you can join fights and defend towers".to_string(),
+                    _ => format!("{} Buy a TP before you leave the fountain.", "You died without a TP scroll!".red().bold()),
+                };
+                raise_alert(&mut self.alerts, &mut events, current_game_time,
+                    Alert::new(severity, AlertCategory::Teleport, message)
+                        .with_speech("Buy a TP scroll"));
+            }
+        }
+
+        // Follow wards, smokes and dust from purchase to use
+        if let (Some(clock_time), Some(inventory), Some(gold)) = (clock_time, state.inventory(), player.and_then(|p| p.gold)) {
+            for (kind, count) in self.consumables.update(inventory, gold, clock_time) {
+                if matches!(kind, Consumable::Observer | Consumable::Sentry) {
+                    for _ in 0..count {
+                        self.wards.place(kind, WardOwner::Own, clock_time, state.hero_position(), false);
+                    }
+                }
+            }
+        }
+
+        // Re-ward before the player's observers run out, and smoke through once the enemy's have
+        if let Some(clock_time) = clock_time {
+            for alert in self.wards.update(clock_time, VISION_LEAD) {
+                let (ward, message, speech) = match alert {
+                    WardAlert::Expiring(ward) => {
+                        let message = format!("{} - bring another to re-ward it", ward.describe());
+                        (ward, message, "Ward running out")
+                    },
+                    WardAlert::EnemyExpired(ward) => {
+                        let message = format!("The enemy observer{} has likely run out - smoke through before they re-ward",
+                            ward.location.as_ref().map(|l| format!(" in {}", l)).unwrap_or_default());
+                        (ward, message, "Enemy ward gone, smoke now")
+                    },
+                };
+                raise_alert(&mut self.alerts, &mut events, current_game_time,
+                    Alert::new(Severity::Info, AlertCategory::Vision, message)
+                        .with_key(format!("{:?}_{}", ward.owner, ward.placed_at))
+                        .with_speech(speech));
+            }
+        }
+
+        // Work out the player's role over the laning stage, unless they've said
+        if let Some(detection) = clock_time.and_then(|t| self.roles.update(&state, t)) {
+            if self.role_override.is_none() {
+                events.push(GameEvent::new(current_game_time, EventKind::Role, detection.describe()));
+            }
+        }
+
+        // Compare a core's farm with the role's benchmark at each checkpoint
+        let hero_info = state.hero().and_then(|h| h.name.as_deref()).and_then(heroes::by_class);
+        let position = hero_info.map(|hero| self.position_of(hero));
+        if let (Some(clock_time), Some(hero), Some(position), Some(player)) = (clock_time, hero_info, position, state.player()) {
+            let (last_hits, denies) = (player.last_hits.unwrap_or(0), player.denies.unwrap_or(0));
+            let check = self.cs_benchmark.check(&self.config.benchmarks, clock_time, position, last_hits, denies)
+                .filter(|_| roles::coached_on(position, AlertCategory::LastHits));
+            if let Some(check) = check {
+                // Players of the same hero on OpenDota say more than a fixed target for the role
+                let grades = if turbo { Vec::new() } else { self.grade(&state) };
+                // Turbo farm runs well ahead of the normal-game targets, so it's only a rough guide
+                let (severity, message) = if !grades.is_empty() {
+                    let behind = grades.iter().any(|g| g.below_par());
+                    (if behind { Severity::Warning } else { Severity::Info },
+                        format!("At {}:00 against {} players on OpenDota: {}", check.minute, hero.name,
+                            grades.iter().map(|g| g.describe()).collect::<Vec<_>>().join(", ")))
+                } else if turbo {
+                    (Severity::Info, format!("{} (Turbo - normal-game targets)", check.describe()))
+                } else {
+                    (if check.on_track() { Severity::Info } else { Severity::Warning }, check.describe())
+                };
+                raise_alert(&mut self.alerts, &mut events, current_game_time,
+                    Alert::new(severity, AlertCategory::LastHits, message)
+                        .with_key(check.minute.to_string())
+                        .with_speech(format!("{} last hits at {} minutes", check.last_hits, check.minute)));
+
+                // The player's own past games say more than the generic target
+                if let Some(baseline) = self.baselines_for(&state).into_iter().find(|b| b.metric == Metric::LastHits) {
+                    let difference = (baseline.current - baseline.average).abs();
+                    events.push(GameEvent::new(current_game_time, EventKind::Baseline,
+                        format!("{} - {} {}", baseline.describe(), difference, if baseline.ahead() { "ahead" } else { "behind" })));
+                }
+            }
+        }
+
+        // Whether the enemy is sneaking Roshan, or the player's team has a window for him
+        if let (Some(clock_time), Some(team)) = (clock_time, team) {
+            let up = !matches!(self.roshan.status(clock_time), RoshanStatus::Dead { .. });
+            if up && clock_time >= CONTEST_FROM && self.roshan.aegis(clock_time).is_none() {
+                let enemy = if team == Team::Radiant { Team::Dire } else { Team::Radiant };
+                let map = state.map.as_ref();
+                let night = !DayNight::from_clock(clock_time, map.and_then(|m| m.daytime), map.and_then(|m| m.nightstalker_night).unwrap_or(false)).is_day;
+                let call = roshan_contest::assess(&Situation {
+                    game_time: current_game_time,
+                    night,
+                    enemies: &self.enemies,
+                    enemies_dead: &self.respawns.dead(enemy),
+                    missing_after: thresholds.missing_after_seconds,
+                    missing_count: thresholds.missing_enemy_count,
+                    smokes: self.consumables.held(Consumable::Smoke),
+                    pit_warded: self.wards.active(clock_time).iter()
+                        .any(|w| w.owner == WardOwner::Own && w.kind == Consumable::Observer
+                            && w.location.as_deref().map(|l| l.contains("Roshan pit")).unwrap_or(false)),
+                });
+                if let Some(call) = call.filter(|c| self.roshan_contest.due(c, current_game_time)) {
+                    let (severity, key, speech) = match call.kind {
+                        ContestKind::EnemyTaking => (Severity::Warning, "contest", "They may be taking Roshan"),
+                        ContestKind::Opportunity => (Severity::Info, "opportunity", "Chance to take Roshan"),
+                    };
+                    raise_alert(&mut self.alerts, &mut events, current_game_time,
+                        Alert::new(severity, AlertCategory::Roshan, call.describe())
+                            .with_key(key)
+                            .with_speech(speech));
+                }
+            }
+        }
+
+        // Supports get ward and smoke timings instead: vision is worth most just before night falls
+        // and the power runes spawn
+        if let (Some(clock_time), Some(position)) = (clock_time, position) {
+            let supporting = roles::coached_on(position, AlertCategory::Vision) && clock_time > 0;
+            let map = state.map.as_ref();
+            let day_night = DayNight::from_clock(clock_time, map.and_then(|m| m.daytime), map.and_then(|m| m.nightstalker_night).unwrap_or(false));
+            let idle = self.consumables.idle_observers(clock_time).map(|(count, secs)|
+                format!("you've held {} {} for {}", count, Consumable::Observer.name(count), format_game_time(Some(secs))));
+            let power_rune = RuneKind::Power.next_spawn(clock_time).filter(|spawn| spawn - clock_time <= VISION_LEAD);
+            if let (true, Some(until)) = (supporting, day_night.night_falls_in().filter(|secs| *secs <= VISION_LEAD)) {
+                let night = clock_time + until;
+                let carrying = self.consumables.held(Consumable::Observer) > 0;
+                let wards = match (&idle, carrying) {
+                    (Some(idle), _) => format!("{} - ward the enemy's approaches", idle),
+                    (None, true) => "ward the enemy's approaches".to_string(),
+                    (None, false) => "buy observers, ward the enemy's approaches".to_string(),
+                };
+                raise_alert(&mut self.alerts, &mut events, current_game_time,
+                    Alert::new(Severity::Info, AlertCategory::Vision,
+                        format!("Night falls at {} - {} and look for a smoke with your cores while their vision is short",
+                            format_game_time(Some(night)), wards))
+                        .with_key(night.to_string())
+                        .with_speech("Ward before night"));
+            } else if let (true, Some(idle), Some(spawn)) = (supporting, &idle, power_rune) {
+                raise_alert(&mut self.alerts, &mut events, current_game_time,
+                    Alert::new(Severity::Info, AlertCategory::Vision,
+                        format!("Power rune at {} - {}, ward the rune and the river around it", format_game_time(Some(spawn)), idle))
+                        .with_key(format!("rune_{}", spawn))
+                        .with_speech("Plant your wards"));
+            }
+
+            // Compare the wards and smokes used with a support's usual pace
+            let enemy_invisibility = counters::threat_profile(&self.enemy_hero_infos()).iter().any(|(threat, _)| *threat == Threat::Invisibility);
+            if let Some(check) = self.consumables.check(clock_time, enemy_invisibility).filter(|_| supporting) {
+                let behind = !check.behind().is_empty();
+                raise_alert(&mut self.alerts, &mut events, current_game_time,
+                    Alert::new(if behind { Severity::Warning } else { Severity::Info }, AlertCategory::Vision, check.describe())
+                        .with_key(format!("usage_{}", check.minute))
+                        .with_speech(if behind { "Use more wards" } else { "Good ward usage" }));
+            }
+        }
+
+        // Sum up the laning stage once the clock reaches 10:00. Turbo lanes are over well before that.
+        if let (None, Some(position), false) = (&self.laning_report, position, turbo) {
+            let detected_lane = self.roles.detected().and_then(|d| match d.lane {
+                Assignment::Lane(lane) => Some(lane),
+                Assignment::Roaming => None,
+            });
+            let lane = self.briefing.as_ref().and_then(|b| b.lane).or(detected_lane).unwrap_or_else(|| Lane::of_position(position));
+            if let Some(report) = laning::build(&state, lane, position, &self.config.benchmarks, &self.performance) {
+                events.push(GameEvent::new(current_game_time, EventKind::LaningReport, report.summary()));
+                self.laning_report = Some(report);
+            }
+        }
+
+        // Note when key items are finished
+        if let (Some(clock_time), Some(position), Some(inventory)) = (clock_time, position, state.inventory()) {
+            let mut targets = self.config.benchmarks.item_timings.for_position(position).clone();
+            if turbo {
+                for minute in targets.values_mut() {
+                    *minute = (*minute as f32 * TURBO_ITEM_PACE).round() as i32;
+                }
+            }
+            for timing in self.item_timings.update(inventory, clock_time, &targets) {
+                events.push(GameEvent::new(current_game_time, EventKind::ItemTiming, timing.describe()));
+            }
+        }
+
+        // Point out items that answer the enemy lineup
+        let enemies = self.enemy_hero_infos();
+        for advice in counters::advice(&enemies) {
+            raise_alert(&mut self.alerts, &mut events, current_game_time,
+                Alert::new(Severity::Info, AlertCategory::Counter, advice.describe())
+                    .with_key(format!("{}_{}", advice.threat.slug(), advice.heroes.len())));
+        }
+
+        // Work out the next purchase when gold or items change
+        let gold = state.player().and_then(|p| p.gold);
+        if let (Some(hero), Some(inventory), Some(gold)) = (hero_info, state.inventory(), gold) {
+            let popular = self.hero_meta.as_ref().filter(|m| m.hero_id == hero.id).map(|m| m.popular_build.as_slice());
+            if let Some(recommendation) = self.item_advisor.update(hero, inventory, gold, &enemies, popular) {
+                events.push(GameEvent::new(current_game_time, EventKind::ItemSuggestion, recommendation.describe()));
+            }
+        }
+
+        // Watch buyback gold from the late mid game on
+        let late = clock_time.map(|t| t >= self.config.thresholds.buyback_from_minute * 60).unwrap_or(false);
+        if let (true, Some(hero), Some(gold)) = (late, state.hero(), gold) {
+            let planned = self.item_advisor.current()
+                .filter(|r| !r.buy_now.is_empty())
+                .map(|r| (r.item.name, r.buy_now.iter().map(|p| p.cost).sum()));
+            if let Some((severity, mut message)) = self.buyback.check(hero, gold, planned) {
+                // With megas the waves win the game; buyback is for defending, not chasing
+                if severity == Severity::Info && team.map(|t| self.creeps.has_megas(t)).unwrap_or(false) {
+                    message.push_str(" - with mega creeps, save it to defend rather than to chase");
+                }
+                raise_alert(&mut self.alerts, &mut events, current_game_time,
+                    Alert::new(severity, AlertCategory::Buyback, message)
+                        .with_speech(if severity == Severity::Warning { "Careful, buyback gold" } else { "Buyback is safe" }));
+            }
+        }
+
+        // Nudge the player to spend skill points after a level-up
+        if let (Some(hero), Some(abilities)) = (state.hero(), state.abilities()) {
+            if let Some((level, unspent, pick)) = self.skill_advisor.check(hero, abilities) {
+                let mut message = format!("Level {} - {} skill point{} to spend", level, unspent, if unspent == 1 { "" } else { "s" });
+                if let Some(pick) = &pick {
+                    message.push_str(&format!(": take {}", pick.describe().green()));
+                }
+                if TALENT_LEVELS.contains(&level) && !matches!(pick, Some(SkillPick::Talent(_))) {
+                    message.push_str(&format!(" (level {} talents are open)", level));
+                }
+                raise_alert(&mut self.alerts, &mut events, current_game_time,
+                    Alert::new(Severity::Info, AlertCategory::Skill, message)
+                        .with_key(level.to_string())
+                        .with_speech(match &pick {
+                            Some(pick) => format!("Level up. Take {}", pick.describe()),
+                            None => "Level up".to_string(),
+                        }));
+            }
+        }
+
+        // Power spikes: seen levels and items where GSI shows them, estimated levels otherwise
+        let mut spikes = Vec::new();
+        if state.is_spectating() {
+            for slot in state.spectated_players() {
+                if let Some(info) = slot.hero.and_then(|h| h.name.as_deref()).and_then(heroes::by_class) {
+                    let items = slot.items.map(|i| i.classes()).unwrap_or_default();
+                    spikes.extend(self.spikes.observe(info, slot.team.name(), slot.hero.and_then(|h| h.level), &items));
+                }
+            }
+        } else {
+            if let Some(hero) = hero_info {
+                let items = state.inventory().map(|i| i.classes()).unwrap_or_default();
+                spikes.extend(self.spikes.observe(hero, "Your", state.hero().and_then(|h| h.level), &items));
+            }
+            if let Some(clock_time) = clock_time {
+                spikes.extend(enemies.iter().filter_map(|enemy| self.spikes.estimate(enemy, clock_time)));
+                for estimate in self.enemy_estimates() {
+                    spikes.extend(self.spikes.infer_items(estimate.hero, &estimate.items));
+                }
+            }
+        }
+        // No egging the player on to look for kills while they're steadying themselves
+        let calming = self.tilt.calming(current_game_time);
+        for spike in spikes.into_iter().filter(|s| !(calming && s.is_own())) {
+            let severity = if spike.is_own() { Severity::Info } else { Severity::Warning };
+            raise_alert(&mut self.alerts, &mut events, current_game_time,
+                Alert::new(severity, AlertCategory::PowerSpike, spike.describe())
+                    .with_key(format!("{}_{:?}", spike.hero.class, spike.kind)));
+        }
+
+        // Explain what went wrong when the player dies
+        let died = self.changes.iter().any(|c| matches!(c, Change::PlayerDied { .. }));
+        if let Some(recap) = self.deaths.update(&state, died, &self.enemies, &self.alerts, self.config.thresholds.medium_distance) {
+            events.push(GameEvent::new(current_game_time, EventKind::DeathRecap, recap.describe()));
+
+            // With one enemy close by, the kill was theirs
+            if let [killer] = recap.nearby_enemies.as_slice() {
+                let streak = self.streaks.credit(killer);
+                if streak >= SPREE {
+                    let level = self.enemies.get(killer).and_then(|e| e.level.value()).unwrap_or_else(|| estimate_hero_level(current_game_time));
+                    raise_alert(&mut self.alerts, &mut events, current_game_time,
+                        Alert::new(Severity::Warning, AlertCategory::Streak,
+                            format!("{} is on a {}-kill streak - worth ~{} gold to shut down", killer, streak, streaks::kill_bounty(level, streak)))
+                            .with_key(killer.clone())
+                            .with_speech(format!("{} is worth a shutdown", killer)));
+                }
+            }
+        }
+
+        // The player's own streak: shutting an enemy down, and the bounty on their own head
+        let kills = |s: &GameState| s.player().and_then(|p| p.kills);
+        let killed = kills(&state).zip(self.game_state.as_ref().and_then(kills)).map(|(now, before)| now > before).unwrap_or(false);
+        if let Some(victim) = state.hero_position().filter(|_| killed)
+            .and_then(|position| streaks::nearest_enemy(&self.enemies, position, current_game_time, self.config.thresholds.medium_distance))
+            .map(|enemy| enemy.name.clone()) {
+            if let Some(streak) = self.streaks.shut_down(&victim).filter(|s| *s >= SPREE) {
+                events.push(GameEvent::new(current_game_time, EventKind::Shutdown,
+                    format!("Shut down {}'s {}-kill streak (+{} bounty gold)", victim, streak, streaks::streak_bounty(streak))));
+            }
+        }
+        if let (Some(streak), Some(level)) = (state.player().and_then(|p| p.kill_streak), state.hero().and_then(|h| h.level)) {
+            if self.streaks.own_streak_due(streak) {
+                raise_alert(&mut self.alerts, &mut events, current_game_time,
+                    Alert::new(Severity::Warning, AlertCategory::Streak,
+                        format!("You're on a {}-kill streak - dying now gives them ~{} gold, don't overextend", streak, streaks::kill_bounty(level, streak)))
+                        .with_key("own")
+                        .with_speech(format!("{} kill streak, play it safe", streak)));
+            }
+        }
+
+        // A calm word when deaths pile up or the farm dries up
+        if let Some(slide) = self.tilt.update(&state, died, self.position(), &self.config.thresholds) {
+            raise_alert(&mut self.alerts, &mut events, current_game_time,
+                Alert::new(Severity::Info, AlertCategory::Pacing, slide.describe())
+                    .with_speech("Rough patch. Play safe near your towers and group with your team"));
+        }
+
+        // Unreliable gold walked into danger, and what deaths cost
+        let risk = gank_risk::assess(&state, &self.enemies, &self.lanes, &self.config.thresholds);
+        if let Some(unspent) = self.gold.update(&state, self.game_state.as_ref(), died, risk, self.config.thresholds.unspent_gold_warning) {
+            raise_alert(&mut self.alerts, &mut events, current_game_time,
+                Alert::new(Severity::Warning, AlertCategory::Gold, unspent.describe())
+                    .with_speech(format!("You're holding {} gold, spend it", format_gold(unspent.unreliable))));
+        }
+
+        // Mark fights in the timeline
+        match self.teamfights.update(&state, self.game_state.as_ref()) {
+            Some(FightTransition::Started(fight)) => events.push(GameEvent::new(current_game_time, EventKind::TeamFight,
+                format!("Fight in progress - {} heroes down since {}", fight.total_deaths(), format_game_time(Some(fight.start))))),
+            Some(FightTransition::Ended(fight)) => events.push(GameEvent::new(current_game_time, EventKind::TeamFight,
+                fight.describe(team))),
+            None => {},
+        }
+
+        // Count down enemy respawns and call out a numbers advantage while fighting
+        for death in &enemy_deaths {
+            events.push(GameEvent::new(current_game_time, EventKind::Respawn, death.describe(current_game_time)));
+        }
+        let fighting = self.teamfights.current().is_some() || self.teamfights.skirmishing(current_game_time);
+        if let Some(team) = team {
+            if let Some(advantage) = self.respawns.take_new_advantage(team, current_game_time).filter(|_| fighting && !self.tilt.calming(current_game_time)) {
+                let aegis = match state.clock_time().and_then(|t| self.roshan.aegis(t)).and_then(|a| a.team) {
+                    Some(holder) if holder == team => " - and you have the Aegis",
+                    Some(_) => ", but mind their Aegis",
+                    None => "",
+                };
+                events.push(GameEvent::new(current_game_time, EventKind::Respawn,
+                    format!("Numbers advantage: {} - take the fight or an objective now{}", advantage.describe(), aegis)));
+            }
+        }
+
+        // Weigh up a siege once the player's team hits the enemy high ground
+        if let (Some(team), Some(clock_time)) = (team, state.clock_time()) {
+            let enemy = if team == Team::Radiant { Team::Dire } else { Team::Radiant };
+            let assessment = high_ground::siege_target(&state, self.game_state.as_ref(), enemy).map(|target| {
+                let gold = state.player().and_then(|p| p.gold).unwrap_or(0);
+                high_ground::assess(target, team, current_game_time, &self.respawns.dead(enemy), self.respawns.dead(team).len(),
+                    self.roshan.aegis(clock_time), state.hero().and_then(|hero| buyback::status(hero, gold)))
+            });
+            let due = self.high_ground.update(current_game_time, assessment.as_ref().map(|a| a.verdict));
+            if let Some(assessment) = assessment.filter(|_| due) {
+                let (severity, speech) = match assessment.verdict {
+                    Verdict::Safe => (Severity::Info, "High ground is safe"),
+                    Verdict::Risky => (Severity::Warning, "Careful on high ground"),
+                    Verdict::Abort => (Severity::Critical, "Back off high ground"),
+                };
+                raise_alert(&mut self.alerts, &mut events, current_game_time,
+                    Alert::new(severity, AlertCategory::Objective, assessment.describe())
+                        .with_key(format!("high_ground_{:?}", assessment.verdict))
+                        .with_speech(speech));
+            }
+        }
+
+        // Call a base race once both ancients are exposed and going down
+        if let Some(team) = team {
+            if let Some(race) = self.base_race.update(&state, team, current_game_time) {
+                let (severity, speech) = match race.outcome() {
+                    Outcome::Winnable => (Severity::Warning, "Base race is winnable, keep hitting"),
+                    Outcome::TooClose => (Severity::Critical, "Base race is too close"),
+                    Outcome::Lost => (Severity::Critical, "You lose the base race, defend"),
+                };
+                raise_alert(&mut self.alerts, &mut events, current_game_time,
+                    Alert::new(severity, AlertCategory::Objective, race.describe())
+                        .with_key(format!("base_race_{:?}", race.outcome()))
+                        .with_speech(speech));
+            }
+        }
+
+        if !state.is_spectating() {
+            self.performance.update(&state);
+            self.awareness.update(&state, &self.enemies, self.config.thresholds.awareness_visible_enemies);
+            if let Some(position) = state.hero_position() {
+                self.series.record_position(current_game_time, position);
+            }
+        }
+
+        // Store the game state
+        self.game_state = Some(state);
+
+        self.record(&events);
+        events
+    }
+
+    // A different match id, or a draft after a game, means the player moved on to a new match
+    fn is_new_match(&self, state: &GameState) -> bool {
+        let match_id = state.map.as_ref().and_then(|m| m.matchid.as_ref());
+        let new_id = matches!((&self.match_id, match_id), (Some(old), Some(new)) if old != new);
+        let after_game = self.phase.current().map(|p| p.is_playing() || p == GamePhase::PostGame).unwrap_or(false);
+        new_id || (after_game && state.phase() == Some(GamePhase::HeroSelection))
+    }
+
+    // Start over for a new match. The session's event log, match history, alert outputs
+    // and config carry over; everything learned about the last match is dropped.
+    fn reset_match(&mut self) {
+        let mut fresh = Coach::new(self.config.clone());
+        std::mem::swap(&mut fresh.event_log, &mut self.event_log);
+        std::mem::swap(&mut fresh.history, &mut self.history);
+        std::mem::swap(&mut fresh.alerts, &mut self.alerts);
+        std::mem::swap(&mut fresh.plugins, &mut self.plugins);
+        fresh.alerts.reset();
+        fresh.plugins.reset();
+        *self = fresh;
+    }
+
+    fn record(&mut self, events: &[GameEvent]) {
+        for event in events {
+            self.event_log.push(event.clone());
+        }
+    }
+
+    // Save game state to file for later analysis
+    pub fn save_game_state(&self) -> std::io::Result<String> {
+        let timestamp = Local::now().format("%Y%m%d_%H%M%S").to_string();
+        let filename = format!("dota_state_{}.json", timestamp);
+
+        // Create a combined state object, still holding the timeline before the first payload
+        let mut combined_state = match &self.game_state {
+            Some(state) => serde_json::to_value(state).unwrap_or_else(|_| serde_json::json!({})),
+            None => serde_json::json!({}),
+        };
+
+        // Add the event history, with the real time of each event for lining it up with a VOD
+        let events: Vec<serde_json::Value> = self.event_log.iter()
+            .map(|e| {
+                let mut json = e.to_json();
+                json["timestamp"] = serde_json::json!(self.clock_mapping.timestamp_at(e.game_time));
+                json
+            })
+            .collect();
+
+        if let Value::Object(ref mut map) = combined_state {
+            map.insert("enemy_tracking".to_string(), snapshot::enemy_tracking(&self.enemies));
+            map.insert("event_log".to_string(), Value::Array(events));
+            map.insert("clock_mapping".to_string(), self.clock_mapping.to_json());
+            map.insert("steam_profiles".to_string(), serde_json::to_value(&self.steam_profiles).unwrap());
+        }
+
+        let mut file = File::create(&filename)?;
+        file.write_all(serde_json::to_string_pretty(&combined_state).unwrap().as_bytes())?;
+        Ok(filename)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    // A support in the middle of a game, `clock_time` into it
+    fn state(clock_time: i32, daytime: bool, nightstalker_night: bool) -> GameState {
+        serde_json::from_value(json!({
+            "map": {
+                "game_time": clock_time,
+                "clock_time": clock_time,
+                "game_state": "DOTA_GAMERULES_STATE_GAME_IN_PROGRESS",
+                "daytime": daytime,
+                "nightstalker_night": nightstalker_night,
+            },
+            "player": { "team_name": "radiant" },
+            "hero": { "name": "npc_dota_hero_crystal_maiden", "alive": true },
+        })).unwrap()
+    }
+
+    fn alerted(events: &[GameEvent], category: AlertCategory) -> bool {
+        events.iter().any(|e| matches!(e.kind, EventKind::Alert(c, _) if c == category) && e.description.starts_with("Night falls"))
+    }
+
+    fn support() -> Coach {
+        let mut coach = Coach::new(Config::default());
+        coach.set_role(Some(5));
+        coach
+    }
+
+    #[test]
+    fn ward_reminder_comes_before_night() {
+        let mut coach = support();
+        assert!(alerted(&coach.update(state(1490, true, false)), AlertCategory::Vision));
+    }
+
+    #[test]
+    fn ward_reminder_is_quiet_while_the_game_reports_night() {
+        let mut coach = support();
+        assert!(!alerted(&coach.update(state(1490, false, false)), AlertCategory::Vision));
+        let mut coach = support();
+        assert!(!alerted(&coach.update(state(1490, true, true)), AlertCategory::Vision));
+    }
+
+    // A fight next to the player with `enemies` on the minimap and Radiant on `kills`
+    fn fight(game_time: i32, enemies: &[&str], kills: i32) -> GameState {
+        let minimap: serde_json::Map<String, Value> = enemies.iter().enumerate().map(|(i, hero)| (format!("o{}", i), json!({
+            "image": "minimap_enemyicon",
+            "name": format!("npc_dota_hero_{}", hero),
+            "team": 3,
+            "xpos": -1000 + 200 * i as i32,
+            "ypos": -1000,
+        }))).collect();
+        serde_json::from_value(json!({
+            "map": {
+                "game_time": game_time,
+                "clock_time": game_time,
+                "game_state": "DOTA_GAMERULES_STATE_GAME_IN_PROGRESS",
+                "radiant_score": kills,
+                "dire_score": 0,
+            },
+            "player": { "team_name": "radiant" },
+            "hero": { "name": "npc_dota_hero_sven", "alive": true, "level": 12, "xpos": -1200, "ypos": -1100 },
+            "minimap": minimap,
+        })).unwrap()
+    }
+
+    fn smoke_warned(events: &[GameEvent]) -> bool {
+        events.iter().any(|e| matches!(e.kind, EventKind::Alert(AlertCategory::Smoke, _)))
+    }
+
+    #[test]
+    fn enemies_vanishing_together_warn_of_a_smoke() {
+        let mut coach = Coach::new(Config::default());
+        coach.update(fight(1200, &["lion", "axe", "lina"], 0));
+        assert!(smoke_warned(&coach.update(fight(1201, &[], 0))));
+    }
+
+    #[test]
+    fn enemies_killed_in_a_fight_are_not_a_smoke() {
+        let mut coach = Coach::new(Config::default());
+        coach.update(fight(1200, &["lion", "axe", "lina"], 0));
+        assert!(!smoke_warned(&coach.update(fight(1201, &[], 3))));
+
+        // The kills can land a tick before the icons go
+        let mut coach = Coach::new(Config::default());
+        coach.update(fight(1200, &["lion", "axe", "lina"], 0));
+        coach.update(fight(1201, &["lion", "axe", "lina"], 2));
+        assert!(!smoke_warned(&coach.update(fight(1202, &["lina"], 2))));
+    }
+
+    #[test]
+    fn outpost_reminder_comes_before_night() {
+        let mut coach = support();
+        assert!(alerted(&coach.update(state(890, true, false)), AlertCategory::Objective));
+    }
+
+    #[test]
+    fn outpost_reminder_is_quiet_while_the_game_reports_night() {
+        let mut coach = support();
+        assert!(!alerted(&coach.update(state(890, false, false)), AlertCategory::Objective));
+        let mut coach = support();
+        assert!(!alerted(&coach.update(state(890, true, true)), AlertCategory::Objective));
+    }
+}
@@ -0,0 +1,526 @@
+// src/config.rs
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+use crate::alerts::{AlertCategory, Severity};
+use crate::audio::AudioSettings;
+use crate::benchmarks::BenchmarkConfig;
+use crate::rules::Condition;
+
+// Everything the coach can be tuned with. Missing sections and keys fall back to defaults.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    pub server: ServerConfig,
+    pub alerts: AlertConfig,
+    pub thresholds: Thresholds,
+    pub benchmarks: BenchmarkConfig,
+    pub theme: ThemeConfig,
+    pub history: HistoryConfig,
+    pub timeline: TimelineConfig,
+    pub opendota: OpenDotaConfig,
+    pub stratz: StratzConfig,
+    pub steam: SteamConfig,
+    pub discord: DiscordConfig,
+    // Written as [[webhooks]] tables, one per URL
+    pub webhooks: Vec<WebhookConfig>,
+    pub mqtt: MqttConfig,
+    pub twitch: TwitchConfig,
+    pub llm: LlmConfig,
+    // Written as [[rules]] tables, one per rule
+    pub rules: Vec<RuleConfig>,
+    pub plugins: PluginConfig,
+    // Your own names for terminal commands, e.g. x = "ward enemy"
+    pub keys: BTreeMap<String, String>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct ServerConfig {
+    pub port: u16,
+    // When set, payloads whose auth token doesn't match are ignored
+    pub auth_token: Option<String>,
+}
+
+impl Default for ServerConfig {
+    fn default() -> Self {
+        ServerConfig {
+            port: 3000,
+            auth_token: None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct AlertConfig {
+    // Alert categories that are raised at all
+    pub categories: Vec<AlertCategory>,
+    pub bell: bool,
+    pub desktop_notifications: bool,
+    pub audio: AudioSettings,
+}
+
+impl Default for AlertConfig {
+    fn default() -> Self {
+        AlertConfig {
+            categories: AlertCategory::ALL.to_vec(),
+            bell: false,
+            desktop_notifications: false,
+            audio: AudioSettings::default(),
+        }
+    }
+}
+
+// Distances are in map units, times in seconds
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct Thresholds {
+    pub very_close_distance: f32,
+    pub nearby_distance: f32,
+    pub medium_distance: f32,
+    pub moved_significantly_distance: f32,
+    pub lost_after_seconds: i32,
+    pub missing_enemy_count: usize,
+    pub missing_after_seconds: i32,
+    pub rune_alert_lead: i32,
+    pub stack_range: f32,
+    pub low_building_percent: i32,
+    // Clock minute from which buyback gold is watched
+    pub buyback_from_minute: i32,
+    // Deaths within five minutes, or the percent GPM drops by over them, that count as a slide
+    pub tilt_deaths: usize,
+    pub tilt_gpm_drop_percent: i32,
+    // Unreliable gold worth spending before walking into danger with it
+    pub unspent_gold_warning: i32,
+    // Enemies that should be on the minimap while the player farms past the river
+    pub awareness_visible_enemies: usize,
+}
+
+impl Default for Thresholds {
+    fn default() -> Self {
+        Thresholds {
+            very_close_distance: 1000.0,
+            nearby_distance: 2000.0,
+            medium_distance: 4000.0,
+            moved_significantly_distance: 1000.0,
+            lost_after_seconds: 10,
+            missing_enemy_count: 3,
+            missing_after_seconds: 20,
+            rune_alert_lead: 20,
+            stack_range: 2500.0,
+            low_building_percent: 30,
+            buyback_from_minute: 25,
+            tilt_deaths: 3,
+            tilt_gpm_drop_percent: 30,
+            unspent_gold_warning: 2000,
+            awareness_visible_enemies: 3,
+        }
+    }
+}
+
+impl Thresholds {
+    // Every key with its current value, in config file order
+    pub fn entries(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("very_close_distance", format!("{:.1}", self.very_close_distance)),
+            ("nearby_distance", format!("{:.1}", self.nearby_distance)),
+            ("medium_distance", format!("{:.1}", self.medium_distance)),
+            ("moved_significantly_distance", format!("{:.1}", self.moved_significantly_distance)),
+            ("lost_after_seconds", self.lost_after_seconds.to_string()),
+            ("missing_enemy_count", self.missing_enemy_count.to_string()),
+            ("missing_after_seconds", self.missing_after_seconds.to_string()),
+            ("rune_alert_lead", self.rune_alert_lead.to_string()),
+            ("stack_range", format!("{:.1}", self.stack_range)),
+            ("low_building_percent", self.low_building_percent.to_string()),
+            ("buyback_from_minute", self.buyback_from_minute.to_string()),
+            ("tilt_deaths", self.tilt_deaths.to_string()),
+            ("tilt_gpm_drop_percent", self.tilt_gpm_drop_percent.to_string()),
+            ("unspent_gold_warning", self.unspent_gold_warning.to_string()),
+            ("awareness_visible_enemies", self.awareness_visible_enemies.to_string()),
+        ]
+    }
+
+    // Change one key by name, e.g. while the coach is running
+    pub fn set(&mut self, key: &str, value: &str) -> Result<(), String> {
+        fn parse<T: std::str::FromStr>(key: &str, value: &str) -> Result<T, String> {
+            value.trim().parse().map_err(|_| format!("'{}' is not a valid value for {}", value.trim(), key))
+        }
+
+        // Changed on a copy, so a rejected value leaves the thresholds as they were
+        let mut updated = self.clone();
+        match key {
+            "very_close_distance" => updated.very_close_distance = parse(key, value)?,
+            "nearby_distance" => updated.nearby_distance = parse(key, value)?,
+            "medium_distance" => updated.medium_distance = parse(key, value)?,
+            "moved_significantly_distance" => updated.moved_significantly_distance = parse(key, value)?,
+            "lost_after_seconds" => updated.lost_after_seconds = parse(key, value)?,
+            "missing_enemy_count" => updated.missing_enemy_count = parse(key, value)?,
+            "missing_after_seconds" => updated.missing_after_seconds = parse(key, value)?,
+            "rune_alert_lead" => updated.rune_alert_lead = parse(key, value)?,
+            "stack_range" => updated.stack_range = parse(key, value)?,
+            "low_building_percent" => updated.low_building_percent = parse(key, value)?,
+            "buyback_from_minute" => updated.buyback_from_minute = parse(key, value)?,
+            "tilt_deaths" => updated.tilt_deaths = parse(key, value)?,
+            "tilt_gpm_drop_percent" => updated.tilt_gpm_drop_percent = parse(key, value)?,
+            "unspent_gold_warning" => updated.unspent_gold_warning = parse(key, value)?,
+            "awareness_visible_enemies" => updated.awareness_visible_enemies = parse(key, value)?,
+            _ => return Err(format!("Unknown threshold '{}'", key)),
+        }
+        updated.validate()?;
+        *self = updated;
+        Ok(())
+    }
+
+    // Checks that hold across thresholds, for the config file as well as `set`
+    pub fn validate(&self) -> Result<(), String> {
+        // The distance bands only make sense from near to far
+        if self.very_close_distance > self.nearby_distance || self.nearby_distance > self.medium_distance {
+            return Err("very_close_distance, nearby_distance and medium_distance must increase".to_string());
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct ThemeConfig {
+    // Turn off to get plain text output, e.g. when logging to a file
+    pub color: bool,
+}
+
+impl Default for ThemeConfig {
+    fn default() -> Self {
+        ThemeConfig { color: true }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct HistoryConfig {
+    // Keep a summary of every finished match in a local SQLite file
+    pub enabled: bool,
+    // Defaults to history.sqlite in the platform's data directory
+    pub path: Option<PathBuf>,
+}
+
+impl Default for HistoryConfig {
+    fn default() -> Self {
+        HistoryConfig {
+            enabled: true,
+            path: None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct TimelineConfig {
+    // Events kept in memory for the timeline
+    pub capacity: usize,
+    // Write events that no longer fit to a JSON lines file instead of dropping them
+    pub spill: bool,
+    // Defaults to timeline.jsonl in the platform's data directory
+    pub path: Option<PathBuf>,
+}
+
+impl Default for TimelineConfig {
+    fn default() -> Self {
+        TimelineConfig {
+            capacity: 500,
+            spill: true,
+            path: None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct OpenDotaConfig {
+    // Fetch hero benchmarks from the OpenDota API and grade the player against them
+    pub enabled: bool,
+    // Optional; raises OpenDota's rate limit
+    pub api_key: Option<String>,
+    // When spectating, look up how each enemy player has done on their hero
+    pub enemy_profiles: bool,
+    // Responses are reused for this many days before asking again
+    pub cache_days: u64,
+    // Defaults to dota2-coach/opendota in the platform's cache directory
+    pub cache_path: Option<PathBuf>,
+}
+
+impl Default for OpenDotaConfig {
+    fn default() -> Self {
+        OpenDotaConfig {
+            enabled: false,
+            api_key: None,
+            enemy_profiles: false,
+            cache_days: 7,
+            cache_path: None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct StratzConfig {
+    // Pull current-patch win rates, builds and matchups from Stratz for the draft and item advice
+    pub enabled: bool,
+    // Required; from stratz.com/api
+    pub token: Option<String>,
+    // Responses are reused for this many hours before asking again
+    pub cache_hours: u64,
+    // Defaults to dota2-coach/stratz in the platform's cache directory
+    pub cache_path: Option<PathBuf>,
+}
+
+impl Default for StratzConfig {
+    fn default() -> Self {
+        StratzConfig {
+            enabled: false,
+            token: None,
+            cache_hours: 24,
+            cache_path: None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct SteamConfig {
+    // Show Steam persona names instead of raw IDs on the scoreboard and in reports
+    pub enabled: bool,
+    // Required; from steamcommunity.com/dev/apikey
+    pub api_key: Option<String>,
+    // Profiles are reused for this many hours before asking again
+    pub cache_hours: u64,
+    // Defaults to dota2-coach/steam in the platform's cache directory
+    pub cache_path: Option<PathBuf>,
+}
+
+impl Default for SteamConfig {
+    fn default() -> Self {
+        SteamConfig {
+            enabled: false,
+            api_key: None,
+            cache_hours: 24,
+            cache_path: None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct DiscordConfig {
+    // Show the hero, KDA and game time as Discord Rich Presence
+    pub enabled: bool,
+    // Application ID of a Discord application, from discord.com/developers
+    pub client_id: Option<String>,
+    // Least seconds between two updates
+    pub interval_secs: u64,
+    // Art asset key uploaded to the application, shown next to the status
+    pub large_image: Option<String>,
+}
+
+impl Default for DiscordConfig {
+    fn default() -> Self {
+        DiscordConfig {
+            enabled: false,
+            client_id: None,
+            interval_secs: 15,
+            large_image: None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct WebhookConfig {
+    // Alerts are POSTed here as JSON
+    pub url: String,
+    // Alert categories sent to this URL
+    pub categories: Vec<AlertCategory>,
+}
+
+impl Default for WebhookConfig {
+    fn default() -> Self {
+        WebhookConfig {
+            url: String::new(),
+            categories: AlertCategory::ALL.to_vec(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct MqttConfig {
+    // Publish alerts and timers to an MQTT broker
+    pub enabled: bool,
+    pub host: String,
+    pub port: u16,
+    pub client_id: String,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    // Topics start with this, e.g. dota/alerts/missing
+    pub topic_prefix: String,
+    // Alert categories that are published
+    pub categories: Vec<AlertCategory>,
+}
+
+impl Default for MqttConfig {
+    fn default() -> Self {
+        MqttConfig {
+            enabled: false,
+            host: "localhost".to_string(),
+            port: 1883,
+            client_id: "dota2-coach".to_string(),
+            username: None,
+            password: None,
+            topic_prefix: "dota".to_string(),
+            categories: AlertCategory::ALL.to_vec(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct TwitchConfig {
+    // Announce selected alerts and the post-game summary in a Twitch chat
+    pub enabled: bool,
+    // Chat to post in, usually the streamer's own
+    pub channel: Option<String>,
+    // Account the bot posts as
+    pub username: Option<String>,
+    // Chat token for that account, with or without the oauth: prefix
+    pub oauth_token: Option<String>,
+    // Alert categories announced
+    pub categories: Vec<AlertCategory>,
+    // Least seconds between two announcements of the same category
+    pub cooldown_secs: u64,
+    // Post a one-line summary when the game ends
+    pub summary: bool,
+}
+
+impl Default for TwitchConfig {
+    fn default() -> Self {
+        TwitchConfig {
+            enabled: false,
+            channel: None,
+            username: None,
+            oauth_token: None,
+            categories: vec![AlertCategory::Roshan, AlertCategory::Objective],
+            cooldown_secs: 120,
+            summary: true,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct LlmConfig {
+    // Ask a language model for a strategic tip every so often
+    pub enabled: bool,
+    // Base URL of an OpenAI-compatible API; /chat/completions is added
+    pub endpoint: String,
+    pub api_key: Option<String>,
+    pub model: String,
+    // Least seconds of game time between two tips; never less than two minutes
+    pub interval_secs: u64,
+    // Upper bound on the length of each answer
+    pub max_tokens: u32,
+}
+
+impl Default for LlmConfig {
+    fn default() -> Self {
+        LlmConfig {
+            enabled: false,
+            endpoint: "http://localhost:11434/v1".to_string(),
+            api_key: None,
+            model: "llama3.1".to_string(),
+            interval_secs: 300,
+            max_tokens: 80,
+        }
+    }
+}
+
+// A coaching rule of the user's own: an alert raised when a condition on the game state
+// becomes true
+#[derive(Clone, Debug, Deserialize)]
+pub struct RuleConfig {
+    // Tells rules apart; several rules can't share one
+    pub name: String,
+    pub when: Condition,
+    // May include values from the game state, e.g. {player.gold}
+    pub message: String,
+    // Warning when left out
+    pub severity: Option<Severity>,
+    pub speech: Option<String>,
+    // Raise the alert again this often while the condition holds, rather than only when it becomes true
+    pub repeat_secs: Option<i32>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct PluginConfig {
+    // Load every .wasm file in the plugin directory (needs the `plugins` feature)
+    pub enabled: bool,
+    // plugins/ next to the default config file when left out
+    pub directory: Option<PathBuf>,
+    // Instructions each plugin may run per update before it's cut off
+    pub fuel: u64,
+    // Upper bound on each plugin's memory
+    pub memory_mb: usize,
+}
+
+impl Default for PluginConfig {
+    fn default() -> Self {
+        PluginConfig {
+            enabled: false,
+            directory: None,
+            fuel: 10_000_000,
+            memory_mb: 32,
+        }
+    }
+}
+
+impl PluginConfig {
+    pub fn directory(&self) -> Option<PathBuf> {
+        self.directory.clone().or_else(|| default_config_path()?.parent().map(|dir| dir.join("plugins")))
+    }
+}
+
+// Where the config lives unless --config says otherwise
+pub fn default_config_path() -> Option<PathBuf> {
+    dirs::config_dir().map(|config| config.join("dota2-coach").join("config.toml"))
+}
+
+impl Config {
+    pub fn from_file(path: &Path) -> Result<Self, String> {
+        let contents = fs::read_to_string(path)
+            .map_err(|e| format!("Could not read {}: {}", path.display(), e))?;
+        let config: Config = toml::from_str(&contents)
+            .map_err(|e| format!("Invalid config file {}: {}", path.display(), e))?;
+        config.thresholds.validate()
+            .map_err(|e| format!("Invalid config file {}: {}", path.display(), e))?;
+        for (i, rule) in config.rules.iter().enumerate() {
+            if config.rules[..i].iter().any(|r| r.name == rule.name) {
+                return Err(format!("Invalid config file {}: more than one rule named '{}'", path.display(), rule.name));
+            }
+        }
+        Ok(config)
+    }
+
+    // Load an explicitly requested config, or the default one if it exists
+    pub fn load(explicit: Option<&Path>) -> Result<Self, String> {
+        match explicit {
+            Some(path) => Self::from_file(path),
+            None => match default_config_path() {
+                Some(path) if path.exists() => Self::from_file(&path),
+                _ => Ok(Config::default()),
+            },
+        }
+    }
+}
@@ -0,0 +1,201 @@
+// src/consumables.rs
+use std::collections::HashMap;
+
+use crate::inventory::Inventory;
+use crate::items;
+
+// Observers held this long without planting one are sitting idle
+pub const WARD_IDLE: i32 = 90;
+
+// Usage checks every this many minutes
+const CHECK_EVERY: i32 = 10;
+
+// Short of this share of the expected count is behind
+const BEHIND_SHARE: f32 = 0.6;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Consumable {
+    Observer,
+    Sentry,
+    Smoke,
+    Dust,
+}
+
+impl Consumable {
+    pub const ALL: [Consumable; 4] = [Consumable::Observer, Consumable::Sentry, Consumable::Smoke, Consumable::Dust];
+
+    pub fn class(&self) -> &'static str {
+        match self {
+            Consumable::Observer => "ward_observer",
+            Consumable::Sentry => "ward_sentry",
+            Consumable::Smoke => "smoke_of_deceit",
+            Consumable::Dust => "dust",
+        }
+    }
+
+    pub fn name(&self, count: i32) -> &'static str {
+        match (self, count) {
+            (Consumable::Observer, 1) => "observer",
+            (Consumable::Observer, _) => "observers",
+            (Consumable::Sentry, 1) => "sentry",
+            (Consumable::Sentry, _) => "sentries",
+            (Consumable::Smoke, 1) => "smoke",
+            (Consumable::Smoke, _) => "smokes",
+            (Consumable::Dust, _) => "dust",
+        }
+    }
+
+    // Rough count a support gets through every 10 minutes. Dust only matters against
+    // invisible heroes.
+    fn expected_per_check(&self, enemy_invisibility: bool) -> i32 {
+        match self {
+            Consumable::Observer => 4,
+            Consumable::Sentry => 3,
+            Consumable::Smoke => 1,
+            Consumable::Dust => if enemy_invisibility { 1 } else { 0 },
+        }
+    }
+
+    // Charges carried or in the stash. A stacked observer/sentry pair only reports
+    // its observers.
+    fn count(&self, inventory: &Inventory) -> i32 {
+        let dispenser = if *self == Consumable::Observer { Some("item_ward_dispenser") } else { None };
+        let class = format!("item_{}", self.class());
+        inventory.all_items()
+            .filter(|item| item.name.as_deref() == Some(class.as_str()) || item.name.as_deref() == dispenser)
+            .map(|item| item.charges.unwrap_or(1))
+            .sum()
+    }
+}
+
+// What the player got through compared with what a support usually does by a check
+#[derive(Clone, Debug)]
+pub struct UsageCheck {
+    pub minute: i32,
+    // (consumable, used, expected) for those with an expected count
+    pub usage: Vec<(Consumable, i32, i32)>,
+}
+
+impl UsageCheck {
+    pub fn behind(&self) -> Vec<Consumable> {
+        self.usage.iter()
+            .filter(|(_, used, expected)| (*used as f32) < *expected as f32 * BEHIND_SHARE)
+            .map(|(consumable, _, _)| *consumable)
+            .collect()
+    }
+
+    pub fn describe(&self) -> String {
+        let used: Vec<String> = self.usage.iter()
+            .map(|(consumable, used, expected)| format!("{} {} (about {})", used, consumable.name(*used), expected))
+            .collect();
+        let behind = self.behind();
+        let advice = if behind.is_empty() {
+            "keep it up".to_string()
+        } else {
+            let names: Vec<&str> = behind.iter().map(|c| c.name(2)).collect();
+            match names.split_last() {
+                Some((last, rest)) if !rest.is_empty() => format!("buy and use more {} and {}", rest.join(", "), last),
+                _ => format!("buy and use more {}", names.join("")),
+            }
+        };
+        format!("By {}:00 you've used {} - {}", self.minute, used.join(", "), advice)
+    }
+}
+
+// Follows wards, smokes and dust through the inventory. A new charge counts as bought
+// when the gold went down by its price (observers are free), otherwise it was handed
+// over; a charge that disappears counts as used.
+pub struct ConsumableTracker {
+    held: HashMap<Consumable, i32>,
+    bought: HashMap<Consumable, i32>,
+    used: HashMap<Consumable, i32>,
+    last_gold: Option<i32>,
+    // Since when observers have been held without one being planted
+    observers_idle_since: Option<i32>,
+    last_check: i32,
+}
+
+impl ConsumableTracker {
+    pub fn new() -> Self {
+        ConsumableTracker {
+            held: HashMap::new(),
+            bought: HashMap::new(),
+            used: HashMap::new(),
+            last_gold: None,
+            observers_idle_since: None,
+            last_check: 0,
+        }
+    }
+
+    pub fn held(&self, consumable: Consumable) -> i32 {
+        self.held.get(&consumable).copied().unwrap_or(0)
+    }
+
+    pub fn bought(&self, consumable: Consumable) -> i32 {
+        self.bought.get(&consumable).copied().unwrap_or(0)
+    }
+
+    pub fn used(&self, consumable: Consumable) -> i32 {
+        self.used.get(&consumable).copied().unwrap_or(0)
+    }
+
+    // Returns the charges that left the inventory, planted or dropped
+    pub fn update(&mut self, inventory: &Inventory, gold: i32, clock_time: i32) -> Vec<(Consumable, i32)> {
+        let mut used = Vec::new();
+        let mut spent = self.last_gold.map(|last| last - gold).unwrap_or(0);
+        self.last_gold = Some(gold);
+
+        for consumable in Consumable::ALL {
+            let count = consumable.count(inventory);
+            let change = count - self.held(consumable);
+            self.held.insert(consumable, count);
+            if change > 0 {
+                let cost = items::by_class(consumable.class()).map(|i| i.cost).unwrap_or(0);
+                if spent >= cost * change {
+                    spent -= cost * change;
+                    *self.bought.entry(consumable).or_default() += change;
+                }
+            } else if change < 0 {
+                *self.used.entry(consumable).or_default() -= change;
+                used.push((consumable, -change));
+                if consumable == Consumable::Observer {
+                    self.observers_idle_since = None;
+                }
+            }
+        }
+
+        if self.held(Consumable::Observer) == 0 {
+            self.observers_idle_since = None;
+        } else if self.observers_idle_since.is_none() {
+            self.observers_idle_since = Some(clock_time);
+        }
+        used
+    }
+
+    // Observers held and for how long, once they've sat for WARD_IDLE or more
+    pub fn idle_observers(&self, clock_time: i32) -> Option<(i32, i32)> {
+        let idle = clock_time - self.observers_idle_since?;
+        Some((self.held(Consumable::Observer), idle)).filter(|_| idle >= WARD_IDLE)
+    }
+
+    // Usage at each 10 minute mark, once
+    pub fn check(&mut self, clock_time: i32, enemy_invisibility: bool) -> Option<UsageCheck> {
+        let minute = clock_time / 60;
+        if minute < CHECK_EVERY || minute % CHECK_EVERY != 0 || minute <= self.last_check {
+            return None;
+        }
+        self.last_check = minute;
+        let checks = minute / CHECK_EVERY;
+        let usage = Consumable::ALL.iter()
+            .map(|&c| (c, self.used(c), c.expected_per_check(enemy_invisibility) * checks))
+            .filter(|(_, _, expected)| *expected > 0)
+            .collect();
+        Some(UsageCheck { minute, usage })
+    }
+}
+
+impl Default for ConsumableTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
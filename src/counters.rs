@@ -0,0 +1,131 @@
+// src/counters.rs
+use crate::heroes::{HeroInfo, Role};
+
+// Something about the enemy lineup that specific items answer
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Threat {
+    MagicDamage,
+    Evasion,
+    Invisibility,
+    Healing,
+}
+
+impl Threat {
+    pub const ALL: [Threat; 4] = [Threat::MagicDamage, Threat::Evasion, Threat::Invisibility, Threat::Healing];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Threat::MagicDamage => "heavy magic damage",
+            Threat::Evasion => "evasion",
+            Threat::Invisibility => "invisibility",
+            Threat::Healing => "healing",
+        }
+    }
+
+    pub fn slug(&self) -> &'static str {
+        match self {
+            Threat::MagicDamage => "magic",
+            Threat::Evasion => "evasion",
+            Threat::Invisibility => "invis",
+            Threat::Healing => "healing",
+        }
+    }
+
+    // Enemy heroes with this threat before it's worth buying against
+    pub fn threshold(&self) -> usize {
+        match self {
+            Threat::MagicDamage => 3,
+            Threat::Healing => 2,
+            Threat::Evasion | Threat::Invisibility => 1,
+        }
+    }
+
+    pub fn counter_items(&self) -> &'static str {
+        match self {
+            Threat::MagicDamage => "Pipe of Insight or Eternal Shroud for the team, Black King Bar for cores",
+            Threat::Evasion => "Monkey King Bar or Bloodthorn",
+            Threat::Invisibility => "Dust of Appearance and Sentry Wards, Gem of True Sight later",
+            Threat::Healing => "Spirit Vessel, Eye of Skadi or Shiva's Guard",
+        }
+    }
+}
+
+// Heroes known for a threat that their role tags don't show
+const HERO_THREATS: &[(&str, &[Threat])] = &[
+    ("riki", &[Threat::Invisibility]),
+    ("clinkz", &[Threat::Invisibility]),
+    ("bounty_hunter", &[Threat::Invisibility]),
+    ("nyx_assassin", &[Threat::Invisibility]),
+    ("weaver", &[Threat::Invisibility]),
+    ("mirana", &[Threat::Invisibility]),
+    ("invoker", &[Threat::Invisibility]),
+    ("treant", &[Threat::Invisibility, Threat::Healing]),
+    ("sand_king", &[Threat::Invisibility]),
+    ("void_spirit", &[Threat::Invisibility]),
+    ("phantom_assassin", &[Threat::Evasion]),
+    ("windrunner", &[Threat::Evasion]),
+    ("brewmaster", &[Threat::Evasion]),
+    ("troll_warlord", &[Threat::Evasion]),
+    ("faceless_void", &[Threat::Evasion]),
+    ("juggernaut", &[Threat::Healing]),
+    ("huskar", &[Threat::Healing]),
+    ("necrolyte", &[Threat::Healing]),
+    ("alchemist", &[Threat::Healing]),
+    ("abaddon", &[Threat::Healing]),
+    ("dazzle", &[Threat::Healing]),
+    ("omniknight", &[Threat::Healing]),
+    ("oracle", &[Threat::Healing]),
+    ("warlock", &[Threat::Healing]),
+    ("skeleton_king", &[Threat::Healing]),
+    ("undying", &[Threat::Healing]),
+    ("chen", &[Threat::Healing]),
+    ("wisp", &[Threat::Healing]),
+    ("slark", &[Threat::Healing]),
+    ("morphling", &[Threat::Healing]),
+    ("broodmother", &[Threat::Healing]),
+];
+
+pub fn threats_of(hero: &HeroInfo) -> Vec<Threat> {
+    let mut threats: Vec<Threat> = HERO_THREATS.iter()
+        .find(|(class, _)| *class == hero.class)
+        .map(|(_, threats)| threats.to_vec())
+        .unwrap_or_default();
+    if hero.has_role(Role::Nuker) {
+        threats.push(Threat::MagicDamage);
+    }
+    threats
+}
+
+// A threat the enemy lineup crosses the threshold for
+#[derive(Clone, Debug)]
+pub struct CounterAdvice {
+    pub threat: Threat,
+    pub heroes: Vec<&'static str>,
+}
+
+impl CounterAdvice {
+    pub fn describe(&self) -> String {
+        format!("Enemy {} ({}): consider {}", self.threat.name(), self.heroes.join(", "), self.threat.counter_items())
+    }
+}
+
+// Threats of the known enemy heroes, strongest first, with the heroes behind each
+pub fn threat_profile(enemies: &[&HeroInfo]) -> Vec<(Threat, Vec<&'static str>)> {
+    let mut profile: Vec<(Threat, Vec<&'static str>)> = Threat::ALL.iter()
+        .map(|&threat| (threat, enemies.iter()
+            .filter(|hero| threats_of(hero).contains(&threat))
+            .map(|hero| hero.name)
+            .collect::<Vec<_>>()))
+        .filter(|(_, heroes)| !heroes.is_empty())
+        .collect();
+    profile.sort_by_key(|(_, heroes)| std::cmp::Reverse(heroes.len()));
+    profile
+}
+
+// Item advice for every threat that met its threshold
+pub fn advice(enemies: &[&HeroInfo]) -> Vec<CounterAdvice> {
+    threat_profile(enemies).into_iter()
+        .filter(|(threat, heroes)| heroes.len() >= threat.threshold())
+        .map(|(threat, heroes)| CounterAdvice { threat, heroes })
+        .collect()
+}
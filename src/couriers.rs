@@ -0,0 +1,119 @@
+// src/couriers.rs
+use crate::format::format_item_name;
+use crate::items;
+use crate::missing::RIVER_MARGIN;
+use crate::state::{GameState, Team};
+use crate::tracking::calculate_distance;
+
+// Enemy couriers carrying at least this much gold in items are worth going out of the way for,
+// as long as they are within DETOUR_RANGE
+pub const SNIPE_VALUE: i32 = 1500;
+const DETOUR_RANGE: f32 = 4000.0;
+
+// An enemy courier this close is worth a hit whatever it carries
+const SNIPE_RANGE: f32 = 1200.0;
+
+// Minimap icons that stand for a courier
+fn is_courier_icon(image: &str) -> bool {
+    image.contains("courier")
+}
+
+// In the river or on the far side of it for `team`. x + y grows towards Dire.
+fn exposed(position: (i32, i32), team: Team) -> bool {
+    let depth = if team == Team::Radiant { position.0 + position.1 } else { -(position.0 + position.1) };
+    depth > -RIVER_MARGIN
+}
+
+// One of the player's team's couriers moving through the river or enemy territory
+#[derive(Clone, Debug)]
+pub struct CourierInDanger {
+    pub key: String,
+    pub position: (i32, i32),
+    // Item names without the item_ prefix
+    pub items: Vec<String>,
+    pub value: i32,
+}
+
+// The team's living couriers that moved since the last payload and are now exposed
+pub fn in_danger(state: &GameState, previous: Option<&GameState>, team: Team) -> Vec<CourierInDanger> {
+    let Some(couriers) = state.couriers.as_ref() else { return Vec::new() };
+    let previous = previous.and_then(|s| s.couriers.as_ref());
+
+    let mut endangered: Vec<CourierInDanger> = couriers.iter()
+        .filter(|(_, c)| c.team() == Some(team) && c.is_alive())
+        .filter_map(|(key, courier)| {
+            let position = courier.position()?;
+            let before = previous.and_then(|p| p.get(key)).and_then(|c| c.position())?;
+            (before != position && exposed(position, team)).then(|| CourierInDanger {
+                key: key.clone(),
+                position,
+                items: courier.item_names().iter().map(|n| n.trim_start_matches("item_").to_string()).collect(),
+                value: items::value_of(courier.item_names()),
+            })
+        })
+        .collect();
+    endangered.sort_by(|a, b| a.key.cmp(&b.key));
+    endangered
+}
+
+// An enemy courier the player could go and kill
+#[derive(Clone, Debug)]
+pub struct SnipeTarget {
+    pub position: (i32, i32),
+    // Known only from the couriers block, which carries enemy couriers when spectating
+    pub items: Vec<String>,
+    pub value: i32,
+    pub distance: Option<f32>,
+}
+
+impl SnipeTarget {
+    // e.g. "carrying Black King Bar, Tango (4140 gold)"
+    pub fn describe_cargo(&self) -> Option<String> {
+        if self.items.is_empty() {
+            return None;
+        }
+        let names: Vec<String> = self.items.iter().map(|i| format_item_name(i)).collect();
+        Some(format!("carrying {} ({} gold)", names.join(", "), self.value))
+    }
+}
+
+fn worth_sniping(value: i32, distance: Option<f32>) -> bool {
+    match distance {
+        Some(distance) => distance <= SNIPE_RANGE || (value >= SNIPE_VALUE && distance <= DETOUR_RANGE),
+        None => value >= SNIPE_VALUE,
+    }
+}
+
+// Visible enemy couriers that carry enough or are close enough to the player to chase, closest first
+pub fn snipe_targets(state: &GameState, enemy_team: Team, player_position: Option<(i32, i32)>) -> Vec<SnipeTarget> {
+    let distance = |position: (i32, i32)| player_position.map(|p| calculate_distance(p, position));
+    let enemy_team_id = if enemy_team == Team::Radiant { 2 } else { 3 };
+
+    let mut targets: Vec<SnipeTarget> = state.couriers.iter()
+        .flat_map(|couriers| couriers.values())
+        .filter(|c| c.team() == Some(enemy_team) && c.is_alive())
+        .filter_map(|courier| {
+            let position = courier.position()?;
+            Some(SnipeTarget {
+                position,
+                items: courier.item_names().iter().map(|n| n.trim_start_matches("item_").to_string()).collect(),
+                value: items::value_of(courier.item_names()),
+                distance: distance(position),
+            })
+        })
+        .collect();
+
+    // Couriers the minimap shows, unless the couriers block already has one there
+    let seen_on_minimap = state.minimap.iter()
+        .flat_map(|minimap| minimap.values())
+        .filter(|o| o.team == enemy_team_id && is_courier_icon(&o.image))
+        .map(|o| (o.xpos, o.ypos))
+        .filter(|&position| !targets.iter().any(|t| calculate_distance(t.position, position) < SNIPE_RANGE / 2.0))
+        .map(|position| SnipeTarget { position, items: Vec::new(), value: 0, distance: distance(position) })
+        .collect::<Vec<_>>();
+    targets.extend(seen_on_minimap);
+
+    targets.retain(|t| worth_sniping(t.value, t.distance));
+    targets.sort_by_key(|t| (t.distance.map(|d| d as i32).unwrap_or(i32::MAX), -t.value));
+    targets
+}
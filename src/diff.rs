@@ -0,0 +1,127 @@
+// src/diff.rs
+use std::collections::HashMap;
+
+use serde_json::{json, Value};
+
+use crate::buildings::{self, BuildingKind};
+use crate::format::format_item_name;
+use crate::state::{GameState, Team};
+
+// Gold gained from one payload to the next that counts as a spike rather than passive income
+pub const GOLD_SPIKE: i32 = 150;
+
+// Something that changed between two consecutive game states
+#[derive(Clone, Debug, PartialEq)]
+pub enum Change {
+    // The player's hero went from alive to dead
+    PlayerDied { deaths: i32 },
+    LevelUp { level: i32 },
+    // Item class without the item_ prefix, anywhere in the inventory or stash
+    ItemAcquired { item: String },
+    GoldSpike { gained: i32 },
+    TowerFell { team: Team, tier: u8, key: String },
+    // New team kill counts, and how many each team gained
+    ScoreChanged { radiant: i32, dire: i32, radiant_gained: i32, dire_gained: i32 },
+}
+
+impl Change {
+    pub fn describe(&self) -> String {
+        match self {
+            Change::PlayerDied { deaths } => format!("You died (death {})", deaths),
+            Change::LevelUp { level } => format!("Level {}", level),
+            Change::ItemAcquired { item } => format!("Got {}", format_item_name(item)),
+            Change::GoldSpike { gained } => format!("+{} gold", gained),
+            Change::TowerFell { team, tier, key } => format!("{} T{} {} fell", team.name(), tier, buildings::classify(key).1.name()),
+            Change::ScoreChanged { radiant, dire, .. } => format!("Score {} - {}", radiant, dire),
+        }
+    }
+
+    // As sent to plugins
+    pub fn to_json(&self) -> Value {
+        match self {
+            Change::PlayerDied { deaths } => json!({ "kind": "player_died", "deaths": deaths }),
+            Change::LevelUp { level } => json!({ "kind": "level_up", "level": level }),
+            Change::ItemAcquired { item } => json!({ "kind": "item_acquired", "item": item }),
+            Change::GoldSpike { gained } => json!({ "kind": "gold_spike", "gained": gained }),
+            Change::TowerFell { team, tier, key } => json!({ "kind": "tower_fell", "team": team.name().to_lowercase(), "tier": tier, "key": key }),
+            Change::ScoreChanged { radiant, dire, radiant_gained, dire_gained } => json!({
+                "kind": "score_changed", "radiant": radiant, "dire": dire,
+                "radiant_gained": radiant_gained, "dire_gained": dire_gained,
+            }),
+        }
+    }
+}
+
+fn item_counts(state: &GameState) -> HashMap<String, usize> {
+    let mut counts = HashMap::new();
+    for class in state.inventory().map(|i| i.classes()).unwrap_or_default() {
+        *counts.entry(class).or_insert(0) += 1;
+    }
+    counts
+}
+
+fn scores(state: &GameState) -> Option<(i32, i32)> {
+    let map = state.map.as_ref()?;
+    Some((map.radiant_score?, map.dire_score?))
+}
+
+// Everything that changed from `previous` to `current`, in the order the variants are listed.
+// Blocks missing from either payload are skipped rather than read as a change.
+pub fn diff(previous: &GameState, current: &GameState) -> Vec<Change> {
+    let mut changes = Vec::new();
+
+    if let (Some(before), Some(after)) = (previous.hero(), current.hero()) {
+        if before.alive == Some(true) && after.alive == Some(false) {
+            changes.push(Change::PlayerDied { deaths: current.player().and_then(|p| p.deaths).unwrap_or(0) });
+        }
+        if let (Some(from), Some(to)) = (before.level, after.level) {
+            if to > from {
+                changes.push(Change::LevelUp { level: to });
+            }
+        }
+    }
+
+    if previous.inventory().is_some() && current.inventory().is_some() {
+        let before = item_counts(previous);
+        let mut acquired: Vec<String> = item_counts(current).into_iter()
+            .flat_map(|(item, count)| {
+                let new = count.saturating_sub(before.get(&item).copied().unwrap_or(0));
+                std::iter::repeat_n(item, new)
+            })
+            .collect();
+        acquired.sort();
+        changes.extend(acquired.into_iter().map(|item| Change::ItemAcquired { item }));
+    }
+
+    if let (Some(before), Some(after)) = (previous.player().and_then(|p| p.gold), current.player().and_then(|p| p.gold)) {
+        if after - before >= GOLD_SPIKE {
+            changes.push(Change::GoldSpike { gained: after - before });
+        }
+    }
+
+    if previous.buildings.is_some() && current.buildings.is_some() {
+        for team in Team::BOTH {
+            let standing = buildings::team_buildings(current, team);
+            for building in buildings::team_buildings(previous, team) {
+                if let BuildingKind::Tower(tier) = building.kind {
+                    if !standing.iter().any(|b| b.key == building.key) {
+                        changes.push(Change::TowerFell { team, tier, key: building.key });
+                    }
+                }
+            }
+        }
+    }
+
+    if let (Some((radiant_before, dire_before)), Some((radiant, dire))) = (scores(previous), scores(current)) {
+        if (radiant, dire) != (radiant_before, dire_before) {
+            changes.push(Change::ScoreChanged {
+                radiant,
+                dire,
+                radiant_gained: radiant - radiant_before,
+                dire_gained: dire - dire_before,
+            });
+        }
+    }
+
+    changes
+}
@@ -0,0 +1,60 @@
+// src/draft.rs
+use std::collections::HashSet;
+
+use crate::state::{Draft, Team};
+
+// A pick or ban that just happened
+#[derive(Clone, Debug)]
+pub struct DraftAction {
+    pub team: Team,
+    pub pick: bool,
+    pub hero_id: i32,
+    pub hero: String,
+}
+
+impl DraftAction {
+    pub fn describe(&self) -> String {
+        format!("{} {} {}", self.team.name(), if self.pick { "picked" } else { "banned" }, self.hero)
+    }
+}
+
+// Remembers which picks and bans were already reported
+pub struct DraftTracker {
+    seen: HashSet<(Team, bool, i32)>,
+}
+
+impl DraftTracker {
+    pub fn new() -> Self {
+        DraftTracker { seen: HashSet::new() }
+    }
+
+    // Picks and bans not reported before, Radiant first
+    pub fn update(&mut self, draft: &Draft) -> Vec<DraftAction> {
+        let mut actions = Vec::new();
+
+        for team in Team::BOTH {
+            let Some(side) = draft.team(team) else { continue };
+            let selections = side.picks().into_iter().map(|s| (true, s))
+                .chain(side.bans().into_iter().map(|s| (false, s)));
+
+            for (pick, selection) in selections {
+                if self.seen.insert((team, pick, selection.hero_id)) {
+                    actions.push(DraftAction {
+                        team,
+                        pick,
+                        hero_id: selection.hero_id,
+                        hero: selection.hero_name(),
+                    });
+                }
+            }
+        }
+
+        actions
+    }
+}
+
+impl Default for DraftTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
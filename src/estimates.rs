@@ -0,0 +1,83 @@
+// src/estimates.rs
+use crate::format::format_gold;
+use crate::heroes::HeroInfo;
+use crate::item_build;
+use crate::items::{self, ItemInfo};
+
+pub const STARTING_GOLD: i32 = 600;
+
+// Net worth kept in consumables, wards and small items rather than the core build
+const UPKEEP_SHARE: f32 = 0.25;
+
+// Rough gold for a kill including assists, and lost on a death
+const KILL_GOLD: f32 = 250.0;
+const DEATH_GOLD: f32 = 150.0;
+
+// Typical farm by position, in gold per minute
+fn base_gpm(position: u8) -> i32 {
+    match position {
+        1 => 580,
+        2 => 520,
+        3 => 430,
+        4 => 320,
+        _ => 270,
+    }
+}
+
+// Share of the team's kills a position usually takes part in
+fn kill_share(position: u8) -> f32 {
+    match position {
+        1 => 0.3,
+        2 => 0.25,
+        3 => 0.2,
+        4 => 0.15,
+        _ => 0.1,
+    }
+}
+
+// What an enemy hero probably has, worked out rather than seen
+#[derive(Clone, Debug)]
+pub struct EnemyEstimate {
+    pub hero: &'static HeroInfo,
+    pub net_worth: i32,
+    // Core items they can likely afford by now, in buying order
+    pub items: Vec<&'static ItemInfo>,
+}
+
+impl EnemyEstimate {
+    // e.g. "~9.8k net worth, probably Power Treads, Black King Bar"
+    pub fn describe(&self) -> String {
+        let items = if self.items.is_empty() {
+            "no core items yet".to_string()
+        } else {
+            format!("probably {}", self.items.iter().map(|i| i.name).collect::<Vec<_>>().join(", "))
+        };
+        format!("~{} net worth, {}", format_gold(self.net_worth), items)
+    }
+}
+
+// Estimate an enemy from the clock, their usual position and the team scores.
+// `team_kills` are heroes their team killed, `team_deaths` heroes they lost.
+pub fn estimate(hero: &'static HeroInfo, clock_time: i32, team_kills: i32, team_deaths: i32) -> EnemyEstimate {
+    let minutes = clock_time.max(0) as f32 / 60.0;
+    let farm = base_gpm(hero.position) as f32 * minutes;
+    let kills = team_kills.max(0) as f32 * kill_share(hero.position) * KILL_GOLD;
+    let deaths = team_deaths.max(0) as f32 / 5.0 * DEATH_GOLD;
+    let net_worth = (STARTING_GOLD as f32 + farm + kills - deaths).max(STARTING_GOLD as f32) as i32;
+
+    let mut budget = ((net_worth - STARTING_GOLD) as f32 * (1.0 - UPKEEP_SHARE)) as i32;
+    let mut likely = Vec::new();
+    for item in item_build::core_build(hero).into_iter().filter_map(items::by_class) {
+        if item.cost > budget {
+            break;
+        }
+        budget -= item.cost;
+        likely.push(item);
+    }
+
+    EnemyEstimate {
+        hero,
+        net_worth,
+        items: likely,
+    }
+}
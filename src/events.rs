@@ -0,0 +1,217 @@
+// src/events.rs
+use std::collections::VecDeque;
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Local};
+use colored::{ColoredString, Colorize};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::alerts::{alert_label, strip_colors, AlertCategory, Severity};
+
+// Kinds of events reported to the player
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum EventKind {
+    EnemyDiscovered,
+    EnemySpotted,
+    EnemyMovement,
+    EnemyMissing,
+    NewMatch,
+    Restart,
+    Pause,
+    GameMode,
+    GameStarted,
+    GameEnded,
+    PhaseChanged,
+    Draft,
+    Briefing,
+    EnemyCooldown,
+    TeamFight,
+    ItemTiming,
+    ItemSuggestion,
+    DeathRecap,
+    Respawn,
+    Camp,
+    Roshan,
+    Courier,
+    LaningReport,
+    Baseline,
+    // A moment the player flagged to look at again
+    Marker,
+    // A strategic tip from the language model
+    CoachTip,
+    // The position the player is coached as
+    Role,
+    // A ward marked by hand
+    Ward,
+    // An outpost's owner set by hand
+    Outpost,
+    // The player ending an enemy's kill streak
+    Shutdown,
+    // A question typed in the terminal, and the coach's answer
+    Question,
+    Answer,
+    // Something the diff of two game states picked up; kept in the log, not printed live
+    Change,
+    Alert(AlertCategory, Severity),
+}
+
+impl EventKind {
+    // Colored label printed in front of each event
+    pub fn label(&self) -> ColoredString {
+        match self {
+            EventKind::EnemyDiscovered => "ENEMY HERO DISCOVERED".magenta().bold(),
+            EventKind::EnemySpotted => "ENEMY SPOTTED".red().bold(),
+            EventKind::EnemyMovement => "ENEMY MOVEMENT".yellow(),
+            EventKind::EnemyMissing => "ENEMY MISSING".blue(),
+            EventKind::NewMatch => "NEW MATCH".green().bold(),
+            EventKind::Restart => "RESTART".yellow().bold(),
+            EventKind::Pause => "PAUSE".yellow(),
+            EventKind::GameMode => "GAME MODE".magenta().bold(),
+            EventKind::GameStarted => "GAME STARTED".green().bold(),
+            EventKind::GameEnded => "GAME ENDED".green().bold(),
+            EventKind::PhaseChanged => "PHASE".normal(),
+            EventKind::Draft => "DRAFT".magenta(),
+            EventKind::Briefing => "LANE BRIEFING".cyan().bold(),
+            EventKind::EnemyCooldown => "ENEMY COOLDOWN".yellow().bold(),
+            EventKind::TeamFight => "TEAMFIGHT".red().bold(),
+            EventKind::ItemTiming => "ITEM TIMING".green(),
+            EventKind::ItemSuggestion => "NEXT ITEM".green().bold(),
+            EventKind::DeathRecap => "DEATH RECAP".red(),
+            EventKind::Respawn => "RESPAWNS".yellow().bold(),
+            EventKind::Camp => "JUNGLE CAMP".green(),
+            EventKind::Roshan => "ROSHAN".magenta().bold(),
+            EventKind::Courier => "COURIER".yellow(),
+            EventKind::LaningReport => "LANING REPORT".cyan().bold(),
+            EventKind::Baseline => "YOUR AVERAGE".cyan(),
+            EventKind::Marker => "MARKER".blue().bold(),
+            EventKind::CoachTip => "COACH SAYS".white().on_blue().bold(),
+            EventKind::Role => "ROLE".cyan().bold(),
+            EventKind::Ward => "WARD".blue().bold(),
+            EventKind::Outpost => "OUTPOST".green(),
+            EventKind::Shutdown => "SHUTDOWN".green().bold(),
+            EventKind::Question => "YOU ASKED".blue().bold(),
+            EventKind::Answer => "COACH ANSWERS".white().on_blue().bold(),
+            EventKind::Change => "CHANGE".normal(),
+            EventKind::Alert(category, severity) => alert_label(*category, *severity),
+        }
+    }
+}
+
+// A single timestamped entry in the event history
+#[derive(Clone, Debug)]
+pub struct GameEvent {
+    pub game_time: i32,
+    pub received_at: DateTime<Local>,
+    pub kind: EventKind,
+    pub description: String,
+}
+
+impl GameEvent {
+    pub fn new(game_time: i32, kind: EventKind, description: String) -> Self {
+        GameEvent {
+            game_time,
+            received_at: Local::now(),
+            kind,
+            description,
+        }
+    }
+
+    // As saved to disk, without color codes
+    pub fn to_json(&self) -> Value {
+        json!({
+            "game_time": self.game_time,
+            "received_at": self.received_at.to_rfc3339(),
+            "kind": format!("{:?}", self.kind),
+            "description": strip_colors(&self.description),
+        })
+    }
+}
+
+// A moment the player flagged during play, with an optional note
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Marker {
+    pub game_time: i32,
+    pub note: Option<String>,
+}
+
+impl Marker {
+    pub fn event(&self) -> GameEvent {
+        GameEvent::new(self.game_time, EventKind::Marker,
+            self.note.clone().unwrap_or_else(|| "Marked for review".to_string()))
+    }
+}
+
+// Where events that no longer fit in memory go unless the config says otherwise
+pub fn default_spill_path() -> Option<PathBuf> {
+    dirs::data_dir().map(|data| data.join("dota2-coach").join("timeline.jsonl"))
+}
+
+// Bounded history of every event reported during this session. Once full, the
+// oldest events are appended to a spill file as JSON lines, if one is set.
+pub struct EventLog {
+    events: VecDeque<GameEvent>,
+    capacity: usize,
+    spill: Option<File>,
+    spill_path: Option<PathBuf>,
+    spilled: usize,
+}
+
+impl EventLog {
+    pub fn new(capacity: usize) -> Self {
+        EventLog {
+            events: VecDeque::with_capacity(capacity),
+            capacity,
+            spill: None,
+            spill_path: None,
+            spilled: 0,
+        }
+    }
+
+    // Append events pushed out of memory to `path` from now on
+    pub fn spill_to(&mut self, path: &Path) -> Result<(), String> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(|e| format!("Could not create {}: {}", dir.display(), e))?;
+        }
+        let file = OpenOptions::new().create(true).append(true).open(path)
+            .map_err(|e| format!("Could not open timeline file {}: {}", path.display(), e))?;
+        self.spill = Some(file);
+        self.spill_path = Some(path.to_path_buf());
+        Ok(())
+    }
+
+    // Record an event. Once the log is full the oldest one is spilled, or dropped without a spill file.
+    pub fn push(&mut self, event: GameEvent) {
+        if self.events.len() == self.capacity {
+            if let (Some(oldest), Some(file)) = (self.events.pop_front(), self.spill.as_mut()) {
+                if let Err(e) = writeln!(file, "{}", oldest.to_json()) {
+                    eprintln!("Could not write to the timeline file, older events will be dropped: {}", e);
+                    self.spill = None;
+                } else {
+                    self.spilled += 1;
+                }
+            }
+        }
+        self.events.push_back(event);
+    }
+
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    // Events moved to the spill file this session, and where it is
+    pub fn spilled(&self) -> (usize, Option<&Path>) {
+        (self.spilled, self.spill_path.as_deref())
+    }
+
+    // Oldest first
+    pub fn iter(&self) -> impl Iterator<Item = &GameEvent> {
+        self.events.iter()
+    }
+}
@@ -0,0 +1,98 @@
+// src/export.rs
+use serde_json::{json, Value};
+
+use crate::history::{MatchHistory, MatchSummary};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ExportFormat {
+    Csv,
+    Json,
+}
+
+impl ExportFormat {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "csv" => Some(ExportFormat::Csv),
+            "json" => Some(ExportFormat::Json),
+            _ => None,
+        }
+    }
+}
+
+// The time series kept for a stored match
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Series {
+    // GPM, XPM, last hits, deaths and net worth
+    Samples,
+    Positions,
+    Sightings,
+    Clock,
+}
+
+impl Series {
+    pub const ALL: [Series; 4] = [Series::Samples, Series::Positions, Series::Sightings, Series::Clock];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Series::Samples => "samples",
+            Series::Positions => "positions",
+            Series::Sightings => "sightings",
+            Series::Clock => "clock",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Series::ALL.into_iter().find(|s| s.name() == name)
+    }
+}
+
+// Everything stored for a match as one JSON document
+pub fn to_json(history: &MatchHistory, summary: &MatchSummary) -> rusqlite::Result<Value> {
+    let (match_id, steam_id) = (summary.match_id.as_str(), summary.steam_id.as_str());
+    Ok(json!({
+        "match": summary,
+        "samples": history.samples(match_id, steam_id)?,
+        "positions": history.positions(match_id, steam_id)?,
+        "sightings": history.sightings(match_id, steam_id)?,
+        "clock_mapping": history.clock_mapping(match_id, steam_id)?.to_json(),
+    }))
+}
+
+// One series of a match as CSV, header row first
+pub fn to_csv(history: &MatchHistory, summary: &MatchSummary, series: Series) -> rusqlite::Result<String> {
+    let (match_id, steam_id) = (summary.match_id.as_str(), summary.steam_id.as_str());
+    let mut lines = Vec::new();
+    match series {
+        Series::Samples => {
+            lines.push("time,gpm,xpm,last_hits,deaths,net_worth".to_string());
+            lines.extend(history.samples(match_id, steam_id)?.iter()
+                .map(|s| format!("{},{},{},{},{},{}", s.time, s.gpm, s.xpm, s.last_hits, s.deaths, s.net_worth)));
+        },
+        Series::Positions => {
+            lines.push("time,x,y".to_string());
+            lines.extend(history.positions(match_id, steam_id)?.iter()
+                .map(|p| format!("{},{},{}", p.time, p.x, p.y)));
+        },
+        Series::Sightings => {
+            lines.push("time,hero,x,y".to_string());
+            lines.extend(history.sightings(match_id, steam_id)?.iter()
+                .map(|s| format!("{},{},{},{}", s.time, csv_field(&s.hero), s.x, s.y)));
+        },
+        Series::Clock => {
+            lines.push("game_time,clock_time,timestamp".to_string());
+            lines.extend(history.clock_mapping(match_id, steam_id)?.points().iter()
+                .map(|p| format!("{},{},{}", p.game_time, p.clock_time, p.timestamp)));
+        },
+    }
+    lines.push(String::new());
+    Ok(lines.join("\n"))
+}
+
+// Quoted when it would otherwise break the row
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
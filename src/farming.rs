@@ -0,0 +1,91 @@
+// src/farming.rs
+use crate::camps::{self, Camp, CampSize};
+use crate::missing::MapSide;
+use crate::regions;
+use crate::state::Team;
+use crate::tracking::calculate_distance;
+
+// Camps in a route after the lane wave
+const ROUTE_CAMPS: usize = 3;
+
+// Ancients are too slow for most heroes before this
+const ANCIENTS_FROM: i32 = 15 * 60;
+
+// More missing enemies than this keeps the route on the player's own side of the map
+const SAFE_MISSING: usize = 1;
+
+// Gold worth of a camp relative to the others
+fn value(size: CampSize) -> f32 {
+    match size {
+        CampSize::Small => 1.0,
+        CampSize::Medium => 2.0,
+        CampSize::Large | CampSize::Ancient => 3.0,
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct FarmRoute {
+    // Lane whose wave to take first
+    pub lane: MapSide,
+    // Camps to clear afterwards, in order
+    pub camps: Vec<Camp>,
+    // Enemy camps were left out because enemies are missing
+    pub own_side_only: bool,
+}
+
+impl FarmRoute {
+    // The route as numbered steps
+    pub fn steps(&self) -> Vec<String> {
+        let mut steps = vec![format!("Push the {} lane wave", self.lane.name())];
+        steps.extend(self.camps.iter().map(|camp| format!("Clear the {}", camp.describe())));
+        steps
+    }
+}
+
+// Lane wave to start from: the lane the player is standing in, or the closest by side
+fn starting_lane(position: (i32, i32)) -> MapSide {
+    match regions::locate(position).map(|r| r.name) {
+        Some(name) if name.starts_with("top lane") => MapSide::Top,
+        Some(name) if name.starts_with("bottom lane") => MapSide::Bottom,
+        Some(name) if name.starts_with("mid lane") => MapSide::Mid,
+        _ => MapSide::of(position),
+    }
+}
+
+// A route from the player's position through the most rewarding nearby camps.
+// `is_up` says whether a camp is likely to have neutrals in it.
+pub fn plan(
+    position: (i32, i32),
+    team: Team,
+    clock_time: i32,
+    missing_enemies: usize,
+    is_up: impl Fn(&Camp) -> bool,
+) -> Option<FarmRoute> {
+    let own_side_only = missing_enemies > SAFE_MISSING;
+    let mut left: Vec<Camp> = camps::all_camps().into_iter()
+        .filter(|camp| !own_side_only || camp.radiant_side == (team == Team::Radiant))
+        .filter(|camp| camp.size != CampSize::Ancient || clock_time >= ANCIENTS_FROM)
+        .filter(|camp| is_up(camp))
+        .collect();
+
+    // Nearest-neighbour walk, with bigger camps worth a longer walk
+    let mut route = Vec::new();
+    let mut at = position;
+    while route.len() < ROUTE_CAMPS && !left.is_empty() {
+        let next = left.iter().enumerate()
+            .min_by(|(_, a), (_, b)| {
+                let cost = |camp: &Camp| calculate_distance(at, camp.position) / value(camp.size);
+                cost(a).total_cmp(&cost(b))
+            })
+            .map(|(index, _)| index)?;
+        let camp = left.remove(next);
+        at = camp.position;
+        route.push(camp);
+    }
+
+    Some(FarmRoute {
+        lane: starting_lane(position),
+        camps: route,
+        own_side_only,
+    })
+}
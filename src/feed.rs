@@ -0,0 +1,173 @@
+// src/feed.rs
+use std::collections::VecDeque;
+use std::sync::Mutex;
+
+use serde_json::{json, Map, Value};
+use tokio::sync::broadcast;
+
+use crate::events::{EventKind, GameEvent};
+
+// Messages a slow client can fall behind by before it's sent a fresh snapshot instead,
+// and events kept for clients that reconnect
+const BACKLOG: usize = 256;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FeedKind {
+    // Everything /state would return
+    Snapshot,
+    // What changed in the snapshot since the last message, as a JSON merge patch (RFC 7396)
+    Patch,
+    Event,
+}
+
+impl FeedKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            FeedKind::Snapshot => "snapshot",
+            FeedKind::Patch => "patch",
+            FeedKind::Event => "event",
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct FeedMessage {
+    // Counts up from 1; a snapshot carries the id of the last message it includes
+    pub id: u64,
+    pub kind: FeedKind,
+    pub data: Value,
+}
+
+impl FeedMessage {
+    pub fn to_json(&self) -> Value {
+        json!({
+            "id": self.id,
+            "type": self.kind.name(),
+            "data": self.data,
+        })
+    }
+}
+
+// An event as sent to clients, with the alert's category and severity spelled out
+pub fn event_json(event: &GameEvent) -> Value {
+    let mut json = event.to_json();
+    if let EventKind::Alert(category, severity) = event.kind {
+        json["category"] = json!(category.slug());
+        json["severity"] = json!(severity.slug());
+    }
+    json
+}
+
+// The merge patch that turns `old` into `new`, or None when they're the same
+pub fn merge_patch(old: &Value, new: &Value) -> Option<Value> {
+    if old == new {
+        return None;
+    }
+    let (Value::Object(old), Value::Object(new)) = (old, new) else { return Some(new.clone()) };
+
+    let mut patch = Map::new();
+    for (key, value) in new {
+        let changed = match old.get(key) {
+            Some(previous) => merge_patch(previous, value),
+            None => Some(value.clone()),
+        };
+        if let Some(changed) = changed {
+            patch.insert(key.clone(), changed);
+        }
+    }
+    for key in old.keys().filter(|key| !new.contains_key(*key)) {
+        patch.insert(key.clone(), Value::Null);
+    }
+    Some(Value::Object(patch))
+}
+
+struct Latest {
+    id: u64,
+    snapshot: Value,
+    // The last BACKLOG events, oldest first
+    events: VecDeque<FeedMessage>,
+}
+
+impl Latest {
+    fn events_after(&self, id: u64) -> Vec<FeedMessage> {
+        self.events.iter().filter(|m| m.id > id).cloned().collect()
+    }
+}
+
+// Fans the coach's state and events out to every connected client. Clients get a
+// snapshot when they join and patches and events after that.
+pub struct Feed {
+    sender: broadcast::Sender<FeedMessage>,
+    latest: Mutex<Latest>,
+}
+
+impl Feed {
+    pub fn new() -> Self {
+        Feed {
+            sender: broadcast::channel(BACKLOG).0,
+            latest: Mutex::new(Latest { id: 0, snapshot: Value::Null, events: VecDeque::new() }),
+        }
+    }
+
+    // Send the new snapshot as a patch against the last one, then the events that came with it
+    pub fn publish(&self, snapshot: Value, events: &[GameEvent]) {
+        let mut latest = self.latest.lock().unwrap();
+        if let Some(patch) = merge_patch(&latest.snapshot, &snapshot) {
+            latest.id += 1;
+            self.send(FeedMessage { id: latest.id, kind: FeedKind::Patch, data: patch });
+            latest.snapshot = snapshot;
+        }
+        for event in events {
+            latest.id += 1;
+            let message = FeedMessage { id: latest.id, kind: FeedKind::Event, data: event_json(event) };
+            if latest.events.len() == BACKLOG {
+                latest.events.pop_front();
+            }
+            latest.events.push_back(message.clone());
+            self.send(message);
+        }
+    }
+
+    // The current snapshot, and everything published after it
+    pub fn subscribe(&self) -> (FeedMessage, broadcast::Receiver<FeedMessage>) {
+        let latest = self.latest.lock().unwrap();
+        (self.snapshot(&latest), self.sender.subscribe())
+    }
+
+    // Kept events after `after` for a client that reconnects, and everything published from now on
+    pub fn subscribe_events(&self, after: Option<u64>) -> (Vec<FeedMessage>, broadcast::Receiver<FeedMessage>) {
+        let latest = self.latest.lock().unwrap();
+        // Ids start again when the coach restarts, so one from the future gets every kept event
+        let after = match after {
+            Some(id) if id > latest.id => 0,
+            Some(id) => id,
+            None => latest.id,
+        };
+        (latest.events_after(after), self.sender.subscribe())
+    }
+
+    // Kept events after `id`, for an event client that fell behind
+    pub fn events_after(&self, id: u64) -> Vec<FeedMessage> {
+        self.latest.lock().unwrap().events_after(id)
+    }
+
+    // For a client that fell behind
+    pub fn resync(&self) -> FeedMessage {
+        self.snapshot(&self.latest.lock().unwrap())
+    }
+
+    fn snapshot(&self, latest: &Latest) -> FeedMessage {
+        FeedMessage { id: latest.id, kind: FeedKind::Snapshot, data: latest.snapshot.clone() }
+    }
+
+    fn send(&self, message: FeedMessage) {
+        // Nobody listening is fine
+        let _ = self.sender.send(message);
+    }
+}
+
+impl Default for Feed {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,88 @@
+// src/format.rs
+use crate::heroes;
+use crate::items;
+
+// Format game time from seconds to MM:SS format
+pub fn format_game_time(seconds: Option<i32>) -> String {
+    if let Some(secs) = seconds {
+        // The clock is negative before the horn
+        let sign = if secs < 0 { "-" } else { "" };
+        let minutes = secs.abs() / 60;
+        let remaining_seconds = secs.abs() % 60;
+        format!("{}{}:{:02}", sign, minutes, remaining_seconds)
+    } else {
+        "Unknown".to_string()
+    }
+}
+
+// Format hero names from "npc_dota_hero_xxx" to the name shown in the client
+pub fn format_hero_name(name: &str) -> String {
+    match heroes::by_class(name) {
+        Some(hero) => hero.name.to_string(),
+        None => title_case(&name.replace("npc_dota_hero_", "")),
+    }
+}
+
+// Format item names from "item_xxx" to the name shown in the shop
+pub fn format_item_name(name: &str) -> String {
+    match items::by_class(name) {
+        Some(item) => item.name.to_string(),
+        None => title_case(&name.replace("item_", "")),
+    }
+}
+
+// Format ability names from "hero_class_ability" to a readable format, e.g. "Culling Blade"
+pub fn format_ability_name(name: &str) -> String {
+    // Several hero classes are prefixes of others, so strip the longest match
+    let short = heroes::HEROES.iter()
+        .filter_map(|h| name.strip_prefix(h.class).and_then(|rest| rest.strip_prefix('_')))
+        .min_by_key(|rest| rest.len())
+        .unwrap_or(name);
+    title_case(short)
+}
+
+// Gold amounts as shown on the scoreboard, e.g. "9.8k"
+pub fn format_gold(gold: i32) -> String {
+    if gold.abs() >= 1000 {
+        format!("{:.1}k", gold as f32 / 1000.0)
+    } else {
+        gold.to_string()
+    }
+}
+
+// One line chart of `values` squeezed into at most `width` characters
+pub fn sparkline(values: &[i32], width: usize) -> String {
+    const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+    if values.is_empty() || width == 0 {
+        return String::new();
+    }
+
+    // Average neighbouring values when there are more than fit
+    let buckets = values.len().min(width);
+    let points: Vec<f32> = (0..buckets)
+        .map(|i| {
+            let chunk = &values[i * values.len() / buckets..(i + 1) * values.len() / buckets];
+            chunk.iter().sum::<i32>() as f32 / chunk.len() as f32
+        })
+        .collect();
+
+    let min = points.iter().cloned().fold(f32::MAX, f32::min);
+    let max = points.iter().cloned().fold(f32::MIN, f32::max);
+    points.iter()
+        .map(|p| if max > min { BARS[((p - min) / (max - min) * 7.0).round() as usize] } else { BARS[0] })
+        .collect()
+}
+
+// "shadow_shaman" -> "Shadow Shaman"
+fn title_case(name: &str) -> String {
+    name.split('_')
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                None => String::new(),
+                Some(c) => c.to_uppercase().collect::<String>() + chars.as_str(),
+            }
+        })
+        .collect::<Vec<String>>()
+        .join(" ")
+}
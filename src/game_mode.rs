@@ -0,0 +1,120 @@
+// src/game_mode.rs
+use crate::state::GameState;
+
+// GSI doesn't report the game mode, so Turbo is recognised by its pace: roughly
+// double gold and experience. Between these clock times, a player at or above
+// both rates is almost certainly in Turbo.
+const PACE_CHECK: (i32, i32) = (3 * 60, 10 * 60);
+const TURBO_GPM: i32 = 700;
+const TURBO_XPM: i32 = 900;
+
+// Turbo items come in at about this share of the normal minute
+pub const TURBO_ITEM_PACE: f32 = 0.6;
+
+// An Ability Draft hero carries mostly other heroes' spells; this many is enough to tell
+const FOREIGN_ABILITIES: usize = 2;
+
+// Heroes whose own kit brings other heroes' spells into their ability slots
+const SPELL_THIEVES: [&str; 2] = ["rubick", "morphling"];
+
+// Talents and abilities any hero can have
+const SHARED_ABILITIES: [&str; 4] = ["special_bonus", "generic_", "ability_", "plus_"];
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum GameMode {
+    Normal,
+    Turbo,
+    // An arcade game, named in map.customgamename
+    Custom,
+    AbilityDraft,
+}
+
+impl GameMode {
+    pub fn name(&self) -> &'static str {
+        match self {
+            GameMode::Normal => "normal",
+            GameMode::Turbo => "Turbo",
+            GameMode::Custom => "Custom game",
+            GameMode::AbilityDraft => "Ability Draft",
+        }
+    }
+
+    // Modes where hero, item and map advice would mislead, so only enemies are tracked
+    pub fn tracking_only(&self) -> bool {
+        matches!(self, GameMode::Custom | GameMode::AbilityDraft)
+    }
+}
+
+// A map or custom game name that says Turbo
+fn named_turbo(state: &GameState) -> bool {
+    let map = state.map.as_ref();
+    [map.and_then(|m| m.name.as_deref()), map.and_then(|m| m.customgamename.as_deref())].iter()
+        .flatten()
+        .any(|name| name.to_lowercase().contains("turbo"))
+}
+
+fn custom_game(state: &GameState) -> bool {
+    state.map.as_ref()
+        .and_then(|m| m.customgamename.as_deref())
+        .map(|name| !name.trim().is_empty())
+        .unwrap_or(false)
+}
+
+// Abilities that belong to neither the player's hero nor every hero
+fn ability_draft(state: &GameState) -> bool {
+    let (Some(hero), Some(abilities)) = (state.hero().and_then(|h| h.name.as_deref()), state.abilities()) else { return false };
+    let hero = hero.trim_start_matches("npc_dota_hero_");
+    if SPELL_THIEVES.contains(&hero) {
+        return false;
+    }
+    abilities.values()
+        .filter_map(|a| a.name.as_deref())
+        .filter(|name| !SHARED_ABILITIES.iter().any(|shared| name.starts_with(shared)))
+        .filter(|name| !name.starts_with(hero))
+        .count() >= FOREIGN_ABILITIES
+}
+
+fn turbo_pace(state: &GameState) -> bool {
+    let (Some(clock_time), Some(player)) = (state.clock_time(), state.player()) else { return false };
+    (PACE_CHECK.0..PACE_CHECK.1).contains(&clock_time)
+        && player.gpm.unwrap_or(0) >= TURBO_GPM
+        && player.xpm.unwrap_or(0) >= TURBO_XPM
+}
+
+// Settles on the first mode other than normal it sees and keeps it for the rest of the match
+pub struct GameModeDetector {
+    mode: GameMode,
+}
+
+impl GameModeDetector {
+    pub fn new() -> Self {
+        GameModeDetector { mode: GameMode::Normal }
+    }
+
+    pub fn mode(&self) -> GameMode {
+        self.mode
+    }
+
+    // Returns the mode when it has just been recognised
+    pub fn update(&mut self, state: &GameState) -> Option<GameMode> {
+        if self.mode != GameMode::Normal {
+            return None;
+        }
+        self.mode = if named_turbo(state) || turbo_pace(state) {
+            GameMode::Turbo
+        } else if custom_game(state) {
+            GameMode::Custom
+        } else if ability_draft(state) {
+            GameMode::AbilityDraft
+        } else {
+            return None;
+        };
+        Some(self.mode)
+    }
+}
+
+impl Default for GameModeDetector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,81 @@
+// src/gold.rs
+use crate::analysis::gank_risk::GankRisk;
+use crate::format::format_gold;
+use crate::state::GameState;
+
+// Gank risk from which holding a lot of gold is worth a word
+pub const DANGER_RISK: i32 = 50;
+
+// Gold the player lost to one death
+#[derive(Clone, Copy, Debug)]
+pub struct GoldLoss {
+    pub time: i32,
+    // Unreliable gold held just before dying
+    pub held: i32,
+    pub lost: i32,
+}
+
+// Unspent gold the player is carrying into danger
+#[derive(Clone, Debug)]
+pub struct UnspentGold {
+    pub unreliable: i32,
+    pub risk: GankRisk,
+}
+
+impl UnspentGold {
+    pub fn describe(&self) -> String {
+        format!("You're holding {} unreliable gold with a {} gank risk - back to a shop or have the courier bring it as items before you lose it",
+            format_gold(self.unreliable), self.risk.level())
+    }
+}
+
+fn unreliable(state: &GameState) -> Option<i32> {
+    state.player()?.gold_unreliable
+}
+
+fn total(state: &GameState) -> Option<i32> {
+    state.player()?.gold
+}
+
+// Follows the gold the player carries, and what deaths cost them
+pub struct GoldWatch {
+    losses: Vec<GoldLoss>,
+}
+
+impl GoldWatch {
+    pub fn new() -> Self {
+        GoldWatch {
+            losses: Vec::new(),
+        }
+    }
+
+    pub fn losses(&self) -> &[GoldLoss] {
+        &self.losses
+    }
+
+    // Gold lost to deaths with at least `threshold` unreliable gold unspent
+    pub fn lost_holding(&self, threshold: i32) -> i32 {
+        self.losses.iter().filter(|l| l.held >= threshold).map(|l| l.lost).sum()
+    }
+
+    // Records what a death cost, and says when unspent gold is at risk. `previous` is the
+    // state before this one.
+    pub fn update(&mut self, state: &GameState, previous: Option<&GameState>, died: bool, risk: Option<GankRisk>, threshold: i32) -> Option<UnspentGold> {
+        if died {
+            let before = previous.and_then(|p| unreliable(p).zip(total(p)));
+            if let (Some((held, gold_before)), Some(gold), Some(time)) = (before, total(state), state.game_time()) {
+                self.losses.push(GoldLoss { time, held, lost: (gold_before - gold).max(0) });
+            }
+            return None;
+        }
+        let unreliable = unreliable(state).filter(|gold| *gold >= threshold)?;
+        let risk = risk.filter(|r| r.score >= DANGER_RISK)?;
+        Some(UnspentGold { unreliable, risk })
+    }
+}
+
+impl Default for GoldWatch {
+    fn default() -> Self {
+        Self::new()
+    }
+}
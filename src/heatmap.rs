@@ -0,0 +1,108 @@
+// src/heatmap.rs
+use std::collections::HashMap;
+
+use crate::tracking::describe_map_location;
+
+// Playable map coordinates run roughly from -8000 to 8000 on both axes
+const MAP_MIN: i32 = -8000;
+const MAP_SIZE: i32 = 16000;
+
+// Cells per side. Each covers about 670 units, a bit more than a hero's vision at night.
+pub const GRID: usize = 24;
+
+// Lightest to darkest
+const SHADES: [char; 5] = [' ', '░', '▒', '▓', '█'];
+
+fn cell_of(position: (i32, i32)) -> usize {
+    let scale = |v: i32| (((v - MAP_MIN) * GRID as i32) / MAP_SIZE).clamp(0, GRID as i32 - 1) as usize;
+    // Row 0 is the north edge so the grid prints the right way up
+    (GRID - 1 - scale(position.1)) * GRID + scale(position.0)
+}
+
+fn center_of(cell: usize) -> (i32, i32) {
+    let size = MAP_SIZE / GRID as i32;
+    let (row, column) = (cell / GRID, cell % GRID);
+    (MAP_MIN + column as i32 * size + size / 2, MAP_MIN + (GRID - 1 - row) as i32 * size + size / 2)
+}
+
+// Where enemy heroes have been seen on the minimap over the match, per hero and for the whole team
+pub struct Heatmap {
+    team: Vec<u32>,
+    heroes: HashMap<String, Vec<u32>>,
+}
+
+impl Heatmap {
+    pub fn new() -> Self {
+        Heatmap {
+            team: vec![0; GRID * GRID],
+            heroes: HashMap::new(),
+        }
+    }
+
+    // One sighting of `hero` at a map position
+    pub fn record(&mut self, hero: &str, position: (i32, i32)) {
+        let cell = cell_of(position);
+        self.team[cell] += 1;
+        self.heroes.entry(hero.to_string()).or_insert_with(|| vec![0; GRID * GRID])[cell] += 1;
+    }
+
+    // Heroes with at least one sighting, by name
+    pub fn heroes(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.heroes.keys().map(|n| n.as_str()).collect();
+        names.sort();
+        names
+    }
+
+    fn cells(&self, hero: Option<&str>) -> Option<&Vec<u32>> {
+        match hero {
+            Some(hero) => self.heroes.get(hero),
+            None => Some(&self.team),
+        }
+    }
+
+    pub fn sightings(&self, hero: Option<&str>) -> u32 {
+        self.cells(hero).map(|cells| cells.iter().sum()).unwrap_or(0)
+    }
+
+    // Named areas with the most sightings, busiest first, with their share of all sightings
+    pub fn hotspots(&self, hero: Option<&str>, count: usize) -> Vec<(String, u32)> {
+        let Some(cells) = self.cells(hero) else { return Vec::new() };
+        let total: u32 = cells.iter().sum();
+        if total == 0 {
+            return Vec::new();
+        }
+
+        let mut areas: HashMap<String, u32> = HashMap::new();
+        for (cell, seen) in cells.iter().enumerate().filter(|(_, seen)| **seen > 0) {
+            *areas.entry(describe_map_location(center_of(cell))).or_default() += seen;
+        }
+        let mut areas: Vec<(String, u32)> = areas.into_iter()
+            .map(|(area, seen)| (area, seen * 100 / total))
+            .collect();
+        areas.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        areas.truncate(count);
+        areas
+    }
+
+    // The grid as text, north at the top, shaded relative to the busiest cell
+    pub fn render(&self, hero: Option<&str>) -> Vec<String> {
+        let Some(cells) = self.cells(hero) else { return Vec::new() };
+        let busiest = cells.iter().copied().max().unwrap_or(0).max(1);
+        cells.chunks(GRID)
+            .map(|row| row.iter()
+                .map(|&seen| match seen {
+                    0 => SHADES[0],
+                    _ => SHADES[1 + ((seen * 4 - 1) / busiest).min(3) as usize],
+                })
+                // Cells are square on the map, characters are twice as tall as wide
+                .flat_map(|c| [c, c])
+                .collect())
+            .collect()
+    }
+}
+
+impl Default for Heatmap {
+    fn default() -> Self {
+        Self::new()
+    }
+}
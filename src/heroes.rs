@@ -0,0 +1,219 @@
+// src/heroes.rs
+
+// Primary attribute
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Attribute {
+    Strength,
+    Agility,
+    Intelligence,
+    Universal,
+}
+
+impl Attribute {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Attribute::Strength => "Strength",
+            Attribute::Agility => "Agility",
+            Attribute::Intelligence => "Intelligence",
+            Attribute::Universal => "Universal",
+        }
+    }
+}
+
+// Role tags as shown in the client's hero grid
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Role {
+    Carry,
+    Support,
+    Nuker,
+    Disabler,
+    Initiator,
+    Durable,
+    Escape,
+    Pusher,
+}
+
+// Static facts about a hero
+#[derive(Clone, Copy, Debug)]
+pub struct HeroInfo {
+    pub id: i32,
+    // Internal name without the npc_dota_hero_ prefix, e.g. "nevermore"
+    pub class: &'static str,
+    // Name shown in the client, e.g. "Shadow Fiend"
+    pub name: &'static str,
+    pub attribute: Attribute,
+    pub ranged: bool,
+    // Position (1-5) the hero is most often played in
+    pub position: u8,
+    pub roles: &'static [Role],
+    // Community nicknames and old names, lowercase
+    pub aliases: &'static [&'static str],
+}
+
+impl HeroInfo {
+    pub fn npc_name(&self) -> String {
+        format!("npc_dota_hero_{}", self.class)
+    }
+
+    pub fn has_role(&self, role: Role) -> bool {
+        self.roles.contains(&role)
+    }
+}
+
+// Every hero in the game, by id
+pub const HEROES: &[HeroInfo] = &[
+    HeroInfo { id: 1, class: "antimage", name: "Anti-Mage", attribute: Attribute::Agility, ranged: false, position: 1, roles: &[Role::Carry, Role::Escape, Role::Nuker], aliases: &["am", "magina"] },
+    HeroInfo { id: 2, class: "axe", name: "Axe", attribute: Attribute::Strength, ranged: false, position: 3, roles: &[Role::Initiator, Role::Durable, Role::Disabler], aliases: &[] },
+    HeroInfo { id: 3, class: "bane", name: "Bane", attribute: Attribute::Universal, ranged: true, position: 5, roles: &[Role::Support, Role::Disabler, Role::Nuker, Role::Durable], aliases: &[] },
+    HeroInfo { id: 4, class: "bloodseeker", name: "Bloodseeker", attribute: Attribute::Agility, ranged: false, position: 3, roles: &[Role::Carry, Role::Disabler, Role::Nuker, Role::Initiator], aliases: &["bs", "blood"] },
+    HeroInfo { id: 5, class: "crystal_maiden", name: "Crystal Maiden", attribute: Attribute::Intelligence, ranged: true, position: 5, roles: &[Role::Support, Role::Disabler, Role::Nuker], aliases: &["cm", "rylai"] },
+    HeroInfo { id: 6, class: "drow_ranger", name: "Drow Ranger", attribute: Attribute::Agility, ranged: true, position: 1, roles: &[Role::Carry, Role::Disabler, Role::Pusher], aliases: &["drow", "traxex"] },
+    HeroInfo { id: 7, class: "earthshaker", name: "Earthshaker", attribute: Attribute::Strength, ranged: false, position: 4, roles: &[Role::Support, Role::Initiator, Role::Disabler, Role::Nuker], aliases: &["es", "shaker"] },
+    HeroInfo { id: 8, class: "juggernaut", name: "Juggernaut", attribute: Attribute::Agility, ranged: false, position: 1, roles: &[Role::Carry, Role::Pusher, Role::Escape], aliases: &["jugg", "yurnero"] },
+    HeroInfo { id: 9, class: "mirana", name: "Mirana", attribute: Attribute::Universal, ranged: true, position: 4, roles: &[Role::Carry, Role::Support, Role::Escape, Role::Nuker, Role::Disabler], aliases: &["potm"] },
+    HeroInfo { id: 10, class: "morphling", name: "Morphling", attribute: Attribute::Agility, ranged: true, position: 1, roles: &[Role::Carry, Role::Escape, Role::Durable, Role::Nuker], aliases: &["morph"] },
+    HeroInfo { id: 11, class: "nevermore", name: "Shadow Fiend", attribute: Attribute::Agility, ranged: true, position: 2, roles: &[Role::Carry, Role::Nuker], aliases: &["sf", "nevermore"] },
+    HeroInfo { id: 12, class: "phantom_lancer", name: "Phantom Lancer", attribute: Attribute::Agility, ranged: false, position: 1, roles: &[Role::Carry, Role::Escape, Role::Pusher], aliases: &["pl"] },
+    HeroInfo { id: 13, class: "puck", name: "Puck", attribute: Attribute::Intelligence, ranged: true, position: 2, roles: &[Role::Initiator, Role::Disabler, Role::Escape, Role::Nuker], aliases: &[] },
+    HeroInfo { id: 14, class: "pudge", name: "Pudge", attribute: Attribute::Strength, ranged: false, position: 4, roles: &[Role::Disabler, Role::Initiator, Role::Durable, Role::Nuker], aliases: &["butcher"] },
+    HeroInfo { id: 15, class: "razor", name: "Razor", attribute: Attribute::Agility, ranged: true, position: 2, roles: &[Role::Carry, Role::Durable, Role::Nuker, Role::Pusher], aliases: &[] },
+    HeroInfo { id: 16, class: "sand_king", name: "Sand King", attribute: Attribute::Universal, ranged: false, position: 3, roles: &[Role::Initiator, Role::Disabler, Role::Support, Role::Nuker, Role::Escape], aliases: &["sk", "crixalis"] },
+    HeroInfo { id: 17, class: "storm_spirit", name: "Storm Spirit", attribute: Attribute::Intelligence, ranged: true, position: 2, roles: &[Role::Carry, Role::Escape, Role::Nuker, Role::Initiator, Role::Disabler], aliases: &["storm", "raijin"] },
+    HeroInfo { id: 18, class: "sven", name: "Sven", attribute: Attribute::Strength, ranged: false, position: 1, roles: &[Role::Carry, Role::Disabler, Role::Initiator, Role::Durable, Role::Nuker], aliases: &["rogue knight"] },
+    HeroInfo { id: 19, class: "tiny", name: "Tiny", attribute: Attribute::Strength, ranged: false, position: 4, roles: &[Role::Carry, Role::Nuker, Role::Pusher, Role::Initiator, Role::Durable, Role::Disabler], aliases: &[] },
+    HeroInfo { id: 20, class: "vengefulspirit", name: "Vengeful Spirit", attribute: Attribute::Universal, ranged: true, position: 5, roles: &[Role::Support, Role::Initiator, Role::Disabler, Role::Nuker, Role::Escape], aliases: &["vs", "venge", "shendelzare"] },
+    HeroInfo { id: 21, class: "windrunner", name: "Windranger", attribute: Attribute::Universal, ranged: true, position: 4, roles: &[Role::Carry, Role::Support, Role::Disabler, Role::Escape, Role::Nuker], aliases: &["wr", "windrunner", "lyralei"] },
+    HeroInfo { id: 22, class: "zuus", name: "Zeus", attribute: Attribute::Intelligence, ranged: true, position: 2, roles: &[Role::Nuker], aliases: &["zeus", "zuus"] },
+    HeroInfo { id: 23, class: "kunkka", name: "Kunkka", attribute: Attribute::Strength, ranged: false, position: 2, roles: &[Role::Carry, Role::Support, Role::Disabler, Role::Initiator, Role::Durable, Role::Nuker], aliases: &["admiral"] },
+    HeroInfo { id: 25, class: "lina", name: "Lina", attribute: Attribute::Intelligence, ranged: true, position: 2, roles: &[Role::Support, Role::Carry, Role::Nuker, Role::Disabler], aliases: &[] },
+    HeroInfo { id: 26, class: "lion", name: "Lion", attribute: Attribute::Intelligence, ranged: true, position: 5, roles: &[Role::Support, Role::Disabler, Role::Nuker, Role::Initiator], aliases: &[] },
+    HeroInfo { id: 27, class: "shadow_shaman", name: "Shadow Shaman", attribute: Attribute::Intelligence, ranged: true, position: 5, roles: &[Role::Support, Role::Pusher, Role::Disabler, Role::Nuker, Role::Initiator], aliases: &["shaman", "rhasta"] },
+    HeroInfo { id: 28, class: "slardar", name: "Slardar", attribute: Attribute::Strength, ranged: false, position: 3, roles: &[Role::Carry, Role::Durable, Role::Initiator, Role::Disabler, Role::Escape], aliases: &[] },
+    HeroInfo { id: 29, class: "tidehunter", name: "Tidehunter", attribute: Attribute::Strength, ranged: false, position: 3, roles: &[Role::Initiator, Role::Durable, Role::Disabler, Role::Nuker], aliases: &["tide", "leviathan"] },
+    HeroInfo { id: 30, class: "witch_doctor", name: "Witch Doctor", attribute: Attribute::Intelligence, ranged: true, position: 5, roles: &[Role::Support, Role::Nuker, Role::Disabler], aliases: &["wd"] },
+    HeroInfo { id: 31, class: "lich", name: "Lich", attribute: Attribute::Intelligence, ranged: true, position: 5, roles: &[Role::Support, Role::Nuker], aliases: &[] },
+    HeroInfo { id: 32, class: "riki", name: "Riki", attribute: Attribute::Agility, ranged: false, position: 4, roles: &[Role::Carry, Role::Escape, Role::Disabler], aliases: &["sa", "stealth assassin"] },
+    HeroInfo { id: 33, class: "enigma", name: "Enigma", attribute: Attribute::Universal, ranged: true, position: 3, roles: &[Role::Disabler, Role::Initiator, Role::Pusher], aliases: &[] },
+    HeroInfo { id: 34, class: "tinker", name: "Tinker", attribute: Attribute::Intelligence, ranged: true, position: 2, roles: &[Role::Carry, Role::Nuker, Role::Pusher], aliases: &["boush"] },
+    HeroInfo { id: 35, class: "sniper", name: "Sniper", attribute: Attribute::Agility, ranged: true, position: 2, roles: &[Role::Carry, Role::Nuker], aliases: &["kardel"] },
+    HeroInfo { id: 36, class: "necrolyte", name: "Necrophos", attribute: Attribute::Intelligence, ranged: true, position: 2, roles: &[Role::Carry, Role::Nuker, Role::Durable, Role::Disabler], aliases: &["necro", "necrolyte"] },
+    HeroInfo { id: 37, class: "warlock", name: "Warlock", attribute: Attribute::Intelligence, ranged: true, position: 5, roles: &[Role::Support, Role::Initiator, Role::Disabler], aliases: &["wl", "demnok"] },
+    HeroInfo { id: 38, class: "beastmaster", name: "Beastmaster", attribute: Attribute::Universal, ranged: false, position: 3, roles: &[Role::Initiator, Role::Disabler, Role::Durable, Role::Nuker], aliases: &["bm", "rexxar"] },
+    HeroInfo { id: 39, class: "queenofpain", name: "Queen of Pain", attribute: Attribute::Intelligence, ranged: true, position: 2, roles: &[Role::Carry, Role::Nuker, Role::Escape], aliases: &["qop", "akasha"] },
+    HeroInfo { id: 40, class: "venomancer", name: "Venomancer", attribute: Attribute::Universal, ranged: true, position: 5, roles: &[Role::Support, Role::Nuker, Role::Initiator, Role::Pusher, Role::Disabler], aliases: &["veno"] },
+    HeroInfo { id: 41, class: "faceless_void", name: "Faceless Void", attribute: Attribute::Agility, ranged: false, position: 1, roles: &[Role::Carry, Role::Initiator, Role::Disabler, Role::Escape, Role::Durable], aliases: &["void", "fv", "darkterror"] },
+    HeroInfo { id: 42, class: "skeleton_king", name: "Wraith King", attribute: Attribute::Strength, ranged: false, position: 1, roles: &[Role::Carry, Role::Support, Role::Durable, Role::Disabler, Role::Initiator], aliases: &["wk", "skeleton king", "leoric"] },
+    HeroInfo { id: 43, class: "death_prophet", name: "Death Prophet", attribute: Attribute::Intelligence, ranged: true, position: 2, roles: &[Role::Carry, Role::Pusher, Role::Nuker, Role::Disabler], aliases: &["dp", "krobelus"] },
+    HeroInfo { id: 44, class: "phantom_assassin", name: "Phantom Assassin", attribute: Attribute::Agility, ranged: false, position: 1, roles: &[Role::Carry, Role::Escape], aliases: &["pa", "mortred"] },
+    HeroInfo { id: 45, class: "pugna", name: "Pugna", attribute: Attribute::Intelligence, ranged: true, position: 5, roles: &[Role::Nuker, Role::Pusher], aliases: &[] },
+    HeroInfo { id: 46, class: "templar_assassin", name: "Templar Assassin", attribute: Attribute::Agility, ranged: true, position: 2, roles: &[Role::Carry, Role::Escape], aliases: &["ta", "lanaya"] },
+    HeroInfo { id: 47, class: "viper", name: "Viper", attribute: Attribute::Agility, ranged: true, position: 2, roles: &[Role::Carry, Role::Durable, Role::Initiator, Role::Disabler], aliases: &[] },
+    HeroInfo { id: 48, class: "luna", name: "Luna", attribute: Attribute::Agility, ranged: true, position: 1, roles: &[Role::Carry, Role::Nuker, Role::Pusher], aliases: &[] },
+    HeroInfo { id: 49, class: "dragon_knight", name: "Dragon Knight", attribute: Attribute::Strength, ranged: false, position: 2, roles: &[Role::Carry, Role::Pusher, Role::Durable, Role::Disabler, Role::Initiator, Role::Nuker], aliases: &["dk", "davion"] },
+    HeroInfo { id: 50, class: "dazzle", name: "Dazzle", attribute: Attribute::Universal, ranged: true, position: 5, roles: &[Role::Support, Role::Nuker, Role::Disabler], aliases: &[] },
+    HeroInfo { id: 51, class: "rattletrap", name: "Clockwerk", attribute: Attribute::Universal, ranged: false, position: 4, roles: &[Role::Initiator, Role::Disabler, Role::Durable, Role::Nuker], aliases: &["clock", "rattletrap"] },
+    HeroInfo { id: 52, class: "leshrac", name: "Leshrac", attribute: Attribute::Intelligence, ranged: true, position: 2, roles: &[Role::Carry, Role::Support, Role::Nuker, Role::Pusher, Role::Disabler], aliases: &["lesh"] },
+    HeroInfo { id: 53, class: "furion", name: "Nature's Prophet", attribute: Attribute::Intelligence, ranged: true, position: 4, roles: &[Role::Carry, Role::Pusher, Role::Escape, Role::Nuker], aliases: &["np", "furion", "prophet"] },
+    HeroInfo { id: 54, class: "life_stealer", name: "Lifestealer", attribute: Attribute::Strength, ranged: false, position: 1, roles: &[Role::Carry, Role::Durable, Role::Escape, Role::Disabler], aliases: &["ls", "naix"] },
+    HeroInfo { id: 55, class: "dark_seer", name: "Dark Seer", attribute: Attribute::Universal, ranged: false, position: 3, roles: &[Role::Initiator, Role::Escape, Role::Disabler], aliases: &["ds", "ish'kafel"] },
+    HeroInfo { id: 56, class: "clinkz", name: "Clinkz", attribute: Attribute::Agility, ranged: true, position: 4, roles: &[Role::Carry, Role::Escape, Role::Pusher], aliases: &["bone fletcher"] },
+    HeroInfo { id: 57, class: "omniknight", name: "Omniknight", attribute: Attribute::Strength, ranged: false, position: 5, roles: &[Role::Support, Role::Durable, Role::Nuker], aliases: &["omni", "purist"] },
+    HeroInfo { id: 58, class: "enchantress", name: "Enchantress", attribute: Attribute::Intelligence, ranged: true, position: 5, roles: &[Role::Support, Role::Pusher, Role::Durable, Role::Disabler], aliases: &["ench", "aiushtha"] },
+    HeroInfo { id: 59, class: "huskar", name: "Huskar", attribute: Attribute::Strength, ranged: true, position: 2, roles: &[Role::Carry, Role::Durable, Role::Initiator], aliases: &[] },
+    HeroInfo { id: 60, class: "night_stalker", name: "Night Stalker", attribute: Attribute::Strength, ranged: false, position: 3, roles: &[Role::Carry, Role::Initiator, Role::Durable, Role::Disabler, Role::Nuker], aliases: &["ns", "balanar"] },
+    HeroInfo { id: 61, class: "broodmother", name: "Broodmother", attribute: Attribute::Universal, ranged: false, position: 3, roles: &[Role::Carry, Role::Pusher, Role::Escape, Role::Nuker], aliases: &["brood", "arachnia"] },
+    HeroInfo { id: 62, class: "bounty_hunter", name: "Bounty Hunter", attribute: Attribute::Agility, ranged: false, position: 4, roles: &[Role::Escape, Role::Nuker], aliases: &["bh", "gondar"] },
+    HeroInfo { id: 63, class: "weaver", name: "Weaver", attribute: Attribute::Agility, ranged: true, position: 4, roles: &[Role::Carry, Role::Escape], aliases: &["skitskurr"] },
+    HeroInfo { id: 64, class: "jakiro", name: "Jakiro", attribute: Attribute::Intelligence, ranged: true, position: 5, roles: &[Role::Support, Role::Nuker, Role::Pusher, Role::Disabler], aliases: &["thd", "twin head dragon"] },
+    HeroInfo { id: 65, class: "batrider", name: "Batrider", attribute: Attribute::Universal, ranged: true, position: 3, roles: &[Role::Initiator, Role::Disabler, Role::Escape], aliases: &["bat"] },
+    HeroInfo { id: 66, class: "chen", name: "Chen", attribute: Attribute::Universal, ranged: true, position: 5, roles: &[Role::Support, Role::Pusher], aliases: &[] },
+    HeroInfo { id: 67, class: "spectre", name: "Spectre", attribute: Attribute::Agility, ranged: false, position: 1, roles: &[Role::Carry, Role::Durable, Role::Escape], aliases: &["mercurial"] },
+    HeroInfo { id: 68, class: "ancient_apparition", name: "Ancient Apparition", attribute: Attribute::Intelligence, ranged: true, position: 5, roles: &[Role::Support, Role::Disabler, Role::Nuker], aliases: &["aa", "kaldr"] },
+    HeroInfo { id: 69, class: "doom_bringer", name: "Doom", attribute: Attribute::Strength, ranged: false, position: 3, roles: &[Role::Carry, Role::Disabler, Role::Initiator, Role::Durable, Role::Nuker], aliases: &["doom", "lucifer"] },
+    HeroInfo { id: 70, class: "ursa", name: "Ursa", attribute: Attribute::Agility, ranged: false, position: 1, roles: &[Role::Carry, Role::Durable, Role::Disabler], aliases: &["ulfsaar"] },
+    HeroInfo { id: 71, class: "spirit_breaker", name: "Spirit Breaker", attribute: Attribute::Strength, ranged: false, position: 4, roles: &[Role::Carry, Role::Initiator, Role::Disabler, Role::Durable, Role::Escape], aliases: &["sb", "bara", "barathrum"] },
+    HeroInfo { id: 72, class: "gyrocopter", name: "Gyrocopter", attribute: Attribute::Agility, ranged: true, position: 1, roles: &[Role::Carry, Role::Nuker, Role::Disabler], aliases: &["gyro", "aurel"] },
+    HeroInfo { id: 73, class: "alchemist", name: "Alchemist", attribute: Attribute::Strength, ranged: false, position: 1, roles: &[Role::Carry, Role::Support, Role::Durable, Role::Disabler, Role::Initiator, Role::Nuker], aliases: &["alch", "razzil"] },
+    HeroInfo { id: 74, class: "invoker", name: "Invoker", attribute: Attribute::Universal, ranged: true, position: 2, roles: &[Role::Carry, Role::Nuker, Role::Disabler, Role::Escape, Role::Pusher], aliases: &["voker", "kael", "carl"] },
+    HeroInfo { id: 75, class: "silencer", name: "Silencer", attribute: Attribute::Intelligence, ranged: true, position: 5, roles: &[Role::Carry, Role::Support, Role::Disabler, Role::Initiator, Role::Nuker], aliases: &["nortrom"] },
+    HeroInfo { id: 76, class: "obsidian_destroyer", name: "Outworld Destroyer", attribute: Attribute::Intelligence, ranged: true, position: 2, roles: &[Role::Carry, Role::Nuker, Role::Disabler], aliases: &["od", "outworld devourer", "harbinger"] },
+    HeroInfo { id: 77, class: "lycan", name: "Lycan", attribute: Attribute::Universal, ranged: false, position: 3, roles: &[Role::Carry, Role::Pusher, Role::Durable, Role::Escape], aliases: &["banehallow"] },
+    HeroInfo { id: 78, class: "brewmaster", name: "Brewmaster", attribute: Attribute::Universal, ranged: false, position: 3, roles: &[Role::Carry, Role::Initiator, Role::Durable, Role::Disabler, Role::Nuker], aliases: &["brew", "mangix"] },
+    HeroInfo { id: 79, class: "shadow_demon", name: "Shadow Demon", attribute: Attribute::Intelligence, ranged: true, position: 5, roles: &[Role::Support, Role::Disabler, Role::Initiator, Role::Nuker], aliases: &["sd"] },
+    HeroInfo { id: 80, class: "lone_druid", name: "Lone Druid", attribute: Attribute::Universal, ranged: true, position: 1, roles: &[Role::Carry, Role::Pusher, Role::Durable], aliases: &["ld", "sylla"] },
+    HeroInfo { id: 81, class: "chaos_knight", name: "Chaos Knight", attribute: Attribute::Strength, ranged: false, position: 1, roles: &[Role::Carry, Role::Disabler, Role::Durable, Role::Pusher, Role::Initiator], aliases: &["ck", "nessaj"] },
+    HeroInfo { id: 82, class: "meepo", name: "Meepo", attribute: Attribute::Agility, ranged: false, position: 2, roles: &[Role::Carry, Role::Escape, Role::Nuker, Role::Disabler, Role::Initiator, Role::Pusher], aliases: &["geomancer"] },
+    HeroInfo { id: 83, class: "treant", name: "Treant Protector", attribute: Attribute::Strength, ranged: false, position: 5, roles: &[Role::Support, Role::Initiator, Role::Durable, Role::Disabler, Role::Escape], aliases: &["treant", "rooftrellen"] },
+    HeroInfo { id: 84, class: "ogre_magi", name: "Ogre Magi", attribute: Attribute::Strength, ranged: false, position: 5, roles: &[Role::Support, Role::Nuker, Role::Disabler, Role::Durable, Role::Initiator], aliases: &["ogre", "aggron"] },
+    HeroInfo { id: 85, class: "undying", name: "Undying", attribute: Attribute::Strength, ranged: false, position: 5, roles: &[Role::Support, Role::Durable, Role::Disabler, Role::Nuker], aliases: &["dirge"] },
+    HeroInfo { id: 86, class: "rubick", name: "Rubick", attribute: Attribute::Intelligence, ranged: true, position: 4, roles: &[Role::Support, Role::Disabler, Role::Nuker], aliases: &["grand magus"] },
+    HeroInfo { id: 87, class: "disruptor", name: "Disruptor", attribute: Attribute::Intelligence, ranged: true, position: 5, roles: &[Role::Support, Role::Disabler, Role::Nuker, Role::Initiator], aliases: &["thrall"] },
+    HeroInfo { id: 88, class: "nyx_assassin", name: "Nyx Assassin", attribute: Attribute::Universal, ranged: false, position: 4, roles: &[Role::Disabler, Role::Nuker, Role::Initiator, Role::Escape], aliases: &["nyx"] },
+    HeroInfo { id: 89, class: "naga_siren", name: "Naga Siren", attribute: Attribute::Agility, ranged: false, position: 1, roles: &[Role::Carry, Role::Support, Role::Pusher, Role::Disabler, Role::Initiator, Role::Escape], aliases: &["naga", "slithice"] },
+    HeroInfo { id: 90, class: "keeper_of_the_light", name: "Keeper of the Light", attribute: Attribute::Intelligence, ranged: true, position: 5, roles: &[Role::Support, Role::Nuker, Role::Disabler], aliases: &["kotl", "ezalor"] },
+    HeroInfo { id: 91, class: "wisp", name: "Io", attribute: Attribute::Universal, ranged: true, position: 5, roles: &[Role::Support, Role::Escape, Role::Nuker], aliases: &["io", "wisp"] },
+    HeroInfo { id: 92, class: "visage", name: "Visage", attribute: Attribute::Universal, ranged: true, position: 3, roles: &[Role::Support, Role::Nuker, Role::Durable, Role::Disabler, Role::Pusher], aliases: &[] },
+    HeroInfo { id: 93, class: "slark", name: "Slark", attribute: Attribute::Agility, ranged: false, position: 1, roles: &[Role::Carry, Role::Escape, Role::Disabler, Role::Nuker], aliases: &[] },
+    HeroInfo { id: 94, class: "medusa", name: "Medusa", attribute: Attribute::Agility, ranged: true, position: 1, roles: &[Role::Carry, Role::Disabler, Role::Durable], aliases: &["dusa", "gorgon"] },
+    HeroInfo { id: 95, class: "troll_warlord", name: "Troll Warlord", attribute: Attribute::Agility, ranged: true, position: 1, roles: &[Role::Carry, Role::Pusher, Role::Disabler, Role::Durable], aliases: &["troll", "jah'rakal"] },
+    HeroInfo { id: 96, class: "centaur", name: "Centaur Warrunner", attribute: Attribute::Strength, ranged: false, position: 3, roles: &[Role::Durable, Role::Initiator, Role::Disabler, Role::Nuker, Role::Escape], aliases: &["cent", "bradwarden"] },
+    HeroInfo { id: 97, class: "magnataur", name: "Magnus", attribute: Attribute::Universal, ranged: false, position: 3, roles: &[Role::Initiator, Role::Disabler, Role::Nuker, Role::Escape], aliases: &["magnus", "mag"] },
+    HeroInfo { id: 98, class: "shredder", name: "Timbersaw", attribute: Attribute::Strength, ranged: false, position: 3, roles: &[Role::Nuker, Role::Durable, Role::Escape], aliases: &["timber", "shredder", "rizzrack"] },
+    HeroInfo { id: 99, class: "bristleback", name: "Bristleback", attribute: Attribute::Strength, ranged: false, position: 3, roles: &[Role::Carry, Role::Durable, Role::Initiator, Role::Nuker], aliases: &["bb", "rigwarl"] },
+    HeroInfo { id: 100, class: "tusk", name: "Tusk", attribute: Attribute::Strength, ranged: false, position: 4, roles: &[Role::Initiator, Role::Disabler, Role::Nuker], aliases: &["ymir"] },
+    HeroInfo { id: 101, class: "skywrath_mage", name: "Skywrath Mage", attribute: Attribute::Intelligence, ranged: true, position: 4, roles: &[Role::Support, Role::Nuker, Role::Disabler], aliases: &["sky", "dragonus"] },
+    HeroInfo { id: 102, class: "abaddon", name: "Abaddon", attribute: Attribute::Universal, ranged: false, position: 5, roles: &[Role::Support, Role::Carry, Role::Durable], aliases: &["aba"] },
+    HeroInfo { id: 103, class: "elder_titan", name: "Elder Titan", attribute: Attribute::Strength, ranged: false, position: 5, roles: &[Role::Initiator, Role::Disabler, Role::Nuker, Role::Durable], aliases: &["et"] },
+    HeroInfo { id: 104, class: "legion_commander", name: "Legion Commander", attribute: Attribute::Strength, ranged: false, position: 3, roles: &[Role::Carry, Role::Disabler, Role::Initiator, Role::Durable, Role::Nuker], aliases: &["lc", "legion", "tresdin"] },
+    HeroInfo { id: 105, class: "techies", name: "Techies", attribute: Attribute::Universal, ranged: true, position: 4, roles: &[Role::Nuker, Role::Disabler], aliases: &["squee", "spleen", "spoon"] },
+    HeroInfo { id: 106, class: "ember_spirit", name: "Ember Spirit", attribute: Attribute::Agility, ranged: false, position: 2, roles: &[Role::Carry, Role::Escape, Role::Nuker, Role::Disabler, Role::Initiator], aliases: &["ember", "xin"] },
+    HeroInfo { id: 107, class: "earth_spirit", name: "Earth Spirit", attribute: Attribute::Strength, ranged: false, position: 4, roles: &[Role::Nuker, Role::Escape, Role::Disabler, Role::Initiator, Role::Durable], aliases: &["kaolin"] },
+    HeroInfo { id: 108, class: "abyssal_underlord", name: "Underlord", attribute: Attribute::Strength, ranged: false, position: 3, roles: &[Role::Support, Role::Nuker, Role::Disabler, Role::Durable, Role::Escape], aliases: &["pit lord", "vrogros"] },
+    HeroInfo { id: 109, class: "terrorblade", name: "Terrorblade", attribute: Attribute::Agility, ranged: false, position: 1, roles: &[Role::Carry, Role::Pusher, Role::Nuker], aliases: &["tb"] },
+    HeroInfo { id: 110, class: "phoenix", name: "Phoenix", attribute: Attribute::Strength, ranged: true, position: 4, roles: &[Role::Support, Role::Nuker, Role::Initiator, Role::Escape, Role::Disabler], aliases: &[] },
+    HeroInfo { id: 111, class: "oracle", name: "Oracle", attribute: Attribute::Intelligence, ranged: true, position: 5, roles: &[Role::Support, Role::Nuker, Role::Disabler, Role::Escape], aliases: &["nerif"] },
+    HeroInfo { id: 112, class: "winter_wyvern", name: "Winter Wyvern", attribute: Attribute::Universal, ranged: true, position: 5, roles: &[Role::Support, Role::Disabler, Role::Nuker], aliases: &["ww", "auroth"] },
+    HeroInfo { id: 113, class: "arc_warden", name: "Arc Warden", attribute: Attribute::Agility, ranged: true, position: 2, roles: &[Role::Carry, Role::Escape, Role::Nuker], aliases: &["arc", "zet"] },
+    HeroInfo { id: 114, class: "monkey_king", name: "Monkey King", attribute: Attribute::Agility, ranged: false, position: 1, roles: &[Role::Carry, Role::Escape, Role::Disabler, Role::Initiator], aliases: &["mk", "sun wukong"] },
+    HeroInfo { id: 119, class: "dark_willow", name: "Dark Willow", attribute: Attribute::Universal, ranged: true, position: 4, roles: &[Role::Support, Role::Nuker, Role::Disabler, Role::Escape], aliases: &["dw", "mireska"] },
+    HeroInfo { id: 120, class: "pangolier", name: "Pangolier", attribute: Attribute::Universal, ranged: false, position: 3, roles: &[Role::Carry, Role::Nuker, Role::Disabler, Role::Durable, Role::Escape, Role::Initiator], aliases: &["pango", "donte"] },
+    HeroInfo { id: 121, class: "grimstroke", name: "Grimstroke", attribute: Attribute::Intelligence, ranged: true, position: 5, roles: &[Role::Support, Role::Nuker, Role::Disabler, Role::Escape], aliases: &["grim"] },
+    HeroInfo { id: 123, class: "hoodwink", name: "Hoodwink", attribute: Attribute::Agility, ranged: true, position: 4, roles: &[Role::Support, Role::Nuker, Role::Escape, Role::Disabler], aliases: &[] },
+    HeroInfo { id: 126, class: "void_spirit", name: "Void Spirit", attribute: Attribute::Universal, ranged: false, position: 2, roles: &[Role::Carry, Role::Escape, Role::Nuker, Role::Disabler], aliases: &["inai"] },
+    HeroInfo { id: 128, class: "snapfire", name: "Snapfire", attribute: Attribute::Universal, ranged: true, position: 4, roles: &[Role::Support, Role::Nuker, Role::Disabler, Role::Escape], aliases: &["granny", "beatrix"] },
+    HeroInfo { id: 129, class: "mars", name: "Mars", attribute: Attribute::Strength, ranged: false, position: 3, roles: &[Role::Carry, Role::Initiator, Role::Disabler, Role::Durable], aliases: &[] },
+    HeroInfo { id: 131, class: "ringmaster", name: "Ringmaster", attribute: Attribute::Intelligence, ranged: true, position: 5, roles: &[Role::Support, Role::Nuker, Role::Escape, Role::Disabler], aliases: &[] },
+    HeroInfo { id: 135, class: "dawnbreaker", name: "Dawnbreaker", attribute: Attribute::Strength, ranged: false, position: 3, roles: &[Role::Carry, Role::Durable], aliases: &["valora"] },
+    HeroInfo { id: 136, class: "marci", name: "Marci", attribute: Attribute::Universal, ranged: false, position: 4, roles: &[Role::Support, Role::Carry, Role::Initiator, Role::Disabler, Role::Escape], aliases: &[] },
+    HeroInfo { id: 137, class: "primal_beast", name: "Primal Beast", attribute: Attribute::Strength, ranged: false, position: 3, roles: &[Role::Initiator, Role::Durable, Role::Disabler], aliases: &["pb"] },
+    HeroInfo { id: 138, class: "muerta", name: "Muerta", attribute: Attribute::Intelligence, ranged: true, position: 1, roles: &[Role::Carry, Role::Nuker, Role::Disabler], aliases: &[] },
+    HeroInfo { id: 145, class: "kez", name: "Kez", attribute: Attribute::Agility, ranged: false, position: 1, roles: &[Role::Carry, Role::Escape, Role::Disabler], aliases: &[] },];
+
+pub fn by_id(id: i32) -> Option<&'static HeroInfo> {
+    HEROES.iter().find(|h| h.id == id)
+}
+
+// Accepts both "npc_dota_hero_nevermore" and "nevermore"
+pub fn by_class(name: &str) -> Option<&'static HeroInfo> {
+    let class = name.strip_prefix("npc_dota_hero_").unwrap_or(name);
+    HEROES.iter().find(|h| h.class == class)
+}
+
+// Exact match on name, class or alias (case-insensitive) first, then heroes whose name starts with the query
+pub fn search(query: &str) -> Vec<&'static HeroInfo> {
+    let query = query.trim().to_lowercase();
+    if query.is_empty() {
+        return Vec::new();
+    }
+
+    let exact: Vec<&HeroInfo> = HEROES.iter()
+        .filter(|h| h.name.to_lowercase() == query || h.class == query || h.aliases.contains(&query.as_str()))
+        .collect();
+    if !exact.is_empty() {
+        return exact;
+    }
+
+    HEROES.iter()
+        .filter(|h| h.name.to_lowercase().starts_with(&query))
+        .collect()
+}
@@ -0,0 +1,169 @@
+// src/high_ground.rs
+use crate::buildings::{self, BuildingKind, BuildingStatus};
+use crate::buyback::BuybackStatus;
+use crate::respawns::DeadHero;
+use crate::roshan::Aegis;
+use crate::state::{GameState, Team};
+use crate::tracking::calculate_distance;
+use crate::waves;
+
+// The player's hero this close to an enemy tier 3 tower is at their high ground
+const SIEGE_RANGE: f32 = 2000.0;
+
+// A siege with no building damage for this long is over
+const SIEGE_OVER: i32 = 30;
+
+// Advice is repeated this often while the verdict holds
+const REPEAT_EVERY: i32 = 60;
+
+// An enemy respawning sooner than this is as good as alive for a siege
+const MIN_RESPAWN_LEFT: i32 = 15;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Safe,
+    Risky,
+    Abort,
+}
+
+impl Verdict {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Verdict::Safe => "safe to siege",
+            Verdict::Risky => "risky - hit the buildings with your wave and back off if they show",
+            Verdict::Abort => "abort the siege",
+        }
+    }
+}
+
+// Where a high ground siege stands
+#[derive(Clone, Debug)]
+pub struct Assessment {
+    pub target: BuildingStatus,
+    pub verdict: Verdict,
+    pub reasons: Vec<String>,
+}
+
+impl Assessment {
+    pub fn describe(&self) -> String {
+        format!("Hitting {}: {} ({})", self.target.describe(), self.verdict.name(), self.reasons.join(", "))
+    }
+}
+
+// Weighs up the siege from enemy respawns, the Aegis, the player's buyback and their own
+// team's dead. Each thing going for it counts one up, each against it one down.
+pub fn assess(
+    target: BuildingStatus,
+    team: Team,
+    game_time: i32,
+    enemies_dead: &[&DeadHero],
+    allies_dead: usize,
+    aegis: Option<&Aegis>,
+    buyback: Option<BuybackStatus>,
+) -> Assessment {
+    let mut score = 0;
+    let mut reasons = Vec::new();
+
+    let down: Vec<&&DeadHero> = enemies_dead.iter()
+        .filter(|d| d.remaining(game_time) >= MIN_RESPAWN_LEFT && d.buyback_chance < 0.5)
+        .collect();
+    if down.is_empty() {
+        score -= 1;
+        reasons.push("every enemy is up or about to be".to_string());
+    } else {
+        score += down.len() as i32;
+        let shortest = down.iter().map(|d| d.remaining(game_time)).min().unwrap_or(0);
+        reasons.push(format!("{} enem{} dead for {}s+", down.len(), if down.len() == 1 { "y" } else { "ies" }, shortest));
+    }
+    let buying_back = enemies_dead.iter().filter(|d| d.buyback_chance >= 0.5).count();
+    if buying_back > 0 {
+        reasons.push(format!("{} may buy back", buying_back));
+    }
+
+    match aegis.map(|a| a.team) {
+        Some(Some(holder)) if holder == team => {
+            score += 1;
+            reasons.push("the Aegis is yours".to_string());
+        },
+        Some(Some(_)) => {
+            score -= 1;
+            reasons.push("they hold the Aegis".to_string());
+        },
+        _ => {},
+    }
+
+    match buyback {
+        Some(BuybackStatus::Available(_)) => reasons.push("you have buyback".to_string()),
+        Some(_) => {
+            score -= 1;
+            reasons.push("no buyback".to_string());
+        },
+        None => {},
+    }
+
+    if allies_dead > 0 {
+        score -= allies_dead as i32;
+        reasons.push(format!("{} of your team dead", allies_dead));
+    }
+
+    let verdict = match score {
+        s if s >= 2 => Verdict::Safe,
+        s if s >= 0 => Verdict::Risky,
+        _ => Verdict::Abort,
+    };
+    Assessment { target, verdict, reasons }
+}
+
+// A tier 3 tower or barracks of `enemy` losing health with the player's hero next to it
+pub fn siege_target(state: &GameState, previous: Option<&GameState>, enemy: Team) -> Option<BuildingStatus> {
+    let position = state.hero_position()?;
+    let before = buildings::team_buildings(previous?, enemy);
+    buildings::team_buildings(state, enemy).into_iter()
+        .filter(|b| matches!(b.kind, BuildingKind::Tower(3) | BuildingKind::MeleeBarracks | BuildingKind::RangedBarracks))
+        .filter(|b| before.iter().any(|old| old.key == b.key && old.health > b.health))
+        .find(|b| waves::tower_position(enemy, b.lane, 3).map(|tower| calculate_distance(tower, position) <= SIEGE_RANGE).unwrap_or(false))
+}
+
+// Follows a siege from the first hit on the high ground, saying when it starts, when the
+// verdict changes and every minute while it holds
+pub struct HighGroundAdvisor {
+    last_hit: Option<i32>,
+    last_advice: Option<(Verdict, i32)>,
+}
+
+impl HighGroundAdvisor {
+    pub fn new() -> Self {
+        HighGroundAdvisor {
+            last_hit: None,
+            last_advice: None,
+        }
+    }
+
+    // Whether to pass on the verdict, which is there when the high ground took damage this update
+    pub fn update(&mut self, game_time: i32, verdict: Option<Verdict>) -> bool {
+        if verdict.is_some() {
+            self.last_hit = Some(game_time);
+        }
+        let sieging = self.last_hit.map(|t| game_time - t <= SIEGE_OVER).unwrap_or(false);
+        if !sieging {
+            self.last_hit = None;
+            self.last_advice = None;
+            return false;
+        }
+        let Some(verdict) = verdict else { return false };
+        let due = match self.last_advice {
+            Some((last, at)) => last != verdict || game_time - at >= REPEAT_EVERY,
+            None => true,
+        };
+        if due {
+            self.last_advice = Some((verdict, game_time));
+        }
+        due
+    }
+}
+
+impl Default for HighGroundAdvisor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
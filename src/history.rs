@@ -0,0 +1,523 @@
+// src/history.rs
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use chrono::Utc;
+use rusqlite::{params, Connection, OptionalExtension, Row};
+use serde::Serialize;
+
+use crate::analysis::performance::Sample;
+use crate::series::{MatchSeries, PositionSample, Sighting};
+use crate::state::GameState;
+use crate::vod::{ClockMapping, ClockPoint};
+
+// Where match history lives unless the config says otherwise
+pub fn default_history_path() -> Option<PathBuf> {
+    dirs::data_dir().map(|data| data.join("dota2-coach").join("history.sqlite"))
+}
+
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS matches (
+        match_id TEXT NOT NULL,
+        steam_id TEXT NOT NULL,
+        hero TEXT,
+        team TEXT,
+        won INTEGER,
+        duration INTEGER NOT NULL,
+        kills INTEGER NOT NULL,
+        deaths INTEGER NOT NULL,
+        assists INTEGER NOT NULL,
+        last_hits INTEGER NOT NULL,
+        denies INTEGER NOT NULL,
+        gpm INTEGER NOT NULL,
+        xpm INTEGER NOT NULL,
+        net_worth INTEGER NOT NULL,
+        played_at INTEGER NOT NULL,
+        PRIMARY KEY (match_id, steam_id)
+    );
+    CREATE TABLE IF NOT EXISTS samples (
+        match_id TEXT NOT NULL,
+        steam_id TEXT NOT NULL,
+        time INTEGER NOT NULL,
+        gpm INTEGER NOT NULL,
+        xpm INTEGER NOT NULL,
+        last_hits INTEGER NOT NULL,
+        deaths INTEGER NOT NULL,
+        net_worth INTEGER NOT NULL,
+        PRIMARY KEY (match_id, steam_id, time)
+    );
+    CREATE TABLE IF NOT EXISTS clock_mapping (
+        match_id TEXT NOT NULL,
+        steam_id TEXT NOT NULL,
+        game_time INTEGER NOT NULL,
+        clock_time INTEGER NOT NULL,
+        timestamp INTEGER NOT NULL,
+        PRIMARY KEY (match_id, steam_id, game_time)
+    );
+    CREATE TABLE IF NOT EXISTS positions (
+        match_id TEXT NOT NULL,
+        steam_id TEXT NOT NULL,
+        time INTEGER NOT NULL,
+        x INTEGER NOT NULL,
+        y INTEGER NOT NULL,
+        PRIMARY KEY (match_id, steam_id, time)
+    );
+    CREATE TABLE IF NOT EXISTS sightings (
+        match_id TEXT NOT NULL,
+        steam_id TEXT NOT NULL,
+        time INTEGER NOT NULL,
+        hero TEXT NOT NULL,
+        x INTEGER NOT NULL,
+        y INTEGER NOT NULL,
+        PRIMARY KEY (match_id, steam_id, time, hero)
+    );
+";
+
+// One finished match from the player's point of view
+#[derive(Clone, Debug, Serialize)]
+pub struct MatchSummary {
+    pub match_id: String,
+    pub steam_id: String,
+    // Hero class without the npc_dota_hero_ prefix, e.g. "juggernaut"
+    pub hero: Option<String>,
+    pub team: Option<String>,
+    // None when the game ended without a winner, e.g. the player left early
+    pub won: Option<bool>,
+    pub duration: i32,
+    pub kills: i32,
+    pub deaths: i32,
+    pub assists: i32,
+    pub last_hits: i32,
+    pub denies: i32,
+    pub gpm: i32,
+    pub xpm: i32,
+    pub net_worth: i32,
+    // Unix seconds
+    pub played_at: i64,
+}
+
+impl MatchSummary {
+    // Summary of the match in `state`. None without a match and player to key it by.
+    pub fn from_state(state: &GameState) -> Option<Self> {
+        let map = state.map.as_ref()?;
+        let player = state.player()?;
+        let team = state.player_team();
+        let won = match map.win_team.as_deref() {
+            Some(winner @ ("radiant" | "dire")) => team.as_deref().map(|team| team == winner),
+            _ => None,
+        };
+
+        Some(MatchSummary {
+            match_id: map.matchid.clone()?,
+            steam_id: player.steamid.clone()?,
+            hero: state.hero().and_then(|h| h.name.as_deref()).map(|n| n.replace("npc_dota_hero_", "")),
+            team,
+            won,
+            duration: map.clock_time.unwrap_or(0).max(0),
+            kills: player.kills.unwrap_or(0),
+            deaths: player.deaths.unwrap_or(0),
+            assists: player.assists.unwrap_or(0),
+            last_hits: player.last_hits.unwrap_or(0),
+            denies: player.denies.unwrap_or(0),
+            gpm: player.gpm.unwrap_or(0),
+            xpm: player.xpm.unwrap_or(0),
+            net_worth: player.net_worth.unwrap_or(0),
+            played_at: Utc::now().timestamp(),
+        })
+    }
+
+    fn from_row(row: &Row) -> rusqlite::Result<Self> {
+        Ok(MatchSummary {
+            match_id: row.get("match_id")?,
+            steam_id: row.get("steam_id")?,
+            hero: row.get("hero")?,
+            team: row.get("team")?,
+            won: row.get("won")?,
+            duration: row.get("duration")?,
+            kills: row.get("kills")?,
+            deaths: row.get("deaths")?,
+            assists: row.get("assists")?,
+            last_hits: row.get("last_hits")?,
+            denies: row.get("denies")?,
+            gpm: row.get("gpm")?,
+            xpm: row.get("xpm")?,
+            net_worth: row.get("net_worth")?,
+            played_at: row.get("played_at")?,
+        })
+    }
+}
+
+fn sample_from_row(row: &Row) -> rusqlite::Result<Sample> {
+    Ok(Sample {
+        time: row.get("time")?,
+        gpm: row.get("gpm")?,
+        xpm: row.get("xpm")?,
+        last_hits: row.get("last_hits")?,
+        deaths: row.get("deaths")?,
+        net_worth: row.get("net_worth")?,
+    })
+}
+
+// Totals over a set of stored matches
+#[derive(Clone, Debug, Default)]
+pub struct HistoryStats {
+    pub matches: usize,
+    pub wins: usize,
+    pub losses: usize,
+    pub average_gpm: i32,
+    pub average_xpm: i32,
+    pub deaths_per_game: f32,
+    // Hero class and games played, most played first
+    pub heroes: Vec<(String, usize)>,
+}
+
+// Finished matches and their time series, kept in a SQLite file across sessions
+pub struct MatchHistory {
+    connection: Connection,
+    // Unix seconds when this process opened the history; later matches make up the session
+    session_start: i64,
+}
+
+impl MatchHistory {
+    pub fn open(path: &Path) -> Result<Self, String> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(|e| format!("Could not create {}: {}", dir.display(), e))?;
+        }
+        let connection = Connection::open(path)
+            .map_err(|e| format!("Could not open match history {}: {}", path.display(), e))?;
+        Self::with_connection(connection)
+    }
+
+    // A history that only lasts as long as the process
+    pub fn in_memory() -> Result<Self, String> {
+        Self::with_connection(Connection::open_in_memory().map_err(|e| e.to_string())?)
+    }
+
+    fn with_connection(connection: Connection) -> Result<Self, String> {
+        connection.execute_batch(SCHEMA)
+            .map_err(|e| format!("Could not set up match history: {}", e))?;
+        Ok(MatchHistory {
+            connection,
+            session_start: Utc::now().timestamp(),
+        })
+    }
+
+    // Store a match with its samples, clock mapping, positions and sightings. Saving the same match again replaces it.
+    pub fn save<'a>(&mut self, summary: &MatchSummary, samples: impl IntoIterator<Item = &'a Sample>, clock: &ClockMapping, series: &MatchSeries) -> rusqlite::Result<()> {
+        let transaction = self.connection.transaction()?;
+        transaction.execute(
+            "INSERT OR REPLACE INTO matches
+                (match_id, steam_id, hero, team, won, duration, kills, deaths, assists,
+                 last_hits, denies, gpm, xpm, net_worth, played_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15)",
+            params![summary.match_id, summary.steam_id, summary.hero, summary.team, summary.won,
+                summary.duration, summary.kills, summary.deaths, summary.assists,
+                summary.last_hits, summary.denies, summary.gpm, summary.xpm, summary.net_worth,
+                summary.played_at],
+        )?;
+        transaction.execute("DELETE FROM samples WHERE match_id = ?1 AND steam_id = ?2",
+            params![summary.match_id, summary.steam_id])?;
+        {
+            let mut insert = transaction.prepare(
+                "INSERT OR REPLACE INTO samples (match_id, steam_id, time, gpm, xpm, last_hits, deaths, net_worth)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)")?;
+            for sample in samples {
+                insert.execute(params![summary.match_id, summary.steam_id, sample.time, sample.gpm,
+                    sample.xpm, sample.last_hits, sample.deaths, sample.net_worth])?;
+            }
+        }
+        transaction.execute("DELETE FROM clock_mapping WHERE match_id = ?1 AND steam_id = ?2",
+            params![summary.match_id, summary.steam_id])?;
+        {
+            let mut insert = transaction.prepare(
+                "INSERT OR REPLACE INTO clock_mapping (match_id, steam_id, game_time, clock_time, timestamp)
+                 VALUES (?1, ?2, ?3, ?4, ?5)")?;
+            for point in clock.points() {
+                insert.execute(params![summary.match_id, summary.steam_id, point.game_time, point.clock_time, point.timestamp])?;
+            }
+        }
+        for table in ["positions", "sightings"] {
+            transaction.execute(&format!("DELETE FROM {} WHERE match_id = ?1 AND steam_id = ?2", table),
+                params![summary.match_id, summary.steam_id])?;
+        }
+        {
+            let mut insert = transaction.prepare(
+                "INSERT OR REPLACE INTO positions (match_id, steam_id, time, x, y) VALUES (?1, ?2, ?3, ?4, ?5)")?;
+            for position in series.positions() {
+                insert.execute(params![summary.match_id, summary.steam_id, position.time, position.x, position.y])?;
+            }
+            let mut insert = transaction.prepare(
+                "INSERT OR REPLACE INTO sightings (match_id, steam_id, time, hero, x, y) VALUES (?1, ?2, ?3, ?4, ?5, ?6)")?;
+            for sighting in series.sightings() {
+                insert.execute(params![summary.match_id, summary.steam_id, sighting.time, sighting.hero, sighting.x, sighting.y])?;
+            }
+        }
+        transaction.commit()
+    }
+
+    pub fn session_start(&self) -> i64 {
+        self.session_start
+    }
+
+    // Totals for a player's matches (every player's when None) played at or after `since`
+    pub fn stats(&self, steam_id: Option<&str>, since: Option<i64>) -> rusqlite::Result<HistoryStats> {
+        const FILTER: &str = "WHERE (?1 IS NULL OR steam_id = ?1) AND (?2 IS NULL OR played_at >= ?2)";
+        let mut stats = self.connection.query_row(
+            &format!("SELECT COUNT(*), COALESCE(SUM(won = 1), 0), COALESCE(SUM(won = 0), 0),
+                        COALESCE(AVG(gpm), 0), COALESCE(AVG(xpm), 0), COALESCE(AVG(deaths), 0)
+                      FROM matches {}", FILTER),
+            params![steam_id, since],
+            |row| Ok(HistoryStats {
+                matches: row.get::<_, i64>(0)? as usize,
+                wins: row.get::<_, i64>(1)? as usize,
+                losses: row.get::<_, i64>(2)? as usize,
+                average_gpm: row.get::<_, f64>(3)?.round() as i32,
+                average_xpm: row.get::<_, f64>(4)?.round() as i32,
+                deaths_per_game: row.get::<_, f64>(5)? as f32,
+                heroes: Vec::new(),
+            }),
+        )?;
+
+        let mut query = self.connection.prepare(&format!(
+            "SELECT hero, COUNT(*) AS games FROM matches {} AND hero IS NOT NULL
+             GROUP BY hero ORDER BY games DESC, hero", FILTER))?;
+        stats.heroes = query.query_map(params![steam_id, since], |row| Ok((row.get(0)?, row.get::<_, i64>(1)? as usize)))?
+            .collect::<rusqlite::Result<_>>()?;
+        Ok(stats)
+    }
+
+    // A stored match by its ID alone, or the latest match when None. With several
+    // accounts on one machine, the most recently played one wins.
+    pub fn find(&self, match_id: Option<&str>) -> rusqlite::Result<Option<MatchSummary>> {
+        self.connection.query_row(
+            "SELECT * FROM matches WHERE ?1 IS NULL OR match_id = ?1 ORDER BY played_at DESC LIMIT 1",
+            params![match_id],
+            MatchSummary::from_row,
+        ).optional()
+    }
+
+    pub fn get(&self, match_id: &str, steam_id: &str) -> rusqlite::Result<Option<MatchSummary>> {
+        self.connection.query_row(
+            "SELECT * FROM matches WHERE match_id = ?1 AND steam_id = ?2",
+            params![match_id, steam_id],
+            MatchSummary::from_row,
+        ).optional()
+    }
+
+    // A player's matches, newest first, optionally on one hero
+    pub fn matches(&self, steam_id: &str, hero: Option<&str>, limit: usize) -> rusqlite::Result<Vec<MatchSummary>> {
+        let mut query = self.connection.prepare(
+            "SELECT * FROM matches
+             WHERE steam_id = ?1 AND (?2 IS NULL OR hero = ?2)
+             ORDER BY played_at DESC LIMIT ?3")?;
+        let rows = query.query_map(params![steam_id, hero, limit as i64], MatchSummary::from_row)?;
+        rows.collect()
+    }
+
+    // The time series of one match, oldest first
+    pub fn samples(&self, match_id: &str, steam_id: &str) -> rusqlite::Result<Vec<Sample>> {
+        let mut query = self.connection.prepare(
+            "SELECT * FROM samples WHERE match_id = ?1 AND steam_id = ?2 ORDER BY time")?;
+        let rows = query.query_map(params![match_id, steam_id], sample_from_row)?;
+        rows.collect()
+    }
+
+    // The player's hero position over one match, oldest first
+    pub fn positions(&self, match_id: &str, steam_id: &str) -> rusqlite::Result<Vec<PositionSample>> {
+        let mut query = self.connection.prepare(
+            "SELECT time, x, y FROM positions WHERE match_id = ?1 AND steam_id = ?2 ORDER BY time")?;
+        let rows = query.query_map(params![match_id, steam_id], |row| Ok(PositionSample {
+            time: row.get(0)?,
+            x: row.get(1)?,
+            y: row.get(2)?,
+        }))?;
+        rows.collect()
+    }
+
+    // Enemy sightings over one match, oldest first
+    pub fn sightings(&self, match_id: &str, steam_id: &str) -> rusqlite::Result<Vec<Sighting>> {
+        let mut query = self.connection.prepare(
+            "SELECT time, hero, x, y FROM sightings WHERE match_id = ?1 AND steam_id = ?2 ORDER BY time, hero")?;
+        let rows = query.query_map(params![match_id, steam_id], |row| Ok(Sighting {
+            time: row.get(0)?,
+            hero: row.get(1)?,
+            x: row.get(2)?,
+            y: row.get(3)?,
+        }))?;
+        rows.collect()
+    }
+
+    // How a match's clock lined up with real time, for finding its events in a VOD
+    pub fn clock_mapping(&self, match_id: &str, steam_id: &str) -> rusqlite::Result<ClockMapping> {
+        let mut query = self.connection.prepare(
+            "SELECT game_time, clock_time, timestamp FROM clock_mapping
+             WHERE match_id = ?1 AND steam_id = ?2 ORDER BY game_time")?;
+        let rows = query.query_map(params![match_id, steam_id], |row| Ok(ClockPoint {
+            game_time: row.get(0)?,
+            clock_time: row.get(1)?,
+            timestamp: row.get(2)?,
+        }))?;
+        Ok(ClockMapping::from_points(rows.collect::<rusqlite::Result<_>>()?))
+    }
+
+    // From each of a player's past matches, the last sample at or before `time` (game time).
+    // Matches that ended before `time` are left out.
+    pub fn samples_at(&self, steam_id: &str, hero: Option<&str>, time: i32, exclude_match: Option<&str>) -> rusqlite::Result<Vec<Sample>> {
+        let mut query = self.connection.prepare(
+            "SELECT s.* FROM samples s
+             JOIN matches m ON m.match_id = s.match_id AND m.steam_id = s.steam_id
+             WHERE s.steam_id = ?1 AND (?2 IS NULL OR m.hero = ?2) AND (?4 IS NULL OR s.match_id != ?4)
+               AND EXISTS (SELECT 1 FROM samples
+                           WHERE match_id = s.match_id AND steam_id = s.steam_id AND time >= ?3)
+               AND s.time = (SELECT MAX(time) FROM samples
+                             WHERE match_id = s.match_id AND steam_id = s.steam_id AND time <= ?3)")?;
+        let rows = query.query_map(params![steam_id, hero, time, exclude_match], sample_from_row)?;
+        rows.collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PLAYER: &str = "76561198000000001";
+
+    fn summary(match_id: &str, hero: &str, won: Option<bool>, played_at: i64) -> MatchSummary {
+        MatchSummary {
+            match_id: match_id.to_string(),
+            steam_id: PLAYER.to_string(),
+            hero: Some(hero.to_string()),
+            team: Some("radiant".to_string()),
+            won,
+            duration: 1800,
+            kills: 7,
+            deaths: 3,
+            assists: 11,
+            last_hits: 210,
+            denies: 14,
+            gpm: 540,
+            xpm: 610,
+            net_worth: 16200,
+            played_at,
+        }
+    }
+
+    // One sample a minute up to `minutes`, with last hits growing by `per_minute`
+    fn samples(minutes: i32, per_minute: i32) -> Vec<Sample> {
+        (1..=minutes)
+            .map(|minute| Sample {
+                time: minute * 60,
+                gpm: 300 + minute * 10,
+                xpm: 400 + minute * 10,
+                last_hits: minute * per_minute,
+                deaths: minute / 10,
+                net_worth: minute * 700,
+            })
+            .collect()
+    }
+
+    fn save(history: &mut MatchHistory, summary: &MatchSummary, samples: &[Sample]) {
+        history.save(summary, samples, &ClockMapping::new(), &MatchSeries::new()).unwrap();
+    }
+
+    #[test]
+    fn match_round_trips() {
+        let mut history = MatchHistory::in_memory().unwrap();
+        let stored = summary("7000000001", "juggernaut", Some(true), 1_700_000_000);
+        let clock = ClockMapping::from_points(vec![
+            ClockPoint { game_time: 0, clock_time: -90, timestamp: 1_699_998_000 },
+            ClockPoint { game_time: 600, clock_time: 510, timestamp: 1_699_998_700 },
+        ]);
+        let mut series = MatchSeries::new();
+        series.record_position(30, (-6000, -5800));
+        series.record_sighting(45, "Lion", (1200, 900));
+        history.save(&stored, &samples(3, 8), &clock, &series).unwrap();
+
+        let loaded = history.get("7000000001", PLAYER).unwrap().unwrap();
+        assert_eq!(loaded.hero.as_deref(), Some("juggernaut"));
+        assert_eq!(loaded.team.as_deref(), Some("radiant"));
+        assert_eq!(loaded.won, Some(true));
+        assert_eq!((loaded.kills, loaded.deaths, loaded.assists), (7, 3, 11));
+        assert_eq!((loaded.last_hits, loaded.denies, loaded.gpm, loaded.xpm, loaded.net_worth), (210, 14, 540, 610, 16200));
+        assert_eq!((loaded.duration, loaded.played_at), (1800, 1_700_000_000));
+
+        let loaded_samples = history.samples("7000000001", PLAYER).unwrap();
+        assert_eq!(loaded_samples.iter().map(|s| (s.time, s.last_hits)).collect::<Vec<_>>(), vec![(60, 8), (120, 16), (180, 24)]);
+        assert_eq!(history.clock_mapping("7000000001", PLAYER).unwrap().points(), clock.points());
+        let positions = history.positions("7000000001", PLAYER).unwrap();
+        assert_eq!(positions.iter().map(|p| (p.time, p.x, p.y)).collect::<Vec<_>>(), vec![(30, -6000, -5800)]);
+        let sightings = history.sightings("7000000001", PLAYER).unwrap();
+        assert_eq!(sightings.iter().map(|s| (s.time, s.hero.as_str(), s.x, s.y)).collect::<Vec<_>>(), vec![(45, "Lion", 1200, 900)]);
+
+        assert!(history.get("7000000001", "someone else").unwrap().is_none());
+        assert_eq!(history.find(None).unwrap().map(|s| s.match_id), Some("7000000001".to_string()));
+        assert_eq!(history.find(Some("7000000001")).unwrap().map(|s| s.steam_id), Some(PLAYER.to_string()));
+        assert!(history.find(Some("1")).unwrap().is_none());
+    }
+
+    #[test]
+    fn saving_a_match_again_replaces_it() {
+        let mut history = MatchHistory::in_memory().unwrap();
+        save(&mut history, &summary("7000000001", "juggernaut", None, 100), &samples(10, 8));
+        save(&mut history, &summary("7000000001", "juggernaut", Some(false), 100), &samples(4, 8));
+
+        assert_eq!(history.get("7000000001", PLAYER).unwrap().unwrap().won, Some(false));
+        assert_eq!(history.samples("7000000001", PLAYER).unwrap().len(), 4);
+        assert_eq!(history.stats(None, None).unwrap().matches, 1);
+    }
+
+    #[test]
+    fn stats_count_results_averages_and_heroes() {
+        let mut history = MatchHistory::in_memory().unwrap();
+        let mut lost = summary("2", "juggernaut", Some(false), 200);
+        lost.gpm = 400;
+        lost.deaths = 8;
+        save(&mut history, &summary("1", "juggernaut", Some(true), 100), &[]);
+        save(&mut history, &lost, &[]);
+        save(&mut history, &summary("3", "lion", None, 300), &[]);
+
+        let stats = history.stats(Some(PLAYER), None).unwrap();
+        assert_eq!((stats.matches, stats.wins, stats.losses), (3, 1, 1));
+        assert_eq!(stats.average_gpm, (540 + 400 + 540) / 3);
+        assert!((stats.deaths_per_game - 14.0 / 3.0).abs() < 0.01);
+        assert_eq!(stats.heroes, vec![("juggernaut".to_string(), 2), ("lion".to_string(), 1)]);
+
+        let session = history.stats(Some(PLAYER), Some(200)).unwrap();
+        assert_eq!((session.matches, session.wins, session.losses), (2, 0, 1));
+        assert_eq!(history.stats(Some("someone else"), None).unwrap().matches, 0);
+    }
+
+    #[test]
+    fn matches_are_newest_first_and_filter_by_hero() {
+        let mut history = MatchHistory::in_memory().unwrap();
+        save(&mut history, &summary("1", "juggernaut", Some(true), 100), &[]);
+        save(&mut history, &summary("2", "lion", Some(true), 200), &[]);
+        save(&mut history, &summary("3", "juggernaut", Some(true), 300), &[]);
+
+        let ids = |matches: Vec<MatchSummary>| matches.into_iter().map(|m| m.match_id).collect::<Vec<_>>();
+        assert_eq!(ids(history.matches(PLAYER, None, 10).unwrap()), vec!["3", "2", "1"]);
+        assert_eq!(ids(history.matches(PLAYER, Some("juggernaut"), 10).unwrap()), vec!["3", "1"]);
+        assert_eq!(ids(history.matches(PLAYER, None, 1).unwrap()), vec!["3"]);
+    }
+
+    #[test]
+    fn samples_at_takes_the_last_sample_of_each_long_enough_match() {
+        let mut history = MatchHistory::in_memory().unwrap();
+        save(&mut history, &summary("1", "juggernaut", Some(true), 100), &samples(20, 8));
+        save(&mut history, &summary("2", "juggernaut", Some(true), 200), &samples(20, 6));
+        // Over before minute 10, so it says nothing about it
+        save(&mut history, &summary("3", "juggernaut", Some(false), 300), &samples(8, 9));
+        save(&mut history, &summary("4", "lion", Some(false), 400), &samples(20, 2));
+
+        // Between samples, the one before counts
+        let mut at_ten = history.samples_at(PLAYER, None, 630, None).unwrap();
+        at_ten.sort_by_key(|s| s.last_hits);
+        assert_eq!(at_ten.iter().map(|s| (s.time, s.last_hits)).collect::<Vec<_>>(), vec![(600, 20), (600, 60), (600, 80)]);
+
+        let on_hero = history.samples_at(PLAYER, Some("juggernaut"), 600, None).unwrap();
+        assert_eq!(on_hero.len(), 2);
+        let without_current = history.samples_at(PLAYER, Some("juggernaut"), 600, Some("1")).unwrap();
+        assert_eq!(without_current.iter().map(|s| s.last_hits).collect::<Vec<_>>(), vec![60]);
+        assert!(history.samples_at("someone else", None, 600, None).unwrap().is_empty());
+    }
+}
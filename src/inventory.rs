@@ -0,0 +1,108 @@
+// src/inventory.rs
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::state::Item;
+
+pub const MAIN_SLOTS: usize = 6;
+pub const BACKPACK_SLOTS: usize = 3;
+pub const STASH_SLOTS: usize = 6;
+
+// The items block split by where each item sits. GSI sends it as flat
+// "slot0".."slot8", "stash0".."stash5", "teleport0" and "neutral0" keys;
+// slots 6-8 are the backpack.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(from = "HashMap<String, Item>", into = "HashMap<String, Item>")]
+pub struct Inventory {
+    pub main: Vec<Option<Item>>,
+    pub backpack: Vec<Option<Item>>,
+    pub stash: Vec<Option<Item>>,
+    pub teleport: Option<Item>,
+    pub neutral: Option<Item>,
+}
+
+impl Item {
+    // Unused slots are sent as an item named "empty"
+    pub fn is_empty(&self) -> bool {
+        matches!(self.name.as_deref(), None | Some("empty"))
+    }
+}
+
+impl From<HashMap<String, Item>> for Inventory {
+    fn from(mut slots: HashMap<String, Item>) -> Self {
+        let mut take = |key: String| slots.remove(&key).filter(|item| !item.is_empty());
+
+        Inventory {
+            main: (0..MAIN_SLOTS).map(|i| take(format!("slot{}", i))).collect(),
+            backpack: (MAIN_SLOTS..MAIN_SLOTS + BACKPACK_SLOTS).map(|i| take(format!("slot{}", i))).collect(),
+            stash: (0..STASH_SLOTS).map(|i| take(format!("stash{}", i))).collect(),
+            teleport: take("teleport0".to_string()),
+            neutral: take("neutral0".to_string()),
+        }
+    }
+}
+
+impl From<Inventory> for HashMap<String, Item> {
+    fn from(inventory: Inventory) -> Self {
+        let mut slots = HashMap::new();
+        let carried = inventory.main.into_iter().chain(inventory.backpack);
+
+        for (i, item) in carried.enumerate() {
+            if let Some(item) = item {
+                slots.insert(format!("slot{}", i), item);
+            }
+        }
+        for (i, item) in inventory.stash.into_iter().enumerate() {
+            if let Some(item) = item {
+                slots.insert(format!("stash{}", i), item);
+            }
+        }
+        if let Some(item) = inventory.teleport {
+            slots.insert("teleport0".to_string(), item);
+        }
+        if let Some(item) = inventory.neutral {
+            slots.insert("neutral0".to_string(), item);
+        }
+
+        slots
+    }
+}
+
+impl Inventory {
+    // Items in the six main slots
+    pub fn main_items(&self) -> impl Iterator<Item = &Item> {
+        self.main.iter().flatten()
+    }
+
+    // Everything the hero carries or has waiting in the stash
+    pub fn all_items(&self) -> impl Iterator<Item = &Item> {
+        self.main.iter()
+            .chain(&self.backpack)
+            .chain(&self.stash)
+            .chain([&self.teleport, &self.neutral])
+            .flatten()
+    }
+
+    // Classes of everything in all_items, without the item_ prefix
+    pub fn classes(&self) -> Vec<String> {
+        self.all_items()
+            .filter_map(|item| item.name.as_deref())
+            .map(|name| name.trim_start_matches("item_").to_string())
+            .collect()
+    }
+
+    // Whether any main, backpack or stash slot holds the given item ("item_...")
+    pub fn contains(&self, name: &str) -> bool {
+        self.all_items().any(|item| item.name.as_deref() == Some(name))
+    }
+
+    pub fn has_teleport(&self) -> bool {
+        self.teleport.is_some()
+    }
+
+    // Items sitting in the stash, waiting for a courier
+    pub fn stash_items(&self) -> impl Iterator<Item = &Item> {
+        self.stash.iter().flatten()
+    }
+}
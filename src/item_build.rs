@@ -0,0 +1,209 @@
+// src/item_build.rs
+use crate::heroes::{Attribute, HeroInfo, Role};
+use crate::inventory::Inventory;
+use crate::items::{self, ItemInfo};
+
+// Gold has to move this much before the suggestion is worked out again
+pub const GOLD_STEP: i32 = 250;
+
+// Enemy disablers that make magic immunity the priority for a core
+pub const DISABLERS_FOR_BKB: usize = 3;
+
+// One thing to buy on the way to the suggested item
+#[derive(Clone, Debug, PartialEq)]
+pub struct Purchase {
+    pub name: String,
+    pub cost: i32,
+}
+
+#[derive(Clone, Debug)]
+pub struct Recommendation {
+    pub item: &'static ItemInfo,
+    pub reason: String,
+    // Everything still needed, components first and recipes last
+    pub shopping_list: Vec<Purchase>,
+    // The part of the shopping list the player can afford right now
+    pub buy_now: Vec<Purchase>,
+}
+
+impl Recommendation {
+    pub fn remaining_cost(&self) -> i32 {
+        self.shopping_list.iter().map(|p| p.cost).sum()
+    }
+
+    pub fn describe(&self) -> String {
+        let now = if self.buy_now.is_empty() {
+            "nothing affordable yet".to_string()
+        } else {
+            format!("buy {} now", self.buy_now.iter().map(|p| p.name.as_str()).collect::<Vec<_>>().join(" + "))
+        };
+        format!("{} ({}) - {}, {} gold to go", self.item.name, self.reason, now, self.remaining_cost())
+    }
+}
+
+// Usual core items for a hero, in buying order
+pub fn core_build(hero: &HeroInfo) -> Vec<&'static str> {
+    match (hero.position, hero.attribute) {
+        (1, Attribute::Strength) => vec!["power_treads", "sange_and_yasha", "black_king_bar", "greater_crit"],
+        (1, _) => vec!["power_treads", "manta", "black_king_bar", "greater_crit"],
+        (2, Attribute::Intelligence) => vec!["power_treads", "kaya", "blink", "ultimate_scepter"],
+        (2, _) => vec!["power_treads", "blink", "black_king_bar", "ultimate_scepter"],
+        (3, _) => vec!["phase_boots", "blink", "blade_mail", "black_king_bar"],
+        (4, _) => vec!["arcane_boots", "blink", "force_staff", "glimmer_cape"],
+        _ => vec!["tranquil_boots", "glimmer_cape", "force_staff", "ghost"],
+    }
+}
+
+// Whether `class` is owned, taking one copy out of `owned` if so
+fn take(owned: &mut Vec<String>, class: &str) -> bool {
+    match owned.iter().position(|o| o == class) {
+        Some(index) => {
+            owned.remove(index);
+            true
+        },
+        None => false,
+    }
+}
+
+// Whether `item` is `class` or was built from it
+fn builds_from(item: &str, class: &str) -> bool {
+    item == class || items::by_class(item)
+        .map(|info| info.components.iter().any(|c| builds_from(c, class)))
+        .unwrap_or(false)
+}
+
+// Whether the item, or something built from it, is already in the inventory
+fn is_built(class: &str, owned: &[String]) -> bool {
+    owned.iter().any(|o| builds_from(o, class))
+}
+
+// Gold still needed for `item` given what's in the inventory, or None when it's already built
+pub fn gold_needed(item: &ItemInfo, inventory: &Inventory) -> Option<i32> {
+    let mut owned = inventory.classes();
+    if is_built(item.class, &owned) {
+        return None;
+    }
+    let mut list = Vec::new();
+    shopping_list(item, &mut owned, &mut list);
+    Some(list.iter().map(|p| p.cost).sum())
+}
+
+// What is left to buy for `item`, using up owned components along the way
+fn shopping_list(item: &ItemInfo, owned: &mut Vec<String>, list: &mut Vec<Purchase>) {
+    for component in item.components.iter().filter_map(|c| items::by_class(c)) {
+        if !take(owned, component.class) {
+            if component.components.is_empty() {
+                list.push(Purchase { name: component.name.to_string(), cost: component.cost });
+            } else {
+                shopping_list(component, owned, list);
+            }
+        }
+    }
+    if item.components.is_empty() {
+        list.push(Purchase { name: item.name.to_string(), cost: item.cost });
+    } else if item.recipe_cost() > 0 {
+        list.push(Purchase { name: format!("{} recipe", item.name), cost: item.recipe_cost() });
+    }
+}
+
+// The next item to work towards for `hero`, given what they own, their gold and the enemy heroes.
+// `popular` is this patch's build from Stratz; the built-in core build stands in without it.
+pub fn recommend(hero: &HeroInfo, inventory: &Inventory, gold: i32, enemies: &[&HeroInfo], popular: Option<&[&'static str]>) -> Option<Recommendation> {
+    let owned: Vec<String> = inventory.all_items()
+        .filter_map(|item| item.name.as_deref())
+        .map(|name| name.trim_start_matches("item_").to_string())
+        .collect();
+
+    let popular = popular.filter(|build| !build.is_empty());
+    let mut build = popular.map(|build| build.to_vec()).unwrap_or_else(|| core_build(hero));
+
+    // Heavy lockdown on the other side makes magic immunity urgent for cores
+    let disablers = enemies.iter().filter(|e| e.has_role(Role::Disabler)).count();
+    let rush_bkb = hero.position <= 3 && disablers >= DISABLERS_FOR_BKB;
+    if rush_bkb {
+        build.retain(|item| *item != "black_king_bar");
+        build.insert(1, "black_king_bar");
+    }
+
+    let item = build.iter()
+        .find(|item| !is_built(item, &owned))
+        .and_then(|item| items::by_class(item))?;
+    let reason = if rush_bkb && item.class == "black_king_bar" {
+        format!("{} enemy disablers - get magic immunity early", disablers)
+    } else if popular.is_some() {
+        format!("popular on {} this patch", hero.name)
+    } else {
+        format!("core item for a position {} {} hero", hero.position, hero.attribute.name().to_lowercase())
+    };
+
+    let mut list = Vec::new();
+    shopping_list(item, &mut owned.clone(), &mut list);
+
+    // Buy in order, skipping parts that don't fit so cheaper ones still can
+    let mut left = gold;
+    let buy_now = list.iter()
+        .filter(|p| {
+            let fits = p.cost <= left;
+            if fits {
+                left -= p.cost;
+            }
+            fits
+        })
+        .cloned()
+        .collect();
+
+    Some(Recommendation {
+        item,
+        reason,
+        shopping_list: list,
+        buy_now,
+    })
+}
+
+// Keeps the current suggestion and only works it out again when gold or items change
+pub struct ItemAdvisor {
+    last_gold: Option<i32>,
+    last_items: Vec<String>,
+    current: Option<Recommendation>,
+}
+
+impl ItemAdvisor {
+    pub fn new() -> Self {
+        ItemAdvisor {
+            last_gold: None,
+            last_items: Vec::new(),
+            current: None,
+        }
+    }
+
+    pub fn current(&self) -> Option<&Recommendation> {
+        self.current.as_ref()
+    }
+
+    // Returns the suggestion when it changed enough to tell the player about
+    pub fn update(&mut self, hero: &HeroInfo, inventory: &Inventory, gold: i32, enemies: &[&HeroInfo], popular: Option<&[&'static str]>) -> Option<&Recommendation> {
+        let mut items: Vec<String> = inventory.all_items().filter_map(|i| i.name.clone()).collect();
+        items.sort();
+        let gold_moved = self.last_gold.map(|last| (gold - last).abs() >= GOLD_STEP).unwrap_or(true);
+        if !gold_moved && items == self.last_items {
+            return None;
+        }
+        self.last_gold = Some(gold);
+        self.last_items = items;
+
+        let next = recommend(hero, inventory, gold, enemies, popular);
+        let changed = match (&self.current, &next) {
+            (Some(old), Some(new)) => old.item.class != new.item.class || old.buy_now != new.buy_now,
+            (None, None) => false,
+            _ => true,
+        };
+        self.current = next;
+        if changed { self.current.as_ref() } else { None }
+    }
+}
+
+impl Default for ItemAdvisor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
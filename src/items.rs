@@ -0,0 +1,174 @@
+// src/items.rs
+
+// Static facts about a shop item
+#[derive(Clone, Copy, Debug)]
+pub struct ItemInfo {
+    // Internal name without the item_ prefix, e.g. "blink"
+    pub class: &'static str,
+    // Name shown in the shop
+    pub name: &'static str,
+    // Total gold cost including any recipe
+    pub cost: i32,
+    // Component classes, repeated when an item needs two of the same
+    pub components: &'static [&'static str],
+    // Has something to click (as opposed to a purely passive item)
+    pub active: bool,
+    // Used up or stacked charges rather than kept as part of a build
+    pub consumable: bool,
+    // Base cooldown of the active in seconds
+    pub cooldown: Option<i32>,
+}
+
+impl ItemInfo {
+    // Gold paid for the recipe scroll, if the item has one
+    pub fn recipe_cost(&self) -> i32 {
+        if self.components.is_empty() {
+            return 0;
+        }
+
+        let parts: i32 = self.components.iter()
+            .filter_map(|c| by_class(c))
+            .map(|c| c.cost)
+            .sum();
+        self.cost - parts
+    }
+}
+
+// Basic items, then assembled items roughly by cost. Prices follow the current patch and drift over time.
+pub const ITEMS: &[ItemInfo] = &[
+    ItemInfo { class: "tango", name: "Tango", cost: 90, components: &[], active: true, consumable: true, cooldown: None },
+    ItemInfo { class: "flask", name: "Healing Salve", cost: 100, components: &[], active: true, consumable: true, cooldown: None },
+    ItemInfo { class: "clarity", name: "Clarity", cost: 50, components: &[], active: true, consumable: true, cooldown: None },
+    ItemInfo { class: "faerie_fire", name: "Faerie Fire", cost: 65, components: &[], active: true, consumable: true, cooldown: None },
+    ItemInfo { class: "enchanted_mango", name: "Enchanted Mango", cost: 65, components: &[], active: true, consumable: true, cooldown: None },
+    ItemInfo { class: "smoke_of_deceit", name: "Smoke of Deceit", cost: 50, components: &[], active: true, consumable: true, cooldown: None },
+    ItemInfo { class: "dust", name: "Dust of Appearance", cost: 80, components: &[], active: true, consumable: true, cooldown: None },
+    ItemInfo { class: "blood_grenade", name: "Blood Grenade", cost: 50, components: &[], active: true, consumable: true, cooldown: None },
+    ItemInfo { class: "ward_observer", name: "Observer Ward", cost: 0, components: &[], active: true, consumable: true, cooldown: None },
+    ItemInfo { class: "ward_sentry", name: "Sentry Ward", cost: 50, components: &[], active: true, consumable: true, cooldown: None },
+    ItemInfo { class: "tpscroll", name: "Town Portal Scroll", cost: 100, components: &[], active: true, consumable: true, cooldown: Some(80) },
+    ItemInfo { class: "branches", name: "Iron Branch", cost: 50, components: &[], active: true, consumable: false, cooldown: None },
+    ItemInfo { class: "circlet", name: "Circlet", cost: 155, components: &[], active: false, consumable: false, cooldown: None },
+    ItemInfo { class: "slippers", name: "Slippers of Agility", cost: 140, components: &[], active: false, consumable: false, cooldown: None },
+    ItemInfo { class: "mantle", name: "Mantle of Intelligence", cost: 140, components: &[], active: false, consumable: false, cooldown: None },
+    ItemInfo { class: "gauntlets", name: "Gauntlets of Strength", cost: 140, components: &[], active: false, consumable: false, cooldown: None },
+    ItemInfo { class: "quelling_blade", name: "Quelling Blade", cost: 100, components: &[], active: true, consumable: false, cooldown: None },
+    ItemInfo { class: "ring_of_protection", name: "Ring of Protection", cost: 175, components: &[], active: false, consumable: false, cooldown: None },
+    ItemInfo { class: "ring_of_regen", name: "Ring of Regen", cost: 175, components: &[], active: false, consumable: false, cooldown: None },
+    ItemInfo { class: "sobi_mask", name: "Sage's Mask", cost: 175, components: &[], active: false, consumable: false, cooldown: None },
+    ItemInfo { class: "fluffy_hat", name: "Fluffy Hat", cost: 250, components: &[], active: false, consumable: false, cooldown: None },
+    ItemInfo { class: "wind_lace", name: "Wind Lace", cost: 250, components: &[], active: false, consumable: false, cooldown: None },
+    ItemInfo { class: "magic_stick", name: "Magic Stick", cost: 200, components: &[], active: true, consumable: false, cooldown: Some(13) },
+    ItemInfo { class: "blight_stone", name: "Blight Stone", cost: 300, components: &[], active: false, consumable: false, cooldown: None },
+    ItemInfo { class: "boots", name: "Boots of Speed", cost: 500, components: &[], active: false, consumable: false, cooldown: None },
+    ItemInfo { class: "gloves", name: "Gloves of Haste", cost: 450, components: &[], active: false, consumable: false, cooldown: None },
+    ItemInfo { class: "blades_of_attack", name: "Blades of Attack", cost: 450, components: &[], active: false, consumable: false, cooldown: None },
+    ItemInfo { class: "belt_of_strength", name: "Belt of Strength", cost: 450, components: &[], active: false, consumable: false, cooldown: None },
+    ItemInfo { class: "boots_of_elves", name: "Band of Elvenskin", cost: 450, components: &[], active: false, consumable: false, cooldown: None },
+    ItemInfo { class: "robe", name: "Robe of the Magi", cost: 450, components: &[], active: false, consumable: false, cooldown: None },
+    ItemInfo { class: "crown", name: "Crown", cost: 450, components: &[], active: false, consumable: false, cooldown: None },
+    ItemInfo { class: "chainmail", name: "Chainmail", cost: 550, components: &[], active: false, consumable: false, cooldown: None },
+    ItemInfo { class: "ring_of_health", name: "Ring of Health", cost: 700, components: &[], active: false, consumable: false, cooldown: None },
+    ItemInfo { class: "void_stone", name: "Void Stone", cost: 700, components: &[], active: false, consumable: false, cooldown: None },
+    ItemInfo { class: "cloak", name: "Cloak", cost: 800, components: &[], active: false, consumable: false, cooldown: None },
+    ItemInfo { class: "energy_booster", name: "Energy Booster", cost: 800, components: &[], active: false, consumable: false, cooldown: None },
+    ItemInfo { class: "javelin", name: "Javelin", cost: 900, components: &[], active: false, consumable: false, cooldown: None },
+    ItemInfo { class: "gem", name: "Gem of True Sight", cost: 900, components: &[], active: false, consumable: false, cooldown: None },
+    ItemInfo { class: "broadsword", name: "Broadsword", cost: 1000, components: &[], active: false, consumable: false, cooldown: None },
+    ItemInfo { class: "ogre_axe", name: "Ogre Axe", cost: 1000, components: &[], active: false, consumable: false, cooldown: None },
+    ItemInfo { class: "blade_of_alacrity", name: "Blade of Alacrity", cost: 1000, components: &[], active: false, consumable: false, cooldown: None },
+    ItemInfo { class: "staff_of_wizardry", name: "Staff of Wizardry", cost: 1000, components: &[], active: false, consumable: false, cooldown: None },
+    ItemInfo { class: "shadow_amulet", name: "Shadow Amulet", cost: 1000, components: &[], active: true, consumable: false, cooldown: Some(7) },
+    ItemInfo { class: "vitality_booster", name: "Vitality Booster", cost: 1000, components: &[], active: false, consumable: false, cooldown: None },
+    ItemInfo { class: "point_booster", name: "Point Booster", cost: 1200, components: &[], active: false, consumable: false, cooldown: None },
+    ItemInfo { class: "talisman_of_evasion", name: "Talisman of Evasion", cost: 1300, components: &[], active: false, consumable: false, cooldown: None },
+    ItemInfo { class: "claymore", name: "Claymore", cost: 1350, components: &[], active: false, consumable: false, cooldown: None },
+    ItemInfo { class: "platemail", name: "Platemail", cost: 1400, components: &[], active: false, consumable: false, cooldown: None },
+    ItemInfo { class: "ghost", name: "Ghost Scepter", cost: 1500, components: &[], active: true, consumable: false, cooldown: Some(22) },
+    ItemInfo { class: "mithril_hammer", name: "Mithril Hammer", cost: 1600, components: &[], active: false, consumable: false, cooldown: None },
+    ItemInfo { class: "hyperstone", name: "Hyperstone", cost: 2000, components: &[], active: false, consumable: false, cooldown: None },
+    ItemInfo { class: "ultimate_orb", name: "Ultimate Orb", cost: 2100, components: &[], active: false, consumable: false, cooldown: None },
+    ItemInfo { class: "demon_edge", name: "Demon Edge", cost: 2200, components: &[], active: false, consumable: false, cooldown: None },
+    ItemInfo { class: "blink", name: "Blink Dagger", cost: 2250, components: &[], active: true, consumable: false, cooldown: Some(15) },
+    ItemInfo { class: "eagle", name: "Eaglesong", cost: 2800, components: &[], active: false, consumable: false, cooldown: None },
+    ItemInfo { class: "reaver", name: "Reaver", cost: 2800, components: &[], active: false, consumable: false, cooldown: None },
+    ItemInfo { class: "mystic_staff", name: "Mystic Staff", cost: 2800, components: &[], active: false, consumable: false, cooldown: None },
+    ItemInfo { class: "relic", name: "Sacred Relic", cost: 3800, components: &[], active: false, consumable: false, cooldown: None },
+    ItemInfo { class: "magic_wand", name: "Magic Wand", cost: 450, components: &["branches", "branches", "magic_stick"], active: true, consumable: false, cooldown: Some(13) },
+    ItemInfo { class: "bracer", name: "Bracer", cost: 505, components: &["gauntlets", "circlet"], active: false, consumable: false, cooldown: None },
+    ItemInfo { class: "wraith_band", name: "Wraith Band", cost: 505, components: &["slippers", "circlet"], active: false, consumable: false, cooldown: None },
+    ItemInfo { class: "null_talisman", name: "Null Talisman", cost: 505, components: &["mantle", "circlet"], active: false, consumable: false, cooldown: None },
+    ItemInfo { class: "tranquil_boots", name: "Tranquil Boots", cost: 925, components: &["boots", "wind_lace", "ring_of_regen"], active: false, consumable: false, cooldown: None },
+    ItemInfo { class: "arcane_boots", name: "Arcane Boots", cost: 1400, components: &["boots", "energy_booster"], active: true, consumable: false, cooldown: Some(55) },
+    ItemInfo { class: "power_treads", name: "Power Treads", cost: 1400, components: &["boots", "gloves", "belt_of_strength"], active: true, consumable: false, cooldown: None },
+    ItemInfo { class: "phase_boots", name: "Phase Boots", cost: 1500, components: &["boots", "blades_of_attack", "chainmail"], active: true, consumable: false, cooldown: Some(8) },
+    ItemInfo { class: "aghanims_shard", name: "Aghanim's Shard", cost: 1400, components: &[], active: false, consumable: false, cooldown: None },
+    ItemInfo { class: "glimmer_cape", name: "Glimmer Cape", cost: 1950, components: &["shadow_amulet", "cloak"], active: true, consumable: false, cooldown: Some(14) },
+    ItemInfo { class: "lesser_crit", name: "Crystalys", cost: 1950, components: &["broadsword", "blades_of_attack"], active: false, consumable: false, cooldown: None },
+    ItemInfo { class: "yasha", name: "Yasha", cost: 2050, components: &["blade_of_alacrity", "boots_of_elves"], active: false, consumable: false, cooldown: None },
+    ItemInfo { class: "sange", name: "Sange", cost: 2050, components: &["ogre_axe", "belt_of_strength"], active: false, consumable: false, cooldown: None },
+    ItemInfo { class: "kaya", name: "Kaya", cost: 2050, components: &["staff_of_wizardry", "robe"], active: false, consumable: false, cooldown: None },
+    ItemInfo { class: "blade_mail", name: "Blade Mail", cost: 2100, components: &["broadsword", "chainmail"], active: true, consumable: false, cooldown: Some(25) },
+    ItemInfo { class: "force_staff", name: "Force Staff", cost: 2200, components: &["staff_of_wizardry", "fluffy_hat"], active: true, consumable: false, cooldown: Some(19) },
+    ItemInfo { class: "hand_of_midas", name: "Hand of Midas", cost: 2200, components: &["gloves"], active: true, consumable: false, cooldown: Some(90) },
+    ItemInfo { class: "travel_boots", name: "Boots of Travel", cost: 2500, components: &["boots"], active: true, consumable: false, cooldown: Some(45) },
+    ItemInfo { class: "invis_sword", name: "Shadow Blade", cost: 3000, components: &["shadow_amulet", "claymore"], active: true, consumable: false, cooldown: Some(28) },
+    ItemInfo { class: "desolator", name: "Desolator", cost: 3500, components: &["mithril_hammer", "mithril_hammer", "blight_stone"], active: false, consumable: false, cooldown: None },
+    ItemInfo { class: "black_king_bar", name: "Black King Bar", cost: 4050, components: &["ogre_axe", "mithril_hammer"], active: true, consumable: false, cooldown: Some(95) },
+    ItemInfo { class: "moon_shard", name: "Moon Shard", cost: 4000, components: &["hyperstone", "hyperstone"], active: true, consumable: false, cooldown: None },
+    ItemInfo { class: "sange_and_yasha", name: "Sange and Yasha", cost: 4100, components: &["sange", "yasha"], active: false, consumable: false, cooldown: None },
+    ItemInfo { class: "ultimate_scepter", name: "Aghanim's Scepter", cost: 4200, components: &["point_booster", "staff_of_wizardry", "ogre_axe", "blade_of_alacrity"], active: false, consumable: false, cooldown: None },
+    ItemInfo { class: "manta", name: "Manta Style", cost: 4650, components: &["yasha", "ultimate_orb"], active: true, consumable: false, cooldown: Some(45) },
+    ItemInfo { class: "greater_crit", name: "Daedalus", cost: 5100, components: &["lesser_crit", "demon_edge"], active: false, consumable: false, cooldown: None },];
+
+// What players call items in chat, lowercase, by class
+pub const ALIASES: &[(&str, &str)] = &[
+    ("bkb", "black_king_bar"),
+    ("aghs", "ultimate_scepter"),
+    ("aghanims", "ultimate_scepter"),
+    ("scepter", "ultimate_scepter"),
+    ("shard", "aghanims_shard"),
+    ("treads", "power_treads"),
+    ("phase", "phase_boots"),
+    ("arcanes", "arcane_boots"),
+    ("tranqs", "tranquil_boots"),
+    ("travels", "travel_boots"),
+    ("bots", "travel_boots"),
+    ("midas", "hand_of_midas"),
+    ("deso", "desolator"),
+    ("sny", "sange_and_yasha"),
+    ("s and y", "sange_and_yasha"),
+    ("crit", "greater_crit"),
+    ("glimmer", "glimmer_cape"),
+    ("force", "force_staff"),
+    ("blademail", "blade_mail"),
+    ("sb", "invis_sword"),
+    ("wand", "magic_wand"),
+    ("stick", "magic_stick"),
+    ("tp", "tpscroll"),
+    ("smoke", "smoke_of_deceit"),
+    ("sentry", "ward_sentry"),
+    ("sentries", "ward_sentry"),
+    ("obs", "ward_observer"),
+    ("salve", "flask"),
+    ("moon", "moon_shard"),
+];
+
+// Accepts both "item_blink" and "blink"
+pub fn by_class(name: &str) -> Option<&'static ItemInfo> {
+    let class = name.strip_prefix("item_").unwrap_or(name);
+    ITEMS.iter().find(|i| i.class == class)
+}
+
+// Whether anything in the table is built from `class`, which makes it a component rather than part of a build
+pub fn is_component(class: &str) -> bool {
+    ITEMS.iter().any(|i| i.components.contains(&class))
+}
+
+// Gold value of a set of items given by internal name. Unknown and empty slots count as zero.
+pub fn value_of<'a>(names: impl IntoIterator<Item = &'a str>) -> i32 {
+    names.into_iter()
+        .filter_map(by_class)
+        .map(|i| i.cost)
+        .sum()
+}
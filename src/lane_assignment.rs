@@ -0,0 +1,120 @@
+// src/lane_assignment.rs
+use std::collections::HashMap;
+
+use crate::briefing::Lane;
+use crate::missing::MapSide;
+use crate::regions;
+use crate::state::Team;
+
+// Sightings after this clock time are past the laning stage
+pub const LANING_UNTIL: i32 = 600;
+
+// Sightings needed before guessing, and the share one lane needs to count as theirs
+const MIN_SIGHTINGS: u32 = 5;
+const LANE_SHARE: f32 = 0.6;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Assignment {
+    Lane(Lane),
+    Roaming,
+}
+
+impl Assignment {
+    // e.g. "offlaner"
+    pub fn role(&self) -> &'static str {
+        match self {
+            Assignment::Lane(Lane::Safe) => "safe laner",
+            Assignment::Lane(Lane::Mid) => "mid",
+            Assignment::Lane(Lane::Off) => "offlaner",
+            Assignment::Roaming => "roamer",
+        }
+    }
+}
+
+// The lane a team plays on one side of the map
+pub fn lane_of(side: MapSide, team: Team) -> Lane {
+    match (side, team) {
+        (MapSide::Mid, _) => Lane::Mid,
+        (MapSide::Bottom, Team::Radiant) | (MapSide::Top, Team::Dire) => Lane::Safe,
+        (MapSide::Top, Team::Radiant) | (MapSide::Bottom, Team::Dire) => Lane::Off,
+    }
+}
+
+// Jungle, river and the landmarks in between are not a lane
+pub fn between_lanes(position: (i32, i32)) -> bool {
+    regions::locate(position)
+        .map(|r| ["jungle", "river", "Roshan", "triangle", "outpost"].iter().any(|w| r.name.contains(w)))
+        .unwrap_or(false)
+}
+
+#[derive(Clone, Debug, Default)]
+struct Sightings {
+    sides: HashMap<MapSide, u32>,
+    roaming: u32,
+}
+
+// Counts where each enemy is seen in the first ten minutes to guess their lane
+pub struct LaneTracker {
+    team: Option<Team>,
+    heroes: HashMap<String, Sightings>,
+}
+
+impl LaneTracker {
+    pub fn new() -> Self {
+        LaneTracker {
+            team: None,
+            heroes: HashMap::new(),
+        }
+    }
+
+    // One minimap sighting of an enemy on `enemy_team`
+    pub fn record(&mut self, hero: &str, position: (i32, i32), enemy_team: Team, clock_time: i32) {
+        if !(0..LANING_UNTIL).contains(&clock_time) {
+            return;
+        }
+        self.team = Some(enemy_team);
+        let sightings = self.heroes.entry(hero.to_string()).or_default();
+        if between_lanes(position) {
+            sightings.roaming += 1;
+        } else {
+            *sightings.sides.entry(MapSide::of(position)).or_default() += 1;
+        }
+    }
+
+    pub fn assignment(&self, hero: &str) -> Option<Assignment> {
+        let team = self.team?;
+        let sightings = self.heroes.get(hero)?;
+        let total = sightings.roaming + sightings.sides.values().sum::<u32>();
+        if total < MIN_SIGHTINGS {
+            return None;
+        }
+
+        let (side, count) = sightings.sides.iter().max_by_key(|(_, count)| **count)?;
+        if *count as f32 / total as f32 >= LANE_SHARE {
+            Some(Assignment::Lane(lane_of(*side, team)))
+        } else {
+            Some(Assignment::Roaming)
+        }
+    }
+
+    // Every enemy with enough sightings, safe lane first
+    pub fn assignments(&self) -> Vec<(String, Assignment)> {
+        let order = |a: &Assignment| match a {
+            Assignment::Lane(Lane::Safe) => 0,
+            Assignment::Lane(Lane::Mid) => 1,
+            Assignment::Lane(Lane::Off) => 2,
+            Assignment::Roaming => 3,
+        };
+        let mut assignments: Vec<(String, Assignment)> = self.heroes.keys()
+            .filter_map(|hero| self.assignment(hero).map(|a| (hero.clone(), a)))
+            .collect();
+        assignments.sort_by(|a, b| order(&a.1).cmp(&order(&b.1)).then(a.0.cmp(&b.0)));
+        assignments
+    }
+}
+
+impl Default for LaneTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,85 @@
+// src/lib.rs
+// Game state parsing, enemy tracking and coaching logic shared by the frontends
+
+pub mod abilities;
+pub mod alerts;
+pub mod analysis;
+pub mod audio;
+pub mod barracks;
+pub mod base_race;
+pub mod baselines;
+pub mod benchmarks;
+pub mod briefing;
+pub mod buildings;
+pub mod buyback;
+pub mod cache;
+pub mod camps;
+pub mod coach;
+pub mod config;
+pub mod consumables;
+pub mod counters;
+pub mod couriers;
+pub mod diff;
+pub mod draft;
+pub mod estimates;
+pub mod events;
+pub mod export;
+pub mod farming;
+pub mod feed;
+pub mod format;
+pub mod game_mode;
+pub mod gold;
+pub mod heatmap;
+pub mod high_ground;
+pub mod heroes;
+pub mod history;
+pub mod inventory;
+pub mod item_build;
+pub mod items;
+pub mod lane_assignment;
+pub mod llm;
+pub mod map_control;
+pub mod missing;
+pub mod mqtt;
+pub mod notify;
+pub mod objectives;
+pub mod outposts;
+pub mod opendota;
+pub mod phase;
+pub mod plugins;
+pub mod power_spikes;
+pub mod presence;
+pub mod questions;
+pub mod regions;
+pub mod respawns;
+pub mod review;
+pub mod roles;
+pub mod roshan;
+pub mod roshan_contest;
+pub mod rotations;
+pub mod rules;
+pub mod scoreboard;
+pub mod series;
+pub mod server;
+pub mod skill_build;
+pub mod smoke;
+pub mod snapshot;
+pub mod state;
+pub mod steam;
+pub mod stratz;
+pub mod streaks;
+pub mod team_cooldowns;
+pub mod teleport;
+pub mod terminal;
+pub mod tilt;
+pub mod timers;
+pub mod tracking;
+pub mod twitch;
+pub mod vod;
+pub mod wards;
+pub mod waves;
+pub mod webhook;
+
+pub use coach::Coach;
+pub use config::Config;
+pub use state::GameState;
@@ -0,0 +1,169 @@
+// src/llm.rs
+use std::time::Duration;
+
+use serde_json::{json, Value};
+
+use crate::alerts::strip_colors;
+use crate::buildings::{self, BuildingKind};
+use crate::coach::Coach;
+use crate::config::LlmConfig;
+use crate::format::{format_game_time, format_hero_name, format_item_name};
+use crate::missing;
+use crate::roshan::RoshanStatus;
+use crate::state::Team;
+
+// Local models can be slow to answer; the coach carries on without the tip
+const TIMEOUT: Duration = Duration::from_secs(60);
+
+// Tips are never asked for more often than this, whatever the config says
+pub const MIN_INTERVAL: u64 = 120;
+
+// Active alerts passed along as context
+const PROMPT_ALERTS: usize = 3;
+
+const SYSTEM_PROMPT: &str = "You are a Dota 2 coach watching a live game. Reply with one concise, \
+    concrete strategic tip for the player for the next few minutes, in at most two short sentences. \
+    No preamble, no lists.";
+
+const QUESTION_PROMPT: &str = "You are a Dota 2 coach watching a live game. Answer the player's question \
+    for this game in at most three short sentences, using the game state and facts given. No preamble, no lists.";
+
+// What the coach knows about the game right now, as plain lines for the model
+pub fn prompt(coach: &Coach) -> Option<String> {
+    let state = coach.game_state()?;
+    let clock_time = state.clock_time()?;
+    let own_team = state.player_team().and_then(|t| Team::from_name(&t));
+    let mut lines = vec![format!("Clock: {}", format_game_time(Some(clock_time)))];
+
+    if let (Some(hero), Some(player)) = (state.hero(), state.player()) {
+        lines.push(format!("Player: {} on {}, level {}, {}/{}/{}, {} net worth, {} GPM, {} XPM, {} last hits",
+            hero.name.as_deref().map(format_hero_name).unwrap_or_else(|| "unknown hero".to_string()),
+            own_team.map(|t| t.name()).unwrap_or("unknown team"),
+            hero.level.unwrap_or(0),
+            player.kills.unwrap_or(0), player.deaths.unwrap_or(0), player.assists.unwrap_or(0),
+            player.net_worth.unwrap_or(0), player.gpm.unwrap_or(0), player.xpm.unwrap_or(0), player.last_hits.unwrap_or(0)));
+    }
+    if let Some(inventory) = state.inventory() {
+        let items: Vec<String> = inventory.main_items().filter_map(|item| item.name.as_deref()).map(format_item_name).collect();
+        if !items.is_empty() {
+            lines.push(format!("Items: {}", items.join(", ")));
+        }
+    }
+
+    // The draft when there was one, else the enemies seen so far
+    let picks = |team: Team| coach.draft()
+        .and_then(|d| d.team(team))
+        .map(|side| side.picks().iter().map(|s| s.hero_name()).collect::<Vec<_>>())
+        .unwrap_or_default();
+    if Team::BOTH.iter().any(|&team| !picks(team).is_empty()) {
+        for team in Team::BOTH {
+            lines.push(format!("{} heroes: {}", team.name(), picks(team).join(", ")));
+        }
+    } else if !coach.enemy_team_heroes().is_empty() {
+        lines.push(format!("Enemy heroes: {}", coach.enemy_team_heroes().join(", ")));
+    }
+
+    for team in Team::BOTH {
+        let standing = buildings::team_buildings(state, team);
+        let towers = standing.iter().filter(|b| matches!(b.kind, BuildingKind::Tower(_))).count();
+        let barracks = standing.iter().filter(|b| matches!(b.kind, BuildingKind::MeleeBarracks | BuildingKind::RangedBarracks)).count();
+        if !standing.is_empty() {
+            lines.push(format!("{} buildings standing: {} towers, {} barracks", team.name(), towers, barracks));
+        }
+    }
+    lines.push(match coach.roshan().status(clock_time) {
+        RoshanStatus::Alive => format!("Roshan: alive, killed {} times", coach.roshan().kills()),
+        RoshanStatus::Dead { respawn_from, respawn_by } => format!("Roshan: dead, back between {} and {}",
+            format_game_time(Some(respawn_from)), format_game_time(Some(respawn_by))),
+        RoshanStatus::MaybeUp { respawn_by } => format!("Roshan: may be up, certainly by {}", format_game_time(Some(respawn_by))),
+    });
+    if let Some(aegis) = coach.roshan().aegis(clock_time) {
+        lines.push(format!("Aegis: held by {} until {}", aegis.team.map(|t| t.name()).unwrap_or("a hero"),
+            format_game_time(Some(aegis.expires_at()))));
+    }
+
+    if let Some(report) = coach.performance().report().filter(|r| !r.deaths.is_empty()) {
+        let times: Vec<String> = report.deaths.iter().map(|t| format_game_time(Some(*t))).collect();
+        lines.push(format!("Player died around: {}", times.join(", ")));
+    }
+    let missing = missing::missing_enemies(coach.enemies(), coach.game_time(), coach.config().thresholds.missing_after_seconds);
+    if !missing.is_empty() {
+        let names: Vec<&str> = missing.iter().map(|m| m.name.as_str()).collect();
+        lines.push(format!("Enemies not seen recently: {}", names.join(", ")));
+    }
+    for alert in coach.alerts().active_sorted().iter().take(PROMPT_ALERTS) {
+        lines.push(format!("Coach alert: {}", strip_colors(&alert.message)));
+    }
+    Some(lines.join("\n"))
+}
+
+// Talks to any OpenAI-compatible chat completions API: OpenAI itself, or a local
+// server such as Ollama, llama.cpp or LM Studio
+#[derive(Clone)]
+pub struct LlmClient {
+    http: reqwest::Client,
+    url: String,
+    api_key: Option<String>,
+    model: String,
+    max_tokens: u32,
+}
+
+impl LlmClient {
+    pub fn new(config: &LlmConfig) -> Result<Self, String> {
+        let endpoint = config.endpoint.trim().trim_end_matches('/');
+        if endpoint.is_empty() {
+            return Err("Coaching tips need an endpoint in the [llm] section".to_string());
+        }
+        let http = reqwest::Client::builder()
+            .timeout(TIMEOUT)
+            .user_agent(concat!("dota2-coach/", env!("CARGO_PKG_VERSION")))
+            .build()
+            .map_err(|e| format!("Could not set up the LLM client: {}", e))?;
+        Ok(LlmClient {
+            http,
+            url: format!("{}/chat/completions", endpoint),
+            api_key: config.api_key.clone().filter(|k| !k.trim().is_empty()),
+            model: config.model.clone(),
+            max_tokens: config.max_tokens,
+        })
+    }
+
+    // One tip for the situation `prompt` describes
+    pub async fn tip(&self, prompt: &str) -> Result<String, String> {
+        self.complete(SYSTEM_PROMPT, prompt, self.max_tokens).await
+            .map_err(|e| format!("Coaching tip: {}", e))
+    }
+
+    // An answer to the player's question, given what the coach knows in `context`
+    pub async fn answer(&self, context: &str, question: &str) -> Result<String, String> {
+        let prompt = format!("{}\n\nQuestion: {}", context, question);
+        // Answers run to three sentences where tips run to two
+        self.complete(QUESTION_PROMPT, &prompt, self.max_tokens.saturating_mul(2)).await
+            .map_err(|e| format!("Answering the question: {}", e))
+    }
+
+    async fn complete(&self, system: &str, prompt: &str, max_tokens: u32) -> Result<String, String> {
+        let mut request = self.http.post(&self.url).json(&json!({
+            "model": self.model,
+            "max_tokens": max_tokens,
+            "temperature": 0.4,
+            "messages": [
+                { "role": "system", "content": system },
+                { "role": "user", "content": prompt },
+            ],
+        }));
+        if let Some(key) = &self.api_key {
+            request = request.bearer_auth(key);
+        }
+        let response: Value = request.send().await
+            .and_then(|r| r.error_for_status())
+            .map_err(|e| format!("request failed: {}", e.without_url()))?
+            .json().await
+            .map_err(|e| format!("unexpected response: {}", e.without_url()))?;
+
+        response.pointer("/choices/0/message/content").and_then(Value::as_str)
+            .map(|text| text.split_whitespace().collect::<Vec<_>>().join(" "))
+            .filter(|text| !text.is_empty())
+            .ok_or_else(|| "the model sent no text".to_string())
+    }
+}
@@ -0,0 +1,78 @@
+// src/map_control.rs
+use crate::buildings::{self, BuildingKind, BuildingStatus};
+use crate::state::{GameState, Team};
+
+// How much losing a building opens up the map. Deeper towers and barracks matter more.
+fn weight(kind: BuildingKind) -> i32 {
+    match kind {
+        BuildingKind::Tower(1) => 1,
+        BuildingKind::Tower(2) => 2,
+        BuildingKind::Tower(3) => 3,
+        BuildingKind::Tower(_) => 2,
+        BuildingKind::MeleeBarracks => 3,
+        BuildingKind::RangedBarracks => 2,
+        BuildingKind::Ancient | BuildingKind::Other => 0,
+    }
+}
+
+// Weight of a full set of one team's towers and barracks: 3 lanes of T1-T3 and rax, plus two T4s
+const FULL_BASE_WEIGHT: i32 = 3 * (1 + 2 + 3) + 2 * 2 + 3 * (3 + 2);
+
+#[derive(Clone, Debug)]
+pub struct MapControl {
+    pub own_towers: usize,
+    pub enemy_towers: usize,
+    pub own_barracks: usize,
+    pub enemy_barracks: usize,
+    // -100 (enemy owns the map) to 100 (you do)
+    pub score: i32,
+    pub verdict: &'static str,
+    pub tip: Option<&'static str>,
+}
+
+fn count(buildings: &[BuildingStatus], barracks: bool) -> usize {
+    buildings.iter()
+        .filter(|b| match b.kind {
+            BuildingKind::Tower(_) => !barracks,
+            BuildingKind::MeleeBarracks | BuildingKind::RangedBarracks => barracks,
+            _ => false,
+        })
+        .count()
+}
+
+// Map control from the player's point of view, or None without building data
+pub fn analyze(state: &GameState, team: Team) -> Option<MapControl> {
+    state.buildings.as_ref()?;
+
+    let enemy_team = if team == Team::Radiant { Team::Dire } else { Team::Radiant };
+    let own = buildings::team_buildings(state, team);
+    let enemy = buildings::team_buildings(state, enemy_team);
+
+    let own_weight: i32 = own.iter().map(|b| weight(b.kind)).sum();
+    let enemy_weight: i32 = enemy.iter().map(|b| weight(b.kind)).sum();
+    let score = (own_weight - enemy_weight) * 100 / FULL_BASE_WEIGHT;
+
+    let verdict = match score {
+        s if s >= 25 => "Strong map control advantage. Consider aggressive warding.",
+        s if s > 0 => "Slight map control advantage. Maintain pressure.",
+        0 => "Even map control. Focus on objectives.",
+        s if s > -25 => "Losing map control. Defend remaining towers.",
+        _ => "Significant map control disadvantage. Play defensively.",
+    };
+
+    let tip = match score {
+        s if s < 0 => Some("Smoke up and pick off heroes who push out past their towers."),
+        s if s > 0 => Some("Use your map control to take Roshan and invade their jungle."),
+        _ => None,
+    };
+
+    Some(MapControl {
+        own_towers: count(&own, false),
+        enemy_towers: count(&enemy, false),
+        own_barracks: count(&own, true),
+        enemy_barracks: count(&enemy, true),
+        score,
+        verdict,
+        tip,
+    })
+}
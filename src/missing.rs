@@ -0,0 +1,88 @@
+// src/missing.rs
+use std::collections::HashMap;
+
+use crate::config::Thresholds;
+use crate::tracking::EnemyHeroState;
+
+// How far past the river (along the diagonal) the player must be to count as pushed up
+pub const RIVER_MARGIN: i32 = 1000;
+
+// Rough part of the map a position belongs to, split along the top-left to bottom-right lanes
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum MapSide {
+    Top,
+    Mid,
+    Bottom,
+}
+
+impl MapSide {
+    pub fn of(position: (i32, i32)) -> Self {
+        let offset = position.1 - position.0;
+        if offset > 2500 {
+            MapSide::Top
+        } else if offset < -2500 {
+            MapSide::Bottom
+        } else {
+            MapSide::Mid
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            MapSide::Top => "top",
+            MapSide::Mid => "mid",
+            MapSide::Bottom => "bottom",
+        }
+    }
+}
+
+// Whether a position is on the enemy's side of the river.
+// The river runs from the top-left to the bottom-right, so x + y measures which side you're on.
+pub fn is_past_river(position: (i32, i32), player_is_radiant: bool) -> bool {
+    let diagonal = position.0 + position.1;
+    if player_is_radiant {
+        diagonal > RIVER_MARGIN
+    } else {
+        diagonal < -RIVER_MARGIN
+    }
+}
+
+// An enemy that has not been seen for a while
+#[derive(Clone, Debug)]
+pub struct MissingEnemy {
+    pub name: String,
+    pub seconds_unseen: i32,
+    pub last_seen_side: MapSide,
+}
+
+// Enemies unseen for at least `after_seconds`, longest-unseen first
+pub fn missing_enemies(enemies: &HashMap<String, EnemyHeroState>, game_time: i32, after_seconds: i32) -> Vec<MissingEnemy> {
+    let mut missing: Vec<MissingEnemy> = enemies.values()
+        .filter(|e| game_time - e.last_seen_time >= after_seconds)
+        .map(|e| MissingEnemy {
+            name: e.name.clone(),
+            seconds_unseen: game_time - e.last_seen_time,
+            last_seen_side: MapSide::of(e.last_seen_position),
+        })
+        .collect();
+
+    missing.sort_by_key(|m| -m.seconds_unseen);
+    missing
+}
+
+// Returns the missing enemies when the player should be warned about them right now
+pub fn check_missing_enemies(
+    game_time: i32,
+    enemies: &HashMap<String, EnemyHeroState>,
+    player_position: (i32, i32),
+    player_is_radiant: bool,
+    thresholds: &Thresholds,
+) -> Option<Vec<MissingEnemy>> {
+    let missing = missing_enemies(enemies, game_time, thresholds.missing_after_seconds);
+
+    if missing.len() < thresholds.missing_enemy_count || !is_past_river(player_position, player_is_radiant) {
+        return None;
+    }
+
+    Some(missing)
+}
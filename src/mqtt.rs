@@ -0,0 +1,108 @@
+// src/mqtt.rs
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use rumqttc::{AsyncClient, Event, LastWill, MqttOptions, Packet, QoS};
+use serde_json::Value;
+
+use crate::alerts::{Alert, AlertCategory, AlertSink};
+use crate::config::MqttConfig;
+
+// Messages waiting for the broker before new ones are dropped
+const QUEUE: usize = 64;
+
+// Wait before reconnecting to a broker that went away
+const RECONNECT: Duration = Duration::from_secs(5);
+
+// Broker packets can be this big; the respawn timers get long in teamfights
+const MAX_PACKET: usize = 64 * 1024;
+
+// Publishes alerts to <prefix>/alerts/<category> and each of the /state timers, retained,
+// to <prefix>/timers/<name>, for smart lights and home automation. <prefix>/status says
+// whether the coach is connected.
+#[derive(Clone)]
+pub struct Mqtt {
+    client: AsyncClient,
+    prefix: String,
+    categories: Vec<AlertCategory>,
+    // Last payload sent per topic, so unchanged timers aren't sent again
+    published: Arc<Mutex<HashMap<String, String>>>,
+}
+
+impl Mqtt {
+    // Connects in the background and keeps reconnecting. Must be called from within the Tokio runtime.
+    pub fn connect(config: &MqttConfig) -> Result<Self, String> {
+        if config.host.trim().is_empty() {
+            return Err("MQTT needs a broker host in the [mqtt] section".to_string());
+        }
+        if config.client_id.trim().is_empty() {
+            return Err("MQTT needs a client_id in the [mqtt] section".to_string());
+        }
+        let prefix = config.topic_prefix.trim_end_matches('/').to_string();
+        let status = format!("{}/status", prefix);
+
+        let mut options = MqttOptions::new(config.client_id.trim(), config.host.trim(), config.port);
+        options.set_max_packet_size(MAX_PACKET, MAX_PACKET);
+        options.set_last_will(LastWill::new(&status, "offline", QoS::AtLeastOnce, true));
+        if let Some(username) = &config.username {
+            options.set_credentials(username, config.password.clone().unwrap_or_default());
+        }
+        let (client, mut events) = AsyncClient::new(options, QUEUE);
+
+        let online = client.clone();
+        let published = Arc::new(Mutex::new(HashMap::new()));
+        let forget = published.clone();
+        let broker = format!("{}:{}", config.host.trim(), config.port);
+        tokio::spawn(async move {
+            // Only the first of a run of failures is reported
+            let mut failing = false;
+            loop {
+                match events.poll().await {
+                    Ok(Event::Incoming(Packet::ConnAck(_))) => {
+                        failing = false;
+                        let _ = online.try_publish(status.as_str(), QoS::AtLeastOnce, true, "online");
+                        // A new session may be with a broker that lost the retained timers
+                        forget.lock().unwrap().clear();
+                    },
+                    Ok(_) => {},
+                    Err(e) => {
+                        if !failing {
+                            eprintln!("MQTT broker {}: {} - retrying", broker, e);
+                            failing = true;
+                        }
+                        tokio::time::sleep(RECONNECT).await;
+                    },
+                }
+            }
+        });
+
+        Ok(Mqtt { client, prefix, categories: config.categories.clone(), published })
+    }
+
+    // Publish the timers of a /state snapshot that changed since the last one
+    pub fn publish_timers(&self, snapshot: &Value) {
+        let Some(timers) = snapshot.get("timers").and_then(Value::as_object) else { return };
+        let mut published = self.published.lock().unwrap();
+        for (name, value) in timers {
+            let topic = format!("{}/timers/{}", self.prefix, name);
+            let payload = value.to_string();
+            if published.get(&topic) == Some(&payload) {
+                continue;
+            }
+            // A full queue means the broker is away; the timer goes out again next time
+            if self.client.try_publish(topic.as_str(), QoS::AtMostOnce, true, payload.clone()).is_ok() {
+                published.insert(topic, payload);
+            }
+        }
+    }
+}
+
+impl AlertSink for Mqtt {
+    fn notify(&self, alert: &Alert) {
+        if self.categories.contains(&alert.category) {
+            let topic = format!("{}/alerts/{}", self.prefix, alert.category.slug());
+            let _ = self.client.try_publish(topic, QoS::AtLeastOnce, false, alert.to_json().to_string());
+        }
+    }
+}
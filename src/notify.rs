@@ -0,0 +1,40 @@
+// src/notify.rs
+use std::io::Write;
+use std::thread;
+
+use notify_rust::Notification;
+
+use crate::alerts::{strip_colors, Alert, AlertSink, Severity};
+
+// Rings the terminal bell for critical alerts
+pub struct TerminalBell;
+
+impl AlertSink for TerminalBell {
+    fn notify(&self, alert: &Alert) {
+        if alert.severity == Severity::Critical {
+            print!("\x07");
+            let _ = std::io::stdout().flush();
+        }
+    }
+}
+
+// Pops up an OS notification for critical alerts, for players with the coach on another screen
+pub struct DesktopNotifier;
+
+impl AlertSink for DesktopNotifier {
+    fn notify(&self, alert: &Alert) {
+        if alert.severity != Severity::Critical {
+            return;
+        }
+
+        let summary = format!("Dota 2 Coach: {}", alert.category.name());
+        let body = strip_colors(&alert.message);
+
+        // Showing a notification can block on the notification daemon, so don't hold up the caller
+        thread::spawn(move || {
+            if let Err(e) = Notification::new().summary(&summary).body(&body).show() {
+                eprintln!("Could not show desktop notification: {}", e);
+            }
+        });
+    }
+}
@@ -0,0 +1,123 @@
+// src/objectives.rs
+use std::collections::HashMap;
+
+use crate::buildings::{BuildingKind, BuildingLane, BuildingStatus};
+use crate::missing::MapSide;
+use crate::tracking::EnemyHeroState;
+
+// No pushing advice while everyone is still laning
+pub const SUGGESTIONS_FROM: i32 = 5 * 60;
+
+// How recently an enemy must have been seen to count as showing on a lane
+const SHOWED_WITHIN: i32 = 15;
+
+// Enemies that must show elsewhere before a push is called safe
+const ENEMIES_ELSEWHERE: usize = 3;
+
+// A building already this damaged is worth finishing even without extra information
+const FINISH_PERCENT: i32 = 50;
+
+#[derive(Clone, Debug)]
+pub struct ObjectiveSuggestion {
+    pub target: BuildingStatus,
+    // Enemies seen recently on other lanes, and where
+    pub enemies_elsewhere: usize,
+    pub elsewhere_side: Option<MapSide>,
+}
+
+impl ObjectiveSuggestion {
+    pub fn describe(&self) -> String {
+        let lane = self.target.lane.name();
+        let health = self.target.health_percent();
+        let exposed = if health < 100 {
+            format!("{} is exposed ({}% health)", self.target.describe(), health)
+        } else {
+            format!("{} is exposed", self.target.describe())
+        };
+
+        match self.elsewhere_side {
+            Some(side) => format!("{} and {} enemies showed {} - push {} now", exposed, self.enemies_elsewhere, side.name(), lane),
+            None => format!("{} - finish it off", exposed),
+        }
+    }
+}
+
+pub fn lane_side(lane: BuildingLane) -> Option<MapSide> {
+    match lane {
+        BuildingLane::Top => Some(MapSide::Top),
+        BuildingLane::Mid => Some(MapSide::Mid),
+        BuildingLane::Bottom => Some(MapSide::Bottom),
+        BuildingLane::Base => None,
+    }
+}
+
+// How much taking a building is worth; deeper buildings open up more of the map
+fn value(kind: BuildingKind) -> i32 {
+    match kind {
+        BuildingKind::Tower(tier) => tier as i32,
+        BuildingKind::RangedBarracks | BuildingKind::MeleeBarracks => 4,
+        BuildingKind::Ancient => 6,
+        BuildingKind::Other => 0,
+    }
+}
+
+// The building the team should hit next, or None if nothing stands out.
+// Candidates are the outermost standing building in each lane.
+pub fn suggest(
+    game_time: i32,
+    enemy_buildings: &[BuildingStatus],
+    enemies: &HashMap<String, EnemyHeroState>,
+) -> Option<ObjectiveSuggestion> {
+    if game_time < SUGGESTIONS_FROM {
+        return None;
+    }
+
+    // Where the enemy team has shown itself recently
+    let mut showed: HashMap<MapSide, usize> = HashMap::new();
+    for enemy in enemies.values().filter(|e| game_time - e.last_seen_time <= SHOWED_WITHIN) {
+        *showed.entry(MapSide::of(enemy.last_seen_position)).or_default() += 1;
+    }
+
+    // Base buildings only become targets once a lane is open all the way
+    let lane_open = [BuildingLane::Top, BuildingLane::Mid, BuildingLane::Bottom].iter()
+        .any(|lane| !enemy_buildings.iter().any(|b| b.lane == *lane && b.kind != BuildingKind::Other));
+    let lanes: &[BuildingLane] = if lane_open { &BuildingLane::ALL } else { &BuildingLane::ALL[..3] };
+
+    let mut best: Option<(i32, ObjectiveSuggestion)> = None;
+    for &lane in lanes {
+        let Some(target) = enemy_buildings.iter()
+            .filter(|b| b.lane == lane && b.kind != BuildingKind::Other)
+            .min_by_key(|b| b.kind) else { continue };
+        let side = lane_side(lane);
+
+        // Enemies seen on the same lane make the push a fight, not an objective
+        let defenders = side.and_then(|s| showed.get(&s)).copied().unwrap_or(0);
+        let elsewhere: Vec<(MapSide, usize)> = showed.iter()
+            .filter(|(s, _)| Some(**s) != side)
+            .map(|(s, n)| (*s, *n))
+            .collect();
+        let enemies_elsewhere: usize = elsewhere.iter().map(|(_, n)| n).sum();
+        let elsewhere_side = elsewhere.iter().max_by_key(|(_, n)| *n).map(|(s, _)| *s);
+
+        let health = target.health_percent();
+        let safe_push = enemies_elsewhere >= ENEMIES_ELSEWHERE && defenders == 0;
+        if !safe_push && health > FINISH_PERCENT {
+            continue;
+        }
+
+        let score = value(target.kind) * 10 + (100 - health) / 5
+            + if safe_push { 20 } else { 0 }
+            - defenders as i32 * 15;
+        let suggestion = ObjectiveSuggestion {
+            target: target.clone(),
+            enemies_elsewhere,
+            elsewhere_side: if safe_push { elsewhere_side } else { None },
+        };
+
+        if best.as_ref().map(|(s, _)| score > *s).unwrap_or(true) {
+            best = Some((score, suggestion));
+        }
+    }
+
+    best.map(|(_, suggestion)| suggestion)
+}
@@ -0,0 +1,241 @@
+// src/opendota.rs
+use std::path::PathBuf;
+use std::time::Duration;
+
+use serde::Deserialize;
+
+use crate::cache::DiskCache;
+use crate::config::OpenDotaConfig;
+use crate::steam::STEAM64_BASE;
+
+const API_URL: &str = "https://api.opendota.com/api";
+
+// Requests that take longer than this are given up on; the coach carries on without
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+// Below this percentile the player is falling behind players of the same hero
+pub const BELOW_PAR: f32 = 0.3;
+
+// A player with this many games on a hero, or this share of all their games, is a spammer
+const SPAMMER_GAMES: i32 = 100;
+const SPAMMER_SHARE: i32 = 20;
+
+// OpenDota account ID from a GSI steamid, which is usually a Steam64 ID
+pub fn account_id(steamid: &str) -> Option<u32> {
+    let id: u64 = steamid.trim().parse().ok()?;
+    u32::try_from(id.checked_sub(STEAM64_BASE).unwrap_or(id)).ok().filter(|&id| id > 0)
+}
+
+// Where API responses are cached unless the config says otherwise
+pub fn default_cache_path() -> Option<PathBuf> {
+    dirs::cache_dir().map(|cache| cache.join("dota2-coach").join("opendota"))
+}
+
+#[derive(Clone, Copy, Debug, Deserialize)]
+pub struct Band {
+    // 0.1 for the 10th percentile
+    pub percentile: f32,
+    pub value: f32,
+}
+
+// The percentile bands OpenDota reports for one hero, from recent public matches
+#[derive(Clone, Debug, Deserialize)]
+pub struct HeroBenchmarks {
+    pub hero_id: i32,
+    pub result: BenchmarkBands,
+}
+
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct BenchmarkBands {
+    pub gold_per_min: Vec<Band>,
+    pub xp_per_min: Vec<Band>,
+    pub last_hits_per_min: Vec<Band>,
+}
+
+// Where `value` falls among the bands, interpolated between them. None without bands.
+pub fn percentile_of(bands: &[Band], value: f32) -> Option<f32> {
+    let first = bands.first()?;
+    if value <= first.value {
+        return Some(first.percentile * (value / first.value.max(1.0)).clamp(0.0, 1.0));
+    }
+    for pair in bands.windows(2) {
+        let (low, high) = (pair[0], pair[1]);
+        if value <= high.value {
+            let share = if high.value > low.value { (value - low.value) / (high.value - low.value) } else { 1.0 };
+            return Some(low.percentile + share * (high.percentile - low.percentile));
+        }
+    }
+    bands.last().map(|b| b.percentile)
+}
+
+// Value at the band closest to the median
+pub fn median_of(bands: &[Band]) -> Option<f32> {
+    bands.iter()
+        .min_by(|a, b| (a.percentile - 0.5).abs().total_cmp(&(b.percentile - 0.5).abs()))
+        .map(|b| b.value)
+}
+
+// e.g. "top 10%", "62nd percentile", "bottom 20%"
+pub fn describe_percentile(percentile: f32) -> String {
+    let whole = (percentile * 100.0).round() as i32;
+    if whole >= 90 {
+        format!("top {}%", (100 - whole).max(1))
+    } else if whole <= 20 {
+        format!("bottom {}%", whole.max(1))
+    } else {
+        let suffix = match (whole % 10, whole % 100) {
+            (_, 11..=13) => "th",
+            (1, _) => "st",
+            (2, _) => "nd",
+            (3, _) => "rd",
+            _ => "th",
+        };
+        format!("{}{} percentile", whole, suffix)
+    }
+}
+
+// One of the player's live numbers placed among the hero's bands
+#[derive(Clone, Debug)]
+pub struct Grade {
+    pub metric: &'static str,
+    pub value: f32,
+    pub percentile: f32,
+    pub median: f32,
+}
+
+impl Grade {
+    pub fn below_par(&self) -> bool {
+        self.percentile < BELOW_PAR
+    }
+
+    // e.g. "GPM 480 (62nd percentile, median 455)"
+    pub fn describe(&self) -> String {
+        let format = |v: f32| if v < 20.0 { format!("{:.1}", v) } else { format!("{:.0}", v) };
+        format!("{} {} ({}, median {})", self.metric, format(self.value), describe_percentile(self.percentile), format(self.median))
+    }
+}
+
+impl HeroBenchmarks {
+    // GPM, XPM and last hits per minute against the bands, for those the bands cover
+    pub fn grade(&self, gpm: i32, xpm: i32, last_hits: i32, clock_time: i32) -> Vec<Grade> {
+        let minutes = (clock_time as f32 / 60.0).max(1.0);
+        [
+            ("GPM", &self.result.gold_per_min, gpm as f32),
+            ("XPM", &self.result.xp_per_min, xpm as f32),
+            ("LH/min", &self.result.last_hits_per_min, last_hits as f32 / minutes),
+        ].into_iter()
+            .filter_map(|(metric, bands, value)| Some(Grade {
+                metric,
+                value,
+                percentile: percentile_of(bands, value)?,
+                median: median_of(bands)?,
+            }))
+            .collect()
+    }
+}
+
+// One hero from a player's /heroes list. OpenDota has sent hero_id as both a string and a number.
+#[derive(Clone, Debug, Deserialize)]
+struct HeroRecord {
+    hero_id: serde_json::Value,
+    #[serde(default)]
+    games: i32,
+    #[serde(default)]
+    win: i32,
+}
+
+impl HeroRecord {
+    fn hero_id(&self) -> Option<i32> {
+        match &self.hero_id {
+            serde_json::Value::Number(n) => n.as_i64().map(|n| n as i32),
+            serde_json::Value::String(s) => s.parse().ok(),
+            _ => None,
+        }
+    }
+}
+
+// How a player has done on one hero in their public matches
+#[derive(Clone, Copy, Debug)]
+pub struct PlayerHeroProfile {
+    pub games: i32,
+    pub wins: i32,
+    // Over every hero
+    pub total_games: i32,
+}
+
+impl PlayerHeroProfile {
+    pub fn spammer(&self) -> bool {
+        self.games >= SPAMMER_GAMES || (self.games >= 20 && self.games * 100 >= self.total_games * SPAMMER_SHARE)
+    }
+
+    // e.g. "spammer, 60% WR on this hero (212 games)"
+    pub fn describe(&self) -> String {
+        let Some(win_rate) = (self.wins * 100).checked_div(self.games) else {
+            return format!("first game on this hero ({} games played)", self.total_games);
+        };
+        format!("{}{}% WR on this hero ({} game{})",
+            if self.spammer() { "spammer, " } else { "" },
+            win_rate,
+            self.games,
+            if self.games == 1 { "" } else { "s" })
+    }
+}
+
+// Fetches from the OpenDota API, keeping responses on disk so the same question
+// is asked at most once per `cache_days`
+#[derive(Clone)]
+pub struct OpenDotaClient {
+    http: reqwest::Client,
+    api_key: Option<String>,
+    cache: DiskCache,
+}
+
+impl OpenDotaClient {
+    pub fn new(config: &OpenDotaConfig) -> Result<Self, String> {
+        let http = reqwest::Client::builder()
+            .timeout(TIMEOUT)
+            .user_agent(concat!("dota2-coach/", env!("CARGO_PKG_VERSION")))
+            .build()
+            .map_err(|e| format!("Could not set up the OpenDota client: {}", e))?;
+        Ok(OpenDotaClient {
+            http,
+            api_key: config.api_key.clone(),
+            cache: DiskCache::new(config.cache_path.clone().or_else(default_cache_path),
+                Duration::from_secs(config.cache_days * 24 * 60 * 60)),
+        })
+    }
+
+    pub async fn hero_benchmarks(&self, hero_id: i32) -> Result<HeroBenchmarks, String> {
+        let body = self.cache.get_or_fetch(&format!("benchmarks_{}.json", hero_id), self.fetch(&format!("benchmarks?hero_id={}", hero_id))).await?;
+        serde_json::from_str(&body).map_err(|e| format!("Unexpected OpenDota benchmarks for hero {}: {}", hero_id, e))
+    }
+
+    // How the player behind `account_id` has done on `hero_id`
+    pub async fn player_hero_profile(&self, account_id: u32, hero_id: i32) -> Result<PlayerHeroProfile, String> {
+        let body = self.cache.get_or_fetch(&format!("player_{}_heroes.json", account_id), self.fetch(&format!("players/{}/heroes", account_id))).await?;
+        let records: Vec<HeroRecord> = serde_json::from_str(&body)
+            .map_err(|e| format!("Unexpected OpenDota heroes for player {}: {}", account_id, e))?;
+        let total_games = records.iter().map(|r| r.games).sum();
+        if total_games == 0 {
+            return Err(format!("No public matches for player {}", account_id));
+        }
+        let on_hero = records.iter().find(|r| r.hero_id() == Some(hero_id));
+        Ok(PlayerHeroProfile {
+            games: on_hero.map(|r| r.games).unwrap_or(0),
+            wins: on_hero.map(|r| r.win).unwrap_or(0),
+            total_games,
+        })
+    }
+
+    async fn fetch(&self, endpoint: &str) -> Result<String, String> {
+        let mut request = self.http.get(format!("{}/{}", API_URL, endpoint));
+        if let Some(key) = &self.api_key {
+            request = request.query(&[("api_key", key)]);
+        }
+        let response = request.send().await
+            .and_then(|r| r.error_for_status())
+            .map_err(|e| format!("OpenDota request failed: {}", e))?;
+        response.text().await.map_err(|e| format!("OpenDota request failed: {}", e))
+    }
+}
@@ -0,0 +1,139 @@
+// src/outposts.rs
+use std::collections::HashMap;
+
+use crate::buildings::BuildingLane;
+use crate::format::format_game_time;
+use crate::regions;
+use crate::state::{GameState, MinimapObject, Team};
+
+// Outposts aren't worth walking to while the lanes are still being played
+pub const REMINDERS_FROM: i32 = 10 * 60;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Outpost {
+    Top,
+    Bottom,
+}
+
+impl Outpost {
+    pub const BOTH: [Outpost; 2] = [Outpost::Top, Outpost::Bottom];
+
+    // Same as its map region
+    pub fn name(&self) -> &'static str {
+        match self {
+            Outpost::Top => "top outpost",
+            Outpost::Bottom => "bottom outpost",
+        }
+    }
+
+    pub fn parse(name: &str) -> Option<Outpost> {
+        match name {
+            "top" => Some(Outpost::Top),
+            "bottom" | "bot" => Some(Outpost::Bottom),
+            _ => None,
+        }
+    }
+
+    // The outposts a fallen tower in this lane opens up; mid is between the two
+    pub fn near_lane(lane: BuildingLane) -> Vec<Outpost> {
+        match lane {
+            BuildingLane::Top => vec![Outpost::Top],
+            BuildingLane::Bottom => vec![Outpost::Bottom],
+            BuildingLane::Mid => Outpost::BOTH.to_vec(),
+            BuildingLane::Base => Vec::new(),
+        }
+    }
+
+    fn at(position: (i32, i32)) -> Option<Outpost> {
+        Outpost::BOTH.into_iter().find(|o| regions::locate(position).map(|r| r.name == o.name()).unwrap_or(false))
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Source {
+    // Set from the terminal
+    Marked,
+    // An outpost icon with a team on the minimap
+    Minimap,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Ownership {
+    pub owner: Team,
+    pub since: i32,
+    pub source: Source,
+}
+
+impl Ownership {
+    pub fn describe(&self, team: Option<Team>) -> String {
+        let owner = if Some(self.owner) == team { "yours" } else { "theirs" };
+        format!("{} since {}{}", owner, format_game_time(Some(self.since)),
+            if self.source == Source::Marked { " (marked)" } else { "" })
+    }
+}
+
+// An outpost drawn on the minimap: the outpost unit, or a building icon inside an outpost's area
+fn is_outpost_icon(object: &MinimapObject) -> bool {
+    object.image.contains("outpost")
+        || object.image == "minimap_miscbuilding"
+        || object.name.as_deref().map(|n| n.contains("watch_tower")).unwrap_or(false)
+}
+
+fn team_of(id: i32) -> Option<Team> {
+    match id {
+        2 => Some(Team::Radiant),
+        3 => Some(Team::Dire),
+        _ => None,
+    }
+}
+
+// Who likely holds each outpost. Unknown until the minimap shows it or the player says.
+#[derive(Default)]
+pub struct OutpostTracker {
+    owners: HashMap<Outpost, Ownership>,
+}
+
+impl OutpostTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn owner(&self, outpost: Outpost) -> Option<&Ownership> {
+        self.owners.get(&outpost)
+    }
+
+    // Set the owner by hand, or flip it when none is given (unknown counts as theirs)
+    pub fn mark(&mut self, outpost: Outpost, owner: Option<Team>, team: Team, clock_time: i32) -> Ownership {
+        let enemy = if team == Team::Radiant { Team::Dire } else { Team::Radiant };
+        let owner = owner.unwrap_or(match self.owner(outpost) {
+            Some(held) if held.owner == team => enemy,
+            _ => team,
+        });
+        let ownership = Ownership { owner, since: clock_time, source: Source::Marked };
+        self.owners.insert(outpost, ownership);
+        ownership
+    }
+
+    // Owners of the outposts the minimap shows
+    pub fn update(&mut self, state: &GameState, clock_time: i32) {
+        let seen = state.minimap.iter()
+            .flat_map(|minimap| minimap.values())
+            .filter(|o| is_outpost_icon(o))
+            .filter_map(|o| Some((Outpost::at((o.xpos, o.ypos))?, team_of(o.team)?)));
+        for (outpost, owner) in seen {
+            if self.owner(outpost).map(|held| held.owner != owner).unwrap_or(true) {
+                self.owners.insert(outpost, Ownership { owner, since: clock_time, source: Source::Minimap });
+            }
+        }
+    }
+
+    // What the team should do about an outpost it doesn't hold: "take" one the enemy holds,
+    // "check" one nobody knows about. None when it's the team's.
+    pub fn advice(&self, outpost: Outpost, team: Team) -> Option<&'static str> {
+        match self.owner(outpost) {
+            Some(held) if held.owner == team => None,
+            Some(_) => Some("take"),
+            None => Some("check"),
+        }
+    }
+}
@@ -0,0 +1,52 @@
+// src/phase.rs
+use crate::state::GamePhase;
+
+// A change in the game phase worth reacting to
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PhaseTransition {
+    // Heroes are on the map after drafting (or the coach connected mid-game)
+    GameStarted,
+    // The ancient fell
+    GameEnded,
+    // From the previous phase, if any, to the new one
+    Changed(Option<GamePhase>, GamePhase),
+}
+
+// Watches map.game_state across updates and reports transitions
+pub struct PhaseTracker {
+    current: Option<GamePhase>,
+}
+
+impl PhaseTracker {
+    pub fn new() -> Self {
+        PhaseTracker { current: None }
+    }
+
+    pub fn current(&self) -> Option<GamePhase> {
+        self.current
+    }
+
+    // Feed the latest phase; returns a transition if it differs from the previous one
+    pub fn update(&mut self, phase: GamePhase) -> Option<PhaseTransition> {
+        let previous = self.current.replace(phase);
+        if previous == Some(phase) {
+            return None;
+        }
+
+        let was_playing = previous.map(|p| p.is_playing()).unwrap_or(false);
+        let transition = match phase {
+            GamePhase::PostGame => PhaseTransition::GameEnded,
+            // Pre-game and the horn are one game as far as the coach is concerned
+            _ if phase.is_playing() && !was_playing => PhaseTransition::GameStarted,
+            _ => PhaseTransition::Changed(previous, phase),
+        };
+
+        Some(transition)
+    }
+}
+
+impl Default for PhaseTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,306 @@
+// src/plugins.rs
+use serde::{Deserialize, Serialize};
+
+use crate::alerts::{Alert, AlertCategory, Severity};
+use crate::config::PluginConfig;
+use crate::diff::Change;
+use crate::state::GameState;
+
+// Longest phrase a plugin alert may have read out
+const MAX_SPEECH: usize = 200;
+
+// A coaching module of its own: hears about every new game time and can answer with
+// alerts and a panel of lines for the terminal and overlays
+pub trait Plugin: Send {
+    fn name(&self) -> &str;
+    fn update(&mut self, game_time: i32, state: &GameState, changes: &[Change]) -> Result<PluginReply, String>;
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct PluginReply {
+    pub alerts: Vec<PluginAlert>,
+    // Replaces the plugin's panel; left out, the last one stays
+    pub panel: Option<Panel>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PluginAlert {
+    pub message: String,
+    // Info when left out
+    pub severity: Option<Severity>,
+    // Tells the plugin's alerts apart, so a new one replaces the old rather than stacking
+    pub key: Option<String>,
+    pub speech: Option<String>,
+}
+
+// A few lines a plugin keeps on screen; one without lines is hidden
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Panel {
+    pub title: String,
+    pub lines: Vec<String>,
+}
+
+struct Loaded {
+    plugin: Box<dyn Plugin>,
+    panel: Option<Panel>,
+    // Only the first of a run of failures is reported
+    failing: bool,
+}
+
+// The plugins that were loaded, each with its latest panel
+#[derive(Default)]
+pub struct PluginHost {
+    plugins: Vec<Loaded>,
+}
+
+impl PluginHost {
+    pub fn add(&mut self, plugin: Box<dyn Plugin>) {
+        self.plugins.push(Loaded { plugin, panel: None, failing: false });
+    }
+
+    // Alerts from every plugin for this update
+    pub fn update(&mut self, game_time: i32, state: &GameState, changes: &[Change]) -> Vec<Alert> {
+        let mut alerts = Vec::new();
+        for loaded in &mut self.plugins {
+            let reply = match loaded.plugin.update(game_time, state, changes) {
+                Ok(reply) => reply,
+                Err(e) => {
+                    if !loaded.failing {
+                        eprintln!("Plugin {}: {}", loaded.plugin.name(), e);
+                    }
+                    loaded.failing = true;
+                    continue;
+                },
+            };
+            loaded.failing = false;
+            if let Some(panel) = reply.panel {
+                loaded.panel = Some(panel).filter(|p| !p.lines.is_empty());
+            }
+            for alert in reply.alerts {
+                // Without its own speech, the message is what gets read out
+                let speech = speakable(alert.speech.as_deref().unwrap_or(&alert.message));
+                alerts.push(Alert::new(alert.severity.unwrap_or(Severity::Info), AlertCategory::Plugin, alert.message)
+                    .with_key(format!("{}/{}", loaded.plugin.name(), alert.key.unwrap_or_default()))
+                    .with_speech(speech));
+            }
+        }
+        alerts
+    }
+
+    // Panels on show, by plugin name
+    pub fn panels(&self) -> impl Iterator<Item = (&str, &Panel)> {
+        self.plugins.iter().filter_map(|loaded| Some((loaded.plugin.name(), loaded.panel.as_ref()?)))
+    }
+
+    // Panels belong to the match that produced them
+    pub fn reset(&mut self) {
+        for loaded in &mut self.plugins {
+            loaded.panel = None;
+        }
+    }
+}
+
+// Plugin text as it may be handed to a speech synthesizer: no control or quote characters,
+// capped in length, and nothing that could pass for an option. Empty when nothing is left.
+fn speakable(text: &str) -> String {
+    let cleaned: String = text.chars()
+        .filter(|c| !c.is_control() && !matches!(c, '\'' | '"' | '`' | '\u{2018}'..='\u{201F}'))
+        .take(MAX_SPEECH)
+        .collect();
+    let cleaned = cleaned.trim();
+    if cleaned.starts_with('-') {
+        return String::new();
+    }
+    cleaned.to_string()
+}
+
+// Every .wasm file in the plugin directory, or why it couldn't be loaded
+#[cfg(feature = "plugins")]
+pub fn load(config: &PluginConfig) -> Vec<Result<Box<dyn Plugin>, String>> {
+    let Some(directory) = config.directory() else {
+        return vec![Err("No plugin directory - set one in the [plugins] section".to_string())];
+    };
+    let entries = match std::fs::read_dir(&directory) {
+        Ok(entries) => entries,
+        Err(e) => return vec![Err(format!("Could not read plugins from {}: {}", directory.display(), e))],
+    };
+    let mut paths: Vec<_> = entries.filter_map(|e| e.ok()).map(|e| e.path())
+        .filter(|path| path.extension().map(|ext| ext == "wasm").unwrap_or(false))
+        .collect();
+    paths.sort();
+
+    let engine = match wasm::engine() {
+        Ok(engine) => engine,
+        Err(e) => return vec![Err(e)],
+    };
+    paths.iter()
+        .map(|path| wasm::WasmPlugin::load(&engine, path, config)
+            .map(|plugin| Box::new(plugin) as Box<dyn Plugin>)
+            .map_err(|e| format!("Could not load plugin {}: {}", path.display(), e)))
+        .collect()
+}
+
+#[cfg(not(feature = "plugins"))]
+pub fn load(_config: &PluginConfig) -> Vec<Result<Box<dyn Plugin>, String>> {
+    vec![Err("Plugins are turned on but the coach was built without the `plugins` feature".to_string())]
+}
+
+// Plugins compiled to WebAssembly. A module gets no imports at all, so it can't touch files,
+// the network or the clock; it exports
+//   memory
+//   alloc(len: i32) -> i32            room for the input, which the host writes there
+//   update(ptr: i32, len: i32) -> i64 reads {"game_time", "changes", "state"} as JSON and returns
+//                                     (reply_ptr << 32) | reply_len of a JSON reply, or 0
+#[cfg(feature = "plugins")]
+mod wasm {
+    use std::path::Path;
+
+    use serde_json::json;
+    use wasmtime::{Config, Engine, Instance, Memory, Module, Store, StoreLimits, StoreLimitsBuilder, TypedFunc};
+
+    use super::{Plugin, PluginReply};
+    use crate::config::PluginConfig;
+    use crate::diff::Change;
+    use crate::state::GameState;
+
+    // Longest reply read back from a plugin
+    const MAX_REPLY: usize = 64 * 1024;
+
+    pub fn engine() -> Result<Engine, String> {
+        let mut config = Config::new();
+        config.consume_fuel(true);
+        Engine::new(&config).map_err(|e| format!("Could not start the plugin runtime: {}", e))
+    }
+
+    pub struct WasmPlugin {
+        name: String,
+        store: Store<StoreLimits>,
+        memory: Memory,
+        alloc: TypedFunc<i32, i32>,
+        update: TypedFunc<(i32, i32), i64>,
+        fuel: u64,
+    }
+
+    impl WasmPlugin {
+        pub fn load(engine: &Engine, path: &Path, config: &PluginConfig) -> Result<Self, String> {
+            let module = Module::from_file(engine, path).map_err(|e| e.to_string())?;
+            if let Some(import) = module.imports().next() {
+                return Err(format!("plugins can't import anything, but it asks for {}.{}", import.module(), import.name()));
+            }
+            let limits = StoreLimitsBuilder::new().memory_size(config.memory_mb * 1024 * 1024).instances(1).build();
+            let mut store = Store::new(engine, limits);
+            store.limiter(|limits| limits);
+            store.set_fuel(config.fuel).map_err(|e| e.to_string())?;
+
+            let instance = Instance::new(&mut store, &module, &[]).map_err(|e| e.to_string())?;
+            let memory = instance.get_memory(&mut store, "memory").ok_or("it doesn't export its memory")?;
+            let alloc = instance.get_typed_func(&mut store, "alloc").map_err(|e| format!("alloc: {}", e))?;
+            let update = instance.get_typed_func(&mut store, "update").map_err(|e| format!("update: {}", e))?;
+            Ok(WasmPlugin {
+                name: path.file_stem().map(|s| s.to_string_lossy().into_owned()).unwrap_or_default(),
+                store,
+                memory,
+                alloc,
+                update,
+                fuel: config.fuel,
+            })
+        }
+    }
+
+    impl Plugin for WasmPlugin {
+        fn name(&self) -> &str {
+            &self.name
+        }
+
+        fn update(&mut self, game_time: i32, state: &GameState, changes: &[Change]) -> Result<PluginReply, String> {
+            let input = json!({
+                "game_time": game_time,
+                "changes": changes.iter().map(Change::to_json).collect::<Vec<_>>(),
+                "state": state,
+            }).to_string();
+
+            self.store.set_fuel(self.fuel).map_err(|e| e.to_string())?;
+            let len = i32::try_from(input.len()).map_err(|_| "the game state is too large".to_string())?;
+            let ptr = self.alloc.call(&mut self.store, len).map_err(|e| format!("alloc failed: {}", e.root_cause()))?;
+            self.memory.write(&mut self.store, ptr as u32 as usize, input.as_bytes())
+                .map_err(|_| "alloc returned memory out of bounds".to_string())?;
+            let packed = self.update.call(&mut self.store, (ptr, len)).map_err(|e| format!("update failed: {}", e.root_cause()))?;
+            if packed == 0 {
+                return Ok(PluginReply::default());
+            }
+
+            let (reply_ptr, reply_len) = ((packed >> 32) as u32 as usize, packed as u32 as usize);
+            if reply_len > MAX_REPLY {
+                return Err(format!("reply of {} bytes is over the {} byte limit", reply_len, MAX_REPLY));
+            }
+            let mut reply = vec![0; reply_len];
+            self.memory.read(&self.store, reply_ptr, &mut reply).map_err(|_| "reply out of bounds".to_string())?;
+            serde_json::from_slice(&reply).map_err(|e| format!("invalid reply: {}", e))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Hostile(Vec<(String, Option<String>)>);
+
+    impl Plugin for Hostile {
+        fn name(&self) -> &str {
+            "hostile"
+        }
+
+        fn update(&mut self, _game_time: i32, _state: &GameState, _changes: &[Change]) -> Result<PluginReply, String> {
+            Ok(PluginReply {
+                alerts: self.0.iter()
+                    .map(|(message, speech)| PluginAlert {
+                        message: message.clone(),
+                        severity: None,
+                        key: None,
+                        speech: speech.clone(),
+                    })
+                    .collect(),
+                panel: None,
+            })
+        }
+    }
+
+    fn spoken(alerts: Vec<(&str, Option<&str>)>) -> Vec<String> {
+        let alerts = alerts.into_iter().map(|(message, speech)| (message.to_string(), speech.map(str::to_string))).collect();
+        let mut host = PluginHost::default();
+        host.add(Box::new(Hostile(alerts)));
+        let state: GameState = serde_json::from_str("{}").unwrap();
+        host.update(0, &state, &[]).iter().map(Alert::spoken_text).collect()
+    }
+
+    #[test]
+    fn quotes_and_control_characters_are_dropped() {
+        let spoken = spoken(vec![
+            ("Stack", Some("a\u{2019}); Remove-Item -Recurse ~; (\u{2018}")),
+            ("Line one\nline two 'quoted' \"twice\" `tick`", None),
+        ]);
+        assert_eq!(spoken, vec!["a); Remove-Item -Recurse ~; (", "Line oneline two quoted twice tick"]);
+    }
+
+    #[test]
+    fn leading_dash_is_not_spoken() {
+        assert_eq!(spoken(vec![("Pull", Some("-w /tmp/out.wav")), ("  --help", None)]), vec!["", ""]);
+    }
+
+    #[test]
+    fn long_speech_is_capped() {
+        let long = "go ".repeat(200);
+        assert!(spoken(vec![("Push", Some(&long))])[0].chars().count() <= MAX_SPEECH);
+    }
+
+    #[test]
+    fn nothing_reaches_a_process_as_an_option_or_a_quote() {
+        let hostile = ["-o evil", "\u{201C}x\u{201D}", "'; rm -rf ~; '", "\u{0}--stdout", " \t-x", "ok"];
+        for text in spoken(hostile.iter().map(|t| ("message", Some(*t))).collect()) {
+            assert!(!text.starts_with('-'), "{:?}", text);
+            assert!(!text.chars().any(|c| c.is_control() || matches!(c, '\'' | '"' | '`' | '\u{2018}'..='\u{201F}')), "{:?}", text);
+        }
+    }
+}
@@ -0,0 +1,128 @@
+// src/power_spikes.rs
+use std::collections::HashSet;
+
+use crate::abilities;
+use crate::format::{format_ability_name, format_item_name};
+use crate::heroes::HeroInfo;
+use crate::items::ItemInfo;
+
+// Items that change what a hero can do in a fight
+pub const SPIKE_ITEMS: [&str; 8] = [
+    "blink", "black_king_bar", "desolator", "invis_sword", "manta", "greater_crit", "ultimate_scepter", "aghanims_shard",
+];
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum SpikeKind {
+    LevelSix,
+    // Item class without the item_ prefix
+    Item(String),
+}
+
+#[derive(Clone, Debug)]
+pub struct PowerSpike {
+    pub hero: &'static HeroInfo,
+    // Team label such as "Enemy", "Radiant" or "Your"
+    pub owner: String,
+    pub kind: SpikeKind,
+    // Worked out from the clock rather than seen
+    pub estimated: bool,
+}
+
+// "mid", "offlaner", ... from the hero's usual position
+fn role_label(position: u8) -> &'static str {
+    match position {
+        1 => "carry",
+        2 => "mid",
+        3 => "offlaner",
+        _ => "support",
+    }
+}
+
+fn ultimate_name(hero: &HeroInfo) -> String {
+    abilities::ultimate_of(hero.class)
+        .map(|ult| format_ability_name(ult.class))
+        .unwrap_or_else(|| "their ultimate".to_string())
+}
+
+impl PowerSpike {
+    pub fn is_own(&self) -> bool {
+        self.owner == "Your"
+    }
+
+    pub fn describe(&self) -> String {
+        let who = format!("{} {} {}", self.owner, role_label(self.hero.position), self.hero.name);
+        match (&self.kind, self.is_own()) {
+            (SpikeKind::LevelSix, true) => format!("You are level 6 - {} is up, look for a kill", ultimate_name(self.hero)),
+            (SpikeKind::LevelSix, false) => format!("{} is {}level 6 now, respect {}",
+                who, if self.estimated { "likely " } else { "" }, ultimate_name(self.hero)),
+            (SpikeKind::Item(item), true) if item == "ultimate_scepter" || item == "aghanims_shard" =>
+                format!("{} done - {} just got stronger", format_item_name(item), ultimate_name(self.hero)),
+            (SpikeKind::Item(item), true) => format!("{} done - you hit a power spike, go fight", format_item_name(item)),
+            (SpikeKind::Item(item), false) if self.estimated => format!("{} probably has {} by now - play around it", who, format_item_name(item)),
+            (SpikeKind::Item(item), false) => format!("{} has {} - play around it", who, format_item_name(item)),
+        }
+    }
+}
+
+// Minute a hero in this position usually reaches level 6
+fn level_six_minute(position: u8) -> i32 {
+    match position {
+        2 => 7,
+        1 | 3 => 8,
+        _ => 11,
+    }
+}
+
+// Reports each spike once per hero and side, so mirror matches work
+pub struct SpikeTracker {
+    seen: HashSet<(String, i32, SpikeKind)>,
+}
+
+impl SpikeTracker {
+    pub fn new() -> Self {
+        SpikeTracker { seen: HashSet::new() }
+    }
+
+    fn first_time(&mut self, owner: &str, hero: &HeroInfo, kind: &SpikeKind) -> bool {
+        self.seen.insert((owner.to_string(), hero.id, kind.clone()))
+    }
+
+    // A hero whose level and items are visible, i.e. the player's own or any hero while spectating
+    pub fn observe(&mut self, hero: &'static HeroInfo, owner: &str, level: Option<i32>, items: &[String]) -> Vec<PowerSpike> {
+        let mut spikes = Vec::new();
+        if level.map(|l| l >= 6).unwrap_or(false) && self.first_time(owner, hero, &SpikeKind::LevelSix) {
+            spikes.push(PowerSpike { hero, owner: owner.to_string(), kind: SpikeKind::LevelSix, estimated: false });
+        }
+        for item in items.iter().filter(|i| SPIKE_ITEMS.contains(&i.as_str())) {
+            let kind = SpikeKind::Item(item.clone());
+            if self.first_time(owner, hero, &kind) {
+                spikes.push(PowerSpike { hero, owner: owner.to_string(), kind, estimated: false });
+            }
+        }
+        spikes
+    }
+
+    // An enemy seen only on the minimap. Level 6 is guessed from their usual position and the clock.
+    pub fn estimate(&mut self, hero: &'static HeroInfo, clock_time: i32) -> Option<PowerSpike> {
+        if clock_time < level_six_minute(hero.position) * 60 || !self.first_time("Enemy", hero, &SpikeKind::LevelSix) {
+            return None;
+        }
+        Some(PowerSpike { hero, owner: "Enemy".to_string(), kind: SpikeKind::LevelSix, estimated: true })
+    }
+
+    // Spike items an unseen enemy can likely afford by now, from the net worth estimate
+    pub fn infer_items(&mut self, hero: &'static HeroInfo, items: &[&ItemInfo]) -> Vec<PowerSpike> {
+        items.iter()
+            .filter(|item| SPIKE_ITEMS.contains(&item.class))
+            .map(|item| SpikeKind::Item(item.class.to_string()))
+            .filter(|kind| self.first_time("Enemy", hero, kind))
+            .map(|kind| PowerSpike { hero, owner: "Enemy".to_string(), kind, estimated: true })
+            .collect()
+    }
+}
+
+impl Default for SpikeTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,172 @@
+// src/presence.rs
+use std::thread;
+use std::time::{Duration, Instant};
+
+use chrono::Utc;
+use discord_rich_presence::activity::{Activity, Assets, Timestamps};
+use discord_rich_presence::{DiscordIpc, DiscordIpcClient};
+use tokio::sync::watch;
+
+use crate::config::DiscordConfig;
+use crate::format::format_hero_name;
+use crate::state::{GamePhase, GameState};
+
+// Discord takes five activity updates every 20 seconds; stay well under that
+const MIN_INTERVAL: u64 = 5;
+
+// How often new state is looked at
+const POLL: Duration = Duration::from_secs(1);
+
+// Wait between attempts to reach a Discord client that isn't running
+const RECONNECT: Duration = Duration::from_secs(30);
+
+// Without a payload for this long (GSI heartbeats every 30s) the game was closed
+const STALE: Duration = Duration::from_secs(90);
+
+// Seconds the clock's start can move, e.g. after a pause, before the timer is updated
+const CLOCK_DRIFT: i64 = 5;
+
+// What friends see on Discord
+#[derive(Clone, Debug, PartialEq)]
+pub struct Presence {
+    pub hero: Option<String>,
+    pub kills: i32,
+    pub deaths: i32,
+    pub assists: i32,
+    pub phase: Option<GamePhase>,
+    pub spectating: bool,
+    pub paused: bool,
+    // Unix time the clock showed 0:00, so Discord can count the game time up itself
+    pub clock_start: Option<i64>,
+}
+
+impl Presence {
+    // None outside a match
+    pub fn from_state(state: &GameState) -> Option<Self> {
+        let map = state.map.as_ref()?;
+        let player = state.player();
+        let paused = map.paused.unwrap_or(false);
+        let running = state.phase().map(|p| p.is_playing()).unwrap_or(false) && !paused;
+        Some(Presence {
+            hero: state.hero().and_then(|h| h.name.as_deref()).filter(|name| !name.is_empty()).map(format_hero_name),
+            kills: player.and_then(|p| p.kills).unwrap_or(0),
+            deaths: player.and_then(|p| p.deaths).unwrap_or(0),
+            assists: player.and_then(|p| p.assists).unwrap_or(0),
+            phase: state.phase(),
+            spectating: state.is_spectating(),
+            paused,
+            clock_start: state.clock_time().filter(|_| running).map(|t| Utc::now().timestamp() - t as i64),
+        })
+    }
+
+    // e.g. "Juggernaut - 5/2/7"
+    pub fn details(&self) -> String {
+        match (&self.hero, self.spectating) {
+            (_, true) => "Spectating a match".to_string(),
+            (Some(hero), false) => format!("{} - {}/{}/{}", hero, self.kills, self.deaths, self.assists),
+            (None, false) => "Picking a hero".to_string(),
+        }
+    }
+
+    // e.g. "Game in progress"
+    pub fn state(&self) -> String {
+        if self.paused {
+            return "Paused".to_string();
+        }
+        let phase = self.phase.map(|p| p.name()).unwrap_or("in a match");
+        let mut chars = phase.chars();
+        chars.next().map(|first| first.to_uppercase().chain(chars).collect()).unwrap_or_default()
+    }
+
+    // Whether showing `other` instead is worth an update. The clock running is left to Discord.
+    fn differs(&self, other: &Presence) -> bool {
+        let clock_moved = match (self.clock_start, other.clock_start) {
+            (Some(a), Some(b)) => (a - b).abs() > CLOCK_DRIFT,
+            (a, b) => a.is_some() != b.is_some(),
+        };
+        clock_moved || self.details() != other.details() || self.state() != other.state()
+    }
+}
+
+// Shows the latest presence on Discord from a background thread, at most once per
+// `interval_secs` and only when it changed. Discord not running is retried quietly.
+pub fn spawn(config: &DiscordConfig, mut presence: watch::Receiver<Option<Presence>>) -> Result<(), String> {
+    let client_id = config.client_id.clone()
+        .filter(|id| !id.trim().is_empty())
+        .ok_or_else(|| "Discord Rich Presence needs the client_id of a Discord application in the [discord] section".to_string())?;
+    let interval = Duration::from_secs(config.interval_secs.max(MIN_INTERVAL));
+    let large_image = config.large_image.clone();
+
+    thread::spawn(move || {
+        let mut client = DiscordIpcClient::new(&client_id);
+        let mut connected = false;
+        let mut last_attempt: Option<Instant> = None;
+        let mut last_payload = Instant::now();
+        let mut shown: Option<Presence> = None;
+        let mut last_sent: Option<Instant> = None;
+
+        loop {
+            thread::sleep(POLL);
+            match presence.has_changed() {
+                Ok(true) => {
+                    presence.borrow_and_update();
+                    last_payload = Instant::now();
+                },
+                Ok(false) => {},
+                // The coach is shutting down
+                Err(_) => break,
+            }
+            let current = presence.borrow().clone().filter(|_| last_payload.elapsed() < STALE);
+
+            let changed = match (&shown, &current) {
+                (Some(shown), Some(current)) => shown.differs(current),
+                (None, None) => false,
+                _ => true,
+            };
+            let due = last_sent.map(|t| t.elapsed() >= interval).unwrap_or(true);
+            if !changed || !due {
+                continue;
+            }
+
+            if !connected {
+                if last_attempt.map(|t| t.elapsed() < RECONNECT).unwrap_or(false) {
+                    continue;
+                }
+                last_attempt = Some(Instant::now());
+                connected = client.connect().is_ok();
+                if !connected {
+                    continue;
+                }
+            }
+
+            let sent = match &current {
+                Some(current) => {
+                    let (details, state) = (current.details(), current.state());
+                    let mut activity = Activity::new().details(&details).state(&state);
+                    if let Some(start) = current.clock_start {
+                        activity = activity.timestamps(Timestamps::new().start(start * 1000));
+                    }
+                    if let Some(image) = &large_image {
+                        activity = activity.assets(Assets::new().large_image(image).large_text("Dota 2"));
+                    }
+                    client.set_activity(activity)
+                },
+                None => client.clear_activity(),
+            };
+            // Discord answers every command; read it so replies don't pile up unread
+            let sent = sent.and_then(|_| client.recv().map(|_| ()));
+            match sent {
+                Ok(_) => {
+                    shown = current;
+                    last_sent = Some(Instant::now());
+                },
+                // Discord was closed; connect again next time
+                Err(_) => {
+                    let _ = client.close();
+                    connected = false;
+                },
+            }
+        }
+    });
+    Ok(())
+}
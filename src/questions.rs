@@ -0,0 +1,225 @@
+// src/questions.rs
+use crate::alerts::strip_colors;
+use crate::coach::Coach;
+use crate::counters;
+use crate::heroes::{self, HeroInfo, Role};
+use crate::inventory::Inventory;
+use crate::item_build::{self, DISABLERS_FOR_BKB};
+use crate::items::{self, ItemInfo};
+use crate::tracking::describe_map_location;
+
+// Items or heroes taken from one question, at most
+const MAX_MENTIONS: usize = 3;
+
+// Hero nicknames shorter than this ("am", "pa") are too easily ordinary words
+const MIN_ALIAS: usize = 3;
+
+// Lowercase words split by single spaces, padded with one at either end so phrases match whole words
+fn normalize(text: &str) -> String {
+    let text: String = text.to_lowercase().chars()
+        .filter(|c| *c != '\'')
+        .map(|c| if c.is_alphanumeric() { c } else { ' ' })
+        .collect();
+    format!(" {} ", text.split_whitespace().collect::<Vec<_>>().join(" "))
+}
+
+// Keys of the phrases found in `text`, in the order they appear. Where two overlap the longer
+// one wins, so "phase boots" isn't also read as "boots".
+fn mentions(text: &str, phrases: impl Iterator<Item = (&'static str, String)>) -> Vec<&'static str> {
+    let text = normalize(text);
+    let mut found: Vec<(usize, usize, &'static str)> = phrases
+        .filter_map(|(key, phrase)| {
+            let phrase = normalize(&phrase);
+            (phrase.trim() != "").then_some(())?;
+            text.find(&phrase).map(|start| (start, start + phrase.len(), key))
+        })
+        .collect();
+    found.sort_by_key(|&(start, end, _)| (std::cmp::Reverse(end - start), start));
+
+    // Neighbouring phrases share the space between them
+    let mut kept: Vec<(usize, usize, &'static str)> = Vec::new();
+    for (start, end, key) in found {
+        if kept.iter().all(|&(s, e, k)| k != key && (end <= s + 1 || start + 1 >= e)) {
+            kept.push((start, end, key));
+        }
+    }
+    kept.sort_by_key(|&(start, _, _)| start);
+    kept.into_iter().map(|(_, _, key)| key).take(MAX_MENTIONS).collect()
+}
+
+// Items named in `text` by shop name, internal name or nickname
+pub fn mentioned_items(text: &str) -> Vec<&'static ItemInfo> {
+    let phrases = items::ITEMS.iter()
+        .flat_map(|item| [(item.class, item.name.to_string()), (item.class, item.class.replace('_', " "))])
+        .chain(items::ALIASES.iter().map(|&(alias, class)| (class, alias.to_string())));
+    mentions(text, phrases).into_iter().filter_map(items::by_class).collect()
+}
+
+// Heroes named in `text` by name, internal name or nickname
+pub fn mentioned_heroes(text: &str) -> Vec<&'static HeroInfo> {
+    let phrases = heroes::HEROES.iter()
+        .flat_map(|hero| [(hero.class, hero.name.to_string()), (hero.class, hero.class.replace('_', " "))].into_iter()
+            .chain(hero.aliases.iter().filter(|a| a.len() >= MIN_ALIAS).map(|a| (hero.class, a.to_string()))));
+    mentions(text, phrases).into_iter().filter_map(heroes::by_class).collect()
+}
+
+// What the answers are worked out from
+struct Situation<'a> {
+    hero: Option<&'static HeroInfo>,
+    gold: i32,
+    inventory: Option<&'a Inventory>,
+    enemies: Vec<&'static HeroInfo>,
+    // The coach's current item suggestion
+    next: Option<&'static str>,
+}
+
+impl<'a> Situation<'a> {
+    fn of(coach: &'a Coach) -> Self {
+        let state = coach.game_state();
+        Situation {
+            hero: state.and_then(|s| s.hero()).and_then(|h| h.name.as_deref()).and_then(heroes::by_class),
+            gold: state.and_then(|s| s.player()).and_then(|p| p.gold).unwrap_or(0),
+            inventory: state.and_then(|s| s.inventory()),
+            enemies: coach.enemy_hero_infos(),
+            next: coach.item_recommendation().map(|r| r.item.class),
+        }
+    }
+
+    // Gold still needed, or None when the item is already built
+    fn gold_needed(&self, item: &ItemInfo) -> Option<i32> {
+        match self.inventory {
+            Some(inventory) => item_build::gold_needed(item, inventory),
+            None => Some(item.cost),
+        }
+    }
+
+    // Why this game calls for the item, most pressing first
+    fn reasons(&self, item: &ItemInfo) -> Vec<String> {
+        let mut reasons = Vec::new();
+        let disablers: Vec<&str> = self.enemies.iter().filter(|e| e.has_role(Role::Disabler)).map(|e| e.name).collect();
+        if item.class == "black_king_bar" && disablers.len() >= DISABLERS_FOR_BKB {
+            reasons.push(format!("{} enemy disablers ({})", disablers.len(), disablers.join(", ")));
+        }
+        for advice in counters::advice(&self.enemies) {
+            if advice.threat.counter_items().contains(item.name) {
+                reasons.push(format!("answers the enemy's {} ({})", advice.threat.name(), advice.heroes.join(", ")));
+            }
+        }
+        if self.next == Some(item.class) {
+            reasons.push("it's the coach's next suggested item".to_string());
+        }
+        if let Some(hero) = self.hero.filter(|hero| item_build::core_build(hero).contains(&item.class)) {
+            reasons.push(format!("part of the usual {} build", hero.name));
+        }
+        reasons
+    }
+
+    fn gold_text(&self, item: &ItemInfo) -> String {
+        match self.gold_needed(item) {
+            None => format!("you already have {}", item.name),
+            Some(needed) if needed <= self.gold => format!("{} is affordable now", item.name),
+            Some(needed) => format!("{} needs {} more gold", item.name, needed - self.gold),
+        }
+    }
+}
+
+fn describe_item(situation: &Situation, item: &ItemInfo) -> String {
+    let parts: Vec<&str> = item.components.iter().filter_map(|c| items::by_class(c)).map(|c| c.name).collect();
+    let recipe = match (parts.is_empty(), item.recipe_cost() > 0) {
+        (true, _) => String::new(),
+        (false, true) => format!(", built from {} and a recipe", parts.join(", ")),
+        (false, false) => format!(", built from {}", parts.join(", ")),
+    };
+    let reasons = situation.reasons(item);
+    let why = if reasons.is_empty() {
+        "Nothing in this game makes it urgent".to_string()
+    } else {
+        format!("Worth it here: {}", reasons.join("; "))
+    };
+    format!("{} costs {}{} - {}. {}.", item.name, item.cost, recipe, situation.gold_text(item), why)
+}
+
+// Which of several items to get first: whatever this game calls for most, then the cheaper one
+fn compare_items(situation: &Situation, candidates: &[&'static ItemInfo]) -> String {
+    let (owned, wanted): (Vec<&ItemInfo>, Vec<&ItemInfo>) = candidates.iter().partition(|item| situation.gold_needed(item).is_none());
+    let mut lines: Vec<String> = owned.iter().map(|item| format!("You already have {}.", item.name)).collect();
+    let mut ranked: Vec<(&ItemInfo, Vec<String>, i32)> = wanted.into_iter()
+        .map(|item| (item, situation.reasons(item), situation.gold_needed(item).unwrap_or(0)))
+        .collect();
+    ranked.sort_by_key(|(_, reasons, needed)| (std::cmp::Reverse(reasons.len()), *needed));
+
+    let Some((best, reasons, _)) = ranked.first() else { return lines.join(" ") };
+    if reasons.is_empty() {
+        lines.push(format!("Nothing in this game favours one - {} is cheapest, so it comes online first.", best.name));
+    } else {
+        lines.push(format!("{} first: {}.", best.name, reasons.join("; ")));
+    }
+    for (item, reasons, _) in ranked.iter().skip(1).filter(|(_, reasons, _)| !reasons.is_empty()) {
+        lines.push(format!("{} after: {}.", item.name, reasons.join("; ")));
+    }
+    let gold: Vec<String> = ranked.iter().map(|(item, _, _)| situation.gold_text(item)).collect();
+    lines.push(format!("With {} gold, {}.", situation.gold, gold.join(", ")));
+    lines.join(" ")
+}
+
+fn describe_hero(coach: &Coach, situation: &Situation, hero: &HeroInfo) -> String {
+    let threats: Vec<String> = counters::threats_of(hero).iter()
+        .map(|threat| format!("{} - {}", threat.name(), threat.counter_items()))
+        .collect();
+    let mut text = format!("{} ({} {}, usually position {})", hero.name,
+        if hero.ranged { "ranged" } else { "melee" }, hero.attribute.name().to_lowercase(), hero.position);
+    if !threats.is_empty() {
+        text.push_str(&format!(": {}", threats.join("; ")));
+    }
+    match coach.enemies().get(hero.name) {
+        Some(enemy) => text.push_str(&format!(". Last seen {}s ago near {}", coach.game_time() - enemy.last_seen_time,
+            describe_map_location(enemy.last_seen_position))),
+        None if situation.enemies.iter().any(|e| e.id == hero.id) => text.push_str(". Not seen yet this game"),
+        None => {},
+    }
+    text.push('.');
+    text
+}
+
+// A rule-based answer from the live state and the item and hero tables
+pub fn answer(coach: &Coach, question: &str) -> String {
+    let situation = Situation::of(coach);
+    let items = mentioned_items(question);
+    let mut lines = Vec::new();
+    match items.as_slice() {
+        [] => {},
+        [item] => lines.push(describe_item(&situation, item)),
+        items => lines.push(compare_items(&situation, items)),
+    }
+    for hero in mentioned_heroes(question) {
+        lines.push(describe_hero(coach, &situation, hero));
+    }
+    if lines.is_empty() {
+        lines.push("Without a language model the coach can only answer about items and heroes, e.g. \"bkb or blink first?\".".to_string());
+        if let Some(recommendation) = coach.item_recommendation() {
+            lines.push(format!("Next item: {}.", strip_colors(&recommendation.describe())));
+        }
+    }
+    lines.join(" ")
+}
+
+// Table facts about what the question mentions, as plain lines for a language model
+pub fn facts(question: &str) -> String {
+    let items = mentioned_items(question).into_iter().map(|item| {
+        let parts: Vec<&str> = item.components.iter().filter_map(|c| items::by_class(c)).map(|c| c.name).collect();
+        format!("{}: {} gold{}{}", item.name, item.cost,
+            match (parts.is_empty(), item.recipe_cost()) {
+                (true, _) => String::new(),
+                (false, 0) => format!(", from {}", parts.join(" + ")),
+                (false, recipe) => format!(", from {} + {} recipe", parts.join(" + "), recipe),
+            },
+            item.cooldown.map(|c| format!(", active with {}s cooldown", c)).unwrap_or_default())
+    });
+    let heroes = mentioned_heroes(question).into_iter().map(|hero| {
+        let threats: Vec<&str> = counters::threats_of(hero).iter().map(|t| t.name()).collect();
+        format!("{}: {} {}, position {}{}", hero.name, if hero.ranged { "ranged" } else { "melee" },
+            hero.attribute.name().to_lowercase(), hero.position,
+            if threats.is_empty() { String::new() } else { format!(", brings {}", threats.join(" and ")) })
+    });
+    items.chain(heroes).collect::<Vec<_>>().join("\n")
+}
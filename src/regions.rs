@@ -0,0 +1,98 @@
+// src/regions.rs
+
+// Outline of a named area in map coordinates
+#[derive(Clone, Copy, Debug)]
+pub enum Shape {
+    Rect { min: (i32, i32), max: (i32, i32) },
+    Circle { center: (i32, i32), radius: i32 },
+    Polygon(&'static [(i32, i32)]),
+}
+
+impl Shape {
+    pub fn contains(&self, (x, y): (i32, i32)) -> bool {
+        match *self {
+            Shape::Rect { min, max } => x >= min.0 && x <= max.0 && y >= min.1 && y <= max.1,
+            Shape::Circle { center, radius } => {
+                let (dx, dy) = ((x - center.0) as i64, (y - center.1) as i64);
+                dx * dx + dy * dy <= (radius as i64) * (radius as i64)
+            },
+            Shape::Polygon(points) => {
+                // Even-odd ray casting
+                let mut inside = false;
+                let mut j = points.len() - 1;
+                for i in 0..points.len() {
+                    let ((xi, yi), (xj, yj)) = (points[i], points[j]);
+                    if (yi > y) != (yj > y) && (x as f32) < (xj - xi) as f32 * (y - yi) as f32 / (yj - yi) as f32 + xi as f32 {
+                        inside = !inside;
+                    }
+                    j = i;
+                }
+                inside
+            },
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Region {
+    // Read out in alerts, e.g. "Dire triangle"
+    pub name: &'static str,
+    pub shape: Shape,
+}
+
+const fn rect(name: &'static str, min: (i32, i32), max: (i32, i32)) -> Region {
+    Region { name, shape: Shape::Rect { min, max } }
+}
+
+const fn circle(name: &'static str, center: (i32, i32), radius: i32) -> Region {
+    Region { name, shape: Shape::Circle { center, radius } }
+}
+
+const fn polygon(name: &'static str, points: &'static [(i32, i32)]) -> Region {
+    Region { name, shape: Shape::Polygon(points) }
+}
+
+// Checked in order, so small landmarks come before the lanes and jungles they sit in.
+// Radiant is bottom-left, Dire top-right; the river runs top-left to bottom-right.
+// Coordinates are approximate and drift with map changes between patches.
+pub const REGIONS: &[Region] = &[
+    rect("Radiant fountain", (-8000, -8000), (-6400, -6000)),
+    rect("Dire fountain", (6300, 5800), (8000, 8000)),
+    rect("Radiant base", (-8000, -8000), (-4300, -3800)),
+    rect("Dire base", (3900, 3400), (8000, 8000)),
+
+    circle("top Roshan pit", (-2800, 2300), 700),
+    circle("bottom Roshan pit", (2700, -2300), 700),
+    circle("top outpost", (-4300, 1000), 700),
+    circle("bottom outpost", (4200, -1000), 700),
+
+    circle("Radiant T2 top", (-6100, -900), 1000),
+    circle("Radiant T2 mid", (-3500, -2800), 1000),
+    circle("Radiant T2 bot", (-100, -6200), 1000),
+    circle("Dire T2 top", (0, 6000), 1000),
+    circle("Dire T2 mid", (2500, 2100), 1000),
+    circle("Dire T2 bot", (6300, 400), 1000),
+
+    rect("Radiant triangle", (1500, -5400), (4500, -3000)),
+    rect("Dire triangle", (-4500, 3000), (-1500, 5400)),
+
+    // Lanes hug the map edges and the main diagonal
+    polygon("mid lane", &[(-4300, -3300), (-3300, -4300), (4300, 3300), (3300, 4300)]),
+    rect("top lane", (-8000, -3800), (-5300, 8000)),
+    rect("top lane", (-8000, 5300), (3900, 8000)),
+    rect("bottom lane", (-4300, -8000), (8000, -5600)),
+    rect("bottom lane", (5600, -8000), (8000, 3400)),
+
+    polygon("river", &[(-8000, 7100), (7100, -8000), (8000, -7100), (-7100, 8000)]),
+
+    // What is left, split by the mid lane and the river into four wedges
+    polygon("Radiant jungle", &[(0, 0), (-8000, -8000), (8000, -8000)]),
+    polygon("Radiant offlane jungle", &[(0, 0), (-8000, -8000), (-8000, 8000)]),
+    polygon("Dire jungle", &[(0, 0), (-8000, 8000), (8000, 8000)]),
+    polygon("Dire offlane jungle", &[(0, 0), (8000, 8000), (8000, -8000)]),
+];
+
+// The first region containing the position
+pub fn locate(position: (i32, i32)) -> Option<&'static Region> {
+    REGIONS.iter().find(|r| r.shape.contains(position))
+}
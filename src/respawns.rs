@@ -0,0 +1,244 @@
+// src/respawns.rs
+use crate::format::format_hero_name;
+use crate::state::{GameState, Team};
+use crate::tracking::estimate_hero_level;
+
+// Respawn time in seconds by hero level, level 1 first
+const RESPAWN_BY_LEVEL: [i32; 30] = [
+    6, 8, 10, 14, 16, 26, 28, 30, 32, 34,
+    36, 44, 46, 48, 50, 52, 54, 65, 70, 75,
+    80, 85, 90, 95, 100, 100, 100, 100, 100, 100,
+];
+
+pub fn respawn_time(level: i32) -> i32 {
+    RESPAWN_BY_LEVEL[(level.clamp(1, 30) - 1) as usize]
+}
+
+// Chance an unseen hero holds buyback gold. Buyback costs 200 + net worth / 13,
+// and heroes keep a growing share of their net worth as gold as items get expensive.
+pub fn modeled_buyback_chance(game_time: i32) -> f32 {
+    let minutes = (game_time / 60).max(0) as f32;
+    let gpm = (300.0 + 8.0 * minutes).min(650.0);
+    let net_worth = 600.0 + gpm * minutes;
+    let cost = 200.0 + net_worth / 13.0;
+    let held = net_worth * (minutes / 200.0).min(0.2);
+    (held / cost / 2.0).clamp(0.05, 0.9)
+}
+
+#[derive(Clone, Debug)]
+pub struct DeadHero {
+    pub team: Team,
+    // Hero name when known, e.g. "Sven"
+    pub hero: Option<String>,
+    pub died_at: i32,
+    pub respawn_at: i32,
+    // 0.0 - 1.0, exact when spectating
+    pub buyback_chance: f32,
+    slot: Option<usize>,
+}
+
+impl DeadHero {
+    pub fn remaining(&self, game_time: i32) -> i32 {
+        (self.respawn_at - game_time).max(0)
+    }
+
+    // e.g. "Sven respawns in 32s (70% buyback chance)"
+    pub fn describe(&self, game_time: i32) -> String {
+        format!("{} respawns in {}s ({:.0}% buyback chance)",
+            self.hero.as_deref().unwrap_or("Enemy hero"),
+            self.remaining(game_time),
+            self.buyback_chance * 100.0)
+    }
+}
+
+// More living heroes than the enemy, and for how long
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct NumbersAdvantage {
+    pub up_by: i32,
+    pub seconds: i32,
+    // Dead enemies more likely than not to buy back
+    pub possible_buybacks: i32,
+}
+
+impl NumbersAdvantage {
+    pub fn describe(&self) -> String {
+        let mut text = format!("Up {} hero{} for {}s", self.up_by, if self.up_by == 1 { "" } else { "es" }, self.seconds);
+        if self.possible_buybacks > 0 {
+            text.push_str(&format!(" - {} may buy back", self.possible_buybacks));
+        }
+        text
+    }
+}
+
+// Buyback cooldown in seconds
+pub const BUYBACK_COOLDOWN: i32 = 480;
+
+// Dead heroes of both teams with their estimated respawns. Spectator payloads
+// give exact timers; otherwise deaths come from the team scores.
+pub struct RespawnTracker {
+    dead: Vec<DeadHero>,
+    // Advantage last reported, so fights don't repeat it every tick
+    reported: i32,
+}
+
+fn scores(state: &GameState) -> Option<(i32, i32)> {
+    let map = state.map.as_ref()?;
+    Some((map.radiant_score?, map.dire_score?))
+}
+
+impl RespawnTracker {
+    pub fn new() -> Self {
+        RespawnTracker {
+            dead: Vec::new(),
+            reported: 0,
+        }
+    }
+
+    // Dead heroes of `team`, first to respawn first
+    pub fn dead(&self, team: Team) -> Vec<&DeadHero> {
+        let mut dead: Vec<&DeadHero> = self.dead.iter().filter(|d| d.team == team).collect();
+        dead.sort_by_key(|d| d.respawn_at);
+        dead
+    }
+
+    fn spectated_deaths(&mut self, state: &GameState, last: &GameState, game_time: i32) -> Vec<DeadHero> {
+        let before = last.spectated_players();
+        let mut deaths = Vec::new();
+
+        for now in state.spectated_players() {
+            let Some(hero) = now.hero else { continue };
+            let was_alive = before.iter()
+                .find(|p| p.team == now.team && p.slot == now.slot)
+                .and_then(|p| p.hero)
+                .and_then(|h| h.alive)
+                .unwrap_or(true);
+
+            match hero.alive {
+                Some(false) if was_alive => {
+                    let gold = now.player.and_then(|p| p.gold).unwrap_or(0);
+                    let can_buy_back = hero.buyback_cooldown.unwrap_or(0) == 0
+                        && hero.buyback_cost.map(|cost| gold >= cost).unwrap_or(false);
+                    deaths.push(DeadHero {
+                        team: now.team,
+                        hero: hero.name.as_deref().map(|n| format_hero_name(&n.replace("npc_dota_hero_", ""))),
+                        died_at: game_time,
+                        respawn_at: game_time + hero.respawn_seconds.unwrap_or_else(|| respawn_time(hero.level.unwrap_or(1))),
+                        buyback_chance: if can_buy_back { 1.0 } else { 0.0 },
+                        slot: Some(now.slot),
+                    });
+                },
+                // Back before the timer ran out means a buyback, or a respawn we already counted down
+                Some(true) => self.dead.retain(|d| !(d.team == now.team && d.slot == Some(now.slot))),
+                _ => {},
+            }
+        }
+        deaths
+    }
+
+    fn scored_deaths(state: &GameState, last: &GameState, game_time: i32) -> Vec<DeadHero> {
+        let (Some((radiant, dire)), Some((last_radiant, last_dire))) = (scores(state), scores(last)) else {
+            return Vec::new();
+        };
+        let team = state.player_team().and_then(|t| Team::from_name(&t));
+        let hero = state.hero();
+        let level = hero.and_then(|h| h.level).unwrap_or_else(|| estimate_hero_level(game_time));
+        let own_died = last.hero().and_then(|h| h.alive).unwrap_or(true) && hero.and_then(|h| h.alive) == Some(false);
+
+        let unknown = |team: Team, count: i32| (0..count.max(0)).map(move |_| DeadHero {
+            team,
+            hero: None,
+            died_at: game_time,
+            respawn_at: game_time + respawn_time(level),
+            buyback_chance: modeled_buyback_chance(game_time),
+            slot: None,
+        });
+
+        // Radiant's score counts Dire heroes killed and the other way round
+        let mut deaths: Vec<DeadHero> = unknown(Team::Dire, radiant - last_radiant)
+            .chain(unknown(Team::Radiant, dire - last_dire))
+            .collect();
+
+        // The player's own death is known exactly
+        if let (true, Some(team), Some(hero)) = (own_died, team, hero) {
+            if let Some(index) = deaths.iter().position(|d| d.team == team) {
+                deaths[index] = DeadHero {
+                    team,
+                    hero: hero.name.as_deref().map(|n| format_hero_name(&n.replace("npc_dota_hero_", ""))),
+                    died_at: game_time,
+                    respawn_at: game_time + hero.respawn_seconds.unwrap_or_else(|| respawn_time(level)),
+                    buyback_chance: 0.0,
+                    slot: None,
+                };
+            }
+        }
+        deaths
+    }
+
+    // Record new deaths. Returns the enemies that just died.
+    pub fn update(&mut self, state: &GameState, last_state: Option<&GameState>) -> Vec<DeadHero> {
+        let Some(game_time) = state.game_time() else { return Vec::new() };
+        self.dead.retain(|d| d.respawn_at > game_time);
+
+        let deaths = match last_state {
+            Some(last) if state.is_spectating() => self.spectated_deaths(state, last, game_time),
+            Some(last) => Self::scored_deaths(state, last, game_time),
+            None => Vec::new(),
+        };
+        self.dead.extend(deaths.iter().cloned());
+
+        let team = state.player_team().and_then(|t| Team::from_name(&t));
+        deaths.into_iter().filter(|d| Some(d.team) != team).collect()
+    }
+
+    // Push every timer back, e.g. over a pause the game time kept counting through
+    pub fn shift_times(&mut self, seconds: i32) {
+        for dead in &mut self.dead {
+            dead.died_at += seconds;
+            dead.respawn_at += seconds;
+        }
+    }
+
+    // How long `team` keeps more living heroes than the enemy, if it does
+    pub fn advantage(&self, team: Team, game_time: i32) -> Option<NumbersAdvantage> {
+        let mut respawns: Vec<(i32, i32)> = self.dead.iter()
+            .filter(|d| d.respawn_at > game_time)
+            // An enemy coming back costs one, an ally coming back gains one
+            .map(|d| (d.respawn_at, if d.team == team { 1 } else { -1 }))
+            .collect();
+        let up_by = -respawns.iter().map(|(_, change)| change).sum::<i32>();
+        if up_by <= 0 {
+            return None;
+        }
+
+        respawns.sort();
+        let mut lead = up_by;
+        let mut until = game_time;
+        for (time, change) in respawns {
+            lead += change;
+            until = time;
+            if lead <= 0 {
+                break;
+            }
+        }
+
+        let possible_buybacks = self.dead.iter()
+            .filter(|d| d.team != team && d.respawn_at > game_time && d.buyback_chance >= 0.5)
+            .count() as i32;
+        Some(NumbersAdvantage { up_by, seconds: until - game_time, possible_buybacks })
+    }
+
+    // The advantage, when it grew since it was last reported
+    pub fn take_new_advantage(&mut self, team: Team, game_time: i32) -> Option<NumbersAdvantage> {
+        let advantage = self.advantage(team, game_time);
+        let up_by = advantage.map(|a| a.up_by).unwrap_or(0);
+        let grew = up_by > self.reported;
+        self.reported = up_by;
+        advantage.filter(|_| grew)
+    }
+}
+
+impl Default for RespawnTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
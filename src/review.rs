@@ -0,0 +1,188 @@
+// src/review.rs
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+
+use serde_json::Value;
+
+use crate::config::Config;
+use crate::coach::Coach;
+use crate::events::{GameEvent, Marker};
+use crate::state::GameState;
+
+// Appends every accepted GSI payload to a JSON lines file, for reviewing later. The auth
+// block is left out so recordings can be shared. Markers go in between as {"marker": {...}} lines.
+pub struct Recorder {
+    file: File,
+}
+
+impl Recorder {
+    pub fn open(path: &Path) -> Result<Self, String> {
+        if let Some(dir) = path.parent().filter(|d| !d.as_os_str().is_empty()) {
+            fs::create_dir_all(dir).map_err(|e| format!("Could not create {}: {}", dir.display(), e))?;
+        }
+        let file = OpenOptions::new().create(true).append(true).open(path)
+            .map_err(|e| format!("Could not open recording {}: {}", path.display(), e))?;
+        Ok(Recorder { file })
+    }
+
+    pub fn record(&mut self, mut payload: Value) -> std::io::Result<()> {
+        if let Some(object) = payload.as_object_mut() {
+            object.remove("auth");
+        }
+        writeln!(self.file, "{}", payload)
+    }
+
+    pub fn mark(&mut self, marker: &Marker) -> std::io::Result<()> {
+        writeln!(self.file, "{}", serde_json::json!({ "marker": marker }))
+    }
+}
+
+// A recorded session fed through a fresh coach one payload at a time. Going back
+// replays the session from the start, since the coach only ever moves forward.
+pub struct Review {
+    payloads: Vec<GameState>,
+    // Everything the session reported, with the index of the payload that reported it
+    events: Vec<(usize, GameEvent)>,
+    // Markers with the index of the payload they were dropped after
+    markers: Vec<(usize, Marker)>,
+    config: Config,
+    coach: Coach,
+    // Payloads fed to the coach so far
+    position: usize,
+}
+
+impl Review {
+    // Lines that don't parse as a game state are skipped; returns how many were
+    pub fn load(path: &Path, mut config: Config) -> Result<(Self, usize), String> {
+        // Payloads were checked when recorded, and saved without their token
+        config.server.auth_token = None;
+        let text = fs::read_to_string(path)
+            .map_err(|e| format!("Could not read recording {}: {}", path.display(), e))?;
+        let mut skipped = 0;
+        let mut payloads: Vec<GameState> = Vec::new();
+        let mut markers = Vec::new();
+        for line in text.lines().filter(|line| !line.trim().is_empty()) {
+            let value: Option<Value> = serde_json::from_str(line).ok();
+            match value {
+                Some(Value::Object(mut object)) if object.contains_key("marker") => {
+                    match object.remove("marker").and_then(|m| serde_json::from_value(m).ok()) {
+                        Some(marker) => markers.push((payloads.len().saturating_sub(1), marker)),
+                        None => skipped += 1,
+                    }
+                },
+                Some(value) => match serde_json::from_value(value) {
+                    Ok(state) => payloads.push(state),
+                    Err(_) => skipped += 1,
+                },
+                None => skipped += 1,
+            }
+        }
+        if payloads.is_empty() {
+            return Err(format!("No game states in {}", path.display()));
+        }
+
+        let mut review = Review {
+            payloads,
+            events: Vec::new(),
+            markers,
+            coach: Coach::new(config.clone()),
+            config,
+            position: 0,
+        };
+
+        // One silent pass to find every event up front, so they can be jumped to
+        while !review.at_end() {
+            let index = review.position;
+            let events = review.forward(1);
+            review.events.extend(events.into_iter().map(|e| (index, e)));
+        }
+        review.seek(0);
+        Ok((review, skipped))
+    }
+
+    pub fn coach(&self) -> &Coach {
+        &self.coach
+    }
+
+    pub fn events(&self) -> &[(usize, GameEvent)] {
+        &self.events
+    }
+
+    pub fn len(&self) -> usize {
+        self.payloads.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.payloads.is_empty()
+    }
+
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    pub fn at_end(&self) -> bool {
+        self.position == self.payloads.len()
+    }
+
+    // Game time of the last payload fed, or of the first before any
+    pub fn game_time(&self) -> i32 {
+        self.game_time_at(self.position.saturating_sub(1))
+    }
+
+    // Game time of the first and last payloads
+    pub fn span(&self) -> (i32, i32) {
+        (self.game_time_at(0), self.game_time_at(self.payloads.len().saturating_sub(1)))
+    }
+
+    fn game_time_at(&self, index: usize) -> i32 {
+        self.payloads.get(index).and_then(|s| s.game_time()).unwrap_or(0)
+    }
+
+    // Feed the next `count` payloads, returning what the coach reported
+    pub fn forward(&mut self, count: usize) -> Vec<GameEvent> {
+        let end = (self.position + count).min(self.payloads.len());
+        let mut events = Vec::new();
+        for index in self.position..end {
+            events.extend(self.feed(index));
+        }
+        self.position = end;
+        events
+    }
+
+    // Move to just after payload `position - 1`. Events on the way are not returned.
+    pub fn seek(&mut self, position: usize) {
+        let position = position.min(self.payloads.len());
+        if position < self.position {
+            self.coach = Coach::new(self.config.clone());
+            self.position = 0;
+        }
+        self.forward(position - self.position);
+    }
+
+    // A payload and the markers dropped after it
+    fn feed(&mut self, index: usize) -> Vec<GameEvent> {
+        let mut events = self.coach.update(self.payloads[index].clone());
+        for (_, marker) in self.markers.iter().filter(|(after, _)| *after == index) {
+            events.push(self.coach.add_marker(marker.note.clone()).event());
+        }
+        events
+    }
+
+    // Position `seconds` of game time away from the current one, clamped to the recording
+    pub fn position_after(&self, seconds: i32) -> usize {
+        let target = self.game_time() + seconds;
+        if seconds >= 0 {
+            (self.position..self.payloads.len())
+                .find(|&i| self.game_time_at(i) >= target)
+                .map(|i| i + 1)
+                .unwrap_or(self.payloads.len())
+        } else {
+            (0..self.position)
+                .rev()
+                .find(|&i| self.game_time_at(i) <= target)
+                .map(|i| i + 1)
+                .unwrap_or(0)
+        }
+    }
+}
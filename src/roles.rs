@@ -0,0 +1,191 @@
+// src/roles.rs
+use std::collections::HashMap;
+
+use crate::alerts::AlertCategory;
+use crate::benchmarks::role_name;
+use crate::briefing::Lane;
+use crate::items;
+use crate::lane_assignment::{between_lanes, lane_of, Assignment, LANING_UNTIL};
+use crate::missing::MapSide;
+use crate::state::{GameState, Team};
+use crate::timers::{RuneKind, RuneSpawn};
+
+// Too little of the lane has been played to say before this
+const DECIDE_FROM: i32 = 300;
+
+// Sightings of the player's hero before the lane counts
+const MIN_SIGHTINGS: u32 = 20;
+const LANE_SHARE: f32 = 0.5;
+
+// Farm that marks a core or a support by the time the role is decided
+const CORE_GPM: i32 = 450;
+const SUPPORT_GPM: i32 = 330;
+const CORE_LAST_HITS_PER_MINUTE: f32 = 3.5;
+const SUPPORT_LAST_HITS_PER_MINUTE: f32 = 1.5;
+
+// Bought almost only by supports, without the item_ prefix
+const SUPPORT_ITEMS: [&str; 14] = ["ward_observer", "ward_sentry", "ward_dispenser", "smoke_of_deceit", "dust",
+    "tranquil_boots", "arcane_boots", "glimmer_cape", "force_staff", "mekansm", "urn_of_shadows", "spirit_vessel",
+    "pavise", "solar_crest"];
+
+// Support purchases that settle it on their own
+const SUPPORT_ITEMS_NEEDED: usize = 2;
+
+pub fn is_support(position: u8) -> bool {
+    position >= 4
+}
+
+// Whether a role gets advice of this kind: farm checkpoints are for cores, ward and smoke
+// timings for supports
+pub fn coached_on(position: u8, category: AlertCategory) -> bool {
+    match category {
+        AlertCategory::LastHits => !is_support(position),
+        AlertCategory::Vision => is_support(position),
+        _ => true,
+    }
+}
+
+// Which runes a position should go for at a spawn. Later bounties are for the supports;
+// the water runes are the mid's.
+pub fn rune_hint(position: u8, spawn: &RuneSpawn) -> Option<&'static str> {
+    let first = spawn.spawn_time == 0;
+    match (spawn.kind, position) {
+        (RuneKind::Bounty, 1) if first => Some("leave the bounties to your supports and be in lane for the first wave"),
+        (RuneKind::Bounty, 2) if first => Some("skip the bounties and be in mid for the first wave, water runes follow at 2:00 and 4:00"),
+        (RuneKind::Bounty, 3) if first => Some("grab the bounty by your lane on the way if your support can't"),
+        (RuneKind::Bounty, 4) if first => Some("take the bounty in your jungle on the off lane side, then join your offlaner"),
+        (RuneKind::Bounty, 5) if first => Some("take the bounty in your jungle on the safe lane side, then join your carry"),
+        (RuneKind::Bounty, 4) => Some("the bounty by your off lane is yours, or contest theirs if your lane is winning"),
+        (RuneKind::Bounty, 5) => Some("take the bounty by your safe lane while the wave is pushed"),
+        (RuneKind::Water, 2) if spawn.spawn_time == 120 => Some("both water runes are yours, take one now and the other at 4:00"),
+        (RuneKind::Water, 2) => Some("take the second water rune before the enemy mid does"),
+        _ => None,
+    }
+}
+
+// The role the coach settled on and what gave it away
+#[derive(Clone, Debug)]
+pub struct Detection {
+    pub position: u8,
+    pub lane: Assignment,
+    pub reasons: Vec<String>,
+}
+
+impl Detection {
+    pub fn describe(&self) -> String {
+        format!("Coaching you as position {} ({}): {}", self.position, role_name(self.position), self.reasons.join(", "))
+    }
+}
+
+// Works out the player's position (1-5) over the laning stage from where their hero
+// stands, how much it farms and what it buys. The guess is final once laning is over.
+pub struct RoleDetector {
+    sides: HashMap<MapSide, u32>,
+    roaming: u32,
+    support_items: Vec<&'static str>,
+    detected: Option<Detection>,
+}
+
+impl RoleDetector {
+    pub fn new() -> Self {
+        RoleDetector {
+            sides: HashMap::new(),
+            roaming: 0,
+            support_items: Vec::new(),
+            detected: None,
+        }
+    }
+
+    pub fn detected(&self) -> Option<&Detection> {
+        self.detected.as_ref()
+    }
+
+    // Take in one payload. Returns the detection when the position changed.
+    pub fn update(&mut self, state: &GameState, clock_time: i32) -> Option<&Detection> {
+        if !(0..LANING_UNTIL).contains(&clock_time) || state.is_spectating() {
+            return None;
+        }
+        if let Some(position) = state.hero_position() {
+            if between_lanes(position) {
+                self.roaming += 1;
+            } else {
+                *self.sides.entry(MapSide::of(position)).or_default() += 1;
+            }
+        }
+        for class in state.inventory().map(|i| i.classes()).unwrap_or_default() {
+            if let Some(item) = SUPPORT_ITEMS.iter().find(|&&s| s == class) {
+                if !self.support_items.contains(item) {
+                    self.support_items.push(item);
+                }
+            }
+        }
+
+        let team = state.player_team().and_then(|t| Team::from_name(&t))?;
+        let detection = self.detect(state, team, clock_time).filter(|_| clock_time >= DECIDE_FROM)?;
+        let changed = self.detected.as_ref().map(|d| d.position != detection.position).unwrap_or(true);
+        self.detected = Some(detection);
+        if changed { self.detected.as_ref() } else { None }
+    }
+
+    fn detect(&self, state: &GameState, team: Team, clock_time: i32) -> Option<Detection> {
+        let total = self.roaming + self.sides.values().sum::<u32>();
+        if total < MIN_SIGHTINGS {
+            return None;
+        }
+        let (side, count) = self.sides.iter().max_by_key(|(_, count)| **count)?;
+        let lane = if *count as f32 / total as f32 >= LANE_SHARE {
+            Assignment::Lane(lane_of(*side, team))
+        } else {
+            Assignment::Roaming
+        };
+        let mut reasons = vec![match lane {
+            Assignment::Lane(lane) => format!("mostly in the {}", lane.name()),
+            Assignment::Roaming => "moving between lanes".to_string(),
+        }];
+
+        // Each farm or shopping sign counts for a core or a support
+        let (mut core, mut support) = (0, 0);
+        let player = state.player();
+        let minutes = clock_time as f32 / 60.0;
+        if let Some(gpm) = player.and_then(|p| p.gpm) {
+            if gpm >= CORE_GPM {
+                core += 1;
+                reasons.push(format!("{} GPM", gpm));
+            } else if gpm <= SUPPORT_GPM {
+                support += 1;
+                reasons.push(format!("{} GPM", gpm));
+            }
+        }
+        if let Some(last_hits) = player.and_then(|p| p.last_hits) {
+            let per_minute = last_hits as f32 / minutes;
+            if per_minute >= CORE_LAST_HITS_PER_MINUTE {
+                core += 1;
+                reasons.push(format!("{} last hits", last_hits));
+            } else if per_minute <= SUPPORT_LAST_HITS_PER_MINUTE {
+                support += 1;
+                reasons.push(format!("{} last hits", last_hits));
+            }
+        }
+        if !self.support_items.is_empty() {
+            let names: Vec<&str> = self.support_items.iter().filter_map(|c| items::by_class(c)).map(|i| i.name).collect();
+            reasons.push(format!("bought {}", names.join(", ")));
+            support += if self.support_items.len() >= SUPPORT_ITEMS_NEEDED { 2 } else { 1 };
+        }
+
+        let supporting = support > core;
+        let position = match (lane, supporting) {
+            (Assignment::Lane(Lane::Mid), false) => 2,
+            (Assignment::Lane(Lane::Safe), false) => 1,
+            (Assignment::Lane(Lane::Off), false) => 3,
+            (Assignment::Lane(Lane::Safe), true) => 5,
+            (Assignment::Lane(Lane::Off | Lane::Mid), true) | (Assignment::Roaming, _) => 4,
+        };
+        Some(Detection { position, lane, reasons })
+    }
+}
+
+impl Default for RoleDetector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,169 @@
+// src/roshan.rs
+use crate::format::format_game_time;
+use crate::state::Team;
+
+// Roshan respawns somewhere between 8 and 11 minutes after he dies
+pub const RESPAWN_MIN: i32 = 8 * 60;
+pub const RESPAWN_MAX: i32 = 11 * 60;
+
+// The Aegis disappears this long after it is picked up
+pub const AEGIS_DURATION: i32 = 5 * 60;
+
+// Seconds of warning before the Aegis runs out
+const AEGIS_WARNING: i32 = 30;
+
+// Where Roshan is in his respawn cycle, in clock time
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RoshanStatus {
+    // Not killed yet this match, or certainly back
+    Alive,
+    Dead { respawn_from: i32, respawn_by: i32 },
+    // Inside the respawn window
+    MaybeUp { respawn_by: i32 },
+}
+
+#[derive(Clone, Debug)]
+pub struct Aegis {
+    // None when the payload didn't say who took it
+    pub team: Option<Team>,
+    pub picked_up_at: i32,
+    pub snatched: bool,
+    // Hero carrying it, when the payload shows who
+    pub holder: Option<String>,
+}
+
+impl Aegis {
+    pub fn expires_at(&self) -> i32 {
+        self.picked_up_at + AEGIS_DURATION
+    }
+
+    // How to play around it while it's live, for a player on `team`
+    pub fn advice(&self, team: Option<Team>) -> Option<String> {
+        let holder = self.holder.as_ref().map(|h| format!(" on {}", h)).unwrap_or_default();
+        let until = format_game_time(Some(self.expires_at()));
+        match (self.team?, team?) {
+            (holder_team, team) if holder_team == team => Some(format!(
+                "Your team has the Aegis{} until {} - force fights and take high ground before it runs out", holder, until)),
+            _ => Some(format!(
+                "They have the Aegis{} until {} - avoid even fights and hold under your towers until it runs out", holder, until)),
+        }
+    }
+}
+
+// A point in the cycle worth telling the player about
+#[derive(Clone, Debug)]
+pub enum RoshanAlert {
+    WindowOpen { respawn_by: i32 },
+    Up,
+    AegisExpiring(Aegis),
+}
+
+// Roshan kills and the Aegis, fed by the GSI events block
+pub struct RoshanTracker {
+    killed_at: Option<i32>,
+    killed_by: Option<Team>,
+    kills: i32,
+    aegis: Option<Aegis>,
+    // Alerts already given for the current death and Aegis
+    window_announced: bool,
+    up_announced: bool,
+    aegis_warned: bool,
+}
+
+impl RoshanTracker {
+    pub fn new() -> Self {
+        RoshanTracker {
+            killed_at: None,
+            killed_by: None,
+            kills: 0,
+            aegis: None,
+            window_announced: false,
+            up_announced: false,
+            aegis_warned: false,
+        }
+    }
+
+    pub fn killed(&mut self, clock_time: i32, team: Option<Team>) {
+        self.killed_at = Some(clock_time);
+        self.killed_by = team;
+        self.kills += 1;
+        self.window_announced = false;
+        self.up_announced = false;
+    }
+
+    pub fn aegis_picked_up(&mut self, clock_time: i32, team: Option<Team>, snatched: bool) {
+        self.aegis = Some(Aegis { team, picked_up_at: clock_time, snatched, holder: None });
+        self.aegis_warned = false;
+    }
+
+    // Who carries the current Aegis, once known
+    pub fn set_aegis_holder(&mut self, hero: String) {
+        if let Some(aegis) = self.aegis.as_mut() {
+            aegis.holder = Some(hero);
+        }
+    }
+
+    // Its holder died and came back with it, so it's gone
+    pub fn aegis_used(&mut self) -> Option<Aegis> {
+        self.aegis.take()
+    }
+
+    // Denied, so nobody holds it
+    pub fn aegis_denied(&mut self) {
+        self.aegis = None;
+    }
+
+    // Times Roshan died this match
+    pub fn kills(&self) -> i32 {
+        self.kills
+    }
+
+    // Team that took the last Roshan
+    pub fn killed_by(&self) -> Option<Team> {
+        self.killed_by
+    }
+
+    pub fn status(&self, clock_time: i32) -> RoshanStatus {
+        match self.killed_at {
+            Some(at) if clock_time < at + RESPAWN_MIN => RoshanStatus::Dead { respawn_from: at + RESPAWN_MIN, respawn_by: at + RESPAWN_MAX },
+            Some(at) if clock_time < at + RESPAWN_MAX => RoshanStatus::MaybeUp { respawn_by: at + RESPAWN_MAX },
+            _ => RoshanStatus::Alive,
+        }
+    }
+
+    // The Aegis while it is still held. Its holder dying early is not reported, so this is an upper bound.
+    pub fn aegis(&self, clock_time: i32) -> Option<&Aegis> {
+        self.aegis.as_ref().filter(|a| clock_time < a.expires_at())
+    }
+
+    // Window openings, respawns and expiring Aegis that haven't been announced yet
+    pub fn due_alerts(&mut self, clock_time: i32) -> Vec<RoshanAlert> {
+        let mut due = Vec::new();
+        match self.status(clock_time) {
+            RoshanStatus::MaybeUp { respawn_by } if !self.window_announced => {
+                self.window_announced = true;
+                due.push(RoshanAlert::WindowOpen { respawn_by });
+            },
+            RoshanStatus::Alive if self.killed_at.is_some() && !self.up_announced => {
+                self.window_announced = true;
+                self.up_announced = true;
+                due.push(RoshanAlert::Up);
+            },
+            _ => {},
+        }
+
+        if let Some(aegis) = self.aegis(clock_time).cloned() {
+            if !self.aegis_warned && aegis.expires_at() - clock_time <= AEGIS_WARNING {
+                self.aegis_warned = true;
+                due.push(RoshanAlert::AegisExpiring(aegis));
+            }
+        }
+        due
+    }
+}
+
+impl Default for RoshanTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
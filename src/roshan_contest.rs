@@ -0,0 +1,162 @@
+// src/roshan_contest.rs
+use std::collections::HashMap;
+
+use crate::regions::{Shape, REGIONS};
+use crate::respawns::DeadHero;
+use crate::tracking::{calculate_distance, EnemyHeroState};
+
+// Nobody goes for the first Roshan much before this
+pub const CONTEST_FROM: i32 = 12 * 60;
+
+// An enemy last seen this close to a pit may be heading there
+const NEAR_PIT: f32 = 3000.0;
+
+// An enemy seen this far from both pits in the last few seconds isn't at Roshan
+const FAR_FROM_PIT: f32 = 5000.0;
+const SEEN_RECENTLY: i32 = 5;
+
+// Enemies dead for at least this much longer can't come to the pit
+const DEAD_FOR: i32 = 30;
+
+// Points a call needs, and how often it's repeated while it holds
+const CALL_AT: i32 = 3;
+const REPEAT_EVERY: i32 = 120;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ContestKind {
+    // The enemy may be at Roshan
+    EnemyTaking,
+    // The player's team can go for him
+    Opportunity,
+}
+
+#[derive(Clone, Debug)]
+pub struct RoshanCall {
+    pub kind: ContestKind,
+    pub reasons: Vec<String>,
+}
+
+impl RoshanCall {
+    pub fn describe(&self) -> String {
+        let call = match self.kind {
+            ContestKind::EnemyTaking => "Roshan may be being taken - check the pit or smoke in to contest",
+            ContestKind::Opportunity => "Chance to take Roshan - group up and go",
+        };
+        format!("{} ({})", call, self.reasons.join(", "))
+    }
+}
+
+fn pits() -> impl Iterator<Item = (i32, i32)> {
+    REGIONS.iter()
+        .filter(|r| r.name.ends_with("Roshan pit"))
+        .filter_map(|r| match r.shape {
+            Shape::Circle { center, .. } => Some(center),
+            _ => None,
+        })
+}
+
+fn distance_to_pit(position: (i32, i32)) -> f32 {
+    pits().map(|pit| calculate_distance(pit, position)).fold(f32::MAX, f32::min)
+}
+
+// What the team can know about Roshan right now
+pub struct Situation<'a> {
+    pub game_time: i32,
+    pub night: bool,
+    pub enemies: &'a HashMap<String, EnemyHeroState>,
+    pub enemies_dead: &'a [&'a DeadHero],
+    // Seconds unseen for an enemy to count as missing, and how many make a worry
+    pub missing_after: i32,
+    pub missing_count: usize,
+    // The player's smokes, and whether one of their team's wards watches a pit
+    pub smokes: i32,
+    pub pit_warded: bool,
+}
+
+// Whether the enemy is likely at Roshan, or the player's team has a window to take him.
+// Each sign counts for one or two points; a call needs CALL_AT.
+pub fn assess(situation: &Situation) -> Option<RoshanCall> {
+    let game_time = situation.game_time;
+    let down = situation.enemies_dead.iter().filter(|d| d.remaining(game_time) >= DEAD_FOR).count();
+    let missing: Vec<&EnemyHeroState> = situation.enemies.values()
+        .filter(|e| game_time - e.last_seen_time >= situation.missing_after)
+        .collect();
+    let elsewhere = situation.enemies.values()
+        .filter(|e| game_time - e.last_seen_time <= SEEN_RECENTLY && distance_to_pit(e.last_seen_position) > FAR_FROM_PIT)
+        .count();
+
+    // The enemy sneaking Roshan
+    if missing.len() >= situation.missing_count {
+        let mut score = 2;
+        let mut reasons = vec![format!("{} enemies missing", missing.len())];
+        let near: Vec<&str> = missing.iter()
+            .filter(|e| distance_to_pit(e.last_seen_position) <= NEAR_PIT)
+            .map(|e| e.name.as_str())
+            .collect();
+        if !near.is_empty() {
+            score += 1;
+            reasons.push(format!("{} last seen near the pit", near.join(", ")));
+        }
+        if situation.night {
+            score += 1;
+            reasons.push("it's night".to_string());
+        }
+        if situation.pit_warded {
+            score -= 2;
+        }
+        if score >= CALL_AT {
+            return Some(RoshanCall { kind: ContestKind::EnemyTaking, reasons });
+        }
+    }
+
+    // The player's team taking it while the enemy can't answer
+    let mut score = 0;
+    let mut reasons = Vec::new();
+    if down >= 2 {
+        score += 2;
+        reasons.push(format!("{} enemies dead for {}s+", down, DEAD_FOR));
+    }
+    if elsewhere >= 3 {
+        score += 2;
+        reasons.push(format!("{} enemies seen far from the pit", elsewhere));
+    }
+    if situation.smokes > 0 {
+        score += 1;
+        reasons.push("you have a smoke".to_string());
+    }
+    if situation.night {
+        score += 1;
+        reasons.push("night cover".to_string());
+    }
+    (score >= CALL_AT && (down >= 2 || elsewhere >= 3)).then_some(RoshanCall { kind: ContestKind::Opportunity, reasons })
+}
+
+// Says each kind of call once, then again every REPEAT_EVERY while it holds
+pub struct ContestWatch {
+    last_call: Option<(ContestKind, i32)>,
+}
+
+impl ContestWatch {
+    pub fn new() -> Self {
+        ContestWatch {
+            last_call: None,
+        }
+    }
+
+    pub fn due(&mut self, call: &RoshanCall, game_time: i32) -> bool {
+        let due = match self.last_call {
+            Some((kind, at)) => kind != call.kind || game_time - at >= REPEAT_EVERY,
+            None => true,
+        };
+        if due {
+            self.last_call = Some((call.kind, game_time));
+        }
+        due
+    }
+}
+
+impl Default for ContestWatch {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,127 @@
+// src/rotations.rs
+use std::collections::{HashMap, VecDeque};
+
+use crate::briefing::Lane;
+use crate::buildings::{BuildingKind, BuildingStatus};
+use crate::lane_assignment::{Assignment, LaneTracker};
+use crate::missing::MapSide;
+use crate::objectives::lane_side;
+use crate::tracking::EnemyHeroState;
+
+// Rotations matter once the laning stage is over
+pub const ROTATIONS_FROM: i32 = 10 * 60;
+
+// A tower losing this much of its health within PRESSURE_WINDOW is under pressure
+const PRESSURE_DROP: i32 = 5;
+const PRESSURE_WINDOW: i32 = 10;
+
+// Enemies seen this recently count as being on a lane
+const SHOWED_WITHIN: i32 = 15;
+
+// Enemies pushing a lane that make it worth rotating even before the tower takes damage
+const PUSHERS: usize = 2;
+
+#[derive(Clone, Debug)]
+pub struct Rotation {
+    pub tower: BuildingStatus,
+    pub side: MapSide,
+    // Health percent lost over the pressure window
+    pub damage: i32,
+    pub enemies_seen: Vec<String>,
+    // The enemy mid went missing, a common sign of a rotation of their own
+    pub mid_missing: Option<String>,
+}
+
+impl Rotation {
+    // e.g. "Enemy mid Lina missing and your bot T1 is under pressure (lost 8% in 10s) - consider TPing bot"
+    pub fn describe(&self) -> String {
+        let mut pressure = format!("your {} {} is under pressure", self.side.name(), self.tower.kind.name());
+        if self.damage > 0 {
+            pressure.push_str(&format!(" (lost {}% in {}s)", self.damage, PRESSURE_WINDOW));
+        }
+        if !self.enemies_seen.is_empty() {
+            pressure.push_str(&format!(", {} there", self.enemies_seen.join(", ")));
+        }
+        match &self.mid_missing {
+            Some(mid) => format!("Enemy mid {} missing and {} - consider TPing {}", mid, pressure, self.side.name()),
+            None => format!("{}{} - consider TPing {}", pressure[..1].to_uppercase(), &pressure[1..], self.side.name()),
+        }
+    }
+}
+
+// Watches the player's towers and suggests where to rotate
+pub struct RotationAdvisor {
+    // Recent (game time, health percent) samples per building key
+    history: HashMap<String, VecDeque<(i32, i32)>>,
+}
+
+impl RotationAdvisor {
+    pub fn new() -> Self {
+        RotationAdvisor {
+            history: HashMap::new(),
+        }
+    }
+
+    fn damage_taken(&mut self, tower: &BuildingStatus, game_time: i32) -> i32 {
+        let samples = self.history.entry(tower.key.clone()).or_default();
+        samples.push_back((game_time, tower.health_percent()));
+        while samples.front().map(|(t, _)| *t < game_time - PRESSURE_WINDOW).unwrap_or(false) {
+            samples.pop_front();
+        }
+        samples.front().map(|(_, health)| health - tower.health_percent()).unwrap_or(0).max(0)
+    }
+
+    // The lane most worth rotating to, if any. `own_buildings` are the player's team's.
+    // Call it every update so tower damage is tracked, and act on it from ROTATIONS_FROM.
+    pub fn suggest(
+        &mut self,
+        game_time: i32,
+        own_buildings: &[BuildingStatus],
+        enemies: &HashMap<String, EnemyHeroState>,
+        lanes: &LaneTracker,
+        player_position: (i32, i32),
+        missing_after_seconds: i32,
+    ) -> Option<Rotation> {
+        // The outermost standing tower of each lane is the one being hit
+        let mut fronts: Vec<(MapSide, &BuildingStatus)> = Vec::new();
+        for tower in own_buildings.iter().filter(|b| matches!(b.kind, BuildingKind::Tower(_))) {
+            let Some(side) = lane_side(tower.lane) else { continue };
+            if !fronts.iter().any(|(s, _)| *s == side) {
+                fronts.push((side, tower));
+            }
+        }
+        let damage: Vec<(MapSide, &BuildingStatus, i32)> = fronts.into_iter()
+            .map(|(side, tower)| (side, tower, self.damage_taken(tower, game_time)))
+            .collect();
+
+        let mid_missing = enemies.values()
+            .filter(|e| game_time - e.last_seen_time >= missing_after_seconds)
+            .find(|e| lanes.assignment(&e.name) == Some(Assignment::Lane(Lane::Mid)))
+            .map(|e| e.name.clone());
+        let player_side = MapSide::of(player_position);
+
+        damage.into_iter()
+            .filter(|(side, _, _)| *side != player_side)
+            .filter_map(|(side, tower, damage)| {
+                let enemies_seen: Vec<String> = enemies.values()
+                    .filter(|e| game_time - e.last_seen_time <= SHOWED_WITHIN && MapSide::of(e.last_seen_position) == side)
+                    .map(|e| e.name.clone())
+                    .collect();
+                let pressured = damage >= PRESSURE_DROP || enemies_seen.len() >= PUSHERS;
+                pressured.then(|| Rotation {
+                    tower: tower.clone(),
+                    side,
+                    damage,
+                    enemies_seen,
+                    mid_missing: mid_missing.clone(),
+                })
+            })
+            .max_by_key(|r| r.damage + r.enemies_seen.len() as i32 * PRESSURE_DROP)
+    }
+}
+
+impl Default for RotationAdvisor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
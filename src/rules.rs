@@ -0,0 +1,472 @@
+// src/rules.rs
+use std::cmp::Ordering;
+use std::fmt;
+
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::alerts::{Alert, AlertCategory, Severity};
+use crate::config::RuleConfig;
+use crate::state::GameState;
+
+// Paths starting with this read the payload before the current one
+const PREVIOUS: &str = "previous";
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Number(f64),
+    Text(String),
+    Path(Vec<String>),
+    Bool(bool),
+    Op(&'static str),
+    Open,
+    Close,
+}
+
+// Longest first, so ">=" isn't read as ">" followed by "="
+const OPERATORS: [&str; 14] = ["&&", "||", ">=", "<=", "==", "!=", ">", "<", "!", "+", "-", "*", "/", "%"];
+
+fn tokenize(text: &str) -> Result<Vec<Token>, String> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() {
+            i += 1;
+        } else if c == '(' || c == ')' {
+            tokens.push(if c == '(' { Token::Open } else { Token::Close });
+            i += 1;
+        } else if c == '"' || c == '\'' {
+            let end = chars[i + 1..].iter().position(|&e| e == c).ok_or("unterminated string")?;
+            tokens.push(Token::Text(chars[i + 1..i + 1 + end].iter().collect()));
+            i += end + 2;
+        } else if c.is_ascii_digit() {
+            let len = chars[i..].iter().take_while(|c| c.is_ascii_digit() || **c == '.' || **c == '_').count();
+            let number: String = chars[i..i + len].iter().filter(|c| **c != '_').collect();
+            tokens.push(Token::Number(number.parse().map_err(|_| format!("bad number '{}'", number))?));
+            i += len;
+        } else if c.is_alphabetic() || c == '_' {
+            let len = chars[i..].iter().take_while(|c| c.is_alphanumeric() || **c == '_' || **c == '.').count();
+            let word: String = chars[i..i + len].iter().collect();
+            tokens.push(match word.as_str() {
+                "true" => Token::Bool(true),
+                "false" => Token::Bool(false),
+                "and" => Token::Op("&&"),
+                "or" => Token::Op("||"),
+                "not" => Token::Op("!"),
+                _ if word.split('.').any(str::is_empty) => return Err(format!("bad path '{}'", word)),
+                _ => Token::Path(word.split('.').map(str::to_string).collect()),
+            });
+            i += len;
+        } else {
+            let rest: String = chars[i..].iter().take(2).collect();
+            let op = OPERATORS.iter().find(|op| rest.starts_with(*op)).ok_or_else(|| format!("unexpected '{}'", c))?;
+            tokens.push(Token::Op(op));
+            i += op.len();
+        }
+    }
+    Ok(tokens)
+}
+
+#[derive(Clone, Debug)]
+enum Expr {
+    Literal(Operand),
+    Path(Vec<String>),
+    Not(Box<Expr>),
+    Negate(Box<Expr>),
+    Binary(&'static str, Box<Expr>, Box<Expr>),
+}
+
+// Operators from loosest to tightest binding
+const PRECEDENCE: [&[&str]; 5] = [&["||"], &["&&"], &["==", "!=", ">", ">=", "<", "<="], &["+", "-"], &["*", "/", "%"]];
+
+struct Parser {
+    tokens: Vec<Token>,
+    next: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.next)
+    }
+
+    fn take(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.next).cloned();
+        self.next += 1;
+        token
+    }
+
+    fn binary(&mut self, level: usize) -> Result<Expr, String> {
+        let Some(operators) = PRECEDENCE.get(level) else { return self.unary() };
+        let mut left = self.binary(level + 1)?;
+        let mut compared = false;
+        while let Some(Token::Op(op)) = self.peek().cloned() {
+            if !operators.contains(&op) {
+                break;
+            }
+            // "a < b < c" almost certainly doesn't mean what it says. "(a < b) < c" is spelled
+            // out, so it's left alone.
+            if level == 2 && compared {
+                return Err("comparisons can't be chained, join them with &&".to_string());
+            }
+            compared = level == 2;
+            self.next += 1;
+            let right = self.binary(level + 1)?;
+            left = Expr::Binary(op, Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn unary(&mut self) -> Result<Expr, String> {
+        match self.take() {
+            Some(Token::Op("!")) => Ok(Expr::Not(Box::new(self.unary()?))),
+            Some(Token::Op("-")) => Ok(Expr::Negate(Box::new(self.unary()?))),
+            Some(Token::Number(n)) => Ok(Expr::Literal(Operand::Number(n))),
+            Some(Token::Text(s)) => Ok(Expr::Literal(Operand::Text(s))),
+            Some(Token::Bool(b)) => Ok(Expr::Literal(Operand::Bool(b))),
+            Some(Token::Path(path)) => Ok(Expr::Path(path)),
+            Some(Token::Open) => {
+                let inner = self.binary(0)?;
+                match self.take() {
+                    Some(Token::Close) => Ok(inner),
+                    _ => Err("missing ')'".to_string()),
+                }
+            },
+            Some(Token::Op(op)) => Err(format!("unexpected '{}'", op)),
+            Some(Token::Close) => Err("unexpected ')'".to_string()),
+            None => Err("unexpected end".to_string()),
+        }
+    }
+}
+
+// A value a condition works with. Anything the payload doesn't have is Missing, and
+// comparisons against it are never true, so a rule stays quiet without the data.
+#[derive(Clone, Debug, PartialEq)]
+enum Operand {
+    Number(f64),
+    Text(String),
+    Bool(bool),
+    Missing,
+}
+
+impl Operand {
+    fn from_json(value: Option<&Value>) -> Self {
+        match value {
+            Some(Value::Number(n)) => n.as_f64().map(Operand::Number).unwrap_or(Operand::Missing),
+            Some(Value::String(s)) => Operand::Text(s.clone()),
+            Some(Value::Bool(b)) => Operand::Bool(*b),
+            _ => Operand::Missing,
+        }
+    }
+
+    fn truthy(&self) -> bool {
+        match self {
+            Operand::Number(n) => *n != 0.0,
+            Operand::Text(s) => !s.is_empty(),
+            Operand::Bool(b) => *b,
+            Operand::Missing => false,
+        }
+    }
+
+    fn compare(&self, other: &Operand) -> Option<Ordering> {
+        match (self, other) {
+            (Operand::Number(a), Operand::Number(b)) => a.partial_cmp(b),
+            // GSI sends team names and game states in varying case
+            (Operand::Text(a), Operand::Text(b)) => Some(a.to_lowercase().cmp(&b.to_lowercase())),
+            (Operand::Bool(a), Operand::Bool(b)) => Some(a.cmp(b)),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Operand {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Operand::Number(n) if n.fract() == 0.0 => write!(f, "{}", *n as i64),
+            Operand::Number(n) => write!(f, "{:.1}", n),
+            Operand::Text(s) => write!(f, "{}", s),
+            Operand::Bool(b) => write!(f, "{}", b),
+            Operand::Missing => write!(f, "?"),
+        }
+    }
+}
+
+// The current payload and the one before it, as JSON
+struct Scope<'a> {
+    current: &'a Value,
+    previous: Option<&'a Value>,
+}
+
+impl Scope<'_> {
+    fn lookup(&self, path: &[String]) -> Operand {
+        let (root, path) = match path.split_first() {
+            Some((first, rest)) if first == PREVIOUS => (self.previous, rest),
+            _ => (Some(self.current), path),
+        };
+        Operand::from_json(root.and_then(|root| path.iter().try_fold(root, |value, key| value.get(key.as_str()))))
+    }
+
+    fn eval(&self, expr: &Expr) -> Operand {
+        match expr {
+            Expr::Literal(value) => value.clone(),
+            Expr::Path(path) => self.lookup(path),
+            Expr::Not(inner) => match self.eval(inner) {
+                Operand::Missing => Operand::Missing,
+                value => Operand::Bool(!value.truthy()),
+            },
+            Expr::Negate(inner) => match self.eval(inner) {
+                Operand::Number(n) => Operand::Number(-n),
+                _ => Operand::Missing,
+            },
+            Expr::Binary("&&", left, right) => Operand::Bool(self.eval(left).truthy() && self.eval(right).truthy()),
+            Expr::Binary("||", left, right) => Operand::Bool(self.eval(left).truthy() || self.eval(right).truthy()),
+            Expr::Binary(op, left, right) => {
+                let (left, right) = (self.eval(left), self.eval(right));
+                if let Some(ordering) = left.compare(&right).filter(|_| PRECEDENCE[2].contains(op)) {
+                    return Operand::Bool(match *op {
+                        "==" => ordering == Ordering::Equal,
+                        "!=" => ordering != Ordering::Equal,
+                        ">" => ordering == Ordering::Greater,
+                        ">=" => ordering != Ordering::Less,
+                        "<" => ordering == Ordering::Less,
+                        _ => ordering != Ordering::Greater,
+                    });
+                }
+                match (left, right) {
+                    (Operand::Number(a), Operand::Number(b)) => match *op {
+                        "+" => Operand::Number(a + b),
+                        "-" => Operand::Number(a - b),
+                        "*" => Operand::Number(a * b),
+                        "/" if b != 0.0 => Operand::Number(a / b),
+                        "%" if b != 0.0 => Operand::Number(a % b),
+                        _ => Operand::Missing,
+                    },
+                    _ if PRECEDENCE[2].contains(op) => Operand::Bool(false),
+                    _ => Operand::Missing,
+                }
+            },
+        }
+    }
+
+    // `{path}` in a message replaced by its value
+    fn fill(&self, template: &str) -> String {
+        let mut text = String::new();
+        let mut rest = template;
+        while let Some(start) = rest.find('{') {
+            let Some(end) = rest[start..].find('}') else { break };
+            let path: Vec<String> = rest[start + 1..start + end].trim().split('.').map(str::to_string).collect();
+            text.push_str(&rest[..start]);
+            text.push_str(&self.lookup(&path).to_string());
+            rest = &rest[start + end + 1..];
+        }
+        text.push_str(rest);
+        text
+    }
+}
+
+// A parsed `when` expression, e.g. "player.gold > hero.buyback_cost && map.clock_time > 1800"
+#[derive(Clone, Debug, Deserialize)]
+#[serde(try_from = "String")]
+pub struct Condition {
+    text: String,
+    expr: Expr,
+}
+
+impl Condition {
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut parser = Parser { tokens: tokenize(text)?, next: 0 };
+        let expr = parser.binary(0)?;
+        if let Some(token) = parser.peek() {
+            return Err(format!("unexpected {:?} after the condition", token));
+        }
+        Ok(Condition { text: text.to_string(), expr })
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+}
+
+impl TryFrom<String> for Condition {
+    type Error = String;
+
+    fn try_from(text: String) -> Result<Self, Self::Error> {
+        Condition::parse(&text).map_err(|e| format!("invalid rule condition '{}': {}", text, e))
+    }
+}
+
+struct RuleState {
+    rule: RuleConfig,
+    // Whether the condition held on the last payload
+    holding: bool,
+    last_raised: Option<i32>,
+}
+
+// The user's [[rules]] from the config, checked against every new payload. A rule raises its
+// alert when its condition becomes true, and again every `repeat_secs` while it stays true.
+pub struct RuleEngine {
+    rules: Vec<RuleState>,
+    previous: Option<Value>,
+}
+
+impl RuleEngine {
+    pub fn new(rules: &[RuleConfig]) -> Self {
+        RuleEngine {
+            rules: rules.iter().cloned().map(|rule| RuleState { rule, holding: false, last_raised: None }).collect(),
+            previous: None,
+        }
+    }
+
+    // Alerts for the rules this payload sets off
+    pub fn update(&mut self, state: &GameState, game_time: i32) -> Vec<Alert> {
+        if self.rules.is_empty() {
+            return Vec::new();
+        }
+        let Ok(current) = serde_json::to_value(state) else { return Vec::new() };
+        let scope = Scope { current: &current, previous: self.previous.as_ref() };
+
+        let mut alerts = Vec::new();
+        for state in &mut self.rules {
+            let holding = scope.eval(&state.rule.when.expr).truthy();
+            let repeat = state.rule.repeat_secs
+                .zip(state.last_raised)
+                .map(|(every, last)| game_time - last >= every.max(1))
+                .unwrap_or(false);
+            if holding && (!state.holding || repeat) {
+                let mut alert = Alert::new(state.rule.severity.unwrap_or(Severity::Warning), AlertCategory::Rule,
+                    scope.fill(&state.rule.message))
+                    .with_key(state.rule.name.clone());
+                if let Some(speech) = &state.rule.speech {
+                    alert = alert.with_speech(scope.fill(speech));
+                }
+                // Gone by the time it repeats, as the same message isn't shown twice at once
+                if let Some(every) = state.rule.repeat_secs {
+                    alert.ttl = alert.ttl.min(every.max(1));
+                }
+                alerts.push(alert);
+                state.last_raised = Some(game_time);
+            }
+            state.holding = holding;
+        }
+        self.previous = Some(current);
+        alerts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn eval(condition: &str, current: Value) -> Operand {
+        let condition = Condition::parse(condition).unwrap();
+        Scope { current: &current, previous: None }.eval(&condition.expr)
+    }
+
+    fn rule(when: &str, repeat_secs: Option<i32>) -> RuleConfig {
+        RuleConfig {
+            name: "rich".to_string(),
+            when: Condition::parse(when).unwrap(),
+            message: "Gold: {player.gold}".to_string(),
+            severity: None,
+            speech: None,
+            repeat_secs,
+        }
+    }
+
+    fn state(gold: i32) -> GameState {
+        serde_json::from_value(json!({ "player": { "gold": gold } })).unwrap()
+    }
+
+    #[test]
+    fn tokenizer_reads_numbers_strings_paths_and_operators() {
+        assert_eq!(tokenize("player.gold >= 1_500 and map.game_state != 'x'").unwrap(), vec![
+            Token::Path(vec!["player".to_string(), "gold".to_string()]),
+            Token::Op(">="),
+            Token::Number(1500.0),
+            Token::Op("&&"),
+            Token::Path(vec!["map".to_string(), "game_state".to_string()]),
+            Token::Op("!="),
+            Token::Text("x".to_string()),
+        ]);
+        assert_eq!(tokenize("!(true)").unwrap(), vec![Token::Op("!"), Token::Open, Token::Bool(true), Token::Close]);
+        assert!(tokenize("\"open").is_err());
+        assert!(tokenize("player..gold").is_err());
+        assert!(tokenize("a # b").is_err());
+    }
+
+    #[test]
+    fn operators_bind_by_precedence() {
+        assert_eq!(eval("1 + 2 * 3", json!({})), Operand::Number(7.0));
+        assert_eq!(eval("(1 + 2) * 3", json!({})), Operand::Number(9.0));
+        assert_eq!(eval("-2 * 3 + 10 % 4", json!({})), Operand::Number(-4.0));
+        assert_eq!(eval("true || false && false", json!({})), Operand::Bool(true));
+        assert_eq!(eval("1 + 1 == 2 && !false", json!({})), Operand::Bool(true));
+    }
+
+    #[test]
+    fn chained_comparisons_are_rejected() {
+        assert!(Condition::parse("1 < 2 < 3").is_err());
+        assert!(Condition::parse("1 == 1 != false").is_err());
+        assert!(Condition::parse("1 < 2 && 2 < 3").is_ok());
+    }
+
+    #[test]
+    fn parenthesised_comparisons_can_be_compared() {
+        assert_eq!(eval("(1 < 2) == true", json!({})), Operand::Bool(true));
+        assert_eq!(eval("(2 < 1) != (1 < 2)", json!({})), Operand::Bool(true));
+        assert!(Condition::parse("(1 < 2) < 3").is_ok());
+    }
+
+    #[test]
+    fn missing_values_never_compare_true() {
+        let state = json!({ "player": { "gold": 100 } });
+        assert_eq!(eval("hero.health < 10", state.clone()), Operand::Bool(false));
+        assert_eq!(eval("hero.health >= 10", state.clone()), Operand::Bool(false));
+        assert_eq!(eval("hero.health + 1", state.clone()), Operand::Missing);
+        assert_eq!(eval("!hero.alive", state.clone()), Operand::Missing);
+        assert_eq!(eval("player.gold / 0", state.clone()), Operand::Missing);
+        assert!(!eval("!hero.alive", state.clone()).truthy());
+        assert!(eval("player.gold > 50 || hero.health < 10", state).truthy());
+    }
+
+    #[test]
+    fn fill_replaces_paths_with_values() {
+        let current = json!({ "player": { "gold": 1234, "name": "me" }, "hero": { "health_percent": 42.5 } });
+        let previous = json!({ "player": { "gold": 1000 } });
+        let scope = Scope { current: &current, previous: Some(&previous) };
+        assert_eq!(scope.fill("{player.name} has { player.gold } gold, was {previous.player.gold}"),
+            "me has 1234 gold, was 1000");
+        assert_eq!(scope.fill("{hero.health_percent}% and {hero.mana}"), "42.5% and ?");
+        assert_eq!(scope.fill("left {open"), "left {open");
+    }
+
+    #[test]
+    fn rules_raise_on_becoming_true_and_repeat_while_holding() {
+        let mut once = RuleEngine::new(&[rule("player.gold > 1000", None)]);
+        assert!(once.update(&state(500), 0).is_empty());
+        let alerts = once.update(&state(1500), 10);
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].message, "Gold: 1500");
+        assert!(once.update(&state(1600), 500).is_empty());
+        assert!(once.update(&state(500), 510).is_empty());
+        assert_eq!(once.update(&state(1500), 520).len(), 1);
+
+        let mut repeating = RuleEngine::new(&[rule("player.gold > 1000", Some(60))]);
+        assert_eq!(repeating.update(&state(1500), 0).len(), 1);
+        assert!(repeating.update(&state(1500), 59).is_empty());
+        let alerts = repeating.update(&state(1500), 60);
+        assert_eq!(alerts.len(), 1);
+        assert!(alerts[0].ttl <= 60);
+        assert!(repeating.update(&state(500), 200).is_empty());
+    }
+
+    #[test]
+    fn previous_reads_the_payload_before() {
+        let mut engine = RuleEngine::new(&[rule("player.gold < previous.player.gold - 200", None)]);
+        assert!(engine.update(&state(1000), 0).is_empty());
+        assert_eq!(engine.update(&state(700), 1).len(), 1);
+    }
+}
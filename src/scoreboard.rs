@@ -0,0 +1,73 @@
+// src/scoreboard.rs
+use crate::format::{format_hero_name, format_item_name};
+use crate::state::{GameState, Team};
+
+// Cheap items that say nothing about a hero's build
+const CONSUMABLES: [&str; 12] = [
+    "item_tango",
+    "item_clarity",
+    "item_flask",
+    "item_faerie_fire",
+    "item_enchanted_mango",
+    "item_branches",
+    "item_ward_observer",
+    "item_ward_sentry",
+    "item_ward_dispenser",
+    "item_smoke_of_deceit",
+    "item_dust",
+    "item_tpscroll",
+];
+
+// One line of the scoreboard
+#[derive(Clone, Debug)]
+pub struct ScoreboardRow {
+    pub team: Team,
+    pub player_name: String,
+    pub steam_id: Option<String>,
+    pub hero: String,
+    pub level: i32,
+    pub net_worth: i32,
+    pub kills: i32,
+    pub deaths: i32,
+    pub assists: i32,
+    pub alive: bool,
+    pub key_items: Vec<String>,
+}
+
+// All ten players of a spectator payload, Radiant first. Empty when playing.
+pub fn scoreboard(state: &GameState) -> Vec<ScoreboardRow> {
+    state.spectated_players().into_iter()
+        .map(|p| {
+            let player = p.player;
+            let hero = p.hero;
+
+            // Net worth is only sent to spectators with full access; fall back to what the items are worth
+            let item_value = p.items
+                .map(|items| crate::items::value_of(items.all_items().filter_map(|i| i.name.as_deref())))
+                .unwrap_or(0);
+
+            // Main inventory only, in slot order
+            let key_items = p.items
+                .map(|items| items.main_items()
+                    .filter_map(|item| item.name.as_deref())
+                    .filter(|name| !CONSUMABLES.contains(name))
+                    .map(format_item_name)
+                    .collect())
+                .unwrap_or_default();
+
+            ScoreboardRow {
+                team: p.team,
+                player_name: player.and_then(|p| p.name.clone()).unwrap_or_else(|| format!("Player {}", p.slot + 1)),
+                steam_id: player.and_then(|p| p.steamid.clone()),
+                hero: hero.and_then(|h| h.name.as_deref()).map(format_hero_name).unwrap_or_else(|| "Unknown".to_string()),
+                level: hero.and_then(|h| h.level).unwrap_or(0),
+                net_worth: player.and_then(|p| p.net_worth).unwrap_or(item_value),
+                kills: player.and_then(|p| p.kills).unwrap_or(0),
+                deaths: player.and_then(|p| p.deaths).unwrap_or(0),
+                assists: player.and_then(|p| p.assists).unwrap_or(0),
+                alive: hero.and_then(|h| h.alive).unwrap_or(true),
+                key_items,
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,72 @@
+// src/series.rs
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+// Seconds between two position samples, and between two stored sightings of the same enemy
+pub const SERIES_INTERVAL: i32 = 10;
+
+// Where the player's hero was at a game time
+#[derive(Clone, Copy, Debug, Serialize)]
+pub struct PositionSample {
+    pub time: i32,
+    pub x: i32,
+    pub y: i32,
+}
+
+// An enemy hero seen on the minimap
+#[derive(Clone, Debug, Serialize)]
+pub struct Sighting {
+    pub time: i32,
+    pub hero: String,
+    pub x: i32,
+    pub y: i32,
+}
+
+// Positions and sightings over a match, thinned to one per SERIES_INTERVAL so a
+// whole game fits in the match history
+pub struct MatchSeries {
+    positions: Vec<PositionSample>,
+    sightings: Vec<Sighting>,
+    // Game time each enemy was last stored
+    last_sighting: HashMap<String, i32>,
+}
+
+impl MatchSeries {
+    pub fn new() -> Self {
+        MatchSeries {
+            positions: Vec::new(),
+            sightings: Vec::new(),
+            last_sighting: HashMap::new(),
+        }
+    }
+
+    pub fn record_position(&mut self, time: i32, position: (i32, i32)) {
+        if self.positions.last().map(|p| time < p.time + SERIES_INTERVAL).unwrap_or(false) {
+            return;
+        }
+        self.positions.push(PositionSample { time, x: position.0, y: position.1 });
+    }
+
+    pub fn record_sighting(&mut self, time: i32, hero: &str, position: (i32, i32)) {
+        if self.last_sighting.get(hero).map(|&last| time < last + SERIES_INTERVAL).unwrap_or(false) {
+            return;
+        }
+        self.last_sighting.insert(hero.to_string(), time);
+        self.sightings.push(Sighting { time, hero: hero.to_string(), x: position.0, y: position.1 });
+    }
+
+    pub fn positions(&self) -> &[PositionSample] {
+        &self.positions
+    }
+
+    pub fn sightings(&self) -> &[Sighting] {
+        &self.sightings
+    }
+}
+
+impl Default for MatchSeries {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,341 @@
+// src/server.rs
+use std::collections::VecDeque;
+use std::convert::Infallible;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use colored::Colorize;
+use futures_util::{stream, SinkExt, Stream, StreamExt};
+use serde::Deserialize;
+use serde_json::Value;
+use tokio::runtime::Handle;
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::mpsc::{self, error::TrySendError};
+use tokio::sync::watch;
+use warp::http::StatusCode;
+use warp::ws::{Message, WebSocket};
+use warp::Filter;
+
+use crate::events::EventKind;
+use crate::feed::{Feed, FeedKind, FeedMessage};
+use crate::llm::{self, LlmClient};
+use crate::mqtt::Mqtt;
+use crate::opendota::OpenDotaClient;
+use crate::presence::Presence;
+use crate::review::Recorder;
+use crate::snapshot;
+use crate::steam::SteamClient;
+use crate::stratz::StratzClient;
+use crate::terminal::print::{print_draft, print_event};
+use crate::twitch::{self, TwitchBot};
+use crate::{Coach, GameState};
+
+// How long an SSE client waits before reconnecting
+const SSE_RETRY: Duration = Duration::from_secs(3);
+
+// Browser source page for streamers, fed by /ws
+const OVERLAY: &str = include_str!("overlay.html");
+
+// Payloads waiting for the coach. Dota posts a few a second, so a full queue means the coach
+// is stuck, and newer payloads are dropped until it catches up.
+const BACKLOG: usize = 32;
+
+// The coach and what's fed from it, shared by the GSI endpoint, the terminal and background fetches
+#[derive(Clone)]
+pub struct Shared {
+    pub coach: Arc<Mutex<Coach>>,
+    pub feed: Arc<Feed>,
+    pub recorder: Option<Arc<Mutex<Recorder>>>,
+}
+
+// Clients that fill the coach in from outside the game, and outputs that follow each update
+#[derive(Clone, Default)]
+pub struct Services {
+    pub opendota: Option<OpenDotaClient>,
+    pub stratz: Option<StratzClient>,
+    pub steam: Option<SteamClient>,
+    pub llm: Option<LlmClient>,
+    pub mqtt: Option<Mqtt>,
+    // Only when end-of-game summaries go to chat
+    pub twitch: Option<TwitchBot>,
+}
+
+// Start the thread that feeds payloads through the coach, in the order they arrived. Updates
+// write to SQLite, run plugins and notify alert outputs, so they stay off the async runtime.
+pub fn spawn_updates(shared: Shared, services: Services, presence: watch::Sender<Option<Presence>>) -> mpsc::Sender<Value> {
+    let (payloads, mut incoming) = mpsc::channel::<Value>(BACKLOG);
+    let runtime = Handle::current();
+    thread::spawn(move || {
+        // Background fetches are spawned onto the runtime from here
+        let _runtime = runtime.enter();
+        while let Some(data) = incoming.blocking_recv() {
+            update(&shared, &services, &presence, data);
+        }
+    });
+    payloads
+}
+
+// One GSI payload: update the coach, print and publish what happened, and start any
+// fetches the coach is now waiting on
+fn update(shared: &Shared, services: &Services, presence: &watch::Sender<Option<Presence>>, data: Value) {
+    let state = match GameState::deserialize(&data) {
+        Ok(state) => state,
+        Err(e) => {
+            eprintln!("Error parsing game state: {}", e);
+            return;
+        }
+    };
+    let mut coach = shared.coach.lock().unwrap();
+    // Only what the coach accepts is recorded, so other local processes can't write to it
+    if !coach.authorized(&state) {
+        return;
+    }
+    if let Some(recorder) = &shared.recorder {
+        if let Err(e) = recorder.lock().unwrap().record(data) {
+            eprintln!("Could not write to the recording: {}", e);
+        }
+    }
+    let events = coach.update(state);
+    for event in &events {
+        print_event(event, &coach);
+    }
+    let snapshot = snapshot::snapshot(&coach);
+    if let Some(mqtt) = &services.mqtt {
+        mqtt.publish_timers(&snapshot);
+    }
+    shared.feed.publish(snapshot, &events);
+    presence.send_replace(coach.game_state().and_then(Presence::from_state));
+
+    if let Some(twitch) = &services.twitch {
+        if events.iter().any(|e| e.kind == EventKind::GameEnded) {
+            if let Some(report) = coach.performance().report() {
+                twitch.announce(&twitch::game_summary(&report, coach.game_state()));
+            }
+        }
+    }
+
+    // Redraw the draft after every pick or ban
+    if events.iter().any(|e| e.kind == EventKind::Draft) {
+        if let Some(draft) = coach.draft() {
+            print_draft(draft, coach.hero_win_rates());
+        }
+    }
+
+    // Fetch the hero's benchmarks in the background once it's known
+    if let (Some(client), Some(hero_id)) = (services.opendota.clone(), coach.wanted_benchmarks()) {
+        let coach = shared.coach.clone();
+        tokio::spawn(async move {
+            match client.hero_benchmarks(hero_id).await {
+                Ok(benchmarks) => coach.lock().unwrap().set_hero_benchmarks(benchmarks),
+                Err(e) => eprintln!("{} - grading against fixed targets instead", e.yellow()),
+            }
+        });
+    }
+
+    // This week's win rates for the draft, and the hero's builds and matchups once it's picked
+    if let (Some(client), true) = (services.stratz.clone(), coach.wanted_win_rates()) {
+        let coach = shared.coach.clone();
+        tokio::spawn(async move {
+            match client.hero_win_rates().await {
+                Ok(win_rates) => coach.lock().unwrap().set_hero_win_rates(win_rates),
+                Err(e) => eprintln!("{} - drafting without win rates", e.yellow()),
+            }
+        });
+    }
+    if let (Some(client), Some(hero_id)) = (services.stratz.clone(), coach.wanted_meta()) {
+        let coach = shared.coach.clone();
+        tokio::spawn(async move {
+            match client.hero_meta(hero_id).await {
+                Ok(meta) => coach.lock().unwrap().set_hero_meta(meta),
+                Err(e) => eprintln!("{} - using the built-in builds instead", e.yellow()),
+            }
+        });
+    }
+
+    // Persona names and avatars for the player and, when spectating, everyone else
+    for (steamid, steam64) in coach.wanted_steam_profiles() {
+        let (Some(client), coach) = (services.steam.clone(), shared.coach.clone()) else { break };
+        tokio::spawn(async move {
+            match client.profile(steam64).await {
+                Ok(profile) => coach.lock().unwrap().set_steam_profile(steamid, profile),
+                Err(e) => eprintln!("{}", e.dimmed()),
+            }
+        });
+    }
+
+    // A strategic tip from the model every few minutes, shown as soon as it's in
+    if let (Some(client), true) = (services.llm.clone(), coach.wanted_tip()) {
+        if let Some(prompt) = llm::prompt(&coach) {
+            let (coach, feed) = (shared.coach.clone(), shared.feed.clone());
+            tokio::spawn(async move {
+                match client.tip(&prompt).await {
+                    Ok(tip) => {
+                        let mut coach = coach.lock().unwrap();
+                        if let Some(event) = coach.set_tip(tip) {
+                            print_event(&event, &coach);
+                            feed.publish(snapshot::snapshot(&coach), &[event]);
+                        }
+                    },
+                    Err(e) => eprintln!("{}", e.dimmed()),
+                }
+            });
+        }
+    }
+
+    // And the enemy players' records on their heroes, when spectating
+    for (hero, account_id, hero_id) in coach.wanted_profiles() {
+        let (Some(client), coach) = (services.opendota.clone(), shared.coach.clone()) else { break };
+        tokio::spawn(async move {
+            match client.player_hero_profile(account_id, hero_id).await {
+                Ok(profile) => coach.lock().unwrap().set_enemy_profile(hero, profile),
+                Err(e) => eprintln!("{}", format!("{} ({})", e, hero).dimmed()),
+            }
+        });
+    }
+}
+
+// Serve the GSI endpoint, /state, /status, /ws, /events and /overlay on localhost until the
+// runtime shuts down. Payloads go to the update thread from `spawn_updates`.
+pub async fn serve(port: u16, shared: Shared, payloads: mpsc::Sender<Value>) {
+    // The endpoint Dota posts game states to. It only queues them, so the game never waits on the coach.
+    let gsi_endpoint = warp::post()
+        .and(warp::body::content_length_limit(1024 * 1024 * 10))
+        .and(warp::body::json())
+        .map(move |data: Value| {
+            match payloads.try_send(data) {
+                Ok(()) => {},
+                Err(TrySendError::Full(_)) => eprintln!("{}", "The coach is falling behind - skipping a game state".yellow()),
+                Err(TrySendError::Closed(_)) => eprintln!("{}", "The coach stopped - game states are no longer read".red()),
+            }
+            "OK"
+        });
+
+    // And one for other tools on the machine to read the latest state and analysis
+    let coach_state = shared.coach.clone();
+    let state_endpoint = warp::get()
+        .and(warp::path("state"))
+        .and(warp::path::end())
+        .map(move || warp::reply::json(&snapshot::snapshot(&coach_state.lock().unwrap())));
+
+    // Single values for Stream Deck keys and polling widgets, e.g. /status/roshan
+    let coach_status = shared.coach.clone();
+    let status_endpoint = warp::get()
+        .and(warp::path("status"))
+        .and(warp::path::tail())
+        .map(move |name: warp::path::Tail| {
+            let coach = coach_status.lock().unwrap();
+            let status = match name.as_str().trim_end_matches('/') {
+                "" => Some(snapshot::statuses(&coach)),
+                name => snapshot::status(&coach, name),
+            };
+            match status {
+                Some(status) => warp::reply::with_status(warp::reply::json(&status), StatusCode::OK),
+                None => warp::reply::with_status(
+                    warp::reply::json(&serde_json::json!({ "error": "unknown status", "statuses": snapshot::STATUSES })),
+                    StatusCode::NOT_FOUND),
+            }
+        });
+
+    // And a live feed of state changes and events for overlays, bots and dashboards
+    let ws_endpoint = warp::path("ws")
+        .and(warp::path::end())
+        .and(warp::ws())
+        .map({
+            let feed = shared.feed.clone();
+            move |ws: warp::ws::Ws| {
+                let feed = feed.clone();
+                ws.on_upgrade(move |socket| stream_feed(socket, feed))
+            }
+        });
+
+    // A page that shows timers and alerts over a stream, for an OBS browser source
+    let overlay_endpoint = warp::get()
+        .and(warp::path("overlay"))
+        .and(warp::path::end())
+        .map(|| warp::reply::html(OVERLAY));
+
+    // The same events as Server-Sent Events, for clients that can't do WebSockets. Browsers
+    // send back the last id they saw when reconnecting and get what they missed.
+    let feed = shared.feed.clone();
+    let sse_endpoint = warp::get()
+        .and(warp::path("events"))
+        .and(warp::path::end())
+        .and(warp::header::optional::<String>("last-event-id"))
+        .map(move |last_id: Option<String>| {
+            let events = feed_events(feed.clone(), last_id.and_then(|id| id.trim().parse().ok()));
+            warp::sse::reply(warp::sse::keep_alive().stream(events))
+        });
+
+    warp::serve(ws_endpoint.or(sse_endpoint).or(overlay_endpoint).or(state_endpoint).or(status_endpoint).or(gsi_endpoint))
+        .run(([127, 0, 0, 1], port))
+        .await;
+}
+
+
+// Send a WebSocket client the current snapshot, then patches and events until it goes away
+async fn stream_feed(socket: WebSocket, feed: Arc<Feed>) {
+    let (mut outgoing, mut incoming) = socket.split();
+    let (snapshot, mut messages) = feed.subscribe();
+    let send = |message: &FeedMessage| Message::text(message.to_json().to_string());
+    if outgoing.send(send(&snapshot)).await.is_err() {
+        return;
+    }
+    
+    // Messages already covered by the last snapshot sent
+    let mut sent_up_to = snapshot.id;
+    loop {
+        tokio::select! {
+            message = messages.recv() => {
+                let message = match message {
+                    Ok(message) if message.id <= sent_up_to => continue,
+                    Ok(message) => message,
+                    // Too slow to keep up; start again from a fresh snapshot
+                    Err(RecvError::Lagged(_)) => feed.resync(),
+                    Err(RecvError::Closed) => break,
+                };
+                sent_up_to = message.id;
+                if outgoing.send(send(&message)).await.is_err() {
+                    break;
+                }
+            },
+            // Clients only listen; anything but a close is ignored
+            received = incoming.next() => match received {
+                Some(Ok(message)) if !message.is_close() => {},
+                _ => break,
+            },
+        }
+    }
+}
+
+// Events after `last_id` that the feed still has, then new ones as they come
+fn feed_events(feed: Arc<Feed>, last_id: Option<u64>) -> impl Stream<Item = Result<warp::sse::Event, Infallible>> {
+    let (missed, messages) = feed.subscribe_events(last_id);
+    let sent_up_to = missed.last().map(|m| m.id).or(last_id).unwrap_or(0);
+    let retry = warp::sse::Event::default().retry(SSE_RETRY);
+    let events = stream::unfold((messages, feed, sent_up_to, VecDeque::new()), |(mut messages, feed, mut sent_up_to, mut pending)| async move {
+        loop {
+            if let Some(message) = pending.pop_front() {
+                return Some((message, (messages, feed, sent_up_to, pending)));
+            }
+            match messages.recv().await {
+                Ok(message) if message.kind == FeedKind::Event && message.id > sent_up_to => {
+                    sent_up_to = message.id;
+                    pending.push_back(message);
+                },
+                Ok(_) => {},
+                // Fell behind; pick up what was missed from the kept events
+                Err(RecvError::Lagged(_)) => {
+                    pending.extend(feed.events_after(sent_up_to));
+                    sent_up_to = pending.back().map(|m| m.id).unwrap_or(sent_up_to);
+                },
+                Err(RecvError::Closed) => return None,
+            }
+        }
+    });
+    let sse = |message: FeedMessage| Ok(warp::sse::Event::default().id(message.id.to_string()).data(message.data.to_string()));
+    stream::once(async move { Ok(retry) })
+        .chain(stream::iter(missed).map(sse))
+        .chain(events.map(sse))
+}
+
@@ -0,0 +1,142 @@
+// src/skill_build.rs
+use std::collections::HashMap;
+
+use crate::abilities::{in_slot_order, is_placeholder};
+use crate::format::format_ability_name;
+use crate::state::{Ability, Hero};
+
+// Hero levels at which a talent tier opens
+pub const TALENT_LEVELS: [i32; 4] = [10, 15, 20, 25];
+
+// Basic abilities in the order they are usually maxed. Heroes not listed max in slot order.
+const MAX_ORDERS: &[(&str, &[&str])] = &[
+    ("antimage", &["antimage_blink", "antimage_mana_break", "antimage_counterspell"]),
+    ("axe", &["axe_berserkers_call", "axe_counter_helix", "axe_battle_hunger"]),
+    ("crystal_maiden", &["crystal_maiden_crystal_nova", "crystal_maiden_frostbite", "crystal_maiden_brilliance_aura"]),
+    ("earthshaker", &["earthshaker_fissure", "earthshaker_enchant_totem", "earthshaker_aftershock"]),
+    ("faceless_void", &["faceless_void_time_walk", "faceless_void_time_lock", "faceless_void_time_dilation"]),
+    ("juggernaut", &["juggernaut_blade_fury", "juggernaut_blade_dance", "juggernaut_healing_ward"]),
+    ("lina", &["lina_dragon_slave", "lina_light_strike_array", "lina_fiery_soul"]),
+    ("lion", &["lion_impale", "lion_mana_drain", "lion_voodoo"]),
+    ("nevermore", &["nevermore_shadowraze1", "nevermore_necromastery", "nevermore_dark_lord"]),
+    ("phantom_assassin", &["phantom_assassin_stifling_dagger", "phantom_assassin_phantom_strike", "phantom_assassin_blur"]),
+    ("pudge", &["pudge_meat_hook", "pudge_rot", "pudge_flesh_heap"]),
+    ("shadow_shaman", &["shadow_shaman_ether_shock", "shadow_shaman_shackles", "shadow_shaman_voodoo"]),
+    ("sven", &["sven_storm_bolt", "sven_great_cleave", "sven_warcry"]),
+    ("witch_doctor", &["witch_doctor_paralyzing_cask", "witch_doctor_maledict", "witch_doctor_voodoo_restoration"]),
+    ("zuus", &["zuus_arc_lightning", "zuus_lightning_bolt", "zuus_heavenly_jump"]),
+];
+
+// What to spend the next skill point on
+#[derive(Clone, Debug, PartialEq)]
+pub enum SkillPick {
+    // Ability class, e.g. "axe_culling_blade"
+    Ability(String),
+    // Hero level of the talent tier
+    Talent(i32),
+}
+
+impl SkillPick {
+    pub fn describe(&self) -> String {
+        match self {
+            SkillPick::Ability(class) => format_ability_name(class),
+            SkillPick::Talent(level) => format!("a level {} talent", level),
+        }
+    }
+}
+
+// Ability levels the hero is allowed to have at `hero_level`
+fn level_cap(ultimate: bool, hero_level: i32) -> i32 {
+    if ultimate {
+        (hero_level / 6).min(3)
+    } else {
+        ((hero_level + 1) / 2).min(4)
+    }
+}
+
+// Skill points not spent yet, counting talents that GSI lists among the abilities
+pub fn unspent_points(hero: &Hero, abilities: &HashMap<String, Ability>) -> i32 {
+    let Some(level) = hero.level else { return 0 };
+    let spent: i32 = abilities.values()
+        .filter(|a| a.name.as_deref().map(|n| !n.starts_with("generic_hidden")).unwrap_or(false))
+        .map(|a| a.level.unwrap_or(0))
+        .sum();
+    let listed_talents = abilities.values().any(|a| a.name.as_deref().map(|n| n.starts_with("special_bonus")).unwrap_or(false));
+    let talents = if listed_talents {
+        0
+    } else {
+        hero.talent_tiers().map(|tiers| tiers.iter().filter(|t| **t).count() as i32).unwrap_or(0)
+    };
+    (level - spent - talents - hero.attributes_level.unwrap_or(0)).max(0)
+}
+
+// The next point following the usual build: ultimate whenever possible, then a first point
+// in the second ability by level 2 and the third by level 4, otherwise max in order
+pub fn next_pick(hero: &Hero, abilities: &HashMap<String, Ability>) -> Option<SkillPick> {
+    let level = hero.level?;
+
+    // Talent tiers the client says are still open
+    if let Some(tiers) = hero.talent_tiers() {
+        if let Some((tier_level, _)) = TALENT_LEVELS.iter().zip(tiers).find(|(l, taken)| **l <= level && !taken) {
+            return Some(SkillPick::Talent(*tier_level));
+        }
+    }
+
+    let slots: Vec<&Ability> = in_slot_order(abilities).into_iter()
+        .filter(|a| a.name.as_deref().map(|n| !is_placeholder(n)).unwrap_or(false))
+        .collect();
+    let can_level = |a: &Ability| a.level.unwrap_or(0) < level_cap(a.ultimate.unwrap_or(false), level);
+
+    if let Some(ultimate) = slots.iter().find(|a| a.ultimate.unwrap_or(false)).filter(|a| can_level(a)) {
+        return ultimate.name.clone().map(SkillPick::Ability);
+    }
+
+    let hero_class = hero.name.as_deref().unwrap_or_default().trim_start_matches("npc_dota_hero_");
+    let basics: Vec<&Ability> = match MAX_ORDERS.iter().find(|(class, _)| *class == hero_class) {
+        Some((_, order)) => order.iter()
+            .filter_map(|class| slots.iter().find(|a| a.name.as_deref() == Some(*class)).copied())
+            .collect(),
+        None => slots.iter().filter(|a| !a.ultimate.unwrap_or(false)).take(3).copied().collect(),
+    };
+
+    // Value points: one in the second ability by level 2, one in the third by level 4
+    for (index, by_level) in [(1, 2), (2, 4)] {
+        if let Some(ability) = basics.get(index).filter(|a| a.level.unwrap_or(0) == 0 && level >= by_level) {
+            return ability.name.clone().map(SkillPick::Ability);
+        }
+    }
+
+    basics.iter()
+        .find(|a| can_level(a))
+        .or_else(|| slots.iter().find(|a| can_level(a)))
+        .and_then(|a| a.name.clone())
+        .map(SkillPick::Ability)
+}
+
+// Tells the player about each level-up that left points unspent, once per level
+pub struct SkillAdvisor {
+    announced_level: i32,
+}
+
+impl SkillAdvisor {
+    pub fn new() -> Self {
+        SkillAdvisor { announced_level: 0 }
+    }
+
+    // (hero level, unspent points, suggested pick) when a new level is waiting to be spent
+    pub fn check(&mut self, hero: &Hero, abilities: &HashMap<String, Ability>) -> Option<(i32, i32, Option<SkillPick>)> {
+        let level = hero.level?;
+        let unspent = unspent_points(hero, abilities);
+        if unspent == 0 || level <= self.announced_level {
+            return None;
+        }
+        self.announced_level = level;
+        Some((level, unspent, next_pick(hero, abilities)))
+    }
+}
+
+impl Default for SkillAdvisor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,157 @@
+// src/smoke.rs
+use std::collections::HashMap;
+
+use crate::tracking::calculate_distance;
+use crate::missing::MapSide;
+use crate::respawns::DeadHero;
+
+// Enemies vanishing within this many seconds of each other count as simultaneous
+pub const VANISH_WINDOW: i32 = 3;
+
+// Enemies this close together when they vanished were probably moving as a group
+pub const GROUPED_DISTANCE: f32 = 1500.0;
+
+// A group vanishing within this distance of the player is a direct threat
+pub const THREAT_DISTANCE: f32 = 4500.0;
+
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+pub enum Confidence {
+    Low,
+    Medium,
+    High,
+}
+
+impl Confidence {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Confidence::Low => "low",
+            Confidence::Medium => "medium",
+            Confidence::High => "high",
+        }
+    }
+}
+
+// A suspected smoke gank
+#[derive(Clone, Debug)]
+pub struct SmokeWarning {
+    pub heroes: Vec<String>,
+    pub confidence: Confidence,
+    pub vanished_side: MapSide,
+}
+
+// Watches for several enemies leaving the minimap at the same moment
+pub struct SmokeDetector {
+    visible_last_update: HashMap<String, (i32, i32)>,
+    // Enemies that recently disappeared: name -> (last position, game time)
+    recently_vanished: HashMap<String, ((i32, i32), i32)>,
+    // Enemy deaths not yet matched to a vanished icon: hero name when known, game time
+    recent_deaths: Vec<(Option<String>, i32)>,
+}
+
+impl SmokeDetector {
+    pub fn new() -> Self {
+        SmokeDetector {
+            visible_last_update: HashMap::new(),
+            recently_vanished: HashMap::new(),
+            recent_deaths: Vec::new(),
+        }
+    }
+
+    // A dead enemy's icon leaves the minimap too, so each death takes one vanished hero out:
+    // the one of that name, or without a name the one closest to the player, as that's where
+    // the fights they see are. A death whose icon hasn't gone yet waits for it.
+    fn remove_dead(&mut self, player_position: Option<(i32, i32)>) {
+        let distance = |p: &(i32, i32)| player_position.map(|player| calculate_distance(player, *p)).unwrap_or(0.0);
+        let mut waiting = Vec::new();
+        for (hero, time) in std::mem::take(&mut self.recent_deaths) {
+            let still_visible = hero.as_ref().map(|h| self.visible_last_update.contains_key(h)).unwrap_or(false);
+            let vanished = match &hero {
+                Some(h) if self.recently_vanished.contains_key(h) => Some(h.clone()),
+                _ if still_visible => None,
+                _ => self.recently_vanished.iter()
+                    .min_by(|(_, (a, _)), (_, (b, _))| distance(a).total_cmp(&distance(b)))
+                    .map(|(name, _)| name.clone()),
+            };
+            match vanished {
+                Some(name) => { self.recently_vanished.remove(&name); },
+                None => waiting.push((hero, time)),
+            }
+        }
+        self.recent_deaths = waiting;
+    }
+
+    // Feed the enemies visible in this update and those that just died, and get a warning if
+    // a smoke looks likely
+    pub fn update(
+        &mut self,
+        game_time: i32,
+        visible: &[(String, (i32, i32))],
+        died: &[DeadHero],
+        player_position: Option<(i32, i32)>,
+    ) -> Option<SmokeWarning> {
+        let visible_now: HashMap<String, (i32, i32)> = visible.iter().cloned().collect();
+
+        // Anyone who was on the minimap last update and isn't now just vanished
+        for (name, position) in &self.visible_last_update {
+            if !visible_now.contains_key(name) {
+                self.recently_vanished.insert(name.clone(), (*position, game_time));
+            }
+        }
+        self.recently_vanished.retain(|name, (_, time)| {
+            !visible_now.contains_key(name) && game_time - *time <= VANISH_WINDOW
+        });
+        self.visible_last_update = visible_now;
+
+        self.recent_deaths.extend(died.iter().map(|d| (d.hero.clone(), d.died_at)));
+        self.recent_deaths.retain(|(_, time)| game_time - *time <= VANISH_WINDOW);
+        self.remove_dead(player_position);
+
+        if self.recently_vanished.len() < 2 {
+            return None;
+        }
+
+        let positions: Vec<(i32, i32)> = self.recently_vanished.values().map(|(pos, _)| *pos).collect();
+        let count = positions.len() as i32;
+        let centroid = (
+            positions.iter().map(|p| p.0).sum::<i32>() / count,
+            positions.iter().map(|p| p.1).sum::<i32>() / count,
+        );
+        let grouped = positions.iter().all(|p| calculate_distance(*p, centroid) <= GROUPED_DISTANCE);
+
+        // Score the evidence: more heroes, moving together, near the player
+        let mut score = if count >= 3 { 2 } else { 1 };
+        if grouped {
+            score += 1;
+        }
+        if let Some(player_pos) = player_position {
+            if calculate_distance(player_pos, centroid) <= THREAT_DISTANCE {
+                score += 1;
+            }
+            if MapSide::of(player_pos) == MapSide::of(centroid) {
+                score += 1;
+            }
+        }
+
+        let confidence = match score {
+            0..=1 => return None,
+            2 => Confidence::Low,
+            3 => Confidence::Medium,
+            _ => Confidence::High,
+        };
+
+        let mut heroes: Vec<String> = self.recently_vanished.keys().cloned().collect();
+        heroes.sort();
+
+        Some(SmokeWarning {
+            heroes,
+            confidence,
+            vanished_side: MapSide::of(centroid),
+        })
+    }
+}
+
+impl Default for SmokeDetector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,199 @@
+// src/snapshot.rs
+use std::collections::HashMap;
+
+use serde_json::{json, Value};
+
+use crate::alerts::strip_colors;
+use crate::barracks;
+use crate::benchmarks::role_name;
+use crate::coach::Coach;
+use crate::consumables::Consumable;
+use crate::format::format_game_time;
+use crate::missing;
+use crate::outposts::{Outpost, Source};
+use crate::roshan::RoshanStatus;
+use crate::state::Team;
+use crate::timers::{self, DayNight};
+use crate::tracking::{EnemyHeroState, EnemyStatus};
+use crate::wards::WardOwner;
+
+// The latest game state with what the coach made of it, for other tools to read
+pub fn snapshot(coach: &Coach) -> Value {
+    let state = coach.game_state();
+    json!({
+        "game_time": coach.game_time(),
+        "game_state": state,
+        "enemy_tracking": enemy_tracking(coach.enemies()),
+        "timers": state.and_then(|s| s.clock_time()).map(|clock_time| timers(coach, clock_time)),
+        "alerts": coach.alerts().active_sorted().iter().map(|alert| json!({
+            "severity": alert.severity.slug(),
+            "category": alert.category.slug(),
+            "message": strip_colors(&alert.message),
+            "raised_at": alert.raised_at,
+            "expires_at": alert.expires_at(),
+        })).collect::<Vec<_>>(),
+        "streaks": json!({
+            "own": state.and_then(|s| s.player()).and_then(|p| p.kill_streak),
+            "enemies": coach.streaks().enemy_streaks().into_iter()
+                .map(|(hero, kills)| (hero.to_string(), json!(kills)))
+                .collect::<serde_json::Map<_, _>>(),
+        }),
+        "creeps": Team::BOTH.iter()
+            .filter_map(|&team| Some((team.name().to_lowercase(), barracks::creeps(state?, team)?)))
+            .map(|(team, lanes)| (team, lanes.iter()
+                .map(|(lane, tier)| (lane.name().to_string(), json!(format!("{:?}", tier).to_lowercase())))
+                .collect::<serde_json::Map<_, _>>().into()))
+            .collect::<serde_json::Map<_, Value>>(),
+        "role": coach.position().map(|position| json!({
+            "position": position,
+            "name": role_name(position),
+            "source": if coach.role_override().is_some() { "set" } else if coach.role_detection().is_some() { "detected" } else { "hero" },
+        })),
+        "consumables": Consumable::ALL.iter()
+            .map(|&c| (c.name(1).to_string(), json!({
+                "held": coach.consumables().held(c),
+                "bought": coach.consumables().bought(c),
+                "used": coach.consumables().used(c),
+            })))
+            .collect::<serde_json::Map<_, _>>(),
+        "item_suggestion": coach.item_recommendation().map(|r| strip_colors(&r.describe())),
+        "coach_says": coach.coach_tip().map(|(time, tip)| json!({ "tip": tip, "game_time": time })),
+        "plugins": coach.plugin_panels()
+            .map(|(name, panel)| json!({ "name": name, "title": panel.title, "lines": panel.lines }))
+            .collect::<Vec<_>>(),
+    })
+}
+
+// Each tracked enemy hero by name
+pub fn enemy_tracking(enemies: &HashMap<String, EnemyHeroState>) -> Value {
+    enemies.iter()
+        .map(|(name, enemy)| (name.clone(), json!({
+            "name": enemy.name,
+            "last_seen_position": [enemy.last_seen_position.0, enemy.last_seen_position.1],
+            "last_seen_time": enemy.last_seen_time,
+            "level": enemy.level.value(),
+            "health_percent": enemy.health.value(),
+            "mana_percent": enemy.mana.value(),
+            "times_spotted": enemy.times_spotted,
+            "status": match enemy.status {
+                EnemyStatus::NewlySpotted => "newly_spotted",
+                EnemyStatus::Tracking => "tracking",
+                EnemyStatus::MovedSignificantly => "moved",
+                EnemyStatus::Lost => "lost",
+            },
+        })))
+        .collect::<serde_json::Map<_, _>>()
+        .into()
+}
+
+// Runes, day and night, Roshan and respawns, all in clock time
+fn timers(coach: &Coach, clock_time: i32) -> Value {
+    let cycle = day_night(coach, clock_time);
+    let roshan = match coach.roshan().status(clock_time) {
+        RoshanStatus::Alive => json!({ "status": "alive" }),
+        RoshanStatus::Dead { respawn_from, respawn_by } => json!({ "status": "dead", "respawn_from": respawn_from, "respawn_by": respawn_by }),
+        RoshanStatus::MaybeUp { respawn_by } => json!({ "status": "maybe_up", "respawn_by": respawn_by }),
+    };
+    let game_time = coach.game_time();
+    json!({
+        "clock_time": clock_time,
+        "runes": timers::upcoming_runes(clock_time).iter().map(|spawn| json!({
+            "rune": spawn.kind.name(),
+            "spawn_time": spawn.spawn_time,
+            "seconds_until": spawn.seconds_until,
+        })).collect::<Vec<_>>(),
+        "day_night": {
+            "is_day": cycle.is_day,
+            "seconds_until_change": cycle.seconds_until_change,
+            "nightstalker_night": cycle.nightstalker_night,
+        },
+        "roshan": roshan,
+        "aegis": coach.roshan().aegis(clock_time).map(|aegis| json!({
+            "team": aegis.team.map(|t| t.name()),
+            "picked_up_at": aegis.picked_up_at,
+            "expires_at": aegis.expires_at(),
+            "snatched": aegis.snatched,
+            "holder": aegis.holder,
+        })),
+        "respawns": Team::BOTH.iter().flat_map(|&team| coach.respawns().dead(team)).map(|dead| json!({
+            "team": dead.team.name(),
+            "hero": dead.hero,
+            "respawn_at": dead.respawn_at,
+            "seconds_left": dead.remaining(game_time),
+            "buyback_chance": dead.buyback_chance,
+        })).collect::<Vec<_>>(),
+        "outposts": Outpost::BOTH.iter().map(|&outpost| (outpost.name().to_string(), match coach.outposts().owner(outpost) {
+            Some(ownership) => json!({
+                "owner": ownership.owner.name(),
+                "since": ownership.since,
+                "marked": ownership.source == Source::Marked,
+            }),
+            None => Value::Null,
+        })).collect::<serde_json::Map<_, _>>(),
+        "wards": coach.wards().active(clock_time).iter().map(|ward| json!({
+            "kind": ward.kind.name(1),
+            "owner": if ward.owner == WardOwner::Own { "own" } else { "enemy" },
+            "location": ward.location,
+            "placed_at": ward.placed_at,
+            "expires_at": ward.expires_at(),
+            "marked": ward.marked,
+        })).collect::<Vec<_>>(),
+    })
+}
+
+fn day_night(coach: &Coach, clock_time: i32) -> DayNight {
+    let map = coach.game_state().and_then(|s| s.map.as_ref());
+    DayNight::from_clock(clock_time, map.and_then(|m| m.daytime), map.and_then(|m| m.nightstalker_night).unwrap_or(false))
+}
+
+// Names served under /status/<name>
+pub const STATUSES: [&str; 6] = ["clock", "roshan", "aegis", "rune", "day-night", "missing-count"];
+
+// One thing from the snapshot as {"value", "text"}, small enough for a Stream Deck key or a
+// polling widget to show `text` and act on `value`. None for a name not in STATUSES.
+pub fn status(coach: &Coach, name: &str) -> Option<Value> {
+    let Some(clock_time) = coach.game_state().and_then(|s| s.clock_time()) else {
+        return STATUSES.contains(&name).then(|| json!({ "value": null, "text": "-" }));
+    };
+    let clock = |seconds: i32| format_game_time(Some(seconds));
+    let (value, text) = match name {
+        "clock" => (json!(clock_time), clock(clock_time)),
+        "roshan" => match coach.roshan().status(clock_time) {
+            RoshanStatus::Alive => (json!("alive"), "Alive".to_string()),
+            RoshanStatus::Dead { respawn_from, respawn_by } => (json!("dead"), format!("{}-{}", clock(respawn_from), clock(respawn_by))),
+            RoshanStatus::MaybeUp { respawn_by } => (json!("maybe_up"), format!("Maybe up, by {}", clock(respawn_by))),
+        },
+        // Seconds the Aegis has left
+        "aegis" => match coach.roshan().aegis(clock_time) {
+            Some(aegis) => {
+                let left = aegis.expires_at() - clock_time;
+                (json!(left), format!("{} {}", aegis.team.map(|t| t.name()).unwrap_or("Aegis"), clock(left)))
+            },
+            None => (Value::Null, "No Aegis".to_string()),
+        },
+        // Seconds until the next rune
+        "rune" => match timers::upcoming_runes(clock_time).first() {
+            Some(spawn) => (json!(spawn.seconds_until), format!("{} {}", spawn.kind.name(), clock(spawn.seconds_until))),
+            None => (Value::Null, "-".to_string()),
+        },
+        "day-night" => {
+            let cycle = day_night(coach, clock_time);
+            let (now, next) = if cycle.is_day { ("day", "Night") } else { ("night", "Day") };
+            (json!(now), format!("{} in {}", next, clock(cycle.seconds_until_change)))
+        },
+        "missing-count" => {
+            let count = missing::missing_enemies(coach.enemies(), coach.game_time(), coach.config().thresholds.missing_after_seconds).len();
+            (json!(count), format!("{} missing", count))
+        },
+        _ => return None,
+    };
+    Some(json!({ "value": value, "text": text }))
+}
+
+// Every status by name, for widgets that poll once for all of them
+pub fn statuses(coach: &Coach) -> Value {
+    STATUSES.iter()
+        .filter_map(|&name| Some((name.to_string(), status(coach, name)?)))
+        .collect::<serde_json::Map<_, _>>()
+        .into()
+}
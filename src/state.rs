@@ -0,0 +1,676 @@
+// src/state.rs
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::format::format_hero_name;
+use crate::heroes;
+use crate::inventory::Inventory;
+
+// Root game state structure, as posted by Dota 2's game state integration
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct GameState {
+    pub provider: Option<Provider>,
+    pub map: Option<Map>,
+    pub player: Option<Slots<Player>>,
+    pub hero: Option<Slots<Hero>>,
+    pub abilities: Option<Slots<HashMap<String, Ability>>>,
+    pub items: Option<Slots<Inventory>>,
+    // Parsed so cosmetics stay out of `other`. Never serialized, so they are left out of
+    // /state, saved states, plugin input and rule conditions (`wearables.*` reads as missing);
+    // use `wearables()` or `slot()` instead.
+    #[serde(skip_serializing)]
+    pub wearables: Option<Slots<Wearables>>,
+    pub minimap: Option<HashMap<String, MinimapObject>>,
+    pub buildings: Option<HashMap<String, HashMap<String, Building>>>,
+    pub draft: Option<Draft>,
+    // Keyed "courier0", "courier1", ...
+    pub couriers: Option<HashMap<String, Courier>>,
+    // Never serialized, so the token stays out of /state, saved states and plugin input
+    #[serde(skip_serializing)]
+    pub auth: Option<Auth>,
+    pub events: Option<Vec<GsiEvent>>,
+    
+    // Fallback for any other fields
+    #[serde(flatten)]
+    pub other: HashMap<String, Value>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Auth {
+    pub token: Option<String>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Provider {
+    pub name: Option<String>,
+    pub appid: Option<i32>,
+    pub version: Option<i32>,
+    pub timestamp: Option<i64>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Map {
+    pub name: Option<String>,
+    pub matchid: Option<String>,
+    // Empty outside arcade games
+    pub customgamename: Option<String>,
+    pub game_time: Option<i32>,
+    pub clock_time: Option<i32>,
+    pub game_state: Option<GamePhase>,
+    pub paused: Option<bool>,
+    pub daytime: Option<bool>,
+    pub nightstalker_night: Option<bool>,
+    // Team kill counts
+    pub radiant_score: Option<i32>,
+    pub dire_score: Option<i32>,
+    // "radiant" or "dire" once the ancient falls, "none" before
+    pub win_team: Option<String>,
+}
+
+// The game rules state reported in map.game_state
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub enum GamePhase {
+    #[serde(rename = "DOTA_GAMERULES_STATE_INIT")]
+    Init,
+    #[serde(rename = "DOTA_GAMERULES_STATE_WAIT_FOR_PLAYERS_TO_LOAD")]
+    WaitForPlayersToLoad,
+    #[serde(rename = "DOTA_GAMERULES_STATE_CUSTOM_GAME_SETUP")]
+    CustomGameSetup,
+    #[serde(rename = "DOTA_GAMERULES_STATE_HERO_SELECTION")]
+    HeroSelection,
+    #[serde(rename = "DOTA_GAMERULES_STATE_STRATEGY_TIME")]
+    StrategyTime,
+    #[serde(rename = "DOTA_GAMERULES_STATE_TEAM_SHOWCASE")]
+    TeamShowcase,
+    #[serde(rename = "DOTA_GAMERULES_STATE_WAIT_FOR_MAP_TO_LOAD")]
+    WaitForMapToLoad,
+    #[serde(rename = "DOTA_GAMERULES_STATE_PRE_GAME")]
+    PreGame,
+    #[serde(rename = "DOTA_GAMERULES_STATE_GAME_IN_PROGRESS")]
+    InProgress,
+    #[serde(rename = "DOTA_GAMERULES_STATE_POST_GAME")]
+    PostGame,
+    #[serde(rename = "DOTA_GAMERULES_STATE_DISCONNECT")]
+    Disconnect,
+    // Anything newer than this list
+    #[serde(other)]
+    Unknown,
+}
+
+impl GamePhase {
+    pub fn name(&self) -> &'static str {
+        match self {
+            GamePhase::Init => "loading",
+            GamePhase::WaitForPlayersToLoad => "waiting for players",
+            GamePhase::CustomGameSetup => "custom game setup",
+            GamePhase::HeroSelection => "hero selection",
+            GamePhase::StrategyTime => "strategy time",
+            GamePhase::TeamShowcase => "team showcase",
+            GamePhase::WaitForMapToLoad => "waiting for map",
+            GamePhase::PreGame => "pre-game",
+            GamePhase::InProgress => "game in progress",
+            GamePhase::PostGame => "post-game",
+            GamePhase::Disconnect => "disconnected",
+            GamePhase::Unknown => "unknown",
+        }
+    }
+
+    // Drafting and loading, before the horn
+    pub fn is_before_game(&self) -> bool {
+        matches!(self,
+            GamePhase::Init | GamePhase::WaitForPlayersToLoad | GamePhase::CustomGameSetup |
+            GamePhase::HeroSelection | GamePhase::StrategyTime | GamePhase::TeamShowcase |
+            GamePhase::WaitForMapToLoad)
+    }
+
+    // Heroes are on the map, including the pre-horn countdown
+    pub fn is_playing(&self) -> bool {
+        matches!(self, GamePhase::PreGame | GamePhase::InProgress)
+    }
+}
+
+// Per-player blocks are sent once for the local player, or for all ten
+// players keyed by team and slot ("team2" -> "player0") when spectating or
+// coaching. The nested form is tried first because every field of a single
+// block is optional.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum Slots<T> {
+    Spectating(HashMap<String, HashMap<String, T>>),
+    Playing(T),
+}
+
+impl<T> Slots<T> {
+    // The local player's block, when playing
+    pub fn own(&self) -> Option<&T> {
+        match self {
+            Slots::Playing(value) => Some(value),
+            Slots::Spectating(_) => None,
+        }
+    }
+
+    // The block for one team and slot, when spectating
+    pub fn slot(&self, team: Team, slot: usize) -> Option<&T> {
+        match self {
+            Slots::Spectating(teams) => teams.get(team.key())
+                .and_then(|players| players.get(&format!("player{}", slot))),
+            Slots::Playing(_) => None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Team {
+    Radiant,
+    Dire,
+}
+
+impl Team {
+    pub const BOTH: [Team; 2] = [Team::Radiant, Team::Dire];
+
+    // Key used for the team in spectator payloads
+    pub fn key(&self) -> &'static str {
+        match self {
+            Team::Radiant => "team2",
+            Team::Dire => "team3",
+        }
+    }
+
+    // From a GSI team name such as "radiant"
+    pub fn from_name(name: &str) -> Option<Team> {
+        match name.to_lowercase().as_str() {
+            "radiant" => Some(Team::Radiant),
+            "dire" => Some(Team::Dire),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Team::Radiant => "Radiant",
+            Team::Dire => "Dire",
+        }
+    }
+
+    // Player slots on this team in spectator payloads
+    pub fn slots(&self) -> std::ops::Range<usize> {
+        match self {
+            Team::Radiant => 0..5,
+            Team::Dire => 5..10,
+        }
+    }
+
+    // Team of a player id as used in the events block
+    pub fn of_player(player_id: i32) -> Option<Team> {
+        let slot = usize::try_from(player_id).ok()?;
+        Team::BOTH.into_iter().find(|team| team.slots().contains(&slot))
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Player {
+    pub steamid: Option<String>,
+    pub name: Option<String>,
+    pub activity: Option<String>,
+    pub kills: Option<i32>,
+    pub deaths: Option<i32>,
+    pub assists: Option<i32>,
+    pub last_hits: Option<i32>,
+    pub denies: Option<i32>,
+    pub kill_streak: Option<i32>,
+    pub team_name: Option<String>,
+    pub gold: Option<i32>,
+    pub gold_reliable: Option<i32>,
+    pub gold_unreliable: Option<i32>,
+    pub net_worth: Option<i32>,
+    pub gpm: Option<i32>,
+    pub xpm: Option<i32>,
+    // Kills per victim, keyed "victimid_<player slot>"
+    pub kill_list: Option<HashMap<String, i32>>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Hero {
+    pub id: Option<i32>,
+    pub name: Option<String>,
+    pub level: Option<i32>,
+    pub alive: Option<bool>,
+    pub respawn_seconds: Option<i32>,
+    pub buyback_cost: Option<i32>,
+    pub buyback_cooldown: Option<i32>,
+    pub health: Option<i32>,
+    pub max_health: Option<i32>,
+    pub mana: Option<i32>,
+    pub max_mana: Option<i32>,
+    pub xpos: Option<i32>,
+    pub ypos: Option<i32>,
+    // Talents picked, two per tier from level 10 upwards
+    pub talent_1: Option<bool>,
+    pub talent_2: Option<bool>,
+    pub talent_3: Option<bool>,
+    pub talent_4: Option<bool>,
+    pub talent_5: Option<bool>,
+    pub talent_6: Option<bool>,
+    pub talent_7: Option<bool>,
+    pub talent_8: Option<bool>,
+    // Points put into attribute bonuses
+    pub attributes_level: Option<i32>,
+}
+
+impl Hero {
+    // Whether a talent was picked in each tier (levels 10, 15, 20, 25), if the client reports talents
+    pub fn talent_tiers(&self) -> Option<[bool; 4]> {
+        let talents = [self.talent_1, self.talent_2, self.talent_3, self.talent_4,
+            self.talent_5, self.talent_6, self.talent_7, self.talent_8];
+        if talents.iter().all(|t| t.is_none()) {
+            return None;
+        }
+        let taken = |i: usize| talents[i].unwrap_or(false) || talents[i + 1].unwrap_or(false);
+        Some([taken(0), taken(2), taken(4), taken(6)])
+    }
+}
+
+// One entry of the abilities block ("ability0", "ability1", ...)
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Ability {
+    pub name: Option<String>,
+    pub level: Option<i32>,
+    pub can_cast: Option<bool>,
+    pub passive: Option<bool>,
+    pub ability_active: Option<bool>,
+    pub cooldown: Option<i32>,
+    pub ultimate: Option<bool>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Item {
+    // "empty" for an unused slot
+    pub name: Option<String>,
+    pub purchaser: Option<i32>,
+    pub can_cast: Option<bool>,
+    pub cooldown: Option<i32>,
+    pub passive: Option<bool>,
+    pub charges: Option<i32>,
+    // Rune stored in a bottle, e.g. "haste"
+    pub contains_rune: Option<String>,
+}
+
+// Captains mode / all pick draft, sent during hero selection
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Draft {
+    // Team id (2 or 3) currently picking or banning
+    pub activeteam: Option<i32>,
+    // True for a pick, false for a ban
+    pub pick: Option<bool>,
+    pub activeteam_time_remaining: Option<i32>,
+    pub radiant_bonus_time: Option<i32>,
+    pub dire_bonus_time: Option<i32>,
+    pub team2: Option<DraftTeam>,
+    pub team3: Option<DraftTeam>,
+}
+
+// One team's side of the draft. Picks and bans arrive as numbered
+// "pick0_id"/"pick0_class" and "ban0_id"/"ban0_class" keys.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct DraftTeam {
+    pub home_team: Option<bool>,
+    #[serde(flatten)]
+    pub slots: HashMap<String, Value>,
+}
+
+// A hero picked or banned, in the order the team made them
+#[derive(Clone, Debug, PartialEq)]
+pub struct DraftSelection {
+    pub order: usize,
+    pub hero_id: i32,
+    // Hero name without the npc_dota_hero_ prefix, e.g. "shadow_shaman"
+    pub hero_class: String,
+}
+
+impl DraftSelection {
+    // Name shown in the client, looked up by id and falling back to the class
+    pub fn hero_name(&self) -> String {
+        match heroes::by_id(self.hero_id) {
+            Some(hero) => hero.name.to_string(),
+            None => format_hero_name(&self.hero_class),
+        }
+    }
+}
+
+impl DraftTeam {
+    pub fn picks(&self) -> Vec<DraftSelection> {
+        self.selections("pick")
+    }
+
+    pub fn bans(&self) -> Vec<DraftSelection> {
+        self.selections("ban")
+    }
+
+    // Filled slots of one kind; unfilled slots report hero id 0
+    fn selections(&self, kind: &str) -> Vec<DraftSelection> {
+        let mut selections = Vec::new();
+        for order in 0.. {
+            let id = match self.slots.get(&format!("{}{}_id", kind, order)) {
+                Some(id) => id.as_i64().unwrap_or(0) as i32,
+                None => break,
+            };
+            if id <= 0 {
+                continue;
+            }
+
+            let class = self.slots.get(&format!("{}{}_class", kind, order))
+                .and_then(|c| c.as_str())
+                .unwrap_or_default();
+            selections.push(DraftSelection {
+                order,
+                hero_id: id,
+                hero_class: class.to_string(),
+            });
+        }
+        selections
+    }
+}
+
+impl Draft {
+    pub fn team(&self, team: Team) -> Option<&DraftTeam> {
+        match team {
+            Team::Radiant => self.team2.as_ref(),
+            Team::Dire => self.team3.as_ref(),
+        }
+    }
+
+    pub fn active_team(&self) -> Option<Team> {
+        match self.activeteam {
+            Some(2) => Some(Team::Radiant),
+            Some(3) => Some(Team::Dire),
+            _ => None,
+        }
+    }
+}
+
+// Cosmetics equipped on a hero. GSI sends them as "wearable0" item ids with an
+// optional "style0" per slot.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(from = "HashMap<String, Value>")]
+pub struct Wearables {
+    pub slots: Vec<Wearable>,
+}
+
+static NO_WEARABLES: Wearables = Wearables { slots: Vec::new() };
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+pub struct Wearable {
+    pub slot: usize,
+    pub item_id: i64,
+    pub style: Option<i64>,
+}
+
+impl From<HashMap<String, Value>> for Wearables {
+    fn from(values: HashMap<String, Value>) -> Self {
+        let mut slots: Vec<Wearable> = values.iter()
+            .filter_map(|(key, value)| {
+                let slot = key.strip_prefix("wearable")?.parse().ok()?;
+                Some(Wearable {
+                    slot,
+                    item_id: value.as_i64()?,
+                    style: values.get(&format!("style{}", slot)).and_then(|s| s.as_i64()),
+                })
+            })
+            .collect();
+        slots.sort_by_key(|w| w.slot);
+        Wearables { slots }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct MinimapObject {
+    pub image: String,
+    pub name: Option<String>,
+    pub team: i32,
+    pub xpos: i32,
+    pub ypos: i32,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Building {
+    pub health: i32,
+    pub max_health: i32,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Courier {
+    pub health: Option<i32>,
+    pub max_health: Option<i32>,
+    pub alive: Option<bool>,
+    pub remaining_respawn_time: Option<i32>,
+    pub location: Option<Location>,
+    // Player id of the owner, as in the events block
+    pub owner: Option<i32>,
+    // Keyed "item0", "item1", ...
+    pub items: Option<HashMap<String, CourierItem>>,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+pub struct Location {
+    pub x: f32,
+    pub y: f32,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct CourierItem {
+    pub name: Option<String>,
+    // Player id the item is for
+    pub owner: Option<i32>,
+}
+
+impl Courier {
+    pub fn team(&self) -> Option<Team> {
+        self.owner.and_then(Team::of_player)
+    }
+
+    pub fn position(&self) -> Option<(i32, i32)> {
+        self.location.map(|l| (l.x.round() as i32, l.y.round() as i32))
+    }
+
+    pub fn is_alive(&self) -> bool {
+        self.alive.unwrap_or(true)
+    }
+
+    // Internal names of the items it carries
+    pub fn item_names(&self) -> Vec<&str> {
+        let mut items: Vec<(&String, &str)> = self.items.iter()
+            .flatten()
+            .filter_map(|(slot, item)| item.name.as_deref().filter(|n| *n != "empty").map(|n| (slot, n)))
+            .collect();
+        items.sort();
+        items.into_iter().map(|(_, name)| name).collect()
+    }
+}
+
+// Something that just happened, from the events block newer clients send
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct GsiEvent {
+    pub game_time: Option<i32>,
+    #[serde(flatten)]
+    pub kind: GsiEventKind,
+}
+
+// Player ids are slots 0 - 9, Radiant first. Teams are "radiant" or "dire".
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(tag = "event_type", rename_all = "snake_case")]
+pub enum GsiEventKind {
+    RoshanKilled {
+        killed_by_team: Option<String>,
+        killer_player_id: Option<i32>,
+    },
+    AegisPickedUp {
+        player_id: Option<i32>,
+        // Taken by the team that didn't kill Roshan
+        snatched: Option<bool>,
+    },
+    AegisDenied {
+        player_id: Option<i32>,
+    },
+    #[serde(alias = "bounty_rune_pickup")]
+    BountyRuneActivated {
+        player_id: Option<i32>,
+        team: Option<String>,
+        // Gold to the player who took it, and to their whole team
+        bounty_value: Option<i32>,
+        team_gold: Option<i32>,
+    },
+    CourierKilled {
+        courier_team: Option<String>,
+        killer_player_id: Option<i32>,
+        owner_player_id: Option<i32>,
+    },
+    // Tips, chat wheel and anything newer
+    #[serde(other)]
+    Other,
+}
+
+impl GameState {
+    pub fn game_time(&self) -> Option<i32> {
+        self.map.as_ref().and_then(|m| m.game_time)
+    }
+
+    pub fn phase(&self) -> Option<GamePhase> {
+        self.map.as_ref().and_then(|m| m.game_state)
+    }
+
+    pub fn clock_time(&self) -> Option<i32> {
+        self.map.as_ref().and_then(|m| m.clock_time)
+    }
+
+    // The local player, when playing rather than spectating
+    pub fn player(&self) -> Option<&Player> {
+        self.player.as_ref().and_then(|p| p.own())
+    }
+
+    pub fn hero(&self) -> Option<&Hero> {
+        self.hero.as_ref().and_then(|h| h.own())
+    }
+
+    pub fn abilities(&self) -> Option<&HashMap<String, Ability>> {
+        self.abilities.as_ref().and_then(|a| a.own())
+    }
+
+    pub fn inventory(&self) -> Option<&Inventory> {
+        self.items.as_ref().and_then(|i| i.own())
+    }
+
+    pub fn wearables(&self) -> Option<&Wearables> {
+        match self.wearables.as_ref()? {
+            // An empty block parses as a spectator view with no teams; when playing it's
+            // the player's own, with nothing equipped
+            Slots::Spectating(teams) if teams.is_empty() && !self.is_spectating() => Some(&NO_WEARABLES),
+            wearables => wearables.own(),
+        }
+    }
+
+    // Events in this payload, oldest first. Payloads repeat recent events.
+    pub fn events(&self) -> &[GsiEvent] {
+        self.events.as_deref().unwrap_or_default()
+    }
+
+    // Whether the payload carries all ten players (spectator or in-game coach)
+    pub fn is_spectating(&self) -> bool {
+        matches!(&self.player, Some(Slots::Spectating(teams)) if !teams.is_empty())
+    }
+
+    // Every player in a spectator payload, Radiant first and in slot order
+    pub fn spectated_players(&self) -> Vec<SpectatedPlayer<'_>> {
+        let mut players = Vec::new();
+        if !self.is_spectating() {
+            return players;
+        }
+
+        for team in Team::BOTH {
+            for slot in team.slots() {
+                let player = self.player.as_ref().and_then(|p| p.slot(team, slot));
+                let hero = self.hero.as_ref().and_then(|h| h.slot(team, slot));
+                let items = self.items.as_ref().and_then(|i| i.slot(team, slot));
+
+                if player.is_some() || hero.is_some() {
+                    players.push(SpectatedPlayer { team, slot, player, hero, items });
+                }
+            }
+        }
+
+        players
+    }
+
+    // Lowercase team name of the local player, e.g. "radiant"
+    pub fn player_team(&self) -> Option<String> {
+        self.player()
+            .and_then(|p| p.team_name.as_ref())
+            .map(|t| t.to_lowercase())
+    }
+
+    pub fn hero_position(&self) -> Option<(i32, i32)> {
+        match self.hero().map(|h| (h.xpos, h.ypos)) {
+            Some((Some(x), Some(y))) => Some((x, y)),
+            _ => None,
+        }
+    }
+}
+
+// One of the ten players in a spectator payload
+#[derive(Clone, Copy, Debug)]
+pub struct SpectatedPlayer<'a> {
+    pub team: Team,
+    pub slot: usize,
+    pub player: Option<&'a Player>,
+    pub hero: Option<&'a Hero>,
+    pub items: Option<&'a Inventory>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(json: Value) -> GameState {
+        serde_json::from_value(json).unwrap()
+    }
+
+    #[test]
+    fn own_wearables_parse_into_slots_in_order() {
+        let state = parse(serde_json::json!({
+            "player": { "name": "me" },
+            "wearables": { "wearable0": 4501, "style0": 1, "wearable10": 5, "wearable2": 7000 },
+        }));
+        let slots = &state.wearables().unwrap().slots;
+        assert_eq!(slots, &[
+            Wearable { slot: 0, item_id: 4501, style: Some(1) },
+            Wearable { slot: 2, item_id: 7000, style: None },
+            Wearable { slot: 10, item_id: 5, style: None },
+        ]);
+        assert!(!state.other.contains_key("wearables"));
+        assert!(serde_json::to_value(&state).unwrap().get("wearables").is_none());
+    }
+
+    #[test]
+    fn spectated_wearables_are_keyed_by_team_and_slot() {
+        let state = parse(serde_json::json!({
+            "player": { "team2": { "player0": { "name": "radiant" } }, "team3": { "player5": { "name": "dire" } } },
+            "wearables": {
+                "team2": { "player0": { "wearable0": 100, "style0": 2 } },
+                "team3": { "player5": { "wearable1": 200 } },
+            },
+        }));
+        let wearables = state.wearables.as_ref().unwrap();
+        assert_eq!(wearables.slot(Team::Radiant, 0).unwrap().slots, vec![Wearable { slot: 0, item_id: 100, style: Some(2) }]);
+        assert_eq!(wearables.slot(Team::Dire, 5).unwrap().slots, vec![Wearable { slot: 1, item_id: 200, style: None }]);
+        assert!(wearables.slot(Team::Radiant, 1).is_none());
+        assert!(state.wearables().is_none());
+    }
+
+    #[test]
+    fn empty_wearables_are_the_players_own() {
+        let state = parse(serde_json::json!({ "player": { "name": "me" }, "wearables": {} }));
+        assert!(state.wearables().unwrap().slots.is_empty());
+        assert!(parse(serde_json::json!({})).wearables().is_none());
+    }
+}
@@ -0,0 +1,89 @@
+// src/steam.rs
+use std::path::PathBuf;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::cache::DiskCache;
+use crate::config::SteamConfig;
+
+const API_URL: &str = "https://api.steampowered.com/ISteamUser/GetPlayerSummaries/v2/";
+
+// Requests that take longer than this are given up on; the coach carries on without
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+// Steam64 IDs are a 32-bit account ID plus this
+pub const STEAM64_BASE: u64 = 76561197960265728;
+
+// Steam64 ID from a GSI steamid, which is usually one already but can be a bare account ID
+pub fn steam64(steamid: &str) -> Option<u64> {
+    let id: u64 = steamid.trim().parse().ok()?;
+    match id {
+        0 => None,
+        id if id < STEAM64_BASE => Some(id + STEAM64_BASE),
+        id => Some(id),
+    }
+}
+
+// Where API responses are cached unless the config says otherwise
+pub fn default_cache_path() -> Option<PathBuf> {
+    dirs::cache_dir().map(|cache| cache.join("dota2-coach").join("steam"))
+}
+
+// A player's public Steam profile
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct SteamProfile {
+    pub steamid: String,
+    #[serde(rename = "personaname")]
+    pub persona_name: String,
+    #[serde(default, rename = "avatarmedium")]
+    pub avatar: Option<String>,
+    #[serde(default, rename = "profileurl")]
+    pub profile_url: Option<String>,
+}
+
+// Looks up persona names and avatars, keeping responses on disk so each player
+// is asked about at most once per `cache_hours`
+#[derive(Clone)]
+pub struct SteamClient {
+    http: reqwest::Client,
+    api_key: String,
+    cache: DiskCache,
+}
+
+impl SteamClient {
+    pub fn new(config: &SteamConfig) -> Result<Self, String> {
+        let api_key = config.api_key.clone()
+            .filter(|k| !k.trim().is_empty())
+            .ok_or_else(|| "The Steam Web API needs a key in the [steam] section".to_string())?;
+        let http = reqwest::Client::builder()
+            .timeout(TIMEOUT)
+            .user_agent(concat!("dota2-coach/", env!("CARGO_PKG_VERSION")))
+            .build()
+            .map_err(|e| format!("Could not set up the Steam client: {}", e))?;
+        Ok(SteamClient {
+            http,
+            api_key,
+            cache: DiskCache::new(config.cache_path.clone().or_else(default_cache_path),
+                Duration::from_secs(config.cache_hours * 60 * 60)),
+        })
+    }
+
+    pub async fn profile(&self, steam64: u64) -> Result<SteamProfile, String> {
+        let body = self.cache.get_or_fetch(&format!("player_{}.json", steam64), self.fetch(steam64)).await?;
+        let response: Value = serde_json::from_str(&body).map_err(|e| format!("Unexpected Steam response: {}", e))?;
+        let profile = response.pointer("/response/players/0")
+            .ok_or_else(|| format!("No public Steam profile for {}", steam64))?;
+        serde_json::from_value(profile.clone()).map_err(|e| format!("Unexpected Steam profile for {}: {}", steam64, e))
+    }
+
+    async fn fetch(&self, steam64: u64) -> Result<String, String> {
+        let response = self.http.get(API_URL)
+            .query(&[("key", self.api_key.clone()), ("steamids", steam64.to_string())])
+            .send().await
+            .and_then(|r| r.error_for_status())
+            .map_err(|e| format!("Steam request failed: {}", e.without_url()))?;
+        response.text().await.map_err(|e| format!("Steam request failed: {}", e.without_url()))
+    }
+}
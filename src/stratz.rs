@@ -0,0 +1,207 @@
+// src/stratz.rs
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::cache::DiskCache;
+use crate::config::StratzConfig;
+use crate::items;
+
+const API_URL: &str = "https://api.stratz.com/graphql";
+
+// Requests that take longer than this are given up on; the coach carries on without
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+// Finished items kept from a hero's purchase data
+const BUILD_LENGTH: usize = 6;
+
+// Matchups over fewer games than this are mostly noise
+const MIN_MATCHUP_GAMES: i32 = 200;
+
+// Every hero's wins and games over the last week of public matches
+const WIN_RATES_QUERY: &str = "{ heroStats { winWeek(take: 1) { heroId matchCount winCount } } }";
+
+// Stratz reports purchases by item id, which the item table doesn't have
+const ITEM_IDS_QUERY: &str = "{ constants { items { id shortName } } }";
+
+fn hero_query(hero_id: i32) -> String {
+    format!("{{ heroStats {{ \
+        heroVsHeroMatchup(heroId: {id}) {{ advantage {{ vs {{ heroId2 synergy matchCount }} }} }} \
+        itemFullPurchase(heroId: {id}) {{ events {{ itemId time matchCount }} }} \
+    }} }}", id = hero_id)
+}
+
+// Where API responses are cached unless the config says otherwise
+pub fn default_cache_path() -> Option<PathBuf> {
+    dirs::cache_dir().map(|cache| cache.join("dota2-coach").join("stratz"))
+}
+
+// The schema changes between patches, so entries that don't parse are skipped
+// rather than failing the whole response
+fn entries<T: DeserializeOwned>(response: &Value, pointer: &str) -> Vec<T> {
+    response.pointer(pointer)
+        .and_then(|list| list.as_array())
+        .map(|list| list.iter().filter_map(|entry| serde_json::from_value(entry.clone()).ok()).collect())
+        .unwrap_or_default()
+}
+
+#[derive(Clone, Copy, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct HeroWeek {
+    hero_id: i32,
+    #[serde(default)]
+    match_count: i64,
+    #[serde(default)]
+    win_count: i64,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ItemName {
+    id: i32,
+    short_name: Option<String>,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ItemPurchase {
+    item_id: i32,
+    // Minute the item was bought in
+    #[serde(default)]
+    time: i32,
+    #[serde(default)]
+    match_count: i64,
+}
+
+// How one hero does against another, from the first hero's side
+#[derive(Clone, Copy, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Matchup {
+    #[serde(rename = "heroId2")]
+    pub hero_id: i32,
+    // Win rate advantage in percentage points
+    #[serde(default, rename = "synergy")]
+    pub advantage: f32,
+    #[serde(default)]
+    pub match_count: i32,
+}
+
+// Current-patch data for the player's hero
+#[derive(Clone, Debug)]
+pub struct HeroMeta {
+    pub hero_id: i32,
+    // Finished items by class, in the order they're usually bought
+    pub popular_build: Vec<&'static str>,
+    pub matchups: Vec<Matchup>,
+}
+
+impl HeroMeta {
+    // Against `hero_id`, when enough games were played to say
+    pub fn matchup(&self, hero_id: i32) -> Option<&Matchup> {
+        self.matchups.iter().find(|m| m.hero_id == hero_id && m.match_count >= MIN_MATCHUP_GAMES)
+    }
+}
+
+// The most bought finished items the item table knows, ordered by when they're bought
+fn popular_build(purchases: &[ItemPurchase], item_names: &HashMap<i32, String>) -> Vec<&'static str> {
+    // Matches and match-weighted purchase minute per item
+    let mut totals: HashMap<&'static str, (i64, i64)> = HashMap::new();
+    for purchase in purchases {
+        let Some(item) = item_names.get(&purchase.item_id).and_then(|name| items::by_class(name)) else { continue };
+        if item.consumable || (item.components.is_empty() && items::is_component(item.class)) {
+            continue;
+        }
+        let total = totals.entry(item.class).or_default();
+        total.0 += purchase.match_count;
+        total.1 += purchase.match_count * purchase.time as i64;
+    }
+
+    let mut build: Vec<(&'static str, i64, i64)> = totals.into_iter().map(|(class, (matches, minutes))| (class, matches, minutes)).collect();
+    build.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+    build.truncate(BUILD_LENGTH);
+    build.sort_by_key(|&(_, matches, minutes)| minutes.checked_div(matches).unwrap_or(0));
+    build.into_iter().map(|(class, _, _)| class).collect()
+}
+
+// Queries the Stratz GraphQL API, keeping responses on disk so the same question
+// is asked at most once per `cache_hours`
+#[derive(Clone)]
+pub struct StratzClient {
+    http: reqwest::Client,
+    token: String,
+    cache: DiskCache,
+}
+
+impl StratzClient {
+    pub fn new(config: &StratzConfig) -> Result<Self, String> {
+        let token = config.token.clone()
+            .filter(|t| !t.trim().is_empty())
+            .ok_or_else(|| "Stratz needs an API token in the [stratz] section".to_string())?;
+        // Stratz turns away requests without this user agent
+        let http = reqwest::Client::builder()
+            .timeout(TIMEOUT)
+            .user_agent("STRATZ_API")
+            .build()
+            .map_err(|e| format!("Could not set up the Stratz client: {}", e))?;
+        Ok(StratzClient {
+            http,
+            token,
+            cache: DiskCache::new(config.cache_path.clone().or_else(default_cache_path),
+                Duration::from_secs(config.cache_hours * 60 * 60)),
+        })
+    }
+
+    // Win rate (0 to 1) by hero id over the last week
+    pub async fn hero_win_rates(&self) -> Result<HashMap<i32, f32>, String> {
+        let response = self.query("win_rates.json", WIN_RATES_QUERY).await?;
+        let win_rates: HashMap<i32, f32> = entries::<HeroWeek>(&response, "/data/heroStats/winWeek").into_iter()
+            .filter(|w| w.match_count > 0)
+            .map(|w| (w.hero_id, w.win_count as f32 / w.match_count as f32))
+            .collect();
+        if win_rates.is_empty() {
+            return Err("Stratz sent no hero win rates".to_string());
+        }
+        Ok(win_rates)
+    }
+
+    pub async fn hero_meta(&self, hero_id: i32) -> Result<HeroMeta, String> {
+        let response = self.query(&format!("hero_{}.json", hero_id), &hero_query(hero_id)).await?;
+        let item_names: HashMap<i32, String> = entries::<ItemName>(&self.query("items.json", ITEM_IDS_QUERY).await?, "/data/constants/items")
+            .into_iter()
+            .filter_map(|item| Some((item.id, item.short_name?)))
+            .collect();
+        let purchases: Vec<ItemPurchase> = entries(&response, "/data/heroStats/itemFullPurchase/events");
+        Ok(HeroMeta {
+            hero_id,
+            popular_build: popular_build(&purchases, &item_names),
+            matchups: entries(&response, "/data/heroStats/heroVsHeroMatchup/advantage/0/vs"),
+        })
+    }
+
+    async fn query(&self, cache_name: &str, query: &str) -> Result<Value, String> {
+        let body = self.cache.get_or_fetch(cache_name, self.fetch(query)).await?;
+        serde_json::from_str(&body).map_err(|e| format!("Unexpected Stratz response: {}", e))
+    }
+
+    async fn fetch(&self, query: &str) -> Result<String, String> {
+        let response = self.http.post(API_URL)
+            .bearer_auth(&self.token)
+            .json(&json!({ "query": query }))
+            .send().await
+            .and_then(|r| r.error_for_status())
+            .map_err(|e| format!("Stratz request failed: {}", e))?;
+        let body = response.text().await.map_err(|e| format!("Stratz request failed: {}", e))?;
+
+        // GraphQL errors come back as 200s; don't cache them
+        let parsed: Value = serde_json::from_str(&body).map_err(|e| format!("Unexpected Stratz response: {}", e))?;
+        if parsed.get("data").map(Value::is_null).unwrap_or(true) {
+            let message = parsed.pointer("/errors/0/message").and_then(Value::as_str).unwrap_or("no data");
+            return Err(format!("Stratz query failed: {}", message));
+        }
+        Ok(body)
+    }
+}
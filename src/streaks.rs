@@ -0,0 +1,88 @@
+// src/streaks.rs
+use std::collections::HashMap;
+
+use crate::tracking::{calculate_distance, EnemyHeroState};
+
+// A streak starts paying out at three kills
+pub const SPREE: i32 = 3;
+
+// An enemy seen this recently next to the player took part in a kill there
+const KILL_WINDOW: i32 = 5;
+
+// Extra gold a hero on a streak gives when killed: 60 for each kill past the second,
+// up to ten kills
+pub fn streak_bounty(streak: i32) -> i32 {
+    if streak < SPREE {
+        return 0;
+    }
+    60 * (streak.min(10) - 2)
+}
+
+// Rough gold for killing a hero of `level` on `streak`, before assists share it out
+pub fn kill_bounty(level: i32, streak: i32) -> i32 {
+    110 + 8 * level + streak_bounty(streak)
+}
+
+// The enemy seen closest to `position` within `range` in the last few seconds
+pub fn nearest_enemy(enemies: &HashMap<String, EnemyHeroState>, position: (i32, i32), game_time: i32, range: f32) -> Option<&EnemyHeroState> {
+    enemies.values()
+        .filter(|e| game_time - e.last_seen_time <= KILL_WINDOW)
+        .map(|e| (e, calculate_distance(position, e.last_seen_position)))
+        .filter(|(_, distance)| *distance <= range)
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(e, _)| e)
+}
+
+// Kill streaks the enemies are likely on. GSI doesn't say who killed whom, so a kill only
+// counts when the player dies with a single enemy close by, and a streak only ends when the
+// player kills that hero; kills elsewhere on the map go unseen.
+#[derive(Default)]
+pub struct StreakTracker {
+    enemies: HashMap<String, i32>,
+    // The player's streak when they last heard about it
+    warned_own: i32,
+}
+
+impl StreakTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn streak(&self, hero: &str) -> i32 {
+        self.enemies.get(hero).copied().unwrap_or(0)
+    }
+
+    // Enemies on a streak, longest first
+    pub fn enemy_streaks(&self) -> Vec<(&str, i32)> {
+        let mut streaks: Vec<(&str, i32)> = self.enemies.iter()
+            .filter(|(_, kills)| **kills > 0)
+            .map(|(hero, kills)| (hero.as_str(), *kills))
+            .collect();
+        streaks.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+        streaks
+    }
+
+    // `hero` killed the player. Returns their streak now.
+    pub fn credit(&mut self, hero: &str) -> i32 {
+        let streak = self.enemies.entry(hero.to_string()).or_insert(0);
+        *streak += 1;
+        *streak
+    }
+
+    // The player killed `hero`. Returns the streak that ended, if there was one.
+    pub fn shut_down(&mut self, hero: &str) -> Option<i32> {
+        self.enemies.remove(hero).filter(|streak| *streak > 0)
+    }
+
+    // Whether the player's streak just grew long enough to be worth a warning
+    pub fn own_streak_due(&mut self, streak: i32) -> bool {
+        if streak < self.warned_own {
+            self.warned_own = 0;
+        }
+        if streak < SPREE || streak <= self.warned_own {
+            return false;
+        }
+        self.warned_own = streak;
+        true
+    }
+}
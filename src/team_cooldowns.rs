@@ -0,0 +1,75 @@
+// src/team_cooldowns.rs
+use std::collections::HashMap;
+
+// Team-wide abilities whose use the enemy can't hide but GSI doesn't report
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TeamAbility {
+    Glyph,
+    Scan,
+}
+
+impl TeamAbility {
+    pub const ALL: [TeamAbility; 2] = [TeamAbility::Glyph, TeamAbility::Scan];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            TeamAbility::Glyph => "glyph",
+            TeamAbility::Scan => "scan",
+        }
+    }
+
+    // Seconds until it can be used again
+    pub fn cooldown(&self) -> i32 {
+        match self {
+            TeamAbility::Glyph => 300,
+            TeamAbility::Scan => 210,
+        }
+    }
+}
+
+// When the enemy last used each team ability, as marked by the player or inferred from the game
+pub struct EnemyCooldowns {
+    used_at: HashMap<TeamAbility, i32>,
+}
+
+impl EnemyCooldowns {
+    pub fn new() -> Self {
+        EnemyCooldowns {
+            used_at: HashMap::new(),
+        }
+    }
+
+    pub fn mark_used(&mut self, ability: TeamAbility, game_time: i32) {
+        self.used_at.insert(ability, game_time);
+    }
+
+    // The ability is available again, e.g. glyph after losing a tier 1 tower
+    pub fn reset(&mut self, ability: TeamAbility) {
+        self.used_at.remove(&ability);
+    }
+
+    // Seconds until the ability is back, or None if it should be up
+    pub fn remaining(&self, ability: TeamAbility, game_time: i32) -> Option<i32> {
+        self.used_at.get(&ability)
+            .map(|used| used + ability.cooldown() - game_time)
+            .filter(|remaining| *remaining > 0)
+    }
+
+    // Abilities whose cooldown ran out by `game_time`. Each is reported once.
+    pub fn take_ready(&mut self, game_time: i32) -> Vec<TeamAbility> {
+        let ready: Vec<TeamAbility> = TeamAbility::ALL.iter()
+            .copied()
+            .filter(|a| self.used_at.contains_key(a) && self.remaining(*a, game_time).is_none())
+            .collect();
+        for ability in &ready {
+            self.used_at.remove(ability);
+        }
+        ready
+    }
+}
+
+impl Default for EnemyCooldowns {
+    fn default() -> Self {
+        Self::new()
+    }
+}
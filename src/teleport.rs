@@ -0,0 +1,46 @@
+// src/teleport.rs
+use crate::alerts::Severity;
+use crate::items;
+
+// Watches the teleport slot and remembers deaths taken without a TP
+pub struct TpReminder {
+    last_deaths: Option<i32>,
+    died_without_tp: bool,
+}
+
+impl TpReminder {
+    pub fn new() -> Self {
+        TpReminder {
+            last_deaths: None,
+            died_without_tp: false,
+        }
+    }
+
+    // Severity of the reminder due right now, or None if the player is fine.
+    // Escalates once the player has died while carrying no TP.
+    pub fn check(&mut self, has_teleport: bool, gold: i32, deaths: i32) -> Option<Severity> {
+        let died = self.last_deaths.map(|last| deaths > last).unwrap_or(false);
+        self.last_deaths = Some(deaths);
+
+        if has_teleport {
+            self.died_without_tp = false;
+            return None;
+        }
+        if died {
+            self.died_without_tp = true;
+        }
+
+        let cost = items::by_class("tpscroll").map(|i| i.cost).unwrap_or(100);
+        if gold < cost {
+            return None;
+        }
+
+        Some(if self.died_without_tp { Severity::Warning } else { Severity::Info })
+    }
+}
+
+impl Default for TpReminder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
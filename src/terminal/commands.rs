@@ -0,0 +1,272 @@
+// src/terminal/commands.rs
+use std::collections::BTreeMap;
+
+use colored::Colorize;
+use tokio::runtime::Handle;
+
+use crate::benchmarks::role_name;
+use crate::consumables::Consumable;
+use crate::llm::{self, LlmClient};
+use crate::outposts::Outpost;
+use crate::questions;
+use crate::server::Shared;
+use crate::snapshot;
+use crate::state::Team;
+use crate::team_cooldowns::TeamAbility;
+use crate::terminal::print::{print_event, print_heatmap, print_stats, print_timeline};
+use crate::wards::WardOwner;
+
+// Commands typed into the terminal while the coach runs, for things GSI can't see
+pub enum Command {
+    MarkEnemy(TeamAbility),
+    // The player just cleared the camp they are standing at
+    ClearCamp,
+    // Enemy heatmap, for one hero when a name is given
+    Heatmap(Option<String>),
+    // List the thresholds, or change one
+    Settings,
+    Set(String, String),
+    // Totals for this session, or every stored match
+    Stats { all: bool },
+    // A page of the event timeline, 1 being the most recent
+    Timeline(usize),
+    // Flag this moment in the timeline, with an optional note
+    Mark(Option<String>),
+    // Write the game state and event log to a file
+    Save,
+    // A question for the coach about this game
+    Ask(String),
+    // Show the position the player is coached as, or set it (None goes back to detecting it)
+    Role,
+    SetRole(Option<u8>),
+    // A ward the player just planted, or an enemy observer they just spotted
+    Ward(Consumable, WardOwner),
+    // Who holds the outposts, or set one (flipped when ours/theirs isn't given)
+    Outposts,
+    MarkOutpost(Outpost, Option<bool>),
+    // The Aegis just taken by the player's team (or the enemy's), or None once it's gone
+    Aegis(Option<bool>),
+    Help,
+}
+
+impl Command {
+    pub fn parse(line: &str) -> Option<Command> {
+        match line.trim() {
+            "g" | "glyph" => Some(Command::MarkEnemy(TeamAbility::Glyph)),
+            "s" | "scan" => Some(Command::MarkEnemy(TeamAbility::Scan)),
+            "c" | "cleared" => Some(Command::ClearCamp),
+            "?" | "h" | "help" => Some(Command::Help),
+            "settings" => Some(Command::Settings),
+            "stats" => Some(Command::Stats { all: false }),
+            "stats all" => Some(Command::Stats { all: true }),
+            "k" | "mark" => Some(Command::Mark(None)),
+            "save" => Some(Command::Save),
+            "r" | "role" => Some(Command::Role),
+            "w" | "ward" => Some(Command::Ward(Consumable::Observer, WardOwner::Own)),
+            "o" | "outpost" => Some(Command::Outposts),
+            "ae" | "aegis" | "aegis ours" => Some(Command::Aegis(Some(true))),
+            "aegis theirs" => Some(Command::Aegis(Some(false))),
+            "aegis gone" => Some(Command::Aegis(None)),
+            line => match line.split_once(' ').map(|(c, rest)| (c, rest.trim())).unwrap_or((line, "")) {
+                ("m" | "heatmap", "") => Some(Command::Heatmap(None)),
+                ("m" | "heatmap", hero) => Some(Command::Heatmap(Some(hero.to_string()))),
+                ("t" | "timeline", "") => Some(Command::Timeline(1)),
+                ("t" | "timeline", page) => page.parse().ok().filter(|&p| p > 0).map(Command::Timeline),
+                ("k" | "mark", note) => Some(Command::Mark(Some(note.to_string()))),
+                ("set", rest) => rest.split_once(' ').map(|(key, value)| Command::Set(key.to_string(), value.trim().to_string())),
+                ("r" | "role", "auto") => Some(Command::SetRole(None)),
+                ("r" | "role", position) => position.parse().ok().filter(|p| (1..=5).contains(p)).map(|p| Command::SetRole(Some(p))),
+                ("w" | "ward", "sentry") => Some(Command::Ward(Consumable::Sentry, WardOwner::Own)),
+                ("w" | "ward", "enemy") => Some(Command::Ward(Consumable::Observer, WardOwner::Enemy)),
+                ("o" | "outpost", rest) => {
+                    let (name, owner) = rest.split_once(' ').map(|(n, o)| (n, Some(o.trim()))).unwrap_or((rest, None));
+                    let ours = match owner {
+                        None => None,
+                        Some("ours") => Some(true),
+                        Some("theirs") => Some(false),
+                        Some(_) => return None,
+                    };
+                    Outpost::parse(name).map(|outpost| Command::MarkOutpost(outpost, ours))
+                },
+                ("a" | "ask", question) if !question.is_empty() => Some(Command::Ask(question.to_string())),
+                // Anything else phrased as a question is one
+                _ if line.ends_with('?') => Some(Command::Ask(line.to_string())),
+                _ => None,
+            },
+        }
+    }
+}
+
+pub fn print_commands(keys: &BTreeMap<String, String>) {
+    println!("Commands (type and press Enter):");
+    println!("  g, glyph   the enemy just used their glyph");
+    println!("  s, scan    the enemy just scanned");
+    println!("  c, cleared you just cleared the camp you're standing at");
+    println!("  m, heatmap [hero]  where the enemy team, or one enemy hero, has been seen");
+    println!("  settings   show the thresholds, ready to paste into the config file");
+    println!("  set <key> <value>  change a threshold for this session, e.g. set very_close_distance 1400");
+    println!("  stats [all]  win/loss, averages and heroes for this session, or every stored match");
+    println!("  t, timeline [page]  everything that happened so far, newest page first");
+    println!("  k, mark [note]  flag this moment in the timeline to look at later");
+    println!("  save       write the game state and timeline to a dota_state_<time>.json file");
+    println!("  w, ward [sentry|enemy]  you just planted an observer (or sentry), or spotted an enemy observer");
+    println!("  o, outpost [top|bottom] [ours|theirs]  who holds the outposts, or say who took one");
+    println!("  ae, aegis [theirs|gone]  your team just took the Aegis, the enemy did, or it's been used");
+    println!("  r, role [1-5|auto]  show the position you're coached as, set it, or go back to detecting it");
+    println!("  a, ask <question>  ask the coach about this game, e.g. bkb or blink first? (the ask is optional with a ?)");
+    println!("  ?, help    show this list");
+    for (key, command) in keys {
+        println!("  {:<10} {} (from [keys])", key, command);
+    }
+}
+
+// Read commands from the terminal until it closes. This runs on a thread of its own, since
+// commands lock the coach and some read the match history.
+pub fn run(shared: Shared, llm: Option<LlmClient>, runtime: Handle) {
+    for line in std::io::stdin().lines() {
+        let Ok(line) = line else { break };
+        // A name from [keys] stands for the command it's bound to
+        let line = shared.coach.lock().unwrap().config().keys.get(line.trim()).cloned().unwrap_or(line);
+        match Command::parse(&line) {
+            Some(Command::MarkEnemy(ability)) => {
+                let mut coach = shared.coach.lock().unwrap();
+                let event = coach.mark_enemy_used(ability);
+                print_event(&event, &coach);
+            },
+            Some(Command::ClearCamp) => {
+                let mut coach = shared.coach.lock().unwrap();
+                match coach.mark_camp_cleared() {
+                    Some((_, event)) => print_event(&event, &coach),
+                    None => println!("No jungle camp close enough to mark as cleared"),
+                }
+            },
+            Some(Command::Heatmap(hero)) => {
+                let coach = shared.coach.lock().unwrap();
+                print_heatmap(coach.game_time(), coach.heatmap(), hero.as_deref());
+            },
+            Some(Command::Settings) => {
+                println!("[thresholds]");
+                for (key, value) in shared.coach.lock().unwrap().config().thresholds.entries() {
+                    println!("{} = {}", key, value);
+                }
+            },
+            Some(Command::Set(key, value)) => match shared.coach.lock().unwrap().set_threshold(&key, &value) {
+                Ok(()) => println!("{} = {} (until the coach restarts)", key, value),
+                Err(err) => println!("{}", err.red()),
+            },
+            Some(Command::Stats { all }) => {
+                let coach = shared.coach.lock().unwrap();
+                let Some(history) = coach.history() else {
+                    println!("Match history is turned off");
+                    continue;
+                };
+                // Stick to the account playing now, when there is one
+                let steam_id = coach.game_state().and_then(|s| s.player()).and_then(|p| p.steamid.as_deref());
+                let since = (!all).then(|| history.session_start());
+                match history.stats(steam_id, since) {
+                    Ok(stats) => {
+                        let scope = if all { "all matches" } else { "this session" };
+                        match coach.player_profile() {
+                            Some(player) => print_stats(&format!("{}, {}", player.persona_name, scope), &stats),
+                            None => print_stats(scope, &stats),
+                        }
+                    },
+                    Err(e) => println!("{}", format!("Could not read match history: {}", e).red()),
+                }
+            },
+            Some(Command::Timeline(page)) => print_timeline(shared.coach.lock().unwrap().event_log(), page),
+            Some(Command::Mark(note)) => {
+                let mut coach = shared.coach.lock().unwrap();
+                let marker = coach.add_marker(note);
+                print_event(&marker.event(), &coach);
+                if let Some(recorder) = &shared.recorder {
+                    if let Err(e) = recorder.lock().unwrap().mark(&marker) {
+                        eprintln!("Could not write to the recording: {}", e);
+                    }
+                }
+            },
+            Some(Command::Save) => match shared.coach.lock().unwrap().save_game_state() {
+                Ok(filename) => println!("Saved to {}", filename),
+                Err(e) => println!("{}", format!("Could not save the game state: {}", e).red()),
+            },
+            Some(Command::Ask(question)) => {
+                let coach = shared.coach.clone();
+                let feed = shared.feed.clone();
+                let (offline, context) = {
+                    let coach = coach.lock().unwrap();
+                    let offline = questions::answer(&coach, &question);
+                    let context = [llm::prompt(&coach).unwrap_or_default(), questions::facts(&question),
+                        format!("The coach's own rules suggest: {}", offline)];
+                    (offline, context.iter().filter(|part| !part.is_empty()).cloned().collect::<Vec<_>>().join("\n"))
+                };
+                // The model answers in the background; its own rules are the fallback
+                let client = llm.clone();
+                runtime.spawn(async move {
+                    let answer = match client {
+                        Some(client) => client.answer(&context, &question).await.unwrap_or_else(|e| {
+                            eprintln!("{}", e.dimmed());
+                            offline
+                        }),
+                        None => offline,
+                    };
+                    let mut coach = coach.lock().unwrap();
+                    let events = coach.add_question(&question, answer);
+                    for event in &events {
+                        print_event(event, &coach);
+                    }
+                    feed.publish(snapshot::snapshot(&coach), &events);
+                });
+            },
+            Some(Command::Role) => {
+                let coach = shared.coach.lock().unwrap();
+                match (coach.position(), coach.role_override(), coach.role_detection()) {
+                    (Some(position), Some(_), _) => println!("Position {} ({}), set by you - role auto to detect it", position, role_name(position)),
+                    (_, None, Some(detection)) => println!("{}", detection.describe()),
+                    (Some(position), None, None) => println!("Position {} ({}), usual for your hero until the laning stage shows otherwise", position, role_name(position)),
+                    (None, _, _) => println!("No hero yet"),
+                }
+            },
+            Some(Command::SetRole(position)) => {
+                let mut coach = shared.coach.lock().unwrap();
+                let event = coach.set_role(position);
+                print_event(&event, &coach);
+            },
+            Some(Command::Ward(kind, owner)) => {
+                let mut coach = shared.coach.lock().unwrap();
+                match coach.mark_ward(kind, owner) {
+                    Some(event) => print_event(&event, &coach),
+                    None => println!("No game clock yet"),
+                }
+            },
+            Some(Command::Outposts) => {
+                let coach = shared.coach.lock().unwrap();
+                let team = coach.game_state().and_then(|s| s.player_team()).and_then(|t| Team::from_name(&t));
+                for outpost in Outpost::BOTH {
+                    match coach.outposts().owner(outpost) {
+                        Some(ownership) => println!("{:<15} {}", outpost.name(), ownership.describe(team)),
+                        None => println!("{:<15} unknown - o {} ours|theirs once you know", outpost.name(),
+                            if outpost == Outpost::Top { "top" } else { "bottom" }),
+                    }
+                }
+            },
+            Some(Command::MarkOutpost(outpost, ours)) => {
+                let mut coach = shared.coach.lock().unwrap();
+                match coach.mark_outpost(outpost, ours) {
+                    Some(event) => print_event(&event, &coach),
+                    None => println!("No game clock yet"),
+                }
+            },
+            Some(Command::Aegis(ours)) => {
+                let mut coach = shared.coach.lock().unwrap();
+                match coach.mark_aegis(ours) {
+                    Some(event) => print_event(&event, &coach),
+                    None if ours.is_none() => println!("No Aegis to clear"),
+                    None => println!("No game clock yet"),
+                }
+            },
+            Some(Command::Help) => print_commands(&shared.coach.lock().unwrap().config().keys),
+            None if line.trim().is_empty() => {},
+            None => println!("Unknown command '{}' - type ? for a list", line.trim()),
+        }
+    }
+}
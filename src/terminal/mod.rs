@@ -0,0 +1,7 @@
+// src/terminal/mod.rs
+// The coach at the terminal: panels and events as they print, typed commands, and stepping
+// through recordings
+
+pub mod commands;
+pub mod print;
+pub mod review;
@@ -0,0 +1,951 @@
+// src/terminal/print.rs
+use std::collections::HashMap;
+
+use chrono::{DateTime, Local};
+use colored::Colorize;
+
+use crate::abilities::{self, AbilityStatus};
+use crate::alerts::{alert_label, Alert};
+use crate::analysis::awareness::AwarenessReport;
+use crate::analysis::item_timings::ItemTiming;
+use crate::analysis::laning::LaningReport;
+use crate::analysis::performance::{HeroPerformanceTracker, Metric, PerformanceReport, Trend};
+use crate::barracks::{self, CreepTier};
+use crate::briefing::LaneBriefing;
+use crate::buildings::{self, BuildingKind, BuildingLane};
+use crate::buyback::{self, BuybackStatus};
+use crate::camps::FIRST_SPAWN;
+use crate::counters;
+use crate::events::{EventKind, EventLog, GameEvent};
+use crate::format::{format_game_time, format_gold, format_hero_name, format_item_name, sparkline};
+use crate::gold::GoldWatch;
+use crate::heatmap::{self, Heatmap};
+use crate::heroes;
+use crate::history::HistoryStats;
+use crate::inventory::Inventory;
+use crate::item_build::Recommendation;
+use crate::items;
+use crate::llm;
+use crate::map_control::{self, MapControl};
+use crate::opendota::{Grade, PlayerHeroProfile};
+use crate::outposts::Outpost;
+use crate::roshan::{RoshanStatus, RoshanTracker};
+use crate::scoreboard::{self, ScoreboardRow};
+use crate::state::{Draft, DraftSelection, Hero, Item, Team};
+use crate::steam::SteamProfile;
+use crate::team_cooldowns::{EnemyCooldowns, TeamAbility};
+use crate::timers::{self, DayNight};
+use crate::tracking::describe_map_location;
+use crate::vod::ClockMapping;
+use crate::wards::WardOwner;
+use crate::{Coach, GameState};
+
+// Characters per performance sparkline
+const SPARKLINE_WIDTH: usize = 40;
+
+// Timeline entries per page
+pub const TIMELINE_PAGE: usize = 20;
+
+// One page of the event log, oldest first within the page
+pub fn print_timeline(log: &EventLog, page: usize) {
+    let pages = log.len().div_ceil(TIMELINE_PAGE).max(1);
+    let page = page.min(pages);
+    let end = log.len().saturating_sub((page - 1) * TIMELINE_PAGE);
+    let start = end.saturating_sub(TIMELINE_PAGE);
+
+    println!("{} (page {} of {}, {} events):", "TIMELINE".cyan().bold(), page, pages, log.len());
+    for event in log.iter().skip(start).take(end - start) {
+        println!("  [{}] {}: {}", format_game_time(Some(event.game_time)), event.kind.label(), event.description);
+    }
+    if page < pages {
+        println!("  {}", format!("timeline {} for older events", page + 1).dimmed());
+    }
+    if let (spilled @ 1.., Some(path)) = log.spilled() {
+        println!("  {}", format!("{} older events are in {}", spilled, path.display()).dimmed());
+    }
+    println!();
+}
+
+// Win/loss and averages over stored matches
+pub fn print_stats(scope: &str, stats: &HistoryStats) {
+    println!("{} ({}):", "STATS".cyan().bold(), scope);
+    if stats.matches == 0 {
+        println!("  No finished matches stored yet\n");
+        return;
+    }
+    
+    let win_rate = (stats.wins * 100).checked_div(stats.wins + stats.losses)
+        .map(|rate| format!(" ({}%)", rate))
+        .unwrap_or_default();
+    println!("  {} game{}, {} - {}{}",
+        stats.matches,
+        if stats.matches == 1 { "" } else { "s" },
+        format!("{}W", stats.wins).green(),
+        format!("{}L", stats.losses).red(),
+        win_rate);
+    println!("  GPM {}  XPM {}  Deaths/game {:.1}", stats.average_gpm, stats.average_xpm, stats.deaths_per_game);
+    let heroes: Vec<String> = stats.heroes.iter()
+        .take(5)
+        .map(|(hero, games)| format!("{} {}", format_hero_name(hero), games))
+        .collect();
+    if !heroes.is_empty() {
+        println!("  Most played: {}", heroes.join(", "));
+    }
+    println!();
+}
+
+// Enemy sightings over the match as a shaded map, north at the top
+pub fn print_heatmap(current_time: i32, heatmap: &Heatmap, query: Option<&str>) {
+    // Accept nicknames and prefixes as well as the name shown in the client
+    let hero = match query {
+        Some(query) => {
+            let matches = heroes::search(query);
+            let found = heatmap.heroes().into_iter()
+                .find(|name| matches.iter().any(|h| h.name == *name) || name.to_lowercase().starts_with(&query.to_lowercase()));
+            match found {
+                Some(name) => Some(name),
+                None => {
+                    println!("No sightings of '{}' yet", query);
+                    return;
+                },
+            }
+        },
+        None => None,
+    };
+
+    println!("[{}] {}: {} ({} sightings)",
+        format_game_time(Some(current_time)),
+        "ENEMY HEATMAP".cyan().bold(),
+        hero.unwrap_or("enemy team"),
+        heatmap.sightings(hero));
+    if heatmap.sightings(hero) == 0 {
+        println!("  No enemies seen yet\n");
+        return;
+    }
+
+    let border = "─".repeat(heatmap::GRID * 2);
+    println!("  ┌{}┐", border);
+    for row in heatmap.render(hero) {
+        println!("  │{}│", row.red());
+    }
+    println!("  └{}┘", border);
+    for (area, share) in heatmap.hotspots(hero, 3) {
+        println!("  {:>3}% {}", share, area);
+    }
+    println!();
+}
+
+// Header line describing the day/night cycle
+fn describe_day_night(cycle: &DayNight) -> String {
+    let countdown = format_game_time(Some(cycle.seconds_until_change));
+    
+    if cycle.nightstalker_night {
+        // Night Stalker's night overrides the cycle until his ultimate ends
+        let natural = if cycle.cycle_is_day { "natural night" } else { "natural day" };
+        format!("{} ({} in {})", "Night Stalker's night".magenta().bold(), natural, countdown)
+    } else if cycle.is_day {
+        format!("{} (night falls in {})", "Day".yellow(), countdown)
+    } else {
+        format!("{} (day breaks in {})", "Night".blue().bold(), countdown)
+    }
+}
+
+// Roshan and Aegis lines of the timers panel, once Roshan has died this match
+fn print_roshan(roshan: &RoshanTracker, clock_time: i32, team: Option<Team>) {
+    match roshan.status(clock_time) {
+        RoshanStatus::Dead { respawn_from, respawn_by } => println!("  {:<13} in {} - {} (at {} - {})",
+            "Roshan",
+            format_game_time(Some(respawn_from - clock_time)),
+            format_game_time(Some(respawn_by - clock_time)),
+            format_game_time(Some(respawn_from)),
+            format_game_time(Some(respawn_by))),
+        RoshanStatus::MaybeUp { respawn_by } => println!("  {:<13} {} (certain by {})",
+            "Roshan", "may be up".yellow().bold(), format_game_time(Some(respawn_by))),
+        RoshanStatus::Alive if roshan.kills() > 0 => println!("  {:<13} {}", "Roshan", "up".red().bold()),
+        RoshanStatus::Alive => {},
+    }
+    if let Some(aegis) = roshan.aegis(clock_time) {
+        let holder = match aegis.team {
+            Some(holder) if Some(holder) == team => "yours".green(),
+            Some(holder) => holder.name().red(),
+            None => "unknown holder".normal(),
+        };
+        let hero = aegis.holder.as_ref().map(|h| format!(" ({})", h)).unwrap_or_default();
+        println!("  {:<13} {}{} - expires in {}", "Aegis", holder, hero, format_game_time(Some(aegis.expires_at() - clock_time)));
+    }
+}
+
+// Every summary panel: fights, enemies, the player's hero, buildings, timers and alerts
+pub fn print_summary(coach: &Coach, current_time: i32) {
+    let own_team = coach.game_state().and_then(|s| s.player_team()).and_then(|t| Team::from_name(&t));
+    // Custom games and Ability Draft get tracking panels only
+    let tracking_only = coach.game_mode().tracking_only();
+    if let Some(fight) = coach.teamfight() {
+        println!("\n[{}] {}: {} heroes down since {}",
+            format_game_time(Some(current_time)),
+            "FIGHT IN PROGRESS".white().on_red().bold(),
+            fight.total_deaths(),
+            format_game_time(Some(fight.start)));
+        if let Some(advantage) = own_team.and_then(|team| coach.respawns().advantage(team, current_time)) {
+            println!("  {}", advantage.describe().green().bold());
+        }
+    } else if coach.skirmishing() {
+        println!("\n[{}] {}",
+            format_game_time(Some(current_time)),
+            "Skirmishes in the last minute - a fight may be developing".yellow().bold());
+    }
+    
+    let dead = own_team.map(|team| coach.respawns().dead(if team == Team::Radiant { Team::Dire } else { Team::Radiant })).unwrap_or_default();
+    if !dead.is_empty() {
+        println!("\n[{}] {}:",
+            format_game_time(Some(current_time)),
+            "ENEMY RESPAWNS".cyan().bold());
+        for hero in dead {
+            println!("  {}", hero.describe(current_time));
+        }
+    }
+    
+    let heroes = coach.enemy_team_heroes();
+    if !heroes.is_empty() {
+        println!("\n[{}] {}: ", 
+            format_game_time(Some(current_time)),
+            "ENEMY TEAM SUMMARY".cyan().bold());
+        
+        for (i, hero) in heroes.iter().enumerate() {
+            match coach.enemies().get(hero) {
+                // Anything not seen this tick is shown as a stale estimate
+                Some(enemy) => println!("  {}. {:<19} {}", i+1, hero.yellow(), format!("seen {}s ago, {}, HP {}, mana {}",
+                    current_time - enemy.last_seen_time,
+                    enemy.describe_level(current_time),
+                    enemy.health.describe(current_time, "%"),
+                    enemy.mana.describe(current_time, "%")).dimmed()),
+                None => println!("  {}. {}", i+1, hero.yellow()),
+            }
+            if let Some(profile) = coach.enemy_profiles().get(hero) {
+                println!("     {}", profile.describe().magenta());
+            }
+        }
+        println!();
+        
+    }
+    
+    if let Some(risk) = coach.gank_risk() {
+        let meter = gauge(risk.score, 100, 20);
+        let meter = match risk.score {
+            0..=24 => meter.green(),
+            25..=49 => meter.yellow(),
+            _ => meter.red().bold(),
+        };
+        println!("[{}] {}: {} {} ({})",
+            format_game_time(Some(current_time)),
+            "GANK RISK".cyan().bold(),
+            meter,
+            risk.score,
+            risk.level());
+        for reason in &risk.reasons {
+            println!("  - {}", reason);
+        }
+        println!();
+    }
+    
+    // The model's latest tip, until the next one is due
+    let tip_interval = coach.config().llm.interval_secs.max(llm::MIN_INTERVAL) as i32;
+    if let Some((time, tip)) = coach.coach_tip().filter(|(time, _)| current_time - time < tip_interval) {
+        println!("[{}] {} ({} ago):",
+            format_game_time(Some(current_time)),
+            "COACH SAYS".cyan().bold(),
+            format_game_time(Some(current_time - time)));
+        println!("  {}", tip);
+        println!();
+    }
+
+    for (name, panel) in coach.plugin_panels() {
+        println!("[{}] {} ({}):", format_game_time(Some(current_time)), panel.title.to_uppercase().cyan().bold(), name);
+        for line in &panel.lines {
+            println!("  {}", line);
+        }
+        println!();
+    }
+    
+    let baselines = coach.baselines();
+    if let Some(first) = baselines.first() {
+        println!("[{}] {} at {}:00 {}({} games):",
+            format_game_time(Some(current_time)),
+            "YOUR AVERAGE".cyan().bold(),
+            first.minute,
+            first.hero.as_ref().map(|h| format!("on {} ", h)).unwrap_or_default(),
+            first.matches);
+        for baseline in &baselines {
+            let current = format!("{:>6}", baseline.current);
+            println!("  {:<4} {} (avg {})",
+                baseline.metric.name(),
+                if baseline.ahead() { current.green() } else { current.red() },
+                baseline.average);
+        }
+        println!();
+    }
+    
+    if let Some(route) = coach.farm_route() {
+        println!("[{}] {}{}:",
+            format_game_time(Some(current_time)),
+            "FARM ROUTE".cyan().bold(),
+            if route.own_side_only { " (own side - enemies missing)" } else { "" });
+        for (i, step) in route.steps().iter().enumerate() {
+            println!("  {}. {}", i + 1, step);
+        }
+        println!();
+    }
+    
+    // Only camps the player marked as cleared are known to be empty
+    if let Some(clock_time) = coach.game_state().and_then(|s| s.clock_time()).filter(|t| *t >= FIRST_SPAWN) {
+        let down = coach.camps().down(clock_time);
+        if !down.is_empty() {
+            println!("[{}] {}: {} down, the rest should be up",
+                format_game_time(Some(current_time)),
+                "JUNGLE CAMPS".cyan().bold(),
+                down.len());
+            for (camp, seconds) in down {
+                println!("  {:<36} back in {}s", camp.describe(), seconds);
+            }
+            println!();
+        }
+    }
+    
+    let lanes = coach.lanes().assignments();
+    if !lanes.is_empty() {
+        println!("[{}] {}:",
+            format_game_time(Some(current_time)),
+            "LIKELY LANING ASSIGNMENTS".cyan().bold());
+        for (hero, assignment) in lanes {
+            println!("  {:<19} {}", hero, assignment.role());
+        }
+        println!();
+    }
+    
+    // Uncertain by nature, so faded as they age
+    let predictions = coach.predictions();
+    if !predictions.is_empty() {
+        println!("[{}] {}:",
+            format_game_time(Some(current_time)),
+            "PREDICTED POSITIONS".cyan().bold());
+        for prediction in predictions {
+            let line = format!("{:<19} near {} (within {:.0}, unseen {}s, {:.0}% confidence)",
+                prediction.hero,
+                describe_map_location(prediction.position),
+                prediction.radius,
+                prediction.seconds_unseen,
+                prediction.confidence * 100.0);
+            println!("  {}", if prediction.confidence >= 0.5 { line.yellow() } else { line.dimmed() });
+        }
+        println!();
+    }
+    
+    let enemies = coach.enemy_hero_infos();
+    let threats = counters::threat_profile(&enemies);
+    if !threats.is_empty() && !tracking_only {
+        println!("[{}] {}:", 
+            format_game_time(Some(current_time)),
+            "ENEMY THREATS".cyan().bold());
+        for (threat, heroes) in threats {
+            let line = format!("{:<19} {}", threat.name(), heroes.join(", "));
+            if heroes.len() >= threat.threshold() {
+                println!("  {} -> {}", line.red(), threat.counter_items());
+            } else {
+                println!("  {}", line);
+            }
+        }
+        println!();
+    }
+    
+    // Worked out from the clock and score, so labelled as estimates
+    let estimates = coach.enemy_estimates();
+    if !estimates.is_empty() && !tracking_only && !coach.game_state().map(|s| s.is_spectating()).unwrap_or(false) {
+        println!("[{}] {} (estimated):", 
+            format_game_time(Some(current_time)),
+            "ENEMY NET WORTH".cyan().bold());
+        for estimate in estimates {
+            println!("  {:<19} {}", estimate.hero.name, estimate.describe().dimmed());
+        }
+        println!();
+    }
+    
+    // Spectators and in-game coaches get the whole scoreboard
+    if let Some(state) = coach.game_state().filter(|s| s.is_spectating()) {
+        print_scoreboard(current_time, &scoreboard::scoreboard(state), coach.enemy_profiles(), coach.steam_profiles());
+    }
+    
+    if let Some(hero) = coach.game_state().and_then(|s| s.hero()) {
+        let gold = coach.game_state().and_then(|s| s.player()).and_then(|p| p.gold);
+        let late = coach.game_state().and_then(|s| s.clock_time())
+            .map(|t| t >= coach.config().thresholds.buyback_from_minute * 60)
+            .unwrap_or(false);
+        print_vitals(current_time, hero, gold, late);
+    }
+    
+    print_performance(current_time, coach.performance(), &coach.benchmark_grades());
+    
+    // The player's own spells, so ultimate availability is a glance away
+    if let Some(state) = coach.game_state() {
+        let mana = state.hero().and_then(|h| h.mana);
+        if let Some(abilities) = state.abilities() {
+            print_abilities(current_time, &abilities::statuses(abilities, mana));
+        }
+    }
+    
+    if let Some(inventory) = coach.game_state().and_then(|s| s.inventory()) {
+        print_inventory(current_time, inventory);
+    }
+    
+    if let Some(recommendation) = coach.item_recommendation().filter(|_| !tracking_only) {
+        print_recommendation(current_time, recommendation);
+    }
+    
+    if let Some(state) = coach.game_state().filter(|s| s.buildings.is_some()) {
+        print_buildings(current_time, state, coach.config().thresholds.low_building_percent);
+    }
+    
+    let team = coach.game_state().and_then(|s| s.player_team()).and_then(|t| Team::from_name(&t));
+    if let Some(control) = team.and_then(|team| coach.game_state().and_then(|s| map_control::analyze(s, team))) {
+        print_map_control(current_time, &control);
+    }
+    
+    print_enemy_cooldowns(current_time, coach.enemy_cooldowns());
+    
+    let map = coach.game_state().and_then(|s| s.map.as_ref());
+    
+    if let Some((clock_time, map)) = map.and_then(|m| m.clock_time.map(|t| (t, m))) {
+        let cycle = DayNight::from_clock(clock_time, map.daytime, map.nightstalker_night.unwrap_or(false));
+        println!("[{}] {}: {}", 
+            format_game_time(Some(current_time)),
+            "TIMERS".cyan().bold(),
+            describe_day_night(&cycle));
+        
+        for spawn in timers::upcoming_runes(clock_time).into_iter().filter(|_| !tracking_only) {
+            let countdown = format_game_time(Some(spawn.seconds_until));
+            println!("  {:<13} in {} (at {})",
+                spawn.kind.name(),
+                if spawn.seconds_until <= coach.config().thresholds.rune_alert_lead { countdown.red().bold() } else { countdown.normal() },
+                format_game_time(Some(spawn.spawn_time)));
+        }
+        if !tracking_only {
+            print_roshan(coach.roshan(), clock_time, team);
+        }
+        for outpost in Outpost::BOTH.into_iter().filter(|_| !tracking_only) {
+            if let Some(ownership) = coach.outposts().owner(outpost) {
+                let name = outpost.name();
+                println!("  {:<13} {}", format!("{}{}", name[..1].to_uppercase(), &name[1..]), ownership.describe(team));
+            }
+        }
+        for ward in coach.wards().active(clock_time) {
+            let label = match ward.owner {
+                WardOwner::Own => "Ward",
+                WardOwner::Enemy => "Enemy ward",
+            };
+            println!("  {:<13} {}{} - {} left (at {})",
+                label,
+                ward.kind.name(1),
+                ward.location.as_ref().map(|l| format!(" in {}", l)).unwrap_or_default(),
+                format_game_time(Some(ward.expires_at() - clock_time)),
+                format_game_time(Some(ward.expires_at())));
+        }
+        println!();
+        
+    }
+    
+    let active: Vec<&Alert> = coach.alerts().active_sorted().into_iter()
+        .filter(|a| a.expires_at() > current_time)
+        .collect();
+    
+    if !active.is_empty() {
+        println!("[{}] {}:", 
+            format_game_time(Some(current_time)),
+            "ACTIVE ALERTS".cyan().bold());
+        
+        for alert in active {
+            println!("  {}: {}", alert_label(alert.category, alert.severity), alert.message);
+        }
+        println!();
+    }
+}
+
+// Print an event as it happens
+pub fn print_event(event: &GameEvent, coach: &Coach) {
+    if event.kind == EventKind::EnemyDiscovered {
+        println!();
+    }
+    
+    println!("[{}] {}: {}",
+        format_game_time(Some(event.game_time)),
+        event.kind.label(),
+        event.description);
+    
+    if event.kind == EventKind::GameEnded {
+        if let Some(report) = coach.performance().report() {
+            print_report(&report, coach.item_timings().timings(), coach.gold(), coach.config().thresholds.unspent_gold_warning,
+                coach.awareness().report().as_ref(), coach.clock_mapping(), coach.player_profile());
+        }
+    }
+    
+    // Live banner while a fight is on
+    if event.kind == EventKind::TeamFight && coach.teamfight().is_some() {
+        println!("  {}", ">>> FIGHT IN PROGRESS <<<".white().on_red().bold());
+    }
+    
+    if event.kind == EventKind::LaningReport {
+        if let Some(report) = coach.laning_report() {
+            print_laning_report(report);
+        }
+    }
+    
+    if event.kind == EventKind::Briefing {
+        if let Some(briefing) = coach.briefing() {
+            print_briefing(briefing);
+        }
+    }
+    
+    // Show the updated enemy team list whenever we discover a new hero
+    if event.kind == EventKind::EnemyDiscovered {
+        for (i, hero_name) in coach.enemy_team_heroes().iter().enumerate() {
+            println!("  {}. {}", i+1, hero_name.yellow());
+        }
+        println!();
+    }
+}
+
+// One-time lane panel shown during strategy time
+fn print_briefing(briefing: &LaneBriefing) {
+    for report in &briefing.lanes {
+        let name = format!("{:<9}", report.lane.name());
+        let name = if Some(report.lane) == briefing.lane { name.yellow().bold() } else { name.bold() };
+        let list = |heroes: &[String]| if heroes.is_empty() { "?".to_string() } else { heroes.join(", ") };
+        println!("  {} {:<9} {} vs {}",
+            name,
+            format!("({})", report.lane.map_side(briefing.team)),
+            list(&report.allies).green(),
+            list(&report.enemies).red());
+    }
+    
+    for advice in &briefing.advice {
+        println!("  {}", advice);
+    }
+    if !briefing.starting_items.is_empty() {
+        println!("  Starting items: {}", briefing.starting_items.join(", ").cyan());
+    }
+    println!();
+}
+
+// Boxed so it stands out from the event stream at 10:00
+fn print_laning_report(report: &LaningReport) {
+    let verdict = match report.verdict() {
+        "won" => "LANE WON".green().bold(),
+        "lost" => "LANE LOST".red().bold(),
+        _ => "LANE EVEN".yellow().bold(),
+    };
+    let lines = report.lines();
+    let width = lines.iter().map(|l| l.chars().count()).max().unwrap_or(0);
+    println!("  ┌─{}─┐", "─".repeat(width));
+    println!("  │ {}{} │", verdict, " ".repeat(width.saturating_sub(report.verdict().len() + 5)));
+    for line in lines {
+        println!("  │ {:<width$} │", line, width = width);
+    }
+    println!("  └─{}─┘", "─".repeat(width));
+    println!();
+}
+
+// A fixed-width bar such as "██████░░░░"
+fn gauge(current: i32, max: i32, width: usize) -> String {
+    let filled = if max > 0 { (current.clamp(0, max) as usize * width) / max as usize } else { 0 };
+    format!("{}{}", "█".repeat(filled), "░".repeat(width - filled))
+}
+
+// Health and mana while alive; respawn timer and buyback affordability while dead, or late in the game
+fn print_vitals(current_time: i32, hero: &Hero, gold: Option<i32>, late: bool) {
+    println!("[{}] {}:", 
+        format_game_time(Some(current_time)),
+        "VITALS".cyan().bold());
+    
+    if hero.alive == Some(false) {
+        println!("  {} respawn in {}s",
+            "DEAD".red().bold(),
+            hero.respawn_seconds.unwrap_or(0));
+    } else {
+        if let (Some(health), Some(max)) = (hero.health, hero.max_health) {
+            println!("  HP   {} {}/{}", gauge(health, max, 20).red(), health, max);
+        }
+        if let (Some(mana), Some(max)) = (hero.mana, hero.max_mana) {
+            println!("  Mana {} {}/{}", gauge(mana, max, 20).blue(), mana, max);
+        }
+    }
+    
+    if hero.alive == Some(false) || late {
+        if let Some(cost) = hero.buyback_cost {
+            let status = match gold.and_then(|gold| buyback::status(hero, gold)) {
+                Some(status @ BuybackStatus::OnCooldown(_)) => status.describe(cost).red(),
+                Some(status @ BuybackStatus::Available(_)) => status.describe(cost).green().bold(),
+                Some(status @ BuybackStatus::Short(_)) => status.describe(cost).yellow(),
+                None if hero.buyback_cooldown.unwrap_or(0) > 0 =>
+                    format!("on cooldown for {}", format_game_time(hero.buyback_cooldown)).red(),
+                None => format!("{} gold", cost).normal(),
+            };
+            println!("  Buyback: {}", status);
+        }
+    }
+    println!();
+}
+
+// Remaining towers and barracks of both teams, one line per lane
+fn print_buildings(current_time: i32, state: &GameState, low_percent: i32) {
+    println!("[{}] {}:", 
+        format_game_time(Some(current_time)),
+        "BUILDINGS".cyan().bold());
+    
+    for team in Team::BOTH {
+        let standing = buildings::team_buildings(state, team);
+        let name = match team {
+            Team::Radiant => team.name().green().bold(),
+            Team::Dire => team.name().red().bold(),
+        };
+        let towers = standing.iter().filter(|b| matches!(b.kind, BuildingKind::Tower(_))).count();
+        let barracks = standing.iter().filter(|b| matches!(b.kind, BuildingKind::MeleeBarracks | BuildingKind::RangedBarracks)).count();
+        // Lanes where the team's creeps are stronger for the barracks the other side lost
+        let creeps = barracks::creeps(state, team).unwrap_or_default();
+        let upgraded = if !creeps.is_empty() && creeps.iter().all(|(_, tier)| *tier == CreepTier::Mega) {
+            format!(" - {}", CreepTier::Mega.name().magenta().bold())
+        } else {
+            let lanes: Vec<&str> = creeps.iter().filter(|(_, tier)| *tier == CreepTier::Super).map(|(lane, _)| lane.name()).collect();
+            if lanes.is_empty() { String::new() } else { format!(" - {} {}", CreepTier::Super.name().yellow(), lanes.join(", ")) }
+        };
+        println!("  {} ({} towers, {} barracks){}", name, towers, barracks, upgraded);
+        
+        for lane in BuildingLane::ALL {
+            let entries: Vec<String> = standing.iter()
+                .filter(|b| b.lane == lane)
+                .map(|b| {
+                    let percent = b.health_percent();
+                    let bar = gauge(b.health, b.max_health, 5);
+                    let bar = if percent <= low_percent { bar.red() } else if percent <= 70 { bar.yellow() } else { bar.green() };
+                    format!("{} {}", b.kind.name(), bar)
+                })
+                .collect();
+            
+            let entries = if entries.is_empty() { "all destroyed".dimmed().to_string() } else { entries.join("  ") };
+            println!("    {:<4} {}", lane.name(), entries);
+        }
+    }
+    println!();
+}
+
+// Building differential turned into a score and a tactical tip
+fn print_map_control(current_time: i32, control: &MapControl) {
+    let score = format!("{:+}", control.score);
+    println!("[{}] {}: {} ({} towers and {} barracks vs {} and {})", 
+        format_game_time(Some(current_time)),
+        "MAP CONTROL".cyan().bold(),
+        if control.score > 0 { score.green().bold() } else if control.score < 0 { score.red().bold() } else { score.normal() },
+        control.own_towers,
+        control.own_barracks,
+        control.enemy_towers,
+        control.enemy_barracks);
+    println!("  {}", control.verdict);
+    if let Some(tip) = control.tip {
+        println!("  Tip: {}", tip);
+    }
+    println!();
+}
+
+// Current GPM, XPM, CS and deaths with where each is heading
+fn print_performance(current_time: i32, tracker: &HeroPerformanceTracker, grades: &[Grade]) {
+    let Some(latest) = tracker.latest() else { return };
+    
+    let stats: Vec<String> = Metric::ALL.iter()
+        .map(|&metric| {
+            let arrow = match tracker.trend(metric) {
+                Some(Trend::Steady) | None => "→".normal(),
+                Some(trend) if (trend == Trend::Rising) == metric.higher_is_better() => trend.arrow().green().bold(),
+                Some(trend) => trend.arrow().red().bold(),
+            };
+            format!("{} {} {}", metric.name(), metric.value(latest), arrow)
+        })
+        .collect();
+    
+    println!("[{}] {}: {}", 
+        format_game_time(Some(current_time)),
+        "PERFORMANCE".cyan().bold(),
+        stats.join("  "));
+    if !grades.is_empty() {
+        let graded: Vec<String> = grades.iter()
+            .map(|g| if g.below_par() { g.describe().red().to_string() } else { g.describe() })
+            .collect();
+        println!("  vs OpenDota: {}", graded.join(", "));
+    }
+    
+    // Farm history, so dips after fights and deaths stand out
+    if tracker.samples().count() >= 2 {
+        let start = tracker.samples().next().map(|s| s.time).unwrap_or(0);
+        for metric in [Metric::Gpm, Metric::Xpm, Metric::NetWorth] {
+            let series = tracker.series(metric);
+            println!("  {:<4} {} {}-{}",
+                metric.name(),
+                sparkline(&series, SPARKLINE_WIDTH).cyan(),
+                series.iter().min().unwrap_or(&0),
+                series.iter().max().unwrap_or(&0));
+        }
+        println!("       {} to {}", format_game_time(Some(start)), format_game_time(Some(latest.time)));
+    }
+    println!();
+}
+
+// End of game summary of the player's own numbers
+fn print_report(
+    report: &PerformanceReport,
+    timings: &[ItemTiming],
+    gold: &GoldWatch,
+    unspent_warning: i32,
+    awareness: Option<&AwarenessReport>,
+    clock: &ClockMapping,
+    player: Option<&SteamProfile>,
+) {
+    match player {
+        Some(player) => println!("\n{} - {}", "POST-GAME REPORT".cyan().bold(), player.persona_name.bold()),
+        None => println!("\n{}", "POST-GAME REPORT".cyan().bold()),
+    }
+    println!("  Game length: {}", format_game_time(Some(report.duration)));
+    println!("  GPM {} / XPM {} (peak GPM {} at {})",
+        report.gpm, report.xpm, report.peak_gpm.0, format_game_time(Some(report.peak_gpm.1)));
+    println!("  Last hits: {} ({:.1} per minute)", report.last_hits, report.cs_per_minute);
+    if report.deaths.is_empty() {
+        println!("  Deaths: {}", "none".green().bold());
+    } else {
+        let times: Vec<String> = report.deaths.iter().map(|t| format_game_time(Some(*t))).collect();
+        println!("  Deaths: {} (around {})", report.deaths.len(), times.join(", "));
+    }
+    let lost: i32 = gold.losses().iter().map(|l| l.lost).sum();
+    if lost > 0 {
+        let holding = gold.lost_holding(unspent_warning);
+        let line = format!("  Gold lost to deaths: {}", format_gold(lost));
+        if holding > 0 {
+            println!("{} ({} of it dying with {}+ unspent)", line, format_gold(holding).yellow().bold(), format_gold(unspent_warning));
+        } else {
+            println!("{}", line);
+        }
+    }
+    if let Some(awareness) = awareness {
+        let score = format!("{}%", awareness.score);
+        let score = match awareness.score {
+            0..=49 => score.red().bold(),
+            50..=74 => score.yellow().bold(),
+            _ => score.green().bold(),
+        };
+        println!("  Map awareness: {} ({}+ enemies on the minimap for that share of your {} past the river)",
+            score, awareness.needed, format_game_time(Some(awareness.exposed)));
+        for stretch in &awareness.worst {
+            println!("    {}", stretch.describe());
+        }
+    }
+    if !timings.is_empty() {
+        println!("  Item timings:");
+        for timing in timings {
+            let line = format!("    {}", timing.describe());
+            match timing.delta() {
+                Some(delta) if delta <= 0 => println!("{}", line.green()),
+                Some(_) => println!("{}", line.yellow()),
+                None => println!("{}", line),
+            }
+        }
+    }
+    // Enough to turn any game time into a point in a stream or recording
+    if let Some(start) = clock.timestamp_at_clock(0) {
+        let local = DateTime::from_timestamp(start, 0).map(|t| t.with_timezone(&Local).format("%H:%M:%S").to_string()).unwrap_or_default();
+        println!("  VOD sync: the clock showed 0:00 at {} (unix {}), {} pause{} in the match",
+            local, start, clock.pauses(), if clock.pauses() == 1 { "" } else { "s" });
+    }
+    println!();
+}
+
+// The suggested next item and what to buy towards it
+fn print_recommendation(current_time: i32, recommendation: &Recommendation) {
+    println!("[{}] {}: {} ({})", 
+        format_game_time(Some(current_time)),
+        "NEXT ITEM".cyan().bold(),
+        recommendation.item.name.green().bold(),
+        recommendation.reason);
+    
+    // Parts can repeat, so tick off affordable ones as they are printed
+    let mut affordable = recommendation.buy_now.clone();
+    for purchase in &recommendation.shopping_list {
+        let line = format!("{:<28} {:>5}", purchase.name, purchase.cost);
+        if let Some(index) = affordable.iter().position(|p| p == purchase) {
+            affordable.remove(index);
+            println!("  {} {}", line.green(), "buy now".green().bold());
+        } else {
+            println!("  {}", line.dimmed());
+        }
+    }
+    println!("  {} gold to go", recommendation.remaining_cost());
+    println!();
+}
+
+// Enemy glyph and scan timers, only while one of them is down
+fn print_enemy_cooldowns(current_time: i32, cooldowns: &EnemyCooldowns) {
+    let down: Vec<(TeamAbility, i32)> = TeamAbility::ALL.iter()
+        .filter_map(|&a| cooldowns.remaining(a, current_time).map(|r| (a, r)))
+        .collect();
+    if down.is_empty() {
+        return;
+    }
+    
+    println!("[{}] {}:", 
+        format_game_time(Some(current_time)),
+        "ENEMY COOLDOWNS".cyan().bold());
+    
+    for (ability, remaining) in down {
+        println!("  {:<6} back in {} (at {})",
+            ability.name(),
+            format_game_time(Some(remaining)).green(),
+            format_game_time(Some(current_time + remaining)));
+    }
+    println!();
+}
+
+// Level, cooldown and castability of each of the player's abilities
+fn print_abilities(current_time: i32, statuses: &[AbilityStatus]) {
+    if statuses.is_empty() {
+        return;
+    }
+    
+    println!("[{}] {}:", 
+        format_game_time(Some(current_time)),
+        "ABILITIES".cyan().bold());
+    
+    for ability in statuses {
+        let state = if ability.level == 0 {
+            "not learned".dimmed()
+        } else if ability.passive {
+            "passive".normal()
+        } else if ability.cooldown > 0 {
+            format!("{}s", ability.cooldown).red()
+        } else if ability.enough_mana == Some(false) {
+            "no mana".blue().bold()
+        } else if ability.can_cast {
+            "ready".green().bold()
+        } else {
+            "unavailable".yellow()
+        };
+        
+        let name = format!("{:<22}", ability.name);
+        println!("  {} lvl {} {}",
+            if ability.ultimate { name.magenta().bold() } else { name.normal() },
+            ability.level,
+            state);
+    }
+    println!();
+}
+
+// One inventory entry: name, charges or bottled rune, and cooldown. Consumables are highlighted so they get used.
+fn describe_item(item: &Item) -> String {
+    let name = item.name.as_deref().unwrap_or_default();
+    let mut text = format_item_name(name);
+    
+    if let Some(rune) = &item.contains_rune {
+        text = format!("{} ({})", text, rune);
+    } else if let Some(charges) = item.charges.filter(|c| *c > 0) {
+        text = format!("{} x{}", text, charges);
+    }
+    
+    // The TP is meant to be carried, so it doesn't count
+    let consumable = name != "item_tpscroll" && items::by_class(name).map(|i| i.consumable).unwrap_or(false);
+    let mut text = if consumable { text.yellow().to_string() } else { text };
+    
+    if let Some(cooldown) = item.cooldown.filter(|c| *c > 0) {
+        text = format!("{} {}", text, format!("{}s", cooldown).red());
+    }
+    text
+}
+
+// Main slots, backpack, stash, TP and neutral item
+fn print_inventory(current_time: i32, inventory: &Inventory) {
+    let list = |slots: &[Option<Item>]| slots.iter()
+        .map(|slot| slot.as_ref().map(describe_item).unwrap_or_else(|| "-".dimmed().to_string()))
+        .collect::<Vec<_>>()
+        .join(" | ");
+    
+    println!("[{}] {}:", 
+        format_game_time(Some(current_time)),
+        "ITEMS".cyan().bold());
+    println!("  Inventory: {}", list(&inventory.main));
+    println!("  Backpack:  {}", list(&inventory.backpack));
+    if inventory.stash_items().next().is_some() {
+        println!("  Stash:     {}", list(&inventory.stash));
+    }
+    println!("  TP:        {}   Neutral: {}",
+        inventory.teleport.as_ref().map(describe_item).unwrap_or_else(|| "none".red().bold().to_string()),
+        inventory.neutral.as_ref().map(describe_item).unwrap_or_else(|| "none".dimmed().to_string()));
+    println!();
+}
+
+// Picks and bans for both teams, with whoever is on the clock highlighted
+// Picks show the hero's win rate this week once Stratz has answered
+pub fn print_draft(draft: &Draft, win_rates: &HashMap<i32, f32>) {
+    println!();
+    for team in Team::BOTH {
+        let Some(side) = draft.team(team) else { continue };
+        let names = |selections: Vec<DraftSelection>| selections.iter()
+            .map(|s| match win_rates.get(&s.hero_id) {
+                Some(win_rate) => format!("{} {:.0}%", s.hero_name(), win_rate * 100.0),
+                None => s.hero_name(),
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        
+        let mut header = format!("{:<8}", team.name()).bold();
+        if draft.active_team() == Some(team) {
+            let action = if draft.pick.unwrap_or(true) { "picking" } else { "banning" };
+            let clock = draft.activeteam_time_remaining.map(|t| format!(" {}s", t)).unwrap_or_default();
+            header = format!("{:<8} ({}{})", team.name(), action, clock).yellow().bold();
+        }
+        
+        println!("  {}", header);
+        println!("    Picks: {}", names(side.picks()).green());
+        println!("    Bans:  {}", names(side.bans()).red());
+    }
+    println!();
+}
+
+// Both teams in two blocks: level, hero, player, K/D/A, net worth and key items
+// Steam persona names replace what GSI sent once they're known
+fn print_scoreboard(current_time: i32, rows: &[ScoreboardRow], profiles: &HashMap<String, PlayerHeroProfile>, steam: &HashMap<String, SteamProfile>) {
+    println!("[{}] {}:", 
+        format_game_time(Some(current_time)),
+        "SCOREBOARD".cyan().bold());
+    
+    for team in Team::BOTH {
+        let team_rows: Vec<&ScoreboardRow> = rows.iter().filter(|r| r.team == team).collect();
+        let net_worth: i32 = team_rows.iter().map(|r| r.net_worth).sum();
+        let name = match team {
+            Team::Radiant => team.name().green().bold(),
+            Team::Dire => team.name().red().bold(),
+        };
+        println!("  {} (net worth {})", name, net_worth);
+        
+        for row in team_rows {
+            let hero = format!("{:<20}", row.hero);
+            let player_name = row.steam_id.as_ref()
+                .and_then(|id| steam.get(id))
+                .map(|p| p.persona_name.as_str())
+                .unwrap_or(&row.player_name);
+            println!("    {:>2} {} {:<14} {:>2}/{:>2}/{:<2} {:>6}  {}",
+                row.level,
+                if row.alive { hero.yellow() } else { hero.dimmed() },
+                player_name.chars().take(14).collect::<String>(),
+                row.kills,
+                row.deaths,
+                row.assists,
+                row.net_worth,
+                row.key_items.join(", "));
+            if let Some(profile) = profiles.get(&row.hero) {
+                println!("       {}", profile.describe().magenta());
+            }
+        }
+    }
+    println!();
+}
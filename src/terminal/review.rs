@@ -0,0 +1,218 @@
+// src/terminal/review.rs
+use std::path::Path;
+use std::time::Duration;
+
+use colored::Colorize;
+use tokio::io::{AsyncBufReadExt, BufReader};
+
+use crate::events::{EventKind, GameEvent};
+use crate::format::format_game_time;
+use crate::review::Review;
+use crate::terminal::print::{print_draft, print_event, print_summary, print_timeline, TIMELINE_PAGE};
+use crate::Config;
+
+// Seconds of game time a review plays through per real second
+const PLAY_SPEED: i32 = 10;
+
+// Commands for stepping through a recorded session with --review
+enum ReviewCommand {
+    // Move by this many payloads, back when negative
+    Step(isize),
+    // Move by this many seconds of game time
+    Seconds(i32),
+    // A page of every event in the recording, oldest first
+    Events(usize),
+    // Go to an event, numbered as in the event list
+    Jump(usize),
+    // Only the markers from the event list
+    Markers,
+    Play,
+    Summary,
+    Timeline(usize),
+    Help,
+    Quit,
+}
+
+impl ReviewCommand {
+    fn parse(line: &str) -> Option<ReviewCommand> {
+        let count = |n: &str| n.parse::<usize>().ok().filter(|&n| n > 0);
+        match line.trim() {
+            "" | "n" | "next" => Some(ReviewCommand::Step(1)),
+            "b" | "back" => Some(ReviewCommand::Step(-1)),
+            "e" | "events" => Some(ReviewCommand::Events(1)),
+            "k" | "markers" => Some(ReviewCommand::Markers),
+            "p" | "play" => Some(ReviewCommand::Play),
+            "s" | "summary" => Some(ReviewCommand::Summary),
+            "t" | "timeline" => Some(ReviewCommand::Timeline(1)),
+            "?" | "h" | "help" => Some(ReviewCommand::Help),
+            "q" | "quit" => Some(ReviewCommand::Quit),
+            line if line.starts_with('+') || line.starts_with('-') => line.parse().ok().map(ReviewCommand::Seconds),
+            line => match line.split_once(' ').map(|(c, rest)| (c, rest.trim()))? {
+                ("n" | "next", n) => count(n).map(|n| ReviewCommand::Step(n as isize)),
+                ("b" | "back", n) => count(n).map(|n| ReviewCommand::Step(-(n as isize))),
+                ("e" | "events", page) => count(page).map(ReviewCommand::Events),
+                ("j" | "jump", n) => count(n).map(ReviewCommand::Jump),
+                ("t" | "timeline", page) => count(page).map(ReviewCommand::Timeline),
+                _ => None,
+            },
+        }
+    }
+}
+
+fn print_review_commands() {
+    println!("Review commands (type and press Enter):");
+    println!("  n [k], Enter  step forward one payload, or k");
+    println!("  b [k]      step back one payload, or k");
+    println!("  +N, -N     move N seconds of game time forward or back, e.g. +60");
+    println!("  e [page]   every event in the recording, numbered, oldest first");
+    println!("  j <n>      jump to event n");
+    println!("  k          the markers dropped while playing, numbered as events");
+    println!("  p          play or pause, {} game seconds a second", PLAY_SPEED);
+    println!("  s          the summary panels as of now");
+    println!("  t [page]   the timeline up to now, newest page first");
+    println!("  ?, help    show this list");
+    println!("  q          quit");
+}
+
+// Move a review to `position`. Events on the way forward are printed as they happened;
+// going back is silent.
+fn move_review(review: &mut Review, position: usize, show_position: bool) {
+    if position >= review.position() {
+        let events = review.forward(position - review.position());
+        for event in &events {
+            print_event(event, review.coach());
+        }
+        if events.iter().any(|e| e.kind == EventKind::Draft) {
+            if let Some(draft) = review.coach().draft() {
+                print_draft(draft, review.coach().hero_win_rates());
+            }
+        }
+    } else {
+        review.seek(position);
+    }
+    if show_position {
+        print_review_position(review);
+    }
+}
+
+fn print_review_position(review: &Review) {
+    println!("{}", format!("[{}] payload {} of {}{}",
+        format_game_time(Some(review.game_time())),
+        review.position(),
+        review.len(),
+        if review.at_end() { " - end of recording" } else { "" }).dimmed());
+}
+
+// One page of the recording's events, numbered for `j`. Those still ahead have a dimmed number.
+fn print_review_events(review: &Review, page: usize) {
+    let events = review.events();
+    let pages = events.len().div_ceil(TIMELINE_PAGE).max(1);
+    let page = page.min(pages);
+    let start = (page - 1) * TIMELINE_PAGE;
+
+    println!("{} (page {} of {}, {} events):", "EVENTS".cyan().bold(), page, pages, events.len());
+    for (number, (index, event)) in events.iter().enumerate().skip(start).take(TIMELINE_PAGE) {
+        let stamp = format!("{:>4} [{}]", number + 1, format_game_time(Some(event.game_time)));
+        let stamp = if *index < review.position() { stamp.normal() } else { stamp.dimmed() };
+        println!("{} {}: {}", stamp, event.kind.label(), event.description);
+    }
+    if page < pages {
+        println!("  {}", format!("e {} for later events", page + 1).dimmed());
+    }
+    println!();
+}
+
+// Markers in the recording, with their numbers in the event list
+fn print_review_markers(review: &Review) {
+    let markers: Vec<(usize, &GameEvent)> = review.events().iter()
+        .enumerate()
+        .filter(|(_, (_, event))| event.kind == EventKind::Marker)
+        .map(|(number, (_, event))| (number + 1, event))
+        .collect();
+    println!("{} ({}):", "MARKERS".cyan().bold(), markers.len());
+    if markers.is_empty() {
+        println!("  None - press k during a recorded game to drop one");
+    }
+    for (number, event) in markers {
+        println!("{:>4} [{}] {}", number, format_game_time(Some(event.game_time)), event.description);
+    }
+    println!();
+}
+
+// Step through a recorded session at the terminal instead of listening for the game
+pub async fn run_review(path: &Path, config: Config) {
+    let (mut review, skipped) = match Review::load(path, config) {
+        Ok(loaded) => loaded,
+        Err(e) => {
+            eprintln!("{}", e.red());
+            std::process::exit(1);
+        }
+    };
+    let (first, last) = review.span();
+    println!("Reviewing {}: {} payloads, {} events, {} to {}",
+        path.display(),
+        review.len(),
+        review.events().len(),
+        format_game_time(Some(first)),
+        format_game_time(Some(last)));
+    if skipped > 0 {
+        println!("{}", format!("Skipped {} lines that weren't game states", skipped).yellow());
+    }
+    print_review_commands();
+
+    let mut lines = BufReader::new(tokio::io::stdin()).lines();
+    let mut playing = false;
+    loop {
+        let line = if playing {
+            tokio::select! {
+                line = lines.next_line() => line,
+                _ = tokio::time::sleep(Duration::from_secs(1)) => {
+                    let position = review.position_after(PLAY_SPEED);
+                    move_review(&mut review, position, false);
+                    if review.at_end() {
+                        playing = false;
+                        print_review_position(&review);
+                    }
+                    continue;
+                },
+            }
+        } else {
+            lines.next_line().await
+        };
+        let Ok(Some(line)) = line else { break };
+
+        match ReviewCommand::parse(&line) {
+            Some(ReviewCommand::Step(count)) => {
+                let position = review.position().saturating_add_signed(count);
+                move_review(&mut review, position, true);
+            },
+            Some(ReviewCommand::Seconds(seconds)) => {
+                let position = review.position_after(seconds);
+                move_review(&mut review, position, true);
+            },
+            Some(ReviewCommand::Events(page)) => print_review_events(&review, page),
+            Some(ReviewCommand::Jump(number)) => match review.events().get(number - 1).cloned() {
+                Some((index, event)) => {
+                    review.seek(index + 1);
+                    print_event(&event, review.coach());
+                    print_review_position(&review);
+                },
+                None => println!("There are only {} events", review.events().len()),
+            },
+            Some(ReviewCommand::Markers) => print_review_markers(&review),
+            Some(ReviewCommand::Play) => {
+                playing = !playing && !review.at_end();
+                if playing {
+                    println!("Playing - p to pause");
+                } else {
+                    print_review_position(&review);
+                }
+            },
+            Some(ReviewCommand::Summary) => print_summary(review.coach(), review.coach().game_time()),
+            Some(ReviewCommand::Timeline(page)) => print_timeline(review.coach().event_log(), page),
+            Some(ReviewCommand::Help) => print_review_commands(),
+            Some(ReviewCommand::Quit) => break,
+            None => println!("Unknown command '{}' - type ? for a list", line.trim()),
+        }
+    }
+}
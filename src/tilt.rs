@@ -0,0 +1,118 @@
+// src/tilt.rs
+use std::collections::VecDeque;
+
+use crate::config::Thresholds;
+use crate::format::format_game_time;
+use crate::roles;
+use crate::state::GameState;
+
+// Deaths and gold are looked at over the last five minutes
+const WINDOW: i32 = 5 * 60;
+
+// The average GPM means little before this
+const GPM_FROM: i32 = 10 * 60;
+
+// Once the player has heard it, the advice holds this long
+const CALM_FOR: i32 = 5 * 60;
+
+// A rough patch: deaths piling up or the gold drying up
+#[derive(Clone, Debug)]
+pub struct Slide {
+    // Deaths in the window, and when the first of them was
+    pub deaths: usize,
+    pub since: i32,
+    // Gold per minute over the window against the match average before it
+    pub gpm: Option<(i32, i32)>,
+    pub position: Option<u8>,
+}
+
+impl Slide {
+    pub fn describe(&self) -> String {
+        let mut what = Vec::new();
+        if self.deaths > 0 {
+            what.push(format!("{} death{} since {}", self.deaths, if self.deaths == 1 { "" } else { "s" }, format_game_time(Some(self.since))));
+        }
+        if let Some((recent, average)) = self.gpm {
+            what.push(format!("{} GPM over the last 5 minutes against {} before", recent, average));
+        }
+        let farm = match self.position {
+            Some(position) if roles::is_support(position) => "ward near your team and stay with them rather than walking the map alone",
+            _ => "farm your own jungle triangle and safe waves until an item comes",
+        };
+        format!("Rough few minutes ({}). No need to force anything: play back near your towers, {}, and group up for the next fight instead of taking one on your own",
+            what.join(", "), farm)
+    }
+}
+
+// Watches for the player going on a slide, and keeps the coach calm while they're on one
+pub struct TiltDetector {
+    deaths: VecDeque<i32>,
+    // Game time and gold earned so far, from the average GPM
+    earned: VecDeque<(i32, i32)>,
+    advised_at: Option<i32>,
+}
+
+impl TiltDetector {
+    pub fn new() -> Self {
+        TiltDetector {
+            deaths: VecDeque::new(),
+            earned: VecDeque::new(),
+            advised_at: None,
+        }
+    }
+
+    // Whether the player was told they're on a slide lately, so pushier advice can wait
+    pub fn calming(&self, game_time: i32) -> bool {
+        self.advised_at.map(|at| game_time - at < CALM_FOR).unwrap_or(false)
+    }
+
+    // GPM over the window against the average before it, when it fell by `drop_percent` or more
+    fn gpm_drop(&self, drop_percent: i32) -> Option<(i32, i32)> {
+        let (&(then, earned_then), &(now, earned_now)) = (self.earned.front()?, self.earned.back()?);
+        if then < GPM_FROM || now - then < WINDOW - 60 {
+            return None;
+        }
+        let recent = (earned_now - earned_then) * 60 / (now - then);
+        let average = earned_then * 60 / then;
+        (average > 0 && recent * 100 <= average * (100 - drop_percent)).then_some((recent, average))
+    }
+
+    // A slide worth a word, at most once every CALM_FOR
+    pub fn update(&mut self, state: &GameState, died: bool, position: Option<u8>, thresholds: &Thresholds) -> Option<Slide> {
+        let game_time = state.game_time()?;
+        if died {
+            self.deaths.push_back(game_time);
+        }
+        if let Some(gpm) = state.player().and_then(|p| p.gpm) {
+            self.earned.push_back((game_time, gpm * game_time.max(0) / 60));
+        }
+        while self.deaths.front().map(|t| game_time - t > WINDOW).unwrap_or(false) {
+            self.deaths.pop_front();
+        }
+        while self.earned.front().map(|(t, _)| game_time - t > WINDOW).unwrap_or(false) {
+            self.earned.pop_front();
+        }
+
+        if self.calming(game_time) {
+            return None;
+        }
+        let deaths = self.deaths.len();
+        let gpm = self.gpm_drop(thresholds.tilt_gpm_drop_percent);
+        if deaths < thresholds.tilt_deaths && gpm.is_none() {
+            return None;
+        }
+        self.advised_at = Some(game_time);
+        Some(Slide {
+            deaths,
+            since: self.deaths.front().copied().unwrap_or(game_time),
+            gpm,
+            position,
+        })
+    }
+}
+
+impl Default for TiltDetector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
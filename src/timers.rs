@@ -0,0 +1,163 @@
+// src/timers.rs
+use std::collections::HashMap;
+
+// Rune types with fixed spawn schedules
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum RuneKind {
+    Power,
+    Bounty,
+    Water,
+    Wisdom,
+}
+
+impl RuneKind {
+    pub const ALL: [RuneKind; 4] = [RuneKind::Bounty, RuneKind::Water, RuneKind::Power, RuneKind::Wisdom];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            RuneKind::Power => "Power rune",
+            RuneKind::Bounty => "Bounty runes",
+            RuneKind::Water => "Water runes",
+            RuneKind::Wisdom => "Wisdom runes",
+        }
+    }
+
+    // Clock time of the first spawn strictly after `clock_time`, if there is one
+    pub fn next_spawn(&self, clock_time: i32) -> Option<i32> {
+        match self {
+            // Bounty runes spawn at 0:00 and every 3 minutes after
+            RuneKind::Bounty => Some(next_multiple(clock_time, 0, 180)),
+            // Water runes only spawn at 2:00 and 4:00 in the power rune spots
+            RuneKind::Water => [120, 240].into_iter().find(|&t| t > clock_time),
+            // Power runes take over the river spots from 6:00, every 2 minutes
+            RuneKind::Power => Some(next_multiple(clock_time, 360, 120)),
+            // Wisdom runes spawn every 7 minutes starting at 7:00
+            RuneKind::Wisdom => Some(next_multiple(clock_time, 420, 420)),
+        }
+    }
+}
+
+// Smallest `first + n * interval` (n >= 0) that is strictly after `clock_time`
+fn next_multiple(clock_time: i32, first: i32, interval: i32) -> i32 {
+    if clock_time < first {
+        return first;
+    }
+    first + ((clock_time - first) / interval + 1) * interval
+}
+
+// An upcoming rune spawn
+#[derive(Clone, Copy, Debug)]
+pub struct RuneSpawn {
+    pub kind: RuneKind,
+    pub spawn_time: i32,
+    pub seconds_until: i32,
+}
+
+// Next spawn of every rune type, soonest first
+pub fn upcoming_runes(clock_time: i32) -> Vec<RuneSpawn> {
+    let mut spawns: Vec<RuneSpawn> = RuneKind::ALL.iter()
+        .filter_map(|kind| kind.next_spawn(clock_time).map(|spawn_time| RuneSpawn {
+            kind: *kind,
+            spawn_time,
+            seconds_until: spawn_time - clock_time,
+        }))
+        .collect();
+
+    spawns.sort_by_key(|s| s.seconds_until);
+    spawns
+}
+
+// Remembers which spawns have already been announced
+pub struct RuneTimers {
+    alerted: HashMap<RuneKind, i32>,
+}
+
+impl RuneTimers {
+    pub fn new() -> Self {
+        RuneTimers {
+            alerted: HashMap::new(),
+        }
+    }
+
+    // Spawns that are within `lead` seconds and have not been announced yet
+    pub fn due_alerts(&mut self, clock_time: i32, lead: i32) -> Vec<RuneSpawn> {
+        let mut due = Vec::new();
+
+        for spawn in upcoming_runes(clock_time) {
+            if spawn.seconds_until > lead {
+                continue;
+            }
+            if self.alerted.get(&spawn.kind) == Some(&spawn.spawn_time) {
+                continue;
+            }
+            self.alerted.insert(spawn.kind, spawn.spawn_time);
+            due.push(spawn);
+        }
+
+        due
+    }
+}
+
+impl Default for RuneTimers {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Day and night each last five minutes, starting with day at 0:00
+pub const DAY_NIGHT_LENGTH: i32 = 300;
+
+// Where the game is in the day/night cycle
+#[derive(Clone, Copy, Debug)]
+pub struct DayNight {
+    pub is_day: bool,
+    // Whether the regular cycle, ignoring Night Stalker, is in its day half
+    pub cycle_is_day: bool,
+    // Seconds until the regular cycle flips
+    pub seconds_until_change: i32,
+    // Night forced by Night Stalker's ultimate, regardless of the cycle
+    pub nightstalker_night: bool,
+}
+
+impl DayNight {
+    // Derive the cycle from the clock, trusting the game's own daytime flag when present
+    pub fn from_clock(clock_time: i32, daytime: Option<bool>, nightstalker_night: bool) -> Self {
+        let (cycle_is_day, next_change) = if clock_time < 0 {
+            (true, DAY_NIGHT_LENGTH)
+        } else {
+            let period = clock_time / DAY_NIGHT_LENGTH;
+            (period % 2 == 0, (period + 1) * DAY_NIGHT_LENGTH)
+        };
+
+        DayNight {
+            is_day: daytime.unwrap_or(cycle_is_day) && !nightstalker_night,
+            cycle_is_day,
+            seconds_until_change: next_change - clock_time,
+            nightstalker_night,
+        }
+    }
+
+    // Seconds until night falls, when it's day and the game agrees the next change is to night
+    pub fn night_falls_in(&self) -> Option<i32> {
+        (self.is_day && self.cycle_is_day).then_some(self.seconds_until_change)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn night_falls_at_the_end_of_a_day_half() {
+        assert_eq!(DayNight::from_clock(290, None, false).night_falls_in(), Some(10));
+        assert_eq!(DayNight::from_clock(290, Some(true), false).night_falls_in(), Some(10));
+        assert_eq!(DayNight::from_clock(590, None, false).night_falls_in(), None);
+    }
+
+    #[test]
+    fn no_night_coming_while_the_game_reports_night() {
+        assert_eq!(DayNight::from_clock(290, Some(false), false).night_falls_in(), None);
+        assert_eq!(DayNight::from_clock(290, Some(true), true).night_falls_in(), None);
+        assert!(!DayNight::from_clock(290, Some(true), true).is_day);
+    }
+}
@@ -0,0 +1,211 @@
+// src/tracking.rs
+use colored::Colorize;
+
+use crate::config::Thresholds;
+use crate::regions;
+
+// A value the game only sends now and then, e.g. enemy vitals while spectating.
+// The minimap alone never shows them, so they start out unknown.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Observed<T> {
+    Unknown,
+    LastKnown { value: T, at: i32 },
+}
+
+impl<T: Copy + std::fmt::Display> Observed<T> {
+    pub fn value(&self) -> Option<T> {
+        match self {
+            Observed::Unknown => None,
+            Observed::LastKnown { value, .. } => Some(*value),
+        }
+    }
+
+    // Seconds since the value was seen
+    pub fn age(&self, game_time: i32) -> Option<i32> {
+        match self {
+            Observed::Unknown => None,
+            Observed::LastKnown { at, .. } => Some(game_time - at),
+        }
+    }
+
+    // Move the time it was seen forward
+    pub fn shift(&mut self, seconds: i32) {
+        if let Observed::LastKnown { at, .. } = self {
+            *at += seconds;
+        }
+    }
+
+    // e.g. "45%" when fresh, "45% (30s ago)" when stale, "unknown" when never seen
+    pub fn describe(&self, game_time: i32, unit: &str) -> String {
+        match (self, self.age(game_time)) {
+            (Observed::LastKnown { value, .. }, Some(age)) if age > 0 => format!("{}{} ({}s ago)", value, unit, age),
+            (Observed::LastKnown { value, .. }, _) => format!("{}{}", value, unit),
+            (Observed::Unknown, _) => "unknown".to_string(),
+        }
+    }
+}
+
+// Persistent state for enemy heroes
+#[derive(Clone, Debug)]
+pub struct EnemyHeroState {
+    pub name: String,
+    pub last_seen_position: (i32, i32),
+    pub last_seen_time: i32,
+    // Position and time of the sighting before, for working out a heading
+    pub previous_sighting: Option<((i32, i32), i32)>,
+    pub level: Observed<i32>,
+    // Percentages
+    pub health: Observed<i32>,
+    pub mana: Observed<i32>,
+    pub times_spotted: i32,
+    pub status: EnemyStatus,
+}
+
+impl EnemyHeroState {
+    // Move every timestamp forward, so time that didn't count doesn't age the sighting
+    pub fn shift_times(&mut self, seconds: i32) {
+        self.last_seen_time += seconds;
+        if let Some((_, time)) = self.previous_sighting.as_mut() {
+            *time += seconds;
+        }
+        self.level.shift(seconds);
+        self.health.shift(seconds);
+        self.mana.shift(seconds);
+    }
+
+    // The seen level, or a guess from the clock marked as such
+    pub fn describe_level(&self, game_time: i32) -> String {
+        match self.level {
+            Observed::Unknown => format!("estimated level ~{}", estimate_hero_level(game_time)),
+            level => format!("level {}", level.describe(game_time, "")),
+        }
+    }
+}
+
+// Predictions stop this long after the last sighting
+pub const PREDICT_FOR: i32 = 30;
+
+// Fastest a hero runs without a blink, in units per second
+const MAX_SPEED: f32 = 550.0;
+
+// How far a hero may have strayed from a straight line, per second unseen
+const SPREAD_PER_SECOND: f32 = 250.0;
+
+// Where an enemy who dropped off the minimap probably is now
+#[derive(Clone, Debug)]
+pub struct Prediction {
+    pub hero: String,
+    pub position: (i32, i32),
+    // The hero is likely somewhere within this distance of `position`
+    pub radius: f32,
+    // 1.0 right after the sighting, falling to 0.0 at PREDICT_FOR
+    pub confidence: f32,
+    pub seconds_unseen: i32,
+}
+
+// Carry the last two sightings forward in a straight line
+pub fn predict_position(enemy: &EnemyHeroState, game_time: i32) -> Option<Prediction> {
+    let unseen = game_time - enemy.last_seen_time;
+    if unseen <= 0 || unseen > PREDICT_FOR {
+        return None;
+    }
+
+    let ((x, y), seen_at) = enemy.previous_sighting?;
+    let dt = (enemy.last_seen_time - seen_at) as f32;
+    if dt <= 0.0 {
+        return None;
+    }
+
+    let (mut vx, mut vy) = ((enemy.last_seen_position.0 - x) as f32 / dt, (enemy.last_seen_position.1 - y) as f32 / dt);
+    let speed = (vx * vx + vy * vy).sqrt();
+    if speed > MAX_SPEED {
+        // A blink or teleport, not a walk
+        vx *= MAX_SPEED / speed;
+        vy *= MAX_SPEED / speed;
+    }
+
+    Some(Prediction {
+        hero: enemy.name.clone(),
+        position: (enemy.last_seen_position.0 + (vx * unseen as f32) as i32, enemy.last_seen_position.1 + (vy * unseen as f32) as i32),
+        radius: unseen as f32 * SPREAD_PER_SECOND,
+        confidence: 1.0 - unseen as f32 / PREDICT_FOR as f32,
+        seconds_unseen: unseen,
+    })
+}
+
+// Status tracking for enemy heroes
+#[derive(Clone, Debug, PartialEq)]
+pub enum EnemyStatus {
+    NewlySpotted,
+    Tracking,
+    MovedSignificantly,
+    Lost,
+}
+
+// Calculate distance between two points
+pub fn calculate_distance(pos1: (i32, i32), pos2: (i32, i32)) -> f32 {
+    let dx = pos1.0 - pos2.0;
+    let dy = pos1.1 - pos2.1;
+    ((dx * dx + dy * dy) as f32).sqrt()
+}
+
+// Describe a position relative to player
+pub fn describe_position_relative_to_player(player_pos: (i32, i32), enemy_pos: (i32, i32), thresholds: &Thresholds) -> String {
+    let distance = calculate_distance(player_pos, enemy_pos);
+    
+    // Determine the direction
+    let dx = enemy_pos.0 - player_pos.0;
+    let dy = enemy_pos.1 - player_pos.1;
+    
+    let direction = if dx.abs() > dy.abs() * 2.0 as i32 {
+        if dx > 0 { "east" } else { "west" }
+    } else if dy.abs() > dx.abs() * 2.0 as i32 {
+        if dy > 0 { "north" } else { "south" }
+    } else if dx > 0 && dy > 0 {
+        "northeast"
+    } else if dx > 0 && dy < 0 {
+        "southeast"
+    } else if dx < 0 && dy > 0 {
+        "northwest"
+    } else {
+        "southwest"
+    };
+    
+    // Determine distance description
+    let distance_desc = if distance < thresholds.very_close_distance {
+        "very close to you".red().bold().to_string()
+    } else if distance < thresholds.nearby_distance {
+        "nearby".yellow().to_string()
+    } else if distance < thresholds.medium_distance {
+        "at medium distance".to_string()
+    } else {
+        "far away".green().to_string()
+    };
+    
+    format!("{} to the {}", distance_desc, direction)
+}
+
+// Convert map position to a named location such as "Dire triangle"
+pub fn describe_map_location(position: (i32, i32)) -> String {
+    regions::locate(position)
+        .map(|region| region.name.to_string())
+        .unwrap_or_else(|| "off the map".to_string())
+}
+
+// Estimate hero level based on game time
+pub fn estimate_hero_level(game_time: i32) -> i32 {
+    let minutes = game_time / 60;
+    
+    if minutes < 10 {
+        (minutes / 2) + 1
+    } else if minutes < 20 {
+        (minutes / 3) + 5
+    } else {
+        (minutes / 5) + 10
+    }
+}
+
+// Check if enemy has moved significantly
+pub fn has_moved_significantly(old_pos: (i32, i32), new_pos: (i32, i32), thresholds: &Thresholds) -> bool {
+    calculate_distance(old_pos, new_pos) > thresholds.moved_significantly_distance
+}
@@ -0,0 +1,179 @@
+// src/twitch.rs
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::TcpStream;
+use tokio::sync::mpsc;
+use tokio_rustls::rustls::pki_types::ServerName;
+use tokio_rustls::rustls::{crypto, ClientConfig, RootCertStore};
+use tokio_rustls::TlsConnector;
+
+use crate::alerts::{strip_colors, Alert, AlertCategory, AlertSink};
+use crate::analysis::performance::PerformanceReport;
+use crate::config::TwitchConfig;
+use crate::format::{format_game_time, format_hero_name};
+use crate::state::GameState;
+
+const SERVER: &str = "irc.chat.twitch.tv";
+const PORT: u16 = 6697;
+
+// Twitch drops chat messages longer than 500 characters
+const MAX_MESSAGE: usize = 450;
+
+// Announcements waiting for the chat before new ones are dropped
+const QUEUE: usize = 16;
+
+// Wait before reconnecting after the chat went away or turned the bot down
+const RECONNECT: Duration = Duration::from_secs(30);
+
+struct Login {
+    channel: String,
+    username: String,
+    token: String,
+}
+
+// Posts selected alerts and the post-game summary to a Twitch chat as the configured
+// account. Each category is announced at most once per `cooldown_secs`, and anything
+// raised while the chat is unreachable is dropped rather than posted late.
+#[derive(Clone)]
+pub struct TwitchBot {
+    sender: mpsc::Sender<String>,
+    categories: Vec<AlertCategory>,
+    cooldown: Duration,
+    last_posted: Arc<Mutex<HashMap<AlertCategory, Instant>>>,
+}
+
+impl TwitchBot {
+    // Joins the chat in the background and keeps rejoining. Must be called from within the Tokio runtime.
+    pub fn connect(config: &TwitchConfig) -> Result<Self, String> {
+        let setting = |value: &Option<String>, name: &str| value.as_deref()
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+            .map(str::to_string)
+            .ok_or_else(|| format!("Twitch chat needs {} in the [twitch] section", name));
+        let login = Login {
+            channel: setting(&config.channel, "a channel")?.trim_start_matches('#').to_lowercase(),
+            username: setting(&config.username, "the bot's username")?.to_lowercase(),
+            token: setting(&config.oauth_token, "an oauth_token")?.trim_start_matches("oauth:").to_string(),
+        };
+
+        let (sender, receiver) = mpsc::channel(QUEUE);
+        tokio::spawn(run(login, receiver));
+        Ok(TwitchBot {
+            sender,
+            categories: config.categories.clone(),
+            cooldown: Duration::from_secs(config.cooldown_secs),
+            last_posted: Arc::new(Mutex::new(HashMap::new())),
+        })
+    }
+
+    // Post a line to the chat, unless the queue is full
+    pub fn announce(&self, text: &str) {
+        let mut line: String = text.chars().filter(|c| !c.is_control()).collect();
+        if let Some((cut, _)) = line.char_indices().nth(MAX_MESSAGE) {
+            line.truncate(cut);
+        }
+        let _ = self.sender.try_send(line);
+    }
+}
+
+impl AlertSink for TwitchBot {
+    fn notify(&self, alert: &Alert) {
+        if !self.categories.contains(&alert.category) {
+            return;
+        }
+        let mut last_posted = self.last_posted.lock().unwrap();
+        if last_posted.get(&alert.category).map(|t| t.elapsed() < self.cooldown).unwrap_or(false) {
+            return;
+        }
+        last_posted.insert(alert.category, Instant::now());
+        self.announce(&format!("{}: {}", alert.category.name(), strip_colors(&alert.message)));
+    }
+}
+
+// e.g. "GG! Victory as Sven, 10/2/8 in 35:12 - 620 GPM, 700 XPM, 250 last hits"
+pub fn game_summary(report: &PerformanceReport, state: Option<&GameState>) -> String {
+    let outcome = state.and_then(|s| {
+        let won = s.map.as_ref()?.win_team.as_deref()?.eq_ignore_ascii_case(&s.player_team()?);
+        Some(if won { "Victory" } else { "Defeat" })
+    });
+    let hero = state.and_then(|s| s.hero()).and_then(|h| h.name.as_deref()).filter(|name| !name.is_empty()).map(format_hero_name);
+    let kda = state.and_then(|s| s.player()).map(|p| format!("{}/{}/{}", p.kills.unwrap_or(0), p.deaths.unwrap_or(0), p.assists.unwrap_or(0)));
+
+    let mut played = match (outcome, hero) {
+        (Some(outcome), Some(hero)) => format!("{} as {}", outcome, hero),
+        (Some(outcome), None) => outcome.to_string(),
+        (None, Some(hero)) => format!("Played {}", hero),
+        (None, None) => "Game over".to_string(),
+    };
+    if let Some(kda) = kda {
+        played = format!("{}, {}", played, kda);
+    }
+    format!("GG! {} in {} - {} GPM, {} XPM, {} last hits",
+        played, format_game_time(Some(report.duration)), report.gpm, report.xpm, report.last_hits)
+}
+
+fn tls() -> Result<TlsConnector, String> {
+    let mut roots = RootCertStore::empty();
+    roots.extend(webpki_roots::TLS_SERVER_ROOTS.iter().cloned());
+    let config = ClientConfig::builder_with_provider(Arc::new(crypto::ring::default_provider()))
+        .with_safe_default_protocol_versions()
+        .map_err(|e| format!("Could not set up TLS: {}", e))?
+        .with_root_certificates(roots)
+        .with_no_client_auth();
+    Ok(TlsConnector::from(Arc::new(config)))
+}
+
+async fn run(login: Login, mut messages: mpsc::Receiver<String>) {
+    // Only the first of a run of failures is reported
+    let mut failing = false;
+    loop {
+        match session(&login, &mut messages, &mut failing).await {
+            // The coach is shutting down
+            Ok(()) => return,
+            Err(e) if !failing => {
+                eprintln!("Twitch chat: {} - retrying", e);
+                failing = true;
+            },
+            Err(_) => {},
+        }
+        tokio::time::sleep(RECONNECT).await;
+    }
+}
+
+// One connection to the chat, until it drops
+async fn session(login: &Login, messages: &mut mpsc::Receiver<String>, failing: &mut bool) -> Result<(), String> {
+    let tcp = TcpStream::connect((SERVER, PORT)).await.map_err(|e| format!("Could not reach {}: {}", SERVER, e))?;
+    let stream = tls()?.connect(ServerName::try_from(SERVER).map_err(|e| e.to_string())?, tcp).await
+        .map_err(|e| format!("Could not reach {}: {}", SERVER, e))?;
+    let (read, mut write) = tokio::io::split(stream);
+    let mut lines = BufReader::new(read).lines();
+    let io = |e: std::io::Error| format!("Lost the connection: {}", e);
+
+    write.write_all(format!("PASS oauth:{}\r\nNICK {}\r\nJOIN #{}\r\n", login.token, login.username, login.channel).as_bytes())
+        .await.map_err(io)?;
+    // Whatever was raised while away is old news
+    while messages.try_recv().is_ok() {}
+
+    loop {
+        tokio::select! {
+            line = lines.next_line() => {
+                let Some(line) = line.map_err(io)? else { return Err("Disconnected".to_string()) };
+                if let Some(server) = line.strip_prefix("PING") {
+                    write.write_all(format!("PONG{}\r\n", server).as_bytes()).await.map_err(io)?;
+                } else if line.contains(" NOTICE * :") {
+                    // Only sent before logging in, e.g. for a bad token
+                    return Err(line.split(" NOTICE * :").nth(1).unwrap_or("").to_string());
+                } else if line.contains(" 001 ") {
+                    *failing = false;
+                }
+            },
+            message = messages.recv() => {
+                let Some(message) = message else { return Ok(()) };
+                write.write_all(format!("PRIVMSG #{} :{}\r\n", login.channel, message).as_bytes()).await.map_err(io)?;
+            },
+        }
+    }
+}
@@ -0,0 +1,98 @@
+// src/vod.rs
+use serde::Serialize;
+use serde_json::{json, Value};
+
+use crate::state::GameState;
+
+// A jump of more than this many seconds between game time and the provider's
+// clock means the game was paused (or the client caught up) and starts a new point
+const DRIFT: i64 = 2;
+
+// Game time, clock time and the provider's unix timestamp at one moment
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+pub struct ClockPoint {
+    pub game_time: i32,
+    pub clock_time: i32,
+    pub timestamp: i64,
+}
+
+impl ClockPoint {
+    fn offset(&self) -> i64 {
+        self.timestamp - self.game_time as i64
+    }
+}
+
+// How the match clock lines up with real time, so events can be found in a VOD.
+// Only the points where that relationship changes are kept: the first payload and
+// the first one after each pause.
+#[derive(Clone, Debug, Default)]
+pub struct ClockMapping {
+    points: Vec<ClockPoint>,
+    // Game time of the last payload, to tell a running clock from a paused one
+    last_game_time: Option<i32>,
+}
+
+impl ClockMapping {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn from_points(points: Vec<ClockPoint>) -> Self {
+        ClockMapping { points, last_game_time: None }
+    }
+
+    pub fn record(&mut self, state: &GameState) {
+        let (Some(game_time), Some(clock_time), Some(timestamp)) =
+            (state.game_time(), state.clock_time(), state.provider.as_ref().and_then(|p| p.timestamp)) else { return };
+        let point = ClockPoint { game_time, clock_time, timestamp };
+        let running = self.last_game_time.map(|t| game_time > t).unwrap_or(true);
+        self.last_game_time = Some(game_time);
+        match self.points.last() {
+            // Paused, or still in step
+            Some(_) if !running => {},
+            Some(last) if (point.offset() - last.offset()).abs() <= DRIFT => {},
+            _ => self.points.push(point),
+        }
+    }
+
+    pub fn points(&self) -> &[ClockPoint] {
+        &self.points
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    // Times the mapping shifted after the first point, mostly pauses
+    pub fn pauses(&self) -> usize {
+        self.points.len().saturating_sub(1)
+    }
+
+    // Unix time at which the match reached `game_time`
+    pub fn timestamp_at(&self, game_time: i32) -> Option<i64> {
+        let point = self.points.iter().rev()
+            .find(|p| p.game_time <= game_time)
+            .or_else(|| self.points.first())?;
+        Some(point.timestamp + (game_time - point.game_time) as i64)
+    }
+
+    // Unix time at which the clock showed `clock_time`
+    pub fn timestamp_at_clock(&self, clock_time: i32) -> Option<i64> {
+        let point = self.points.iter().rev()
+            .find(|p| p.clock_time <= clock_time)
+            .or_else(|| self.points.first())?;
+        Some(point.timestamp + (clock_time - point.clock_time) as i64)
+    }
+
+    // Seconds into a recording that started at unix time `recording_start`
+    pub fn vod_time(&self, game_time: i32, recording_start: i64) -> Option<i64> {
+        self.timestamp_at(game_time).map(|t| t - recording_start)
+    }
+
+    pub fn to_json(&self) -> Value {
+        json!({
+            "points": self.points,
+            "pauses": self.pauses(),
+        })
+    }
+}
@@ -0,0 +1,118 @@
+// src/wards.rs
+use crate::consumables::Consumable;
+use crate::format::format_game_time;
+use crate::tracking::describe_map_location;
+
+pub const OBSERVER_DURATION: i32 = 360;
+pub const SENTRY_DURATION: i32 = 420;
+
+// A ward marked by hand this close to one leaving the inventory is the same ward
+const MARK_WINDOW: i32 = 10;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WardOwner {
+    Own,
+    Enemy,
+}
+
+// An observer or sentry on the map, from the inventory or marked by hand
+#[derive(Clone, Debug)]
+pub struct PlacedWard {
+    pub kind: Consumable,
+    pub owner: WardOwner,
+    pub placed_at: i32,
+    // Where the player's hero stood, e.g. "Radiant triangle"
+    pub location: Option<String>,
+    pub marked: bool,
+    // Whether the player heard it's running out
+    warned: bool,
+}
+
+impl PlacedWard {
+    pub fn expires_at(&self) -> i32 {
+        self.placed_at + if self.kind == Consumable::Sentry { SENTRY_DURATION } else { OBSERVER_DURATION }
+    }
+
+    pub fn describe(&self) -> String {
+        let owner = match self.owner {
+            WardOwner::Own => "Your",
+            WardOwner::Enemy => "Enemy",
+        };
+        format!("{} {}{} - runs out at {}", owner, self.kind.name(1),
+            self.location.as_ref().map(|l| format!(" in {}", l)).unwrap_or_default(),
+            format_game_time(Some(self.expires_at())))
+    }
+}
+
+pub enum WardAlert {
+    // One of the player's observers is about to run out
+    Expiring(PlacedWard),
+    // An enemy observer has likely run out, opening a window to smoke through
+    EnemyExpired(PlacedWard),
+}
+
+// Wards on the map with their expiry. Enemy wards only come from marks, and are assumed
+// freshly planted when marked, so they're never thought gone too early.
+pub struct WardTracker {
+    wards: Vec<PlacedWard>,
+}
+
+impl WardTracker {
+    pub fn new() -> Self {
+        WardTracker {
+            wards: Vec::new(),
+        }
+    }
+
+    // Add a ward unless it's one already known: the same ward seen leaving the inventory
+    // and marked by hand. Returns the ward either way.
+    pub fn place(&mut self, kind: Consumable, owner: WardOwner, clock_time: i32, position: Option<(i32, i32)>, marked: bool) -> &PlacedWard {
+        let known = self.wards.iter().position(|w| w.kind == kind && w.owner == owner && w.marked != marked
+            && (w.placed_at - clock_time).abs() <= MARK_WINDOW);
+        let index = known.unwrap_or_else(|| {
+            self.wards.push(PlacedWard {
+                kind,
+                owner,
+                placed_at: clock_time,
+                location: position.map(describe_map_location),
+                marked,
+                warned: false,
+            });
+            self.wards.len() - 1
+        });
+        &self.wards[index]
+    }
+
+    // Wards still up, soonest to run out first
+    pub fn active(&self, clock_time: i32) -> Vec<&PlacedWard> {
+        let mut active: Vec<&PlacedWard> = self.wards.iter().filter(|w| w.expires_at() > clock_time).collect();
+        active.sort_by_key(|w| w.expires_at());
+        active
+    }
+
+    // Own observers running out within `lead` seconds and enemy observers that have run out
+    pub fn update(&mut self, clock_time: i32, lead: i32) -> Vec<WardAlert> {
+        let mut alerts = Vec::new();
+        for ward in &mut self.wards {
+            let observer = ward.kind == Consumable::Observer;
+            match ward.owner {
+                WardOwner::Own if observer && ward.expires_at() - clock_time <= lead && !ward.warned => {
+                    ward.warned = true;
+                    if ward.expires_at() > clock_time {
+                        alerts.push(WardAlert::Expiring(ward.clone()));
+                    }
+                },
+                WardOwner::Enemy if observer && ward.expires_at() <= clock_time => alerts.push(WardAlert::EnemyExpired(ward.clone())),
+                _ => {},
+            }
+        }
+        self.wards.retain(|w| w.expires_at() > clock_time);
+        alerts
+    }
+}
+
+impl Default for WardTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,158 @@
+// src/waves.rs
+use crate::buildings::{BuildingKind, BuildingLane, BuildingStatus};
+use crate::state::{GameState, Team};
+use crate::tracking::calculate_distance;
+
+// Siege creeps join the lane waves every 5 minutes
+pub const SIEGE_INTERVAL: i32 = 300;
+
+// How long before a siege wave reaches a tower it's called
+pub const SIEGE_LEAD: i32 = 20;
+
+const CREEP_SPEED: f32 = 325.0;
+
+// A team's creeps this close to a tower on the minimap are hitting it or about to
+const CONTEST_RANGE: f32 = 1500.0;
+
+// Each lane from the Radiant barracks to the Dire barracks, roughly as the creeps walk it
+fn path(lane: BuildingLane) -> &'static [(i32, i32)] {
+    match lane {
+        BuildingLane::Top => &[(-6600, -3800), (-6450, 5500), (-6000, 6050), (3700, 5850)],
+        BuildingLane::Mid => &[(-4800, -4300), (4400, 3900)],
+        BuildingLane::Bottom => &[(-3800, -6300), (5600, -6250), (6300, -5600), (6300, 3100)],
+        BuildingLane::Base => &[],
+    }
+}
+
+// Rough tower positions
+pub fn tower_position(team: Team, lane: BuildingLane, tier: u8) -> Option<(i32, i32)> {
+    let position = match (team, lane, tier) {
+        (Team::Radiant, BuildingLane::Top, 1) => (-6250, 1830),
+        (Team::Radiant, BuildingLane::Top, 2) => (-6100, -870),
+        (Team::Radiant, BuildingLane::Top, 3) => (-6600, -3550),
+        (Team::Radiant, BuildingLane::Mid, 1) => (-1540, -1400),
+        (Team::Radiant, BuildingLane::Mid, 2) => (-3550, -2800),
+        (Team::Radiant, BuildingLane::Mid, 3) => (-4650, -4100),
+        (Team::Radiant, BuildingLane::Bottom, 1) => (4930, -6100),
+        (Team::Radiant, BuildingLane::Bottom, 2) => (-350, -6250),
+        (Team::Radiant, BuildingLane::Bottom, 3) => (-3950, -6100),
+        (Team::Dire, BuildingLane::Top, 1) => (-4700, 6000),
+        (Team::Dire, BuildingLane::Top, 2) => (-200, 5950),
+        (Team::Dire, BuildingLane::Top, 3) => (3550, 5780),
+        (Team::Dire, BuildingLane::Mid, 1) => (520, 650),
+        (Team::Dire, BuildingLane::Mid, 2) => (2550, 2100),
+        (Team::Dire, BuildingLane::Mid, 3) => (4250, 3700),
+        (Team::Dire, BuildingLane::Bottom, 1) => (6270, -1700),
+        (Team::Dire, BuildingLane::Bottom, 2) => (6350, 380),
+        (Team::Dire, BuildingLane::Bottom, 3) => (6300, 2900),
+        _ => return None,
+    };
+    Some(position)
+}
+
+// Distance along a path to the point on it closest to `position`
+fn along(path: &[(i32, i32)], position: (i32, i32)) -> f32 {
+    let mut walked = 0.0;
+    let mut best = (f32::MAX, 0.0);
+    for segment in path.windows(2) {
+        let ((ax, ay), (bx, by)) = (segment[0], segment[1]);
+        let length = calculate_distance(segment[0], segment[1]);
+        let (dx, dy) = ((bx - ax) as f32, (by - ay) as f32);
+        let t = (((position.0 - ax) as f32 * dx + (position.1 - ay) as f32 * dy) / (length * length)).clamp(0.0, 1.0);
+        let closest = ((ax as f32 + t * dx) as i32, (ay as f32 + t * dy) as i32);
+        let distance = calculate_distance(closest, position);
+        if distance < best.0 {
+            best = (distance, walked + t * length);
+        }
+        walked += length;
+    }
+    best.1
+}
+
+// Seconds a wave of `team` takes from its barracks to `position`, walking its lane unopposed
+fn travel_time(team: Team, lane: BuildingLane, position: (i32, i32)) -> Option<i32> {
+    let path = path(lane);
+    let total: f32 = path.windows(2).map(|s| calculate_distance(s[0], s[1])).sum();
+    let distance = match team {
+        Team::Radiant => along(path, position),
+        Team::Dire => total - along(path, position),
+    };
+    (total > 0.0).then(|| (distance / CREEP_SPEED).round() as i32)
+}
+
+// A tower is contested when it's been hit or the attackers' creeps are on it
+fn contested(tower: &BuildingStatus, position: (i32, i32), attackers: Team, state: &GameState) -> bool {
+    let team_id = if attackers == Team::Radiant { 2 } else { 3 };
+    tower.health < tower.max_health || state.minimap.iter()
+        .flat_map(|minimap| minimap.values())
+        .any(|o| o.team == team_id && o.image.contains("creep") && calculate_distance((o.xpos, o.ypos), position) <= CONTEST_RANGE)
+}
+
+// A siege wave on its way to a contested tower
+#[derive(Clone, Debug)]
+pub struct SiegeWave {
+    pub attackers: Team,
+    pub target: BuildingStatus,
+    pub spawned_at: i32,
+    pub arrives_at: i32,
+}
+
+impl SiegeWave {
+    pub fn describe(&self, team: Team, clock_time: i32) -> String {
+        let (tower, lane) = (self.target.kind.name(), self.target.lane.name());
+        let seconds = self.arrives_at - clock_time;
+        if self.attackers == team {
+            format!("Siege wave reaching their {} {} in {}s - good push window", lane, tower, seconds)
+        } else {
+            format!("Enemy siege wave reaching your {} {} in {}s - clear it before it hits the tower", lane, tower, seconds)
+        }
+    }
+}
+
+// Remembers the siege waves already called, so each is called once. The timing assumes
+// the wave walks its lane without being held up, which is about right for a lane pushed
+// up to the tower it's hitting.
+pub struct SiegeTimers {
+    called: Vec<(String, i32)>,
+}
+
+impl SiegeTimers {
+    pub fn new() -> Self {
+        SiegeTimers {
+            called: Vec::new(),
+        }
+    }
+
+    // Siege waves reaching the outermost tower of a lane within SIEGE_LEAD seconds, where
+    // that tower is contested. `buildings` are both teams' standing buildings.
+    pub fn due(&mut self, state: &GameState, clock_time: i32, buildings: &[BuildingStatus]) -> Vec<SiegeWave> {
+        let mut due = Vec::new();
+        for tower in buildings {
+            let BuildingKind::Tower(tier) = tower.kind else { continue };
+            let outermost = buildings.iter()
+                .filter(|b| b.team == tower.team && b.lane == tower.lane)
+                .all(|b| !matches!(b.kind, BuildingKind::Tower(other) if other < tier));
+            let attackers = if tower.team == Team::Radiant { Team::Dire } else { Team::Radiant };
+            let Some(position) = tower_position(tower.team, tower.lane, tier).filter(|_| outermost) else { continue };
+            let Some(travel) = travel_time(attackers, tower.lane, position) else { continue };
+
+            // The last siege wave that gets there within the lead
+            let spawned_at = (clock_time + SIEGE_LEAD - travel).div_euclid(SIEGE_INTERVAL) * SIEGE_INTERVAL;
+            let arrives_at = spawned_at + travel;
+            let called = (tower.key.clone(), spawned_at);
+            if spawned_at < SIEGE_INTERVAL || arrives_at <= clock_time || self.called.contains(&called)
+                || !contested(tower, position, attackers, state) {
+                continue;
+            }
+            self.called.push(called);
+            due.push(SiegeWave { attackers, target: tower.clone(), spawned_at, arrives_at });
+        }
+        due
+    }
+}
+
+impl Default for SiegeTimers {
+    fn default() -> Self {
+        Self::new()
+    }
+}
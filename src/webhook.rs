@@ -0,0 +1,87 @@
+// src/webhook.rs
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+use serde_json::Value;
+use tokio::sync::mpsc::{self, error::TrySendError};
+
+use crate::alerts::{Alert, AlertCategory, AlertSink};
+use crate::config::WebhookConfig;
+
+// Requests that take longer than this are given up on; the alert isn't sent again
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+// Alerts waiting to be sent. A slow or dead endpoint fills this up, and newer alerts are
+// dropped until it drains rather than piling up.
+const QUEUE: usize = 32;
+
+// POSTs alerts of the configured categories to a URL, for home automation, Stream Deck
+// bridges and bots. Requests go out one at a time from a background task, in the order
+// the alerts were raised, so a slow endpoint never holds up the coach.
+pub struct Webhook {
+    categories: Vec<AlertCategory>,
+    sender: mpsc::Sender<Value>,
+    host: String,
+    // Only the first of a run of dropped alerts is reported
+    dropping: AtomicBool,
+}
+
+impl Webhook {
+    // Must be called from within the Tokio runtime
+    pub fn new(config: &WebhookConfig) -> Result<Self, String> {
+        let url = reqwest::Url::parse(config.url.trim())
+            .map_err(|e| format!("Invalid webhook URL '{}': {}", config.url, e))?;
+        let http = reqwest::Client::builder()
+            .timeout(TIMEOUT)
+            .user_agent(concat!("dota2-coach/", env!("CARGO_PKG_VERSION")))
+            .build()
+            .map_err(|e| format!("Could not set up the webhook client: {}", e))?;
+
+        // URLs often carry a secret token, so messages only name the host
+        let host = url.host_str().unwrap_or("").to_string();
+        let (sender, mut receiver) = mpsc::channel::<Value>(QUEUE);
+        let task_host = host.clone();
+        tokio::spawn(async move {
+            let host = task_host;
+            // Only the first of a run of failures is reported
+            let mut failing = false;
+            while let Some(body) = receiver.recv().await {
+                let sent = http.post(url.clone()).json(&body).send().await
+                    .and_then(|r| r.error_for_status());
+                match sent {
+                    Ok(_) => failing = false,
+                    Err(e) if !failing => {
+                        eprintln!("Webhook to {} failed: {}", host, e.without_url());
+                        failing = true;
+                    },
+                    Err(_) => {},
+                }
+            }
+        });
+
+        Ok(Webhook {
+            categories: config.categories.clone(),
+            sender,
+            host,
+            dropping: AtomicBool::new(false),
+        })
+    }
+}
+
+impl AlertSink for Webhook {
+    fn notify(&self, alert: &Alert) {
+        if !self.categories.contains(&alert.category) {
+            return;
+        }
+        match self.sender.try_send(alert.to_json()) {
+            Ok(()) => self.dropping.store(false, Ordering::Relaxed),
+            Err(TrySendError::Full(_)) => {
+                if !self.dropping.swap(true, Ordering::Relaxed) {
+                    eprintln!("Webhook to {} is falling behind - dropping alerts until it catches up", self.host);
+                }
+            },
+            // The task only stops with the runtime
+            Err(TrySendError::Closed(_)) => {},
+        }
+    }
+}